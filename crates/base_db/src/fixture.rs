@@ -83,6 +83,17 @@ pub struct ChangeFixture {
 
 impl ChangeFixture {
     pub fn parse(ra_fixture: &str) -> ChangeFixture {
+        ChangeFixture::parse_with_proc_macros(ra_fixture, Vec::new())
+    }
+
+    /// Like [`ChangeFixture::parse`], but additionally adds the given proc macros to the
+    /// `proc_macros` crate, for tests that need proc macros other than the builtin test ones.
+    /// Each proc macro comes with the source of its declaration in the `proc_macros` crate,
+    /// e.g. `#[proc_macro_derive(Builder)] pub fn builder(item: TokenStream) -> TokenStream`.
+    pub fn parse_with_proc_macros(
+        ra_fixture: &str,
+        extra_proc_macros: Vec<(String, ProcMacro)>,
+    ) -> ChangeFixture {
         let (mini_core, proc_macros, fixture) = Fixture::parse(ra_fixture);
         let mut change = Change::new();
 
@@ -215,11 +226,16 @@ impl ChangeFixture {
             }
         }
 
-        if !proc_macros.is_empty() {
+        if !proc_macros.is_empty() || !extra_proc_macros.is_empty() {
             let proc_lib_file = file_id;
             file_id.0 += 1;
 
-            let (proc_macro, source) = test_proc_macros(&proc_macros);
+            let (mut proc_macro, mut source) = test_proc_macros(&proc_macros);
+            for (declaration, expander) in extra_proc_macros {
+                source.push_str(&declaration);
+                source.push('\n');
+                proc_macro.push(expander);
+            }
             let mut fs = FileSet::default();
             fs.insert(
                 proc_lib_file,
@@ -5,8 +5,9 @@
 //! But we need this for at least LRU caching at the query level.
 pub use hir_def::db::*;
 pub use hir_expand::db::{
-    AstDatabase, AstDatabaseStorage, AstIdMapQuery, HygieneFrameQuery, InternMacroQuery,
-    MacroArgTextQuery, MacroDefQuery, MacroExpandQuery, ParseMacroExpansionQuery,
+    trace_expansion, AstDatabase, AstDatabaseStorage, AstIdMapQuery, ExpansionTrace,
+    FormatArgsPiecesQuery, HygieneFrameQuery, InternMacroQuery, MacroArgTextQuery, MacroDefQuery,
+    MacroExpandQuery, ParseMacroExpansionQuery,
 };
 pub use hir_ty::db::*;
 
@@ -33,6 +33,7 @@ diagnostics![
     InactiveCode,
     IncorrectCase,
     MacroError,
+    MacroRulesLint,
     MismatchedArgCount,
     MissingFields,
     MissingMatchArms,
@@ -71,6 +72,15 @@ pub struct UnresolvedMacroCall {
     pub path: ModPath,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MacroRulesLint {
+    /// The `macro_rules!` or `macro` item.
+    pub node: InFile<SyntaxNodePtr>,
+    /// The metavariable the lint is about, if the definition is not in a macro expansion.
+    pub precise_location: Option<TextRange>,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct InactiveCode {
     pub node: InFile<SyntaxNodePtr>,
@@ -51,20 +51,21 @@ use hir_def::{
     LocalEnumVariantId, LocalFieldId, Lookup, ModuleId, StaticId, StructId, TraitId, TypeAliasId,
     TypeParamId, UnionId,
 };
-use hir_expand::{name::name, MacroCallKind, MacroDefId, MacroDefKind};
+use hir_expand::{db::TokenExpander, name::name, MacroCallKind, MacroDefId, MacroDefKind};
 use hir_ty::{
     autoderef,
     consteval::ConstExt,
     could_unify,
     diagnostics::BodyValidationDiagnostic,
+    fold_tys, from_assoc_type_id,
     method_resolution::{self, TyFingerprint},
     primitive::UintTy,
     subst_prefix,
     traits::FnTrait,
     AliasEq, AliasTy, BoundVar, CallableDefId, CallableSig, Canonical, CanonicalVarKinds, Cast,
-    DebruijnIndex, InEnvironment, Interner, QuantifiedWhereClause, Scalar, Solution, Substitution,
-    TraitEnvironment, TraitRefExt, Ty, TyBuilder, TyDefId, TyExt, TyKind, TyVariableKind,
-    WhereClause,
+    DebruijnIndex, InEnvironment, Interner, ProjectionTy, ProjectionTyExt, QuantifiedWhereClause,
+    Scalar, Solution, Substitution, TraitEnvironment, TraitRefExt, Ty, TyBuilder, TyDefId, TyExt,
+    TyKind, TyVariableKind, WhereClause,
 };
 use itertools::Itertools;
 use nameres::diagnostics::DefDiagnosticKind;
@@ -83,10 +84,10 @@ pub use crate::{
     attrs::{HasAttrs, Namespace},
     diagnostics::{
         AddReferenceHere, AnyDiagnostic, BreakOutsideOfLoop, InactiveCode, IncorrectCase,
-        MacroError, MismatchedArgCount, MissingFields, MissingMatchArms, MissingOkOrSomeInTailExpr,
-        MissingUnsafe, NoSuchField, RemoveThisSemicolon, ReplaceFilterMapNextWithFindMap,
-        UnimplementedBuiltinMacro, UnresolvedExternCrate, UnresolvedImport, UnresolvedMacroCall,
-        UnresolvedModule, UnresolvedProcMacro,
+        MacroError, MacroRulesLint, MismatchedArgCount, MissingFields, MissingMatchArms,
+        MissingOkOrSomeInTailExpr, MissingUnsafe, NoSuchField, RemoveThisSemicolon,
+        ReplaceFilterMapNextWithFindMap, UnimplementedBuiltinMacro, UnresolvedExternCrate,
+        UnresolvedImport, UnresolvedMacroCall, UnresolvedModule, UnresolvedProcMacro,
     },
     has_source::HasSource,
     semantics::{PathResolution, Semantics, SemanticsScope, TypeInfo},
@@ -115,7 +116,7 @@ pub use {
     },
     hir_expand::{
         name::{known, Name},
-        ExpandResult, HirFileId, InFile, MacroFile, Origin,
+        ExpandResult, HirFileId, InFile, InFileAstExt, MacroFile, Origin,
     },
     hir_ty::display::HirDisplay,
 };
@@ -646,6 +647,9 @@ impl Module {
                 }
             }
         }
+        for def in def_map[self.id.local_id].scope.macro_declarations() {
+            macro_rules_lints(db, def, acc);
+        }
         for decl in self.declarations(db) {
             match decl {
                 ModuleDef::Module(m) => {
@@ -704,6 +708,48 @@ impl Module {
     }
 }
 
+fn macro_rules_lints(db: &dyn HirDatabase, def: MacroDefId, acc: &mut Vec<AnyDiagnostic>) {
+    let ast_id = match def.kind {
+        MacroDefKind::Declarative(it) => it,
+        _ => return,
+    };
+    let expander = match db.macro_def(def) {
+        Ok(it) => it,
+        Err(_) => return,
+    };
+    let (mac, def_site_token_map) = match &*expander {
+        TokenExpander::DeclarativeMacro { mac, def_site_token_map } => (mac, def_site_token_map),
+        _ => return,
+    };
+    let lints = mac.validate();
+    if lints.is_empty() {
+        return;
+    }
+
+    let node = ast_id.to_node(db.upcast());
+    let body = match &node {
+        ast::Macro::MacroRules(it) => it.token_tree(),
+        ast::Macro::MacroDef(it) => it.body(),
+    };
+    let body_start = body.map(|it| it.syntax().text_range().start());
+    for lint in lints {
+        let precise_location =
+            body_start.filter(|_| !ast_id.file_id.is_macro()).and_then(|start| {
+                let range =
+                    def_site_token_map.first_range_by_token(lint.token_id(), SyntaxKind::IDENT)?;
+                Some(range + start)
+            });
+        acc.push(
+            MacroRulesLint {
+                node: ast_id.with_value(SyntaxNodePtr::new(node.syntax())),
+                precise_location,
+                message: lint.to_string(),
+            }
+            .into(),
+        );
+    }
+}
+
 impl HasVisibility for Module {
     fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
         let def_map = self.id.def_map(db.upcast());
@@ -1311,6 +1357,10 @@ impl Function {
         db.function_data(self.id).is_async()
     }
 
+    pub fn is_const(self, db: &dyn HirDatabase) -> bool {
+        db.function_data(self.id).is_const()
+    }
+
     /// Whether this function declaration has a definition.
     ///
     /// This is false in the case of required (not provided) trait methods.
@@ -1520,6 +1570,15 @@ impl Trait {
     pub fn is_unsafe(&self, db: &dyn HirDatabase) -> bool {
         db.trait_data(self.id).is_unsafe
     }
+
+    /// Returns the traits this trait inherits from, directly or transitively.
+    pub fn super_traits(self, db: &dyn HirDatabase) -> Vec<Trait> {
+        hir_ty::all_super_traits(db.upcast(), self.id)
+            .into_iter()
+            .skip(1)
+            .map(Trait::from)
+            .collect()
+    }
 }
 
 impl HasVisibility for Trait {
@@ -1633,6 +1692,11 @@ impl MacroDef {
         }
     }
 
+    /// Whether this is a built-in macro that expands its arguments first, like `concat!`.
+    pub fn is_eager(&self) -> bool {
+        matches!(self.id.kind, MacroDefKind::BuiltInEager(..))
+    }
+
     pub fn is_fn_like(&self) -> bool {
         match self.kind() {
             MacroKind::Declarative | MacroKind::BuiltIn | MacroKind::ProcMacro => true,
@@ -1984,6 +2048,13 @@ pub enum GenericParam {
 impl_from!(TypeParam, LifetimeParam, ConstParam for GenericParam);
 
 impl GenericParam {
+    pub fn as_const_param(self) -> Option<ConstParam> {
+        match self {
+            GenericParam::ConstParam(it) => Some(it),
+            _ => None,
+        }
+    }
+
     pub fn module(self, db: &dyn HirDatabase) -> Module {
         match self {
             GenericParam::TypeParam(it) => it.module(db),
@@ -2279,10 +2350,22 @@ impl Type {
         matches!(self.ty.kind(&Interner), TyKind::Ref(..))
     }
 
+    /// Returns the element type of a slice or an array, like `T` for `[T]` or `[T; N]`.
+    pub fn as_slice_item_type(&self) -> Option<Type> {
+        match self.ty.kind(&Interner) {
+            TyKind::Slice(ty) | TyKind::Array(ty, _) => Some(self.derived(ty.clone())),
+            _ => None,
+        }
+    }
+
     pub fn is_usize(&self) -> bool {
         matches!(self.ty.kind(&Interner), TyKind::Scalar(Scalar::Uint(UintTy::Usize)))
     }
 
+    pub fn is_integral(&self) -> bool {
+        matches!(self.ty.kind(&Interner), TyKind::Scalar(Scalar::Int(_) | Scalar::Uint(_)))
+    }
+
     pub fn remove_ref(&self) -> Option<Type> {
         match &self.ty.kind(&Interner) {
             TyKind::Ref(.., ty) => Some(self.derived(ty.clone())),
@@ -2394,6 +2477,34 @@ impl Type {
         }
     }
 
+    /// Normalizes the projections like `<T as Trait>::Assoc` inside this type where possible.
+    fn normalize_projections(&self, db: &dyn HirDatabase) -> Type {
+        let normalize = |projection: &ProjectionTy| {
+            let alias = TypeAlias::from(from_assoc_type_id(projection.associated_ty_id));
+            let self_ty = self.derived(projection.self_type_parameter(&Interner).clone());
+            let args: Vec<_> = projection
+                .substitution
+                .iter(&Interner)
+                .skip(1)
+                .filter_map(|arg| arg.ty(&Interner))
+                .map(|ty| self.derived(ty.clone()))
+                .collect();
+            self_ty.normalize_trait_assoc_type(db, &args, alias)
+        };
+        let ty = fold_tys(
+            self.ty.clone(),
+            |ty, _| {
+                let normalized = match ty.kind(&Interner) {
+                    TyKind::Alias(AliasTy::Projection(projection)) => normalize(projection),
+                    _ => None,
+                };
+                normalized.map_or(ty, |it| it.ty)
+            },
+            DebruijnIndex::INNERMOST,
+        );
+        self.derived(ty)
+    }
+
     pub fn is_copy(&self, db: &dyn HirDatabase) -> bool {
         let lang_item = db.lang_item(self.krate, SmolStr::new("copy"));
         let copy_trait = match lang_item {
@@ -2855,6 +2966,13 @@ impl Callable {
         let param_list = src.value.param_list()?;
         param_list.self_param()
     }
+    /// The type the receiver of a method call is passed as, after auto-referencing.
+    pub fn receiver_type(&self) -> Option<Type> {
+        if !self.is_bound_method {
+            return None;
+        }
+        self.sig.params().first().map(|ty| self.ty.derived(ty.clone()))
+    }
     pub fn n_params(&self) -> usize {
         self.sig.params().len() - if self.is_bound_method { 1 } else { 0 }
     }
@@ -2887,6 +3005,90 @@ impl Callable {
     pub fn return_type(&self) -> Type {
         self.ty.derived(self.sig.ret().clone())
     }
+    /// Returns the parameter types of a closure passed as the `idx`-th argument, taken either
+    /// from the parameter's own type (`fn(u32)`) or from its `Fn*` bound (`F: FnMut(u32)`).
+    pub fn closure_params(&self, db: &dyn HirDatabase, idx: usize) -> Option<Vec<Type>> {
+        let idx = idx + if self.is_bound_method { 1 } else { 0 };
+        let param_ty = self.sig.params().get(idx)?;
+        if let Some(sig) = param_ty.callable_sig(db) {
+            return Some(sig.params().iter().map(|ty| self.ty.derived(ty.clone())).collect());
+        }
+
+        let (def, subst, self_var) = self.generic_param_var(db, idx)?;
+        let krate = self.ty.krate;
+        let fn_traits: Vec<_> = [FnTrait::FnOnce, FnTrait::FnMut, FnTrait::Fn]
+            .iter()
+            .filter_map(|it| it.get_id(db, krate))
+            .collect();
+
+        db.generic_predicates(def.into()).iter().find_map(|pred| {
+            // NOTE(skip_binders): the predicate is matched before the substitution is applied
+            match pred.skip_binders().skip_binders() {
+                WhereClause::Implemented(trait_ref)
+                    if fn_traits.contains(&trait_ref.hir_trait_id())
+                        && trait_ref.self_type_parameter(&Interner).bound_var(&Interner)
+                            == Some(self_var) => {}
+                _ => return None,
+            }
+            let pred = pred.clone().substitute(&Interner, subst);
+            let trait_ref = match pred.skip_binders() {
+                WhereClause::Implemented(trait_ref) => trait_ref,
+                _ => return None,
+            };
+            let args = trait_ref.substitution.at(&Interner, 1).ty(&Interner)?.as_tuple()?;
+            let params = args
+                .iter(&Interner)
+                .filter_map(|arg| arg.ty(&Interner))
+                .map(|ty| self.ty.derived(ty.clone()).normalize_projections(db))
+                .collect();
+            Some(params)
+        })
+    }
+    /// Returns the return type of a closure passed as the `idx`-th argument, taken either from
+    /// the parameter's own type (`fn() -> u32`) or from its `Fn*` bound (`F: Fn() -> u32`).
+    pub fn closure_ret(&self, db: &dyn HirDatabase, idx: usize) -> Option<Type> {
+        let idx = idx + if self.is_bound_method { 1 } else { 0 };
+        let param_ty = self.sig.params().get(idx)?;
+        if let Some(sig) = param_ty.callable_sig(db) {
+            return Some(self.ty.derived(sig.ret().clone()));
+        }
+
+        let (def, subst, self_var) = self.generic_param_var(db, idx)?;
+        let fn_once = FnTrait::FnOnce.get_id(db, self.ty.krate)?;
+        db.generic_predicates(def.into()).iter().find_map(|pred| {
+            // NOTE(skip_binders): the predicate is matched before the substitution is applied
+            match pred.skip_binders().skip_binders() {
+                WhereClause::AliasEq(AliasEq { alias: AliasTy::Projection(proj), .. })
+                    if proj.trait_(db) == fn_once
+                        && proj.self_type_parameter(&Interner).bound_var(&Interner)
+                            == Some(self_var) => {}
+                _ => return None,
+            }
+            match pred.clone().substitute(&Interner, subst).skip_binders() {
+                WhereClause::AliasEq(it) => {
+                    Some(self.ty.derived(it.ty.clone()).normalize_projections(db))
+                }
+                _ => None,
+            }
+        })
+    }
+    /// The generic parameter the type of the `idx`-th parameter is declared as, as a variable
+    /// bound by the predicates of the callable.
+    fn generic_param_var(
+        &self,
+        db: &dyn HirDatabase,
+        idx: usize,
+    ) -> Option<(CallableDefId, &Substitution, BoundVar)> {
+        let def = self.def?;
+        let subst = match self.ty.ty.kind(&Interner) {
+            TyKind::FnDef(_, subst) => subst,
+            _ => return None,
+        };
+        let declared_sig = db.callable_item_signature(def);
+        let param_var = declared_sig.skip_binders().params().get(idx)?.bound_var(&Interner)?;
+        // the predicates have their own binder, so the generic parameters are shifted in by one
+        Some((def, subst, BoundVar::new(DebruijnIndex::ONE, param_var.index)))
+    }
 }
 
 /// For IDE only
@@ -22,7 +22,7 @@ use syntax::{
 };
 
 use crate::{
-    db::HirDatabase,
+    db::{trace_expansion, ExpansionTrace, HirDatabase},
     semantics::source_to_def::{ChildContainer, SourceToDefCache, SourceToDefCtx},
     source_analyzer::{resolve_hir_path, SourceAnalyzer},
     Access, AssocItem, Callable, ConstParam, Crate, Field, Function, HasSource, HirFileId, Impl,
@@ -144,6 +144,12 @@ impl<'db, DB: HirDatabase> Semantics<'db, DB> {
         self.imp.expand(macro_call)
     }
 
+    /// Expands `macro_call` once more, bypassing the cache, and records the details of the
+    /// expansion.
+    pub fn trace_expansion(&self, macro_call: &ast::MacroCall) -> Option<ExpansionTrace> {
+        self.imp.trace_expansion(macro_call)
+    }
+
     /// If `item` has an attribute macro attached to it, expands it.
     pub fn expand_attr_macro(&self, item: &ast::Item) -> Option<SyntaxNode> {
         self.imp.expand_attr_macro(item)
@@ -409,6 +415,12 @@ impl<'db> SemanticsImpl<'db> {
         Some(node)
     }
 
+    fn trace_expansion(&self, macro_call: &ast::MacroCall) -> Option<ExpansionTrace> {
+        let sa = self.analyze(macro_call.syntax());
+        let macro_call_id = sa.macro_call_id(self.db, InFile::new(sa.file_id, macro_call))?;
+        Some(trace_expansion(self.db.upcast(), macro_call_id))
+    }
+
     fn expand_attr_macro(&self, item: &ast::Item) -> Option<SyntaxNode> {
         let sa = self.analyze(item.syntax());
         let src = InFile::new(sa.file_id, item.clone());
@@ -5,7 +5,10 @@
 //!
 //! So, this modules should not be used during hir construction, it exists
 //! purely for "IDE needs".
-use std::{iter::once, sync::Arc};
+use std::{
+    iter::{self, once},
+    sync::Arc,
+};
 
 use hir_def::{
     body::{
@@ -18,14 +21,14 @@ use hir_def::{
     resolver::{resolver_for_scope, Resolver, TypeNs, ValueNs},
     AsMacroCall, DefWithBodyId, FieldId, FunctionId, LocalFieldId, VariantId,
 };
-use hir_expand::{hygiene::Hygiene, name::AsName, HirFileId, InFile};
+use hir_expand::{hygiene::Hygiene, name::AsName, HirFileId, InFile, MacroCallId};
 use hir_ty::{
     diagnostics::{record_literal_missing_fields, record_pattern_missing_fields},
     InferenceResult, Interner, Substitution, TyExt, TyLoweringContext,
 };
 use syntax::{
     ast::{self, AstNode},
-    SyntaxNode, TextRange, TextSize,
+    SyntaxKind, SyntaxNode, TextRange, TextSize,
 };
 
 use crate::{
@@ -325,6 +328,15 @@ impl SourceAnalyzer {
             }
         }
 
+        // Case where path is the path of an attribute, e.g. `#[proc_macros::identity]`, which
+        // names an attribute macro rather than a function of the same name.
+        if parent().map_or(false, |it| ast::Meta::can_cast(it.kind())) {
+            if let Some(mac) = self.resolver.resolve_path_as_macro(db.upcast(), hir_path.mod_path())
+            {
+                return Some(PathResolution::Macro(mac.into()));
+            }
+        }
+
         if parent().map_or(false, |it| ast::Visibility::can_cast(it.kind())) {
             resolve_hir_path_qualifier(db, &self.resolver, &hir_path)
         } else {
@@ -393,11 +405,19 @@ impl SourceAnalyzer {
         db: &dyn HirDatabase,
         macro_call: InFile<&ast::MacroCall>,
     ) -> Option<HirFileId> {
+        let macro_call_id = self.macro_call_id(db, macro_call)?;
+        Some(macro_call_id.as_file()).filter(|it| it.expansion_level(db.upcast()) < 64)
+    }
+
+    pub(crate) fn macro_call_id(
+        &self,
+        db: &dyn HirDatabase,
+        macro_call: InFile<&ast::MacroCall>,
+    ) -> Option<MacroCallId> {
         let krate = self.resolver.krate()?;
-        let macro_call_id = macro_call.as_call_id(db.upcast(), krate, |path| {
+        macro_call.as_call_id(db.upcast(), krate, |path| {
             self.resolver.resolve_path_as_macro(db.upcast(), &path)
-        })?;
-        Some(macro_call_id.as_file()).filter(|it| it.expansion_level(db.upcast()) < 64)
+        })
     }
 
     pub(crate) fn resolve_variant(
@@ -434,13 +454,19 @@ fn scope_for_offset(
         .iter()
         .filter_map(|(id, scope)| {
             let source = source_map.expr_syntax(*id).ok()?;
-            // FIXME: correctly handle macro expansion
-            if source.file_id != offset.file_id {
-                return None;
+            if source.file_id == offset.file_id {
+                let root = source.file_syntax(db.upcast());
+                let node = source.value.to_node(&root);
+                return Some((node.syntax().text_range(), scope));
             }
-            let root = source.file_syntax(db.upcast());
-            let node = source.value.to_node(&root);
-            Some((node.syntax().text_range(), scope))
+            // Expressions from macro expansions are covered by the macro call they come from.
+            // FIXME: handle attribute expansions
+            let call = iter::successors(source.file_id.call_node(db.upcast()), |it| {
+                it.file_id.call_node(db.upcast())
+            })
+            .find(|it| it.file_id == offset.file_id)
+            .filter(|it| it.value.kind() == SyntaxKind::MACRO_CALL)?;
+            Some((call.value.text_range(), scope))
         })
         // find containing scope
         .min_by_key(|(expr_range, _scope)| {
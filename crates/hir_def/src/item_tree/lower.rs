@@ -449,7 +449,7 @@ impl<'a> Ctx<'a> {
 
     fn lower_const(&mut self, konst: &ast::Const) -> FileItemTreeId<Const> {
         let mut name = konst.name().map(|it| it.as_name());
-        if name.as_ref().map_or(false, |n| n.to_string().starts_with("_DERIVE_")) {
+        if name.as_ref().and_then(|n| n.as_str()).map_or(false, |n| n.starts_with("_DERIVE_")) {
             // FIXME: this is a hack to treat consts generated by synstructure as unnamed
             // remove this some time in the future
             name = None;
@@ -13,12 +13,15 @@ mod mbe;
 mod builtin_fn_macro;
 mod builtin_derive_macro;
 mod proc_macros;
+mod benchmark;
+mod expansion_graph;
+mod queries;
 
 use std::{iter, ops::Range};
 
 use base_db::{fixture::WithFixture, SourceDatabase};
 use expect_test::Expect;
-use hir_expand::{db::AstDatabase, InFile, MacroFile};
+use hir_expand::{db::AstDatabase, ExpandError, InFile, MacroCallId, MacroFile};
 use stdx::format_to;
 use syntax::{
     ast::{self, edit::IndentLevel},
@@ -32,20 +35,54 @@ use crate::{
     AdtId, AsMacroCall, Lookup, ModuleDefId,
 };
 
-#[track_caller]
-fn check(ra_fixture: &str, mut expect: Expect) {
+/// A macro call with its id, and the error reported while resolving it.
+type ResolvedMacroCall = (ast::MacroCall, MacroCallId, Option<ExpandError>);
+
+/// Builds the fixture and resolves the macro calls written in the root module of its first crate,
+/// in source order.
+fn resolve_root_macro_calls(
+    ra_fixture: &str,
+) -> (TestDB, InFile<ast::SourceFile>, Vec<ResolvedMacroCall>) {
     let db = TestDB::with_files(ra_fixture);
     let krate = db.crate_graph().iter().next().unwrap();
     let def_map = db.crate_def_map(krate);
     let local_id = def_map.root();
-    let module = def_map.module_id(local_id);
-    let resolver = module.resolver(&db);
-    let source = def_map[local_id].definition_source(&db);
-    let source_file = match source.value {
+    let resolver = def_map.module_id(local_id).resolver(&db);
+    let InFile { file_id, value: source } = def_map[local_id].definition_source(&db);
+    let source_file = match source {
         ModuleSource::SourceFile(it) => it,
         ModuleSource::Module(_) | ModuleSource::BlockExpr(_) => panic!(),
     };
 
+    let calls = source_file
+        .syntax()
+        .descendants()
+        .filter_map(ast::MacroCall::cast)
+        .map(|macro_call| {
+            let mut error = None;
+            let macro_call_id = InFile::new(file_id, &macro_call)
+                .as_call_id_with_errors(
+                    &db,
+                    krate,
+                    |path| resolver.resolve_path_as_macro(&db, &path),
+                    &mut |err| error = Some(err),
+                )
+                .unwrap()
+                .unwrap();
+            (macro_call, macro_call_id, error)
+        })
+        .collect();
+    (db, InFile::new(file_id, source_file), calls)
+}
+
+#[track_caller]
+fn check(ra_fixture: &str, mut expect: Expect) {
+    let (db, source_file, calls) = resolve_root_macro_calls(ra_fixture);
+    let source_file = source_file.value;
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let local_id = def_map.root();
+
     // What we want to do is to replace all macros (fn-like, derive, attr) with
     // their expansions. Turns out, we don't actually store enough information
     // to do this precisely though! Specifically, if a macro expands to nothing,
@@ -62,22 +99,11 @@ fn check(ra_fixture: &str, mut expect: Expect) {
     // `HirFileId`, than it came from macro expansion.
 
     let mut expansions = Vec::new();
-    for macro_call in source_file.syntax().descendants().filter_map(ast::MacroCall::cast) {
-        let macro_call = InFile::new(source.file_id, &macro_call);
-        let mut error = None;
-        let macro_call_id = macro_call
-            .as_call_id_with_errors(
-                &db,
-                krate,
-                |path| resolver.resolve_path_as_macro(&db, &path),
-                &mut |err| error = Some(err),
-            )
-            .unwrap()
-            .unwrap();
+    for (macro_call, macro_call_id, error) in calls {
         let macro_file = MacroFile { macro_call_id };
         let mut expansion_result = db.parse_macro_expansion(macro_file);
         expansion_result.err = expansion_result.err.or(error);
-        expansions.push((macro_call.value.clone(), expansion_result));
+        expansions.push((macro_call, expansion_result));
     }
 
     let mut expanded_text = source_file.to_string();
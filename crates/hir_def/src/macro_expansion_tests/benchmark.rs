@@ -0,0 +1,75 @@
+//! Benchmarks for macro expansion in larger workspaces.
+//!
+//! Run with `RA_PROFILE='*>10'` to see how the time is split between argument lowering
+//! (`macro_arg`), expansion (`macro_expand`) and parsing of the result (`parse_macro_expansion`).
+
+use std::fmt::Write;
+
+use base_db::{fixture::WithFixture, SourceDatabase};
+use hir_expand::{db::AstDatabase, MacroFile};
+use test_utils::{bench, skip_slow_tests};
+
+use crate::{db::DefDatabase, test_db::TestDB};
+
+#[test]
+fn benchmark_expand_derives() {
+    if skip_slow_tests() {
+        return;
+    }
+
+    let db = TestDB::with_files(&derive_fixture(1000, 10));
+    let krate = db.crate_graph().iter().next().unwrap();
+
+    // Builtin derives are expanded eagerly during name resolution.
+    let def_map = {
+        let _pt = bench("expand derives");
+        db.crate_def_map(krate)
+    };
+
+    let mut n_expansions = 0;
+    for (_, module) in def_map.modules() {
+        for (_, calls) in module.scope.derive_macro_invocs() {
+            for &(_, macro_call_id) in calls {
+                let expansion = db.parse_macro_expansion(MacroFile { macro_call_id });
+                assert!(expansion.err.is_none());
+                assert!(expansion.value.is_some());
+                n_expansions += 1;
+            }
+        }
+    }
+    assert_eq!(n_expansions, 1000 * 10 * 3);
+}
+
+/// Generates a crate with `n_files` structurally identical modules, each containing `n_structs`
+/// structs with `#[derive(Debug, Clone, PartialEq)]`.
+fn derive_fixture(n_files: usize, n_structs: usize) -> String {
+    let mut fixture = String::from(
+        "
+//- /lib.rs
+#[rustc_builtin_macro]
+macro derive {}
+#[rustc_builtin_macro]
+macro Debug {}
+#[rustc_builtin_macro]
+macro Clone {}
+#[rustc_builtin_macro]
+macro PartialEq {}
+",
+    );
+    for file in 0..n_files {
+        writeln!(fixture, "mod m{};", file).unwrap();
+    }
+    for file in 0..n_files {
+        writeln!(fixture, "//- /m{}.rs", file).unwrap();
+        writeln!(fixture, "use super::*;").unwrap();
+        for idx in 0..n_structs {
+            writeln!(
+                fixture,
+                "#[derive(Debug, Clone, PartialEq)]\nstruct S{}<T> {{ a: u32, b: T, c: (u8, u16) }}",
+                idx
+            )
+            .unwrap();
+        }
+    }
+    fixture
+}
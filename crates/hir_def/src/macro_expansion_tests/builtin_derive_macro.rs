@@ -88,3 +88,53 @@ struct Foo<A, B>;
 impl <T0: core::clone::Clone, T1: core::clone::Clone> core::clone::Clone for Foo<T0, T1> {}"##]],
     );
 }
+
+#[test]
+fn test_default_expand_with_field_attributes() {
+    check(
+        r#"
+//- minicore: derive, default
+#[derive(Default)]
+struct Foo {
+    #[default = 92]
+    a: u32,
+    b: u32,
+}
+"#,
+        expect![[r##"
+#[derive(Default)]
+struct Foo {
+    #[default = 92]
+    a: u32,
+    b: u32,
+}
+
+impl < > core::default::Default for Foo< > {
+    fn default() -> Self {
+        Self {
+            a:92, b: core::default::Default::default(),
+        }
+    }
+}"##]],
+    );
+}
+
+#[test]
+fn test_default_expand_tuple_struct() {
+    check(
+        r#"
+//- minicore: derive, default
+#[derive(Default)]
+struct Foo<T>(#[default = "a"] &'static str, T);
+"#,
+        expect![[r##"
+#[derive(Default)]
+struct Foo<T>(#[default = "a"] &'static str, T);
+
+impl <T0: core::default::Default> core::default::Default for Foo<T0> {
+    fn default() -> Self {
+        Self("a", core::default::Default::default(), )
+    }
+}"##]],
+    );
+}
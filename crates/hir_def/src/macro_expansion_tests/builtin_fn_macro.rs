@@ -1,8 +1,15 @@
 //! Tests for `builtin_fn_macro.rs` from `hir_expand`.
 
-use expect_test::expect;
+use expect_test::{expect, Expect};
+use hir_expand::db::AstDatabase;
 
-use crate::macro_expansion_tests::check;
+use crate::macro_expansion_tests::{check, resolve_root_macro_calls};
+
+fn check_format_args_pieces(ra_fixture: &str, expect: Expect) {
+    let (db, _, calls) = resolve_root_macro_calls(ra_fixture);
+    let pieces = db.format_args_pieces(calls[0].1);
+    expect.assert_debug_eq(&pieces);
+}
 
 #[test]
 fn test_column_expand() {
@@ -66,6 +73,24 @@ fn main() {
     );
 }
 
+#[test]
+fn test_stringify_expand_expr() {
+    check(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! stringify {() => {}}
+
+fn main() { stringify!(1 + 2); }
+"#,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! stringify {() => {}}
+
+fn main() { "1 + 2"; }
+"##]],
+    );
+}
+
 #[test]
 fn test_env_expand() {
     check(
@@ -84,6 +109,24 @@ fn main() { "__RA_UNIMPLEMENTED__"; }
     );
 }
 
+#[test]
+fn test_env_expand_nonexistent_var() {
+    check(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! env {() => {}}
+
+fn main() { env!("NONEXISTENT"); }
+"#,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! env {() => {}}
+
+fn main() { "__RA_UNIMPLEMENTED__"; }
+"##]],
+    );
+}
+
 #[test]
 fn test_option_env_expand() {
     check(
@@ -271,6 +314,36 @@ unsafe {
     );
 }
 
+#[test]
+fn test_format_args_expand_without_captured_args() {
+    check(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! format_args {
+    ($fmt:expr) => ({ /* compiler built-in */ });
+    ($fmt:expr, $($args:tt)*) => ({ /* compiler built-in */ })
+}
+
+fn main() {
+    format_args!("{a} {b:?} {{c}} {0} {a}", x, b = y);
+}
+"#,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! format_args {
+    ($fmt:expr) => ({ /* compiler built-in */ });
+    ($fmt:expr, $($args:tt)*) => ({ /* compiler built-in */ })
+}
+
+fn main() {
+    unsafe {
+        std::fmt::Arguments::new_v1(&[], &[std::fmt::ArgumentV1::new(&(x), std::fmt::Display::fmt), std::fmt::ArgumentV1::new(&(y), std::fmt::Display::fmt), ])
+    };
+}
+"##]],
+    );
+}
+
 #[test]
 fn test_include_bytes_expand() {
     check(
@@ -295,6 +368,24 @@ fn main() { include_bytes("foo"); }
     );
 }
 
+#[test]
+fn test_include_str_expand() {
+    check(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! include_str {() => {}}
+
+fn main() { include_str!("foo.txt"); }
+"#,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! include_str {() => {}}
+
+fn main() { ""; }
+"##]],
+    );
+}
+
 #[test]
 fn test_concat_expand() {
     check(
@@ -313,6 +404,47 @@ fn main() { "foor0bar\nfalse"; }
     );
 }
 
+#[test]
+fn test_concat_expand_no_args() {
+    check(
+        r##"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+
+fn main() { concat!(); }
+"##,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+
+fn main() { ""; }
+"##]],
+    );
+}
+
+#[test]
+fn test_concat_expand_with_nested_eager_macros() {
+    check(
+        r##"
+//- /main.rs crate:main env:CARGO_PKG_NAME=foo,CARGO_PKG_VERSION=0.1.0
+#[rustc_builtin_macro]
+macro_rules! concat {}
+#[rustc_builtin_macro]
+macro_rules! env {}
+
+fn main() { concat!(env!("CARGO_PKG_NAME"), "_", concat!(env!("CARGO_PKG_VERSION"), "-", 92)); }
+"##,
+        expect![[r##"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+#[rustc_builtin_macro]
+macro_rules! env {}
+
+fn main() { "foo_0.1.0-92"; }
+"##]],
+    );
+}
+
 #[test]
 fn test_concat_idents_expand() {
     check(
@@ -330,3 +462,72 @@ fn main() { foobar; }
 "##]],
     );
 }
+
+#[test]
+fn test_format_args_pieces() {
+    check_format_args_pieces(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! format_args {
+    ($fmt:expr) => ({ /* compiler built-in */ });
+    ($fmt:expr, $($args:tt)*) => ({ /* compiler built-in */ })
+}
+
+fn main() {
+    format_args!("a {} {{b}} {x:?} {0:>5} {captured}\n", 92, x = 2);
+}
+"#,
+        expect![[r#"
+            Some(
+                [
+                    Literal(
+                        "a ",
+                    ),
+                    Argument {
+                        index: 0,
+                        format_spec: "",
+                    },
+                    Literal(
+                        " {b} ",
+                    ),
+                    Argument {
+                        index: 1,
+                        format_spec: "?",
+                    },
+                    Literal(
+                        " ",
+                    ),
+                    Argument {
+                        index: 0,
+                        format_spec: ">5",
+                    },
+                    Literal(
+                        " ",
+                    ),
+                    Argument {
+                        index: 2,
+                        format_spec: "",
+                    },
+                    Literal(
+                        "\n",
+                    ),
+                ],
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_format_args_pieces_of_other_macros() {
+    check_format_args_pieces(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+
+fn main() { concat!("{}", 92); }
+"#,
+        expect![[r#"
+            None
+        "#]],
+    );
+}
@@ -0,0 +1,59 @@
+//! Tests for `expansion_graph.rs` from `hir_expand`.
+
+use expect_test::{expect, Expect};
+use hir_expand::expansion_graph::{macro_expansion_graph, macro_expansion_graph_dot};
+
+use crate::macro_expansion_tests::resolve_root_macro_calls;
+
+fn check(ra_fixture: &str, expect: Expect) {
+    let (db, source_file, _) = resolve_root_macro_calls(ra_fixture);
+    let graph = macro_expansion_graph(&db, source_file.file_id.original_file(&db));
+    expect.assert_eq(&macro_expansion_graph_dot(&db, &graph));
+}
+
+#[test]
+fn nested_macro_calls() {
+    check(
+        r#"
+macro_rules! item { ($name:ident) => { struct $name; }; }
+macro_rules! two_items {
+    ($a:ident, $b:ident) => { item!($a); item!($b); };
+}
+macro_rules! nested { () => { two_items!(A, B); }; }
+
+nested!();
+item!(C);
+"#,
+        expect![[r#"
+            digraph {
+                0 [ label = "nested! (0)" ]
+                1 [ label = "item! (4)" ]
+                2 [ label = "two_items! (1)" ]
+                3 [ label = "item! (2)" ]
+                4 [ label = "item! (3)" ]
+                0 -> 2 [ ]
+                2 -> 3 [ ]
+                2 -> 4 [ ]
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn derives_inside_expansions() {
+    check(
+        r#"
+//- minicore: derive, clone
+macro_rules! cloned { ($name:ident) => { #[derive(Clone)] struct $name; }; }
+
+cloned!(A);
+"#,
+        expect![[r##"
+            digraph {
+                0 [ label = "cloned! (0)" ]
+                1 [ label = "#[derive(Clone)] (1)" ]
+                0 -> 1 [ ]
+            }
+        "##]],
+    );
+}
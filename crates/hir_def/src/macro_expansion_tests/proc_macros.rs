@@ -3,9 +3,10 @@
 //! Note `//- proc_macros: identity` fixture metas in tests -- we don't use real
 //! proc-macros here, as that would be slow. Instead, we use several hard-coded
 //! in-memory macros.
+use base_db::{fixture::WithFixture, SourceDatabase};
 use expect_test::expect;
 
-use crate::macro_expansion_tests::check;
+use crate::{db::DefDatabase, macro_expansion_tests::check, test_db::TestDB};
 
 #[test]
 fn attribute_macro_attr_censoring() {
@@ -51,3 +52,21 @@ struct S;
 #[attr2] struct S;"##]],
     );
 }
+
+#[test]
+fn calls_with_equal_inputs_share_the_expansion() {
+    let db = TestDB::with_files(
+        r#"
+//- proc_macros: mirror
+proc_macros::mirror! { S struct }
+proc_macros::mirror! { S struct }
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let executed = db.log_executed(|| {
+        db.crate_def_map(krate);
+    });
+    let count = |query: &str| executed.iter().filter(|it| it.starts_with(query)).count();
+    assert_eq!(count("expand_proc_macro("), 2);
+    assert_eq!(count("expand_proc_macro_input("), 1);
+}
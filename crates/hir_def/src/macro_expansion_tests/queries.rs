@@ -0,0 +1,137 @@
+//! Tests for the `hir_expand` queries and helpers that inspect single macro
+//! calls.
+
+use base_db::{fixture::WithFixture, SourceDatabase, SourceDatabaseExt};
+use expect_test::{expect, Expect};
+use hir_expand::{db::AstDatabase, hygiene::Hygiene, HirFileId, InFile, MacroCallId, MacroFile};
+use stdx::format_to;
+use syntax::{ast, AstNode};
+
+use crate::{
+    db::DefDatabase, macro_expansion_tests::resolve_root_macro_calls, resolver::HasResolver,
+    test_db::TestDB, AsMacroCall,
+};
+
+/// Resolves every macro call written in the root module of the fixture and
+/// renders `f` for each of them.
+fn check(ra_fixture: &str, f: impl Fn(&TestDB, MacroCallId) -> String, expect: Expect) {
+    let (db, _, calls) = resolve_root_macro_calls(ra_fixture);
+    let mut actual = String::new();
+    for (_, macro_call_id, _) in calls {
+        format_to!(actual, "{}\n", f(&db, macro_call_id));
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn macro_arg_source_text_keeps_whitespace() {
+    check(
+        r#"
+macro_rules! m { ($($tt:tt)*) => {} }
+m!(a  +   b);
+m! {
+    struct S;
+}
+"#,
+        |db, id| format!("{:?}", db.macro_arg_source_text(id)),
+        expect![[r#"
+            Some("(a  +   b)")
+            Some("{\n    struct S;\n}")
+        "#]],
+    );
+}
+
+#[test]
+fn macro_expansion_text_is_unformatted() {
+    check(
+        r#"
+macro_rules! m { ($name:ident) => { struct $name; fn f() -> $name { $name } } }
+m!(S);
+"#,
+        |db, id| format!("{:?}", id.as_file().macro_expansion_text(db)),
+        expect![[r#"
+            Some("structS;fnf()->S{S}")
+        "#]],
+    );
+}
+
+#[test]
+fn no_macro_expansion_text_for_real_files() {
+    let (db, file_id) = TestDB::with_single_file("struct S;");
+    assert_eq!(HirFileId::from(file_id).macro_expansion_text(&db), None);
+}
+
+#[test]
+fn macro_def_span() {
+    check(
+        r#"
+macro_rules! m { () => {} }
+#[rustc_builtin_macro]
+macro_rules! stringify {}
+m!();
+stringify!(S);
+"#,
+        |db, id| match id.as_file().macro_def_span(db) {
+            Some(range) => db.file_text(range.file_id)[range.range].to_string(),
+            None => "None".to_string(),
+        },
+        expect![[r#"
+            macro_rules! m { () => {} }
+            None
+        "#]],
+    );
+}
+
+#[test]
+fn def_site_hygiene_resolves_dollar_crate_to_defining_crate() {
+    // `outer!` passes its own `$crate` into `lib::m!`, so in the expansion of `lib::m!` the first
+    // `$crate` comes from the call site while the second one is written in the definition.
+    let (db, _, calls) = resolve_root_macro_calls(
+        r#"
+//- /main.rs crate:main deps:lib
+macro_rules! outer { () => { lib::m!($crate::U); } }
+outer!();
+pub struct U;
+//- /lib.rs crate:lib
+#[macro_export]
+macro_rules! m { ($t:path) => { struct S($t, $crate::T); } }
+pub struct T;
+"#,
+    );
+    let krate = db.crate_graph().iter().next().unwrap();
+    let def_map = db.crate_def_map(krate);
+    let resolver = def_map.module_id(def_map.root()).resolver(&db);
+
+    let outer_file = calls[0].1.as_file();
+    let outer_expansion = db.parse_or_expand(outer_file).unwrap();
+    let nested_call = outer_expansion.descendants().find_map(ast::MacroCall::cast).unwrap();
+    let nested_id = InFile::new(outer_file, &nested_call)
+        .as_call_id(&db, krate, |path| resolver.resolve_path_as_macro(&db, &path))
+        .unwrap();
+
+    let call_site = Hygiene::new(&db, nested_id.as_file());
+    let def_site = Hygiene::for_def_site(MacroFile { macro_call_id: nested_id }, &db);
+    let crate_name = |hygiene: &Hygiene, name_ref: ast::NameRef| {
+        let krate = hygiene.name_ref_to_name(&db, name_ref).right().unwrap();
+        db.crate_graph()[krate].display_name.as_ref().unwrap().to_string()
+    };
+    let mut actual = String::new();
+    let expansion = db.parse_or_expand(nested_id.as_file()).unwrap();
+    for dollar_crate in
+        expansion.descendants().filter_map(ast::NameRef::cast).filter(|it| it.text() == "$crate")
+    {
+        let path = dollar_crate.syntax().ancestors().filter_map(ast::Path::cast).last().unwrap();
+        format_to!(
+            actual,
+            "{}: call site {}, def site {}\n",
+            path,
+            crate_name(&call_site, dollar_crate.clone()),
+            crate_name(&def_site, dollar_crate),
+        );
+    }
+    expect![[r#"
+        $crate::U: call site main, def site lib
+        $crate::T: call site lib, def site lib
+    "#]]
+    .assert_eq(&actual);
+}
@@ -610,6 +610,8 @@ impl DefCollector<'_> {
         macro_: MacroDefId,
         export: bool,
     ) {
+        self.def_map.modules[module_id].scope.declare_macro(macro_);
+
         // Textual scoping
         self.define_legacy_macro(module_id, name.clone(), macro_);
 
@@ -648,6 +650,7 @@ impl DefCollector<'_> {
         macro_: MacroDefId,
         vis: &RawVisibility,
     ) {
+        self.def_map.modules[module_id].scope.declare_macro(macro_);
         let vis =
             self.def_map.resolve_visibility(self.db, module_id, vis).unwrap_or(Visibility::Public);
         self.update(module_id, &[(Some(name), PerNs::macros(macro_, vis))], vis, ImportType::Named);
@@ -1758,8 +1761,9 @@ impl ModCollector<'_, '_> {
 
     fn is_builtin_or_registered_attr(&self, path: &ModPath) -> bool {
         if path.kind == PathKind::Plain {
+            // Raw identifiers name the same tools and attributes, so `r#` is left out here.
             if let Some(tool_module) = path.segments().first() {
-                let tool_module = tool_module.to_string();
+                let tool_module = tool_module.to_smol_str();
                 let is_tool = builtin_attr::TOOL_MODULES
                     .iter()
                     .copied()
@@ -1771,7 +1775,7 @@ impl ModCollector<'_, '_> {
             }
 
             if let Some(name) = path.as_ident() {
-                let name = name.to_string();
+                let name = name.to_smol_str();
                 let is_inert = builtin_attr::INERT_ATTRIBUTES
                     .iter()
                     .chain(builtin_attr::EXTRA_ATTRIBUTES)
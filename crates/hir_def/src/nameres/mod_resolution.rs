@@ -41,8 +41,9 @@ impl ModDir {
     ) -> Option<ModDir> {
         let path = match attr_path.map(|it| it.as_str()) {
             None => {
+                // Like the file of a raw module name, its directory has no `r#` prefix.
                 let mut path = self.dir_path.clone();
-                path.push(&name.to_string());
+                path.push(&name.to_smol_str());
                 path
             }
             Some(attr_path) => {
@@ -64,6 +65,8 @@ impl ModDir {
         attr_path: Option<&SmolStr>,
     ) -> Result<(FileId, bool, ModDir), String> {
         let orig_file_id = file_id.original_file(db.upcast());
+        // Raw module names (`mod r#async;`) refer to files without the `r#` prefix.
+        let name = name.to_smol_str();
 
         let mut candidate_files = Vec::new();
         match attr_path {
@@ -166,6 +166,63 @@ m!(Z);
     }
 }
 
+#[test]
+fn macro_rules_are_parsed_once_for_all_invocations() {
+    let (mut db, pos) = TestDB::with_position(
+        r#"
+//- /lib.rs
+macro_rules! m {
+    () => {};
+    ($ident:ident $($rest:ident)*) => {
+        fn $ident() {}
+        m!($($rest)*);
+    };
+}
+m!(a b c);
+m!(d e f);
+m!(g h i);
+fn quux() { 1$0 }
+"#,
+    );
+    let krate = db.test_crate();
+    {
+        let events = db.log_executed(|| {
+            let crate_def_map = db.crate_def_map(krate);
+            let (_, module_data) = crate_def_map.modules.iter().last().unwrap();
+            assert_eq!(module_data.scope.resolutions().count(), 10);
+        });
+        let n_parsed_macro_defs = events.iter().filter(|it| it.contains("macro_def")).count();
+        assert_eq!(n_parsed_macro_defs, 1);
+    }
+
+    let new_text = r#"
+macro_rules! m {
+    () => {};
+    ($ident:ident $($rest:ident)*) => {
+        fn $ident() {}
+        m!($($rest)*);
+    };
+}
+m!(a b c);
+m!(d e f);
+m!(g h i);
+fn quux() { 92 }
+"#;
+    db.set_file_text(pos.file_id, Arc::new(new_text.to_string()));
+
+    {
+        let events = db.log_executed(|| {
+            db.crate_def_map(krate);
+        });
+        // The definition is re-parsed once, as its file changed, but as it parses to the same
+        // rules, none of the invocations are expanded again.
+        let n_parsed_macro_defs = events.iter().filter(|it| it.contains("macro_def")).count();
+        assert_eq!(n_parsed_macro_defs, 1);
+        let n_expanded_macros = events.iter().filter(|it| it.contains("macro_expand")).count();
+        assert_eq!(n_expanded_macros, 0);
+    }
+}
+
 #[test]
 fn item_tree_prevents_reparsing() {
     // The `ItemTree` is used by both name resolution and the various queries in `adt.rs` and
@@ -129,14 +129,14 @@ use self::r#async::Bar;
 //- /async.rs
 pub struct Bar;
 "#,
-        expect![[r#"
+        expect![[r##"
             crate
             Bar: t v
-            async: t
+            r#async: t
 
-            crate::async
+            crate::r#async
             Bar: t v
-        "#]],
+        "##]],
     );
 }
 
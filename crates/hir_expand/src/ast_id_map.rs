@@ -112,6 +112,11 @@ impl AstIdMap {
         self.arena[id.raw].clone().cast::<N>().unwrap()
     }
 
+    /// Returns all nodes stored in the map together with their ids, in the order of allocation.
+    pub fn all_ids(&self) -> impl Iterator<Item = (SyntaxNodePtr, ErasedFileAstId)> + '_ {
+        self.arena.iter().map(|(id, ptr)| (ptr.clone(), id))
+    }
+
     fn alloc(&mut self, item: &SyntaxNode) -> ErasedFileAstId {
         self.arena.alloc(SyntaxNodePtr::new(item))
     }
@@ -145,3 +150,33 @@ fn bdfs(node: &SyntaxNode, mut f: impl FnMut(SyntaxNode) -> bool) {
         std::mem::swap(&mut curr_layer, &mut next_layer);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use syntax::SyntaxKind;
+
+    use super::*;
+
+    #[test]
+    fn all_ids_parents_first() {
+        let file = ast::SourceFile::parse("fn f() { struct S; } mod m { fn g(); }").tree();
+        let map = AstIdMap::from_source(file.syntax());
+        let kinds: Vec<SyntaxKind> =
+            map.all_ids().map(|(ptr, _)| ptr.to_node(file.syntax()).kind()).collect();
+        assert_eq!(
+            kinds,
+            [
+                SyntaxKind::FN,
+                SyntaxKind::MODULE,
+                SyntaxKind::BLOCK_EXPR,
+                SyntaxKind::FN,
+                SyntaxKind::STRUCT,
+            ]
+        );
+
+        let f = file.syntax().descendants().find_map(ast::Fn::cast).unwrap();
+        let (ptr, id) = map.all_ids().next().unwrap();
+        assert_eq!(map.ast_id(&f).raw, id);
+        assert_eq!(ptr, SyntaxNodePtr::new(f.syntax()));
+    }
+}
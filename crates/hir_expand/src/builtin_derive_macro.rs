@@ -4,7 +4,7 @@ use tracing::debug;
 
 use mbe::ExpandResult;
 use syntax::{
-    ast::{self, AstNode, HasGenericParams, HasModuleItem, HasName},
+    ast::{self, AstNode, HasAttrs, HasGenericParams, HasModuleItem, HasName},
     match_ast,
 };
 
@@ -148,6 +148,15 @@ fn make_type_args(n: usize, bound: Vec<tt::TokenTree>) -> Vec<tt::TokenTree> {
 }
 
 fn expand_simple_derive(tt: &tt::Subtree, trait_path: tt::Subtree) -> ExpandResult<tt::Subtree> {
+    expand_derive_with_items(tt, trait_path, Vec::new())
+}
+
+/// Like [`expand_simple_derive`], but puts `items` into the body of the impl.
+fn expand_derive_with_items(
+    tt: &tt::Subtree,
+    trait_path: tt::Subtree,
+    items: Vec<tt::TokenTree>,
+) -> ExpandResult<tt::Subtree> {
     let info = match parse_adt(tt) {
         Ok(info) => info,
         Err(e) => return ExpandResult::only_err(e),
@@ -159,7 +168,7 @@ fn expand_simple_derive(tt: &tt::Subtree, trait_path: tt::Subtree) -> ExpandResu
     let type_args = make_type_args(info.type_params, Vec::new());
     let trait_path = trait_path.token_trees;
     let expanded = quote! {
-        impl ##type_params ##trait_path for #name ##type_args {}
+        impl ##type_params ##trait_path for #name ##type_args { ##items }
     };
     ExpandResult::ok(expanded)
 }
@@ -207,7 +216,74 @@ fn default_expand(
     tt: &tt::Subtree,
 ) -> ExpandResult<tt::Subtree> {
     let krate = find_builtin_crate(db, id);
-    expand_simple_derive(tt, quote! { #krate::default::Default })
+    let items = default_fn(&krate, tt).map_or_else(Vec::new, |it| it.token_trees);
+    expand_derive_with_items(tt, quote! { #krate::default::Default }, items)
+}
+
+/// Builds `fn default()` for a struct. A field is initialized with the value of its
+/// `#[default = value]` attribute, if it has one, and with `Default::default()` otherwise.
+fn default_fn(krate: &tt::TokenTree, tt: &tt::Subtree) -> Option<tt::Subtree> {
+    let (parsed, token_map) =
+        mbe::token_tree_to_syntax_node(tt, mbe::ParserEntryPoint::Items).ok()?;
+    let strukt = match ast::MacroItems::cast(parsed.syntax_node())?.items().next()? {
+        ast::Item::Struct(it) => it,
+        _ => return None,
+    };
+
+    // The value is taken from the input token tree, so that it keeps its token ids.
+    let field_value = |field: &dyn HasAttrs| {
+        field
+            .attrs()
+            .filter(|attr| attr.simple_name().as_deref() == Some("default"))
+            .find_map(|attr| {
+                let id = token_map.token_by_range(attr.l_brack_token()?.text_range())?;
+                let attr_tt = find_subtree(tt, id)?;
+                let eq = attr_tt.token_trees.iter().position(|it| {
+                    matches!(it, tt::TokenTree::Leaf(tt::Leaf::Punct(tt::Punct { char: '=', .. })))
+                })?;
+                Some(attr_tt.token_trees[eq + 1..].to_vec())
+            })
+            .unwrap_or_else(|| {
+                let krate = krate.clone();
+                quote! { #krate::default::Default::default() }.token_trees
+            })
+    };
+    let value = match strukt.field_list() {
+        Some(ast::FieldList::RecordFieldList(fields)) => {
+            let fields = fields.fields().flat_map(|field| {
+                let name = field.name().map(|name| {
+                    let id = token_map
+                        .token_by_range(name.syntax().text_range())
+                        .unwrap_or_else(tt::TokenId::unspecified);
+                    tt::Ident { text: name.text().into(), id }
+                });
+                let value = field_value(&field);
+                name.map(|name| quote! { #name: ##value, }.token_trees).unwrap_or_default()
+            });
+            quote! { Self { ##fields } }
+        }
+        Some(ast::FieldList::TupleFieldList(fields)) => {
+            let fields = fields.fields().flat_map(|field| {
+                let value = field_value(&field);
+                quote! { ##value, }.token_trees
+            });
+            quote! { Self(##fields) }
+        }
+        None => quote! { Self },
+    };
+    Some(quote! {
+        fn default() -> Self { #value }
+    })
+}
+
+fn find_subtree(tt: &tt::Subtree, id: tt::TokenId) -> Option<&tt::Subtree> {
+    if tt.delimiter.map_or(false, |it| it.id == id) {
+        return Some(tt);
+    }
+    tt.token_trees.iter().find_map(|it| match it {
+        tt::TokenTree::Subtree(it) => find_subtree(it, id),
+        tt::TokenTree::Leaf(_) => None,
+    })
 }
 
 fn debug_expand(
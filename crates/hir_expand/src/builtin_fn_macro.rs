@@ -8,7 +8,10 @@ use base_db::{AnchoredPath, Edition, FileId};
 use cfg::CfgExpr;
 use either::Either;
 use mbe::{parse_exprs_with_sep, parse_to_token_tree, ExpandResult};
-use syntax::ast::{self, AstToken};
+use syntax::{
+    ast::{self, AstToken},
+    SmolStr,
+};
 
 macro_rules! register_builtin {
     ( LAZY: $(($name:ident, $kind: ident) => $expand:ident),* , EAGER: $(($e_name:ident, $e_kind: ident) => $e_expand:ident),*  ) => {
@@ -235,21 +238,75 @@ fn format_args_expand(
     //   std::fmt::ArgumentV1::new(&arg2,std::fmt::Display::fmt),
     // ])
     // ```,
-    // which is still not really correct, but close enough for now
+    // which is still not really correct, but close enough for now.
+    format_args_expand_with_captured(tt, |_| Vec::new())
+}
+
+/// Expands `format_args!` for completion. Unlike the regular expansion, the argument that is
+/// captured by the placeholder being edited (like `{na$0}`) is added to the arguments, as an
+/// ident carrying the token id of the format string. This lets completion inside the format
+/// string see an expression at the cursor.
+///
+/// The edited placeholder is the one that is captured in `speculative_tt`, but not in the actual
+/// arguments of the call `id`.
+pub(crate) fn format_args_expand_speculative(
+    db: &dyn AstDatabase,
+    id: MacroCallId,
+    speculative_tt: &tt::Subtree,
+    format_string_id: tt::TokenId,
+) -> ExpandResult<tt::Subtree> {
+    let actual_captured = db
+        .macro_arg(id)
+        .and_then(|arg| {
+            let format_string = parse_exprs_with_sep(&arg.0, ',').into_iter().next()?;
+            parse_string(&format_string).ok()
+        })
+        .map(|it| implicitly_captured_args(&it))
+        .unwrap_or_default();
+    format_args_expand_with_captured(speculative_tt, |format_string| {
+        implicitly_captured_args(format_string)
+            .into_iter()
+            .filter(|name| !actual_captured.contains(name))
+            .map(|name| tt::Ident { text: name, id: format_string_id })
+            .collect()
+    })
+}
+
+/// Expands `format_args!`, putting the arguments returned by `captured` for the format string
+/// before the explicit ones. That way the speculative expansion doesn't extend past the end of the
+/// actual one, which completion relies on. The format string itself is dropped: syntax
+/// highlighting relies on it not being mapped down into the expansion.
+fn format_args_expand_with_captured(
+    tt: &tt::Subtree,
+    captured: impl FnOnce(&str) -> Vec<tt::Ident>,
+) -> ExpandResult<tt::Subtree> {
     let mut args = parse_exprs_with_sep(tt, ',');
 
     if args.is_empty() {
         return ExpandResult::only_err(mbe::ExpandError::NoMatchingRule);
     }
+    let mut named_args = Vec::new();
     for arg in &mut args {
         // Remove `key =`.
         if matches!(arg.token_trees.get(1), Some(tt::TokenTree::Leaf(tt::Leaf::Punct(p))) if p.char == '=' && p.spacing != tt::Spacing::Joint)
         {
+            if let Some(tt::TokenTree::Leaf(tt::Leaf::Ident(name))) = arg.token_trees.first() {
+                named_args.push(name.text.clone());
+            }
             arg.token_trees.drain(..2);
         }
     }
-    let _format_string = args.remove(0);
-    let arg_tts = args.into_iter().flat_map(|arg| {
+    let format_string = args.remove(0);
+    let captured_args = parse_string(&format_string)
+        .map(|it| captured(&it))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|ident| !named_args.contains(&ident.text))
+        .map(|ident| tt::Subtree {
+            delimiter: None,
+            token_trees: vec![tt::Leaf::from(ident).into()],
+        });
+    let arg_tts = captured_args.chain(args).flat_map(|arg| {
         quote! { std::fmt::ArgumentV1::new(&(#arg), std::fmt::Display::fmt), }
     }.token_trees);
     let expanded = quote! {
@@ -264,6 +321,130 @@ fn format_args_expand(
     ExpandResult::ok(expanded)
 }
 
+/// A piece of a `format_args!` format string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatPiece {
+    /// Literal text, with `{{` and `}}` unescaped.
+    Literal(SmolStr),
+    /// A `{...}` placeholder. `index` is the position of the argument it refers to, arguments
+    /// that are captured implicitly (like `{name}`) are numbered after the explicit ones.
+    Argument { index: usize, format_spec: SmolStr },
+}
+
+pub(crate) fn format_args_pieces(
+    db: &dyn AstDatabase,
+    id: MacroCallId,
+) -> Option<Vec<FormatPiece>> {
+    let loc = db.lookup_intern_macro(id);
+    match loc.def.kind {
+        MacroDefKind::BuiltIn(
+            BuiltinFnLikeExpander::FormatArgs
+            | BuiltinFnLikeExpander::ConstFormatArgs
+            | BuiltinFnLikeExpander::FormatArgsNl,
+            _,
+        ) => (),
+        _ => return None,
+    }
+    let arg = db.macro_arg(id)?;
+    let mut args = parse_exprs_with_sep(&arg.0, ',');
+    if args.is_empty() {
+        return None;
+    }
+    let format_string = parse_string(&args.remove(0)).ok()?;
+    let named_args: Vec<_> = args
+        .iter()
+        .map(|arg| match &*arg.token_trees {
+            [tt::TokenTree::Leaf(tt::Leaf::Ident(name)), tt::TokenTree::Leaf(tt::Leaf::Punct(p)), ..]
+                if p.char == '=' && p.spacing != tt::Spacing::Joint =>
+            {
+                Some(name.text.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    let captured_args: Vec<_> = implicitly_captured_args(&format_string)
+        .into_iter()
+        .filter(|name| !named_args.contains(&Some(name.clone())))
+        .collect();
+
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut next_positional = 0;
+    let mut chars = format_string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                literal.push(c);
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        c => placeholder.push(c),
+                    }
+                }
+                let (arg, format_spec) = placeholder.split_once(':').unwrap_or((&placeholder, ""));
+                let arg = arg.trim();
+                let index = if arg.is_empty() {
+                    next_positional += 1;
+                    next_positional - 1
+                } else if let Ok(index) = arg.parse() {
+                    index
+                } else if let Some(index) =
+                    named_args.iter().position(|it| it.as_deref() == Some(arg))
+                {
+                    index
+                } else {
+                    args.len() + captured_args.iter().position(|it| it == arg)?
+                };
+                if !literal.is_empty() {
+                    pieces.push(FormatPiece::Literal(std::mem::take(&mut literal).into()));
+                }
+                pieces.push(FormatPiece::Argument { index, format_spec: format_spec.into() });
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(FormatPiece::Literal(literal.into()));
+    }
+    Some(pieces)
+}
+
+/// Returns the names of the arguments that are referred to by a `{name}` placeholder in the
+/// (unescaped) format string, in the order of their first appearance.
+fn implicitly_captured_args(format_string: &str) -> Vec<SmolStr> {
+    let mut res = Vec::new();
+    let mut chars = format_string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '}' || c == ':' {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                let name = name.trim();
+                let is_ident = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if is_ident && name != "_" && !res.iter().any(|it: &SmolStr| it == name) {
+                    res.push(name.into());
+                }
+            }
+            _ => (),
+        }
+    }
+    res
+}
+
 fn asm_expand(
     _db: &dyn AstDatabase,
     _id: MacroCallId,
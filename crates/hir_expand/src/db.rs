@@ -1,6 +1,9 @@
 //! Defines database & queries for macro expansion.
 
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use base_db::{salsa, SourceDatabase};
 use limit::Limit;
@@ -13,9 +16,12 @@ use syntax::{
 };
 
 use crate::{
-    ast_id_map::AstIdMap, hygiene::HygieneFrame, BuiltinAttrExpander, BuiltinDeriveExpander,
-    BuiltinFnLikeExpander, ExpandTo, HirFileId, HirFileIdRepr, MacroCallId, MacroCallKind,
-    MacroCallLoc, MacroDefId, MacroDefKind, MacroFile, ProcMacroExpander,
+    ast_id_map::AstIdMap,
+    builtin_fn_macro::{self, FormatPiece},
+    hygiene::HygieneFrame,
+    BuiltinAttrExpander, BuiltinDeriveExpander, BuiltinFnLikeExpander, ExpandTo, HirFileId,
+    HirFileIdRepr, MacroCallId, MacroCallKind, MacroCallLoc, MacroDefId, MacroDefKind, MacroFile,
+    ProcMacroExpander, ProcMacroInput,
 };
 
 /// Total limit on the number of tokens produced by any macro invocation.
@@ -112,6 +118,10 @@ pub trait AstDatabase: SourceDatabase {
     /// query, only typing in the macro call itself changes the returned
     /// subtree.
     fn macro_arg_text(&self, id: MacroCallId) -> Option<GreenNode>;
+    /// Returns the source text of the macro call's argument, for callers that
+    /// only need to inspect it and don't want to lower or expand the call.
+    #[salsa::transparent]
+    fn macro_arg_source_text(&self, id: MacroCallId) -> Option<Arc<str>>;
     /// Gets the expander for this macro. This compiles declarative macros, and
     /// just fetches procedural ones.
     fn macro_def(&self, id: MacroDefId) -> Result<Arc<TokenExpander>, mbe::ParseError>;
@@ -123,8 +133,15 @@ pub trait AstDatabase: SourceDatabase {
     /// non-determinism breaks salsa in a very, very, very bad way. @edwin0cheng
     /// heroically debugged this once!
     fn expand_proc_macro(&self, call: MacroCallId) -> ExpandResult<tt::Subtree>;
+    /// Runs a proc macro on an input. Calls with equal inputs share the expansion, so a call
+    /// that gets a new id, because items were added or removed before it, isn't expanded again.
+    fn expand_proc_macro_input(&self, input: ProcMacroInput) -> ExpandResult<tt::Subtree>;
     /// Firewall query that returns the error from the `macro_expand` query.
     fn macro_expand_error(&self, macro_call: MacroCallId) -> Option<ExpandError>;
+    /// Splits the format string of a `format_args!` call into literal pieces
+    /// and placeholders, returns `None` for other macro calls.
+    #[salsa::invoke(crate::builtin_fn_macro::format_args_pieces)]
+    fn format_args_pieces(&self, macro_call: MacroCallId) -> Option<Vec<FormatPiece>>;
 
     fn hygiene_frame(&self, file_id: HirFileId) -> Arc<HygieneFrame>;
 }
@@ -190,11 +207,18 @@ pub fn expand_speculative(
 
     // Do the actual expansion, we need to directly expand the proc macro due to the attribute args
     // Otherwise the expand query will fetch the non speculative attribute args and pass those instead.
-    let speculative_expansion = if let MacroDefKind::ProcMacro(expander, ..) = loc.def.kind {
-        tt.delimiter = None;
-        expander.expand(db, loc.krate, &tt, attr_arg.as_ref())
-    } else {
-        macro_def.expand(db, actual_macro_call, &tt)
+    let speculative_expansion = match loc.def.kind {
+        MacroDefKind::ProcMacro(expander, ..) => {
+            tt.delimiter = None;
+            expander.expand(db, loc.krate, &tt, attr_arg.as_ref())
+        }
+        MacroDefKind::BuiltIn(
+            BuiltinFnLikeExpander::FormatArgs
+            | BuiltinFnLikeExpander::ConstFormatArgs
+            | BuiltinFnLikeExpander::FormatArgsNl,
+            _,
+        ) => builtin_fn_macro::format_args_expand_speculative(db, actual_macro_call, &tt, token_id),
+        _ => macro_def.expand(db, actual_macro_call, &tt),
     };
 
     let expand_to = macro_expand_to(db, actual_macro_call);
@@ -253,6 +277,14 @@ fn parse_macro_expansion(
         None => return ExpandResult { value: None, err: result.err },
     };
 
+    if let Some(err) = &result.err {
+        // Safety check for recursive identity macro, done before parsing the expansion when the
+        // expansion is the call itself.
+        if expands_to_own_call(db, macro_file, &tt) {
+            return ExpandResult::only_err(err.clone());
+        }
+    }
+
     let expand_to = macro_expand_to(db, macro_file.macro_call_id);
 
     tracing::debug!("expanded = {}", tt.as_debug_string());
@@ -295,6 +327,7 @@ fn parse_macro_expansion(
 }
 
 fn macro_arg(db: &dyn AstDatabase, id: MacroCallId) -> Option<Arc<(tt::Subtree, mbe::TokenMap)>> {
+    let _p = profile::span("macro_arg");
     let arg = db.macro_arg_text(id)?;
     let loc = db.lookup_intern_macro(id);
 
@@ -361,6 +394,11 @@ fn macro_arg_text(db: &dyn AstDatabase, id: MacroCallId) -> Option<GreenNode> {
     Some(arg.green().into())
 }
 
+fn macro_arg_source_text(db: &dyn AstDatabase, id: MacroCallId) -> Option<Arc<str>> {
+    let arg = db.macro_arg_text(id)?;
+    Some(SyntaxNode::new_root(arg).to_string().into())
+}
+
 fn macro_def(db: &dyn AstDatabase, id: MacroDefId) -> Result<Arc<TokenExpander>, mbe::ParseError> {
     match id.kind {
         MacroDefKind::Declarative(ast_id) => {
@@ -458,12 +496,89 @@ fn expand_proc_macro(db: &dyn AstDatabase, id: MacroCallId) -> ExpandResult<tt::
         MacroCallKind::Attr { attr_args, .. } => {
             let mut attr_args = attr_args.0.clone();
             mbe::Shift::new(&macro_arg.0).shift_all(&mut attr_args);
-            Some(attr_args)
+            Some(Arc::new(attr_args))
         }
         _ => None,
     };
 
-    expander.expand(db, loc.krate, &macro_arg.0, attr_arg.as_ref())
+    db.expand_proc_macro_input(ProcMacroInput {
+        expander,
+        calling_crate: loc.krate,
+        tt: Arc::new(macro_arg.0.clone()),
+        attr_arg,
+    })
+}
+
+fn expand_proc_macro_input(
+    db: &dyn AstDatabase,
+    input: ProcMacroInput,
+) -> ExpandResult<tt::Subtree> {
+    input.expander.expand(db, input.calling_crate, &input.tt, input.attr_arg.as_deref())
+}
+
+/// The details of a single expansion of a macro call, for debugging macro expansion.
+#[derive(Debug)]
+pub struct ExpansionTrace {
+    pub kind: MacroCallKind,
+    /// The token tree the macro is invoked with.
+    pub input: Option<tt::Subtree>,
+    /// The token tree the macro expands to, or why the expansion failed.
+    pub output: ExpandResult<Option<tt::Subtree>>,
+    /// Time spent in the expander, not counting the lowering of the input.
+    pub duration: Duration,
+    /// Number of token ids allocated for the input.
+    pub token_ids: u32,
+}
+
+/// Expands `id` again, bypassing the query cache, and records what went into and came out of the
+/// expansion.
+pub fn trace_expansion(db: &dyn AstDatabase, id: MacroCallId) -> ExpansionTrace {
+    let _p = profile::span("trace_expansion");
+    let loc: MacroCallLoc = db.lookup_intern_macro(id);
+    let macro_arg = db.macro_arg(id);
+    let input = macro_arg.as_ref().map(|it| it.0.clone());
+    let token_ids = input.as_ref().and_then(max_token_id).map_or(0, |it| it + 1);
+
+    let start = Instant::now();
+    let output = match (&loc.eager, &macro_arg) {
+        (Some(eager), _) => ExpandResult::ok(Some(eager.arg_or_expansion.as_ref().clone())),
+        (None, None) => ExpandResult::str_err("Failed to lower macro args to token tree".into()),
+        (None, Some(macro_arg)) => match db.macro_def(loc.def).as_deref() {
+            // `TokenExpander::expand` would go through the `expand_proc_macro` query
+            Ok(TokenExpander::ProcMacro(_)) => expand_proc_macro(db, id).map(Some),
+            Ok(expander) => expander.expand(db, id, &macro_arg.0).map(Some),
+            Err(err) => ExpandResult::str_err(format!("invalid macro definition: {}", err)),
+        },
+    };
+    let duration = start.elapsed();
+
+    ExpansionTrace { kind: loc.kind, input, output, duration, token_ids }
+}
+
+fn max_token_id(subtree: &tt::Subtree) -> Option<u32> {
+    let ids = subtree.token_trees.iter().filter_map(|tt| match tt {
+        tt::TokenTree::Subtree(it) => max_token_id(it),
+        tt::TokenTree::Leaf(tt::Leaf::Ident(it)) => Some(it.id.0),
+        tt::TokenTree::Leaf(tt::Leaf::Literal(it)) => Some(it.id.0),
+        tt::TokenTree::Leaf(tt::Leaf::Punct(it)) => Some(it.id.0),
+    });
+    subtree
+        .delimiter
+        .map(|it| it.id.0)
+        .into_iter()
+        .chain(ids)
+        .filter(|&it| it != tt::TokenId::unspecified().0)
+        .max()
+}
+
+/// Whether `tt` consists of the same tokens as the macro call itself.
+fn expands_to_own_call(db: &dyn AstDatabase, macro_file: MacroFile, tt: &tt::Subtree) -> bool {
+    let call_node = match HirFileId::from(macro_file).call_node(db) {
+        Some(it) => it,
+        None => return false,
+    };
+    let (call_tt, _) = syntax_node_to_token_tree(&call_node.value);
+    mbe::structural_hash(tt) == mbe::structural_hash(&call_tt)
 }
 
 fn is_self_replicating(from: &SyntaxNode, to: &SyntaxNode) -> bool {
@@ -0,0 +1,71 @@
+//! A graph of which macro call produced which other macro call, rooted at the
+//! calls written in a single file.
+//!
+//! Only calls that have already been interned show up in the graph, so callers
+//! should run name resolution (or lower the bodies they are interested in)
+//! first.
+
+use base_db::{
+    salsa::{debug::DebugQueryTable, InternKey},
+    FileId,
+};
+use petgraph::{
+    dot::{Config, Dot},
+    graph::NodeIndex,
+    Graph,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    db::{AstDatabase, InternMacroQuery},
+    HirFileId, MacroCallId, MacroCallKind,
+};
+
+/// Directed graph with an edge from each macro call to the calls contained in
+/// its expansion.
+pub type MacroExpansionGraph = Graph<MacroCallId, ()>;
+
+/// Builds the [`MacroExpansionGraph`] of all macro calls in `file_id`,
+/// including the calls nested in their expansions.
+pub fn macro_expansion_graph(db: &dyn AstDatabase, file_id: FileId) -> MacroExpansionGraph {
+    let _p = profile::span("macro_expansion_graph");
+
+    let mut calls_in_file: FxHashMap<HirFileId, Vec<MacroCallId>> = FxHashMap::default();
+    for entry in InternMacroQuery.in_db(db).entries::<Vec<_>>() {
+        if let Some(call_id) = entry.value {
+            calls_in_file.entry(entry.key.kind.file_id()).or_default().push(call_id);
+        }
+    }
+    calls_in_file.values_mut().for_each(|calls| calls.sort_by_key(|it| it.as_intern_id()));
+
+    let mut graph = MacroExpansionGraph::new();
+    let mut queue: Vec<(Option<NodeIndex>, HirFileId)> = vec![(None, file_id.into())];
+    while let Some((parent, file)) = queue.pop() {
+        for &call_id in calls_in_file.get(&file).into_iter().flatten() {
+            let node = graph.add_node(call_id);
+            if let Some(parent) = parent {
+                graph.add_edge(parent, node, ());
+            }
+            queue.push((Some(node), call_id.as_file()));
+        }
+    }
+    graph
+}
+
+/// Renders `graph` in the `dot` format, labeling each call with the name of
+/// the invoked macro.
+pub fn macro_expansion_graph_dot(db: &dyn AstDatabase, graph: &MacroExpansionGraph) -> String {
+    let labeled = graph.map(|_, &call_id| call_label(db, call_id), |_, _| "");
+    format!("{}", Dot::with_config(&labeled, &[Config::EdgeNoLabel]))
+}
+
+fn call_label(db: &dyn AstDatabase, call_id: MacroCallId) -> String {
+    let name = match db.lookup_intern_macro(call_id).kind {
+        MacroCallKind::FnLike { ast_id, .. } => {
+            ast_id.to_node(db).path().map_or_else(String::new, |it| format!("{}!", it))
+        }
+        MacroCallKind::Derive { derive_name, .. } => format!("#[derive({})]", derive_name),
+        MacroCallKind::Attr { attr_name, .. } => format!("#[{}]", attr_name),
+    };
+    format!("{} ({})", name, call_id.as_intern_id().as_u32())
+}
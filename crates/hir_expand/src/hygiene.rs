@@ -33,6 +33,22 @@ impl Hygiene {
         Hygiene { frames: None }
     }
 
+    /// Creates a `Hygiene` for identifiers that are produced by the macro itself rather than
+    /// copied from the macro input, like fresh identifiers emitted by a proc macro. Such
+    /// identifiers are resolved relative to the crate defining the macro instead of the call
+    /// site, without mapping them back through the expansion.
+    pub fn for_def_site(macro_file: MacroFile, db: &dyn AstDatabase) -> Hygiene {
+        let loc = db.lookup_intern_macro(macro_file.macro_call_id);
+        let frame = HygieneFrame {
+            expansion: None,
+            local_inner: loc.def.local_inner,
+            krate: Some(loc.def.krate),
+            call_site: None,
+            def_site: None,
+        };
+        Hygiene { frames: Some(HygieneFrames(Arc::new(frame))) }
+    }
+
     // FIXME: this should just return name
     pub fn name_ref_to_name(
         &self,
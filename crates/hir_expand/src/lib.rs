@@ -14,6 +14,7 @@ pub mod builtin_fn_macro;
 pub mod proc_macro;
 pub mod quote;
 pub mod eager;
+pub mod expansion_graph;
 
 use base_db::ProcMacroKind;
 use either::Either;
@@ -35,7 +36,7 @@ use crate::{
     builtin_derive_macro::BuiltinDeriveExpander,
     builtin_fn_macro::{BuiltinFnLikeExpander, EagerExpander},
     db::TokenExpander,
-    proc_macro::ProcMacroExpander,
+    proc_macro::{ProcMacroExpander, ProcMacroInput},
 };
 
 #[cfg(test)]
@@ -87,7 +88,7 @@ impl_intern_key!(MacroCallId);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MacroCallLoc {
     pub def: MacroDefId,
-    pub(crate) krate: CrateId,
+    pub krate: CrateId,
     eager: Option<EagerCallInfo>,
     pub kind: MacroCallKind,
 }
@@ -184,6 +185,41 @@ impl HirFileId {
         }
     }
 
+    /// If this is a macro expansion file, returns the source range of the definition of the
+    /// expanded macro. Built-in macros have no meaningful definition site, so this returns `None`
+    /// for them, while for proc macros the range of the annotated function is returned.
+    pub fn macro_def_span(self, db: &dyn db::AstDatabase) -> Option<FileRange> {
+        let macro_file = match self.0 {
+            HirFileIdRepr::FileId(_) => return None,
+            HirFileIdRepr::MacroFile(macro_file) => macro_file,
+        };
+        let loc: MacroCallLoc = db.lookup_intern_macro(macro_file.macro_call_id);
+        match loc.def.kind {
+            MacroDefKind::Declarative(id) => {
+                Some(InFile::new(id.file_id, id.to_node(db)).original_file_range(db))
+            }
+            MacroDefKind::ProcMacro(_, _, id) => {
+                Some(InFile::new(id.file_id, id.to_node(db)).original_file_range(db))
+            }
+            MacroDefKind::BuiltIn(..)
+            | MacroDefKind::BuiltInAttr(..)
+            | MacroDefKind::BuiltInDerive(..)
+            | MacroDefKind::BuiltInEager(..) => None,
+        }
+    }
+
+    /// If this is a macro expansion file, returns the text of the expansion. Meant for debugging,
+    /// the text is not formatted in any way.
+    pub fn macro_expansion_text(self, db: &dyn db::AstDatabase) -> Option<String> {
+        match self.0 {
+            HirFileIdRepr::FileId(_) => None,
+            HirFileIdRepr::MacroFile(macro_file) => {
+                let (parse, _) = db.parse_macro_expansion(macro_file).value?;
+                Some(parse.syntax_node().to_string())
+            }
+        }
+    }
+
     /// Return expansion information if it is a macro-expansion file
     pub fn expansion_info(self, db: &dyn db::AstDatabase) -> Option<ExpansionInfo> {
         match self.0 {
@@ -316,7 +352,7 @@ impl MacroDefId {
 
 impl MacroCallKind {
     /// Returns the file containing the macro invocation.
-    fn file_id(&self) -> HirFileId {
+    pub fn file_id(&self) -> HirFileId {
         match self {
             MacroCallKind::FnLike { ast_id, .. } => ast_id.file_id,
             MacroCallKind::Derive { ast_id, .. } | MacroCallKind::Attr { ast_id, .. } => {
@@ -692,6 +728,27 @@ impl<N: AstNode> InFile<N> {
     }
 }
 
+/// Maps typed AST nodes back to the original file, like the `InFile<&SyntaxNode>` methods do.
+///
+/// This is a trait rather than an inherent impl, as an inherent `InFile<N: AstNode>` impl with the
+/// same method names would overlap with the `InFile<&SyntaxNode>` one.
+pub trait InFileAstExt {
+    /// Falls back to the macro call range if the node cannot be mapped up fully.
+    fn original_file_range(&self, db: &dyn db::AstDatabase) -> FileRange;
+    /// Attempts to map the node back up its macro calls.
+    fn original_file_range_opt(&self, db: &dyn db::AstDatabase) -> Option<FileRange>;
+}
+
+impl<N: AstNode> InFileAstExt for InFile<N> {
+    fn original_file_range(&self, db: &dyn db::AstDatabase) -> FileRange {
+        self.syntax().original_file_range(db)
+    }
+
+    fn original_file_range_opt(&self, db: &dyn db::AstDatabase) -> Option<FileRange> {
+        self.syntax().original_file_range_opt(db)
+    }
+}
+
 /// In Rust, macros expand token trees to token trees. When we want to turn a
 /// token tree into an AST node, we need to figure out what kind of AST node we
 /// want: something like `foo` can be a type, an expression, or a pattern.
@@ -733,15 +790,13 @@ impl ExpandTo {
             MACRO_PAT => ExpandTo::Pattern,
             MACRO_TYPE => ExpandTo::Type,
 
+            // Macro calls in the pattern slots of `let`, `for` and match arms are wrapped in
+            // `MACRO_PAT`, so only the expression slots end up here.
             ARG_LIST | TRY_EXPR | TUPLE_EXPR | PAREN_EXPR | ARRAY_EXPR | FOR_EXPR | PATH_EXPR
             | CLOSURE_EXPR | CONDITION | BREAK_EXPR | RETURN_EXPR | MATCH_EXPR | MATCH_ARM
             | MATCH_GUARD | RECORD_EXPR_FIELD | CALL_EXPR | INDEX_EXPR | METHOD_CALL_EXPR
             | FIELD_EXPR | AWAIT_EXPR | CAST_EXPR | REF_EXPR | PREFIX_EXPR | RANGE_EXPR
-            | BIN_EXPR => ExpandTo::Expr,
-            LET_STMT => {
-                // FIXME: Handle LHS Pattern
-                ExpandTo::Expr
-            }
+            | BIN_EXPR | LET_STMT => ExpandTo::Expr,
 
             _ => {
                 // Unknown , Just guess it is `Items`
@@ -750,3 +805,40 @@ impl ExpandTo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use syntax::{ast, AstNode, SourceFile};
+
+    use super::ExpandTo;
+
+    fn check(ra_fixture: &str, expected: &[ExpandTo]) {
+        let file = SourceFile::parse(ra_fixture).tree();
+        let actual: Vec<_> = file
+            .syntax()
+            .descendants()
+            .filter_map(ast::MacroCall::cast)
+            .map(|call| ExpandTo::from_call_site(&call))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn expand_to_let_stmt() {
+        check("fn f() { let x = m!(); }", &[ExpandTo::Expr]);
+        check("fn f() { let m!() = m!(); }", &[ExpandTo::Pattern, ExpandTo::Expr]);
+    }
+
+    #[test]
+    fn expand_to_for_expr() {
+        check("fn f() { for x in m!() {} }", &[ExpandTo::Expr]);
+        check("fn f() { for m!() in m!() {} }", &[ExpandTo::Pattern, ExpandTo::Expr]);
+    }
+
+    #[test]
+    fn expand_to_match_arm() {
+        check("fn f() { match () { _ => m!(), } }", &[ExpandTo::Expr]);
+        check("fn f() { match () { m!() => m!(), } }", &[ExpandTo::Pattern, ExpandTo::Expr]);
+        check("fn f() { match () { _ if m!() => (), } }", &[ExpandTo::Expr]);
+    }
+}
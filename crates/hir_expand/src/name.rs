@@ -1,14 +1,24 @@
 //! See [`Name`].
 
-use std::fmt;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 use syntax::{ast, SmolStr};
 
 /// `Name` is a wrapper around string, which is used in hir for both references
 /// and declarations. In theory, names should also carry hygiene info, but we are
 /// not there yet!
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Name(Repr);
+///
+/// Names remember whether they were written as raw identifiers (`r#match`), but
+/// `match` and `r#match` are still considered to be the same name.
+#[derive(Debug, Clone)]
+pub struct Name {
+    repr: Repr,
+    is_raw: bool,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum Repr {
@@ -16,9 +26,36 @@ enum Repr {
     TupleField(usize),
 }
 
+impl PartialEq for Name {
+    fn eq(&self, other: &Name) -> bool {
+        self.repr == other.repr
+    }
+}
+
+impl Eq for Name {}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.repr.hash(state);
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Name) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Name) -> Ordering {
+        self.repr.cmp(&other.repr)
+    }
+}
+
 impl fmt::Display for Name {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self.0 {
+        match &self.repr {
+            Repr::Text(text) if self.is_raw => write!(f, "r#{}", text),
             Repr::Text(text) => fmt::Display::fmt(&text, f),
             Repr::TupleField(idx) => fmt::Display::fmt(&idx, f),
         }
@@ -30,11 +67,11 @@ impl Name {
     /// Hopefully, this should allow us to integrate hygiene cleaner in the
     /// future, and to switch to interned representation of names.
     const fn new_text(text: SmolStr) -> Name {
-        Name(Repr::Text(text))
+        Name { repr: Repr::Text(text), is_raw: false }
     }
 
     pub fn new_tuple_field(idx: usize) -> Name {
-        Name(Repr::TupleField(idx))
+        Name { repr: Repr::TupleField(idx), is_raw: false }
     }
 
     pub fn new_lifetime(lt: &ast::Lifetime) -> Name {
@@ -46,14 +83,20 @@ impl Name {
         Name::new_text(SmolStr::new_inline(text))
     }
 
-    /// Resolve a name from the text of token.
-    fn resolve(raw_text: &str) -> Name {
-        match raw_text.strip_prefix("r#") {
-            Some(text) => Name::new_text(SmolStr::new(text)),
-            None => Name::new_text(raw_text.into()),
+    /// Creates a name from the text of an identifier, which may be a raw identifier like
+    /// `r#match`. Raw names compare equal to their non-raw counterparts, but are displayed with
+    /// the `r#` prefix.
+    pub fn from_str_raw(text: &str) -> Name {
+        match text.strip_prefix("r#") {
+            Some(text) => Name { repr: Repr::Text(SmolStr::new(text)), is_raw: true },
+            None => Name::new_text(text.into()),
         }
     }
 
+    pub fn is_raw(&self) -> bool {
+        self.is_raw
+    }
+
     /// A fake name for things missing in the source code.
     ///
     /// For example, `impl Foo for {}` should be treated as a trait impl for a
@@ -68,11 +111,29 @@ impl Name {
     }
 
     pub fn as_tuple_index(&self) -> Option<usize> {
-        match self.0 {
+        match self.repr {
             Repr::TupleField(idx) => Some(idx),
             _ => None,
         }
     }
+
+    /// Returns the text of the name. Tuple field names are stored as indices, so they have no
+    /// text to borrow and `None` is returned for them.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.repr {
+            Repr::Text(text) => Some(text),
+            Repr::TupleField(_) => None,
+        }
+    }
+
+    /// Returns the text of the name without the `r#` prefix of raw identifiers, which `Display`
+    /// does print. Doesn't allocate for textual names.
+    pub fn to_smol_str(&self) -> SmolStr {
+        match &self.repr {
+            Repr::Text(text) => text.clone(),
+            Repr::TupleField(idx) => SmolStr::new(idx.to_string()),
+        }
+    }
 }
 
 pub trait AsName {
@@ -83,14 +144,14 @@ impl AsName for ast::NameRef {
     fn as_name(&self) -> Name {
         match self.as_tuple_field() {
             Some(idx) => Name::new_tuple_field(idx),
-            None => Name::resolve(&self.text()),
+            None => Name::from_str_raw(&self.text()),
         }
     }
 }
 
 impl AsName for ast::Name {
     fn as_name(&self) -> Name {
-        Name::resolve(&self.text())
+        Name::from_str_raw(&self.text())
     }
 }
 
@@ -105,7 +166,7 @@ impl AsName for ast::NameOrNameRef {
 
 impl AsName for tt::Ident {
     fn as_name(&self) -> Name {
-        Name::resolve(&self.text)
+        Name::from_str_raw(&self.text)
     }
 }
 
@@ -338,3 +399,28 @@ pub mod known {
 }
 
 pub use crate::name;
+
+#[cfg(test)]
+mod tests {
+    use syntax::AstNode;
+
+    use super::*;
+
+    #[test]
+    fn raw_names() {
+        let raw = Name::from_str_raw("r#match");
+        assert!(raw.is_raw());
+        assert_eq!(raw.to_string(), "r#match");
+        assert_eq!(raw.as_str(), Some("match"));
+
+        let plain = Name::from_str_raw("match");
+        assert!(!plain.is_raw());
+        assert_eq!(plain.to_string(), "match");
+        assert_eq!(raw, plain);
+        assert_eq!(raw.cmp(&plain), Ordering::Equal);
+
+        let file = syntax::SourceFile::parse("fn r#match() {}").tree();
+        let name = file.syntax().descendants().find_map(ast::Name::cast).unwrap();
+        assert_eq!(name.as_name().to_string(), "r#match");
+    }
+}
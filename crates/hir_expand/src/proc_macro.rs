@@ -1,5 +1,10 @@
 //! Proc Macro Expander stub
 
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
 use crate::db::AstDatabase;
 use base_db::{CrateId, ProcMacroExpansionError, ProcMacroId};
 use mbe::ExpandResult;
@@ -55,3 +60,23 @@ impl ProcMacroExpander {
         }
     }
 }
+
+/// The input of a proc macro expansion, see [`AstDatabase::expand_proc_macro_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcMacroInput {
+    pub expander: ProcMacroExpander,
+    pub calling_crate: CrateId,
+    pub tt: Arc<tt::Subtree>,
+    pub attr_arg: Option<Arc<tt::Subtree>>,
+}
+
+// The trees are hashed by value, so that equal inputs of different calls share the expansion.
+// Leaving token ids out of the hash is fine, as equal inputs also have equal ids.
+impl Hash for ProcMacroInput {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expander.hash(state);
+        self.calling_crate.hash(state);
+        mbe::structural_hash(&self.tt).hash(state);
+        self.attr_arg.as_deref().map(mbe::structural_hash).hash(state);
+    }
+}
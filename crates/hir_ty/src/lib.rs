@@ -301,7 +301,7 @@ pub(crate) fn fold_free_vars<T: HasInterner<Interner = Interner> + Fold<Interner
     t.fold_with(&mut FreeVarFolder(f), DebruijnIndex::INNERMOST).expect("fold failed unexpectedly")
 }
 
-pub(crate) fn fold_tys<T: HasInterner<Interner = Interner> + Fold<Interner>>(
+pub fn fold_tys<T: HasInterner<Interner = Interner> + Fold<Interner>>(
     t: T,
     f: impl FnMut(Ty, DebruijnIndex) -> Ty,
     binders: DebruijnIndex,
@@ -9,7 +9,7 @@ use ide_db::{
     search::FileReference,
     RootDatabase,
 };
-use syntax::{ast, AstNode, SyntaxKind::NAME, TextRange};
+use syntax::{ast, AstNode, SyntaxKind::NAME, SyntaxNode, TextRange};
 
 use crate::{display::TryToNav, goto_definition, FilePosition, NavigationTarget, RangeInfo};
 
@@ -94,12 +94,12 @@ pub(crate) fn outgoing_calls(db: &RootDatabase, position: FilePosition) -> Optio
         .into_iter()
         .filter_map(|it| it.ancestors().nth(1).and_then(ast::Item::cast))
         .filter_map(|item| match item {
-            ast::Item::Const(c) => c.body().map(|it| it.syntax().descendants()),
-            ast::Item::Fn(f) => f.body().map(|it| it.syntax().descendants()),
-            ast::Item::Static(s) => s.body().map(|it| it.syntax().descendants()),
+            ast::Item::Const(c) => c.body().map(|it| it.syntax().clone()),
+            ast::Item::Fn(f) => f.body().map(|it| it.syntax().clone()),
+            ast::Item::Static(s) => s.body().map(|it| it.syntax().clone()),
             _ => None,
         })
-        .flatten()
+        .flat_map(|body| descendants_with_expansions(&sema, body))
         .filter_map(ast::CallableExpr::cast)
         .filter_map(|call_node| {
             let (nav_target, range) = match call_node {
@@ -108,14 +108,14 @@ pub(crate) fn outgoing_calls(db: &RootDatabase, position: FilePosition) -> Optio
                     let callable = sema.type_of_expr(&expr)?.original.as_callable(db)?;
                     match callable.kind() {
                         hir::CallableKind::Function(it) => {
-                            let range = expr.syntax().text_range();
+                            let range = sema.original_range(expr.syntax()).range;
                             it.try_to_nav(db).zip(Some(range))
                         }
                         _ => None,
                     }
                 }
                 ast::CallableExpr::MethodCall(expr) => {
-                    let range = expr.name_ref()?.syntax().text_range();
+                    let range = sema.original_range(expr.name_ref()?.syntax()).range;
                     let function = sema.resolve_method_call(&expr)?;
                     function.try_to_nav(db).zip(Some(range))
                 }
@@ -127,6 +127,26 @@ pub(crate) fn outgoing_calls(db: &RootDatabase, position: FilePosition) -> Optio
     Some(calls.into_items())
 }
 
+/// Returns all descendants of `node`, including the nodes of the macro calls within it, expanded.
+fn descendants_with_expansions(
+    sema: &Semantics<RootDatabase>,
+    node: SyntaxNode,
+) -> Vec<SyntaxNode> {
+    let mut res = Vec::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        for node in node.descendants() {
+            if let Some(expansion) =
+                ast::MacroCall::cast(node.clone()).and_then(|it| sema.expand(&it))
+            {
+                stack.push(expansion);
+            }
+            res.push(node);
+        }
+    }
+    res
+}
+
 #[derive(Default)]
 struct CallLocations {
     funcs: IndexMap<NavigationTarget, Vec<TextRange>>,
@@ -459,8 +479,36 @@ fn caller$0() {
 "#,
             expect![[r#"caller Function FileId(0) 160..194 163..169"#]],
             expect![[]],
-            // FIXME
+            expect![[r#"callee Function FileId(0) 144..159 152..158 : [184..190]"#]],
+        );
+    }
+
+    #[test]
+    fn test_call_hierarchy_through_macro_expansions() {
+        check_hierarchy(
+            r#"
+macro_rules! call_both {
+    () => {
+        first();
+        second(S.third())
+    }
+}
+struct S;
+impl S {
+    fn third(&self) {}
+}
+fn first() {}
+fn second(_: ()) {}
+fn caller$0() {
+    call_both!();
+}
+"#,
+            expect![[r#"caller Function FileId(0) 166..199 169..175"#]],
             expect![[]],
+            expect![[r#"
+                first Function FileId(0) 132..145 135..140 : [184..196]
+                second Function FileId(0) 146..165 149..155 : [184..196]
+                third Function FileId(0) 111..129 114..119 : [184..196]"#]],
         );
     }
 }
@@ -4,8 +4,8 @@ use std::fmt;
 
 use either::Either;
 use hir::{
-    AssocItem, Documentation, FieldSource, HasAttrs, HasSource, HirDisplay, InFile, ModuleSource,
-    Semantics,
+    AssocItem, Documentation, FieldSource, HasAttrs, HasSource, HirDisplay, InFile, InFileAstExt,
+    ModuleSource, Semantics,
 };
 use ide_db::{
     base_db::{FileId, FileRange},
@@ -293,8 +293,8 @@ impl TryToNav for hir::Impl {
         let src = self.source(db)?;
         let derive_attr = self.is_builtin_derive(db);
         let frange = match &derive_attr {
-            Some(item) => item.syntax().original_file_range(db),
-            None => src.syntax().original_file_range(db),
+            Some(item) => item.original_file_range(db),
+            None => src.original_file_range(db),
         };
         let focus_range = if derive_attr.is_some() {
             None
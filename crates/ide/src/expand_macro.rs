@@ -14,12 +14,15 @@ pub struct ExpandedMacro {
 
 // Feature: Expand Macro Recursively
 //
-// Shows the full macro expansion of the macro at current cursor.
+// Shows the full macro expansion of the macro at current cursor. The expansion is updated as you
+// edit the macro's arguments. Previews of several macro calls can be kept open side by side, each
+// following its own call.
 //
 // |===
 // | Editor  | Action Name
 //
 // | VS Code | **Rust Analyzer: Expand macro recursively**
+// | VS Code | **Rust Analyzer: Preview macro expansion**
 // |===
 //
 // image::https://user-images.githubusercontent.com/48062697/113020648-b3973180-917a-11eb-84a9-ecb921293dc5.gif[]
@@ -73,6 +76,24 @@ pub(crate) fn expand_macro(db: &RootDatabase, position: FilePosition) -> Option<
     Some(ExpandedMacro { name: name.unwrap_or_else(|| "???".to_owned()), expansion })
 }
 
+/// Returns the text of the expansion of the macro call at `position`, without expanding the calls
+/// in it and without formatting it. See [`hir::HirFileId::macro_expansion_text`].
+pub(crate) fn macro_expansion_text(db: &RootDatabase, position: FilePosition) -> Option<String> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+
+    let tok = pick_best_token(file.syntax().token_at_offset(position.offset), |kind| match kind {
+        SyntaxKind::IDENT => 1,
+        _ => 0,
+    })?;
+
+    let expanded = tok.ancestors().find_map(|node| match ast::Item::cast(node.clone()) {
+        Some(item) if sema.is_attr_macro_call(&item) => sema.expand_attr_macro(&item),
+        _ => sema.expand(&ast::MacroCall::cast(node)?),
+    })?;
+    sema.hir_file_for(&expanded).macro_expansion_text(db)
+}
+
 fn expand_macro_recur(
     sema: &Semantics<RootDatabase>,
     macro_call: &ast::MacroCall,
@@ -197,6 +218,38 @@ mod tests {
         expect.assert_eq(&actual);
     }
 
+    #[track_caller]
+    fn check_expansion_text(ra_fixture: &str, expect: Expect) {
+        let (analysis, pos) = fixture::position(ra_fixture);
+        let text = analysis.macro_expansion_text(pos).unwrap();
+        expect.assert_eq(&format!("{:?}", text));
+    }
+
+    #[test]
+    fn macro_expansion_text_is_not_recursive() {
+        check_expansion_text(
+            r#"
+macro_rules! double {
+    ($e:expr) => { $e * 2 };
+}
+fn f() -> u32 {
+    double!(double!(1$0))
+}
+"#,
+            expect![[r#"Some("double!(1)*2")"#]],
+        );
+    }
+
+    #[test]
+    fn no_macro_expansion_text_outside_of_macro_calls() {
+        check_expansion_text(
+            r#"
+fn f$0() {}
+"#,
+            expect![[r#"None"#]],
+        );
+    }
+
     #[test]
     fn macro_expand_recursive_expansion() {
         check(
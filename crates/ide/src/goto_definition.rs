@@ -298,6 +298,53 @@ fn bar() {
         );
     }
 
+    #[test]
+    fn goto_def_for_builtin_macros() {
+        check(
+            r#"
+#[rustc_builtin_macro]
+macro_rules! concat {}
+           //^^^^^^
+fn bar() {
+    conc$0at!("a", "b");
+}
+"#,
+        );
+        check(
+            r#"
+#[rustc_builtin_macro]
+pub macro Copy {}
+        //^^^^
+#[derive(Cop$0y)]
+struct S;
+"#,
+        );
+    }
+
+    #[test]
+    fn goto_def_for_proc_macros() {
+        let (analysis, position) = fixture::position(
+            r#"
+//- proc_macros: mirror
+proc_macros::mir$0ror! {}
+"#,
+        );
+        let navs = analysis.goto_definition(position).unwrap().expect("no definition found").info;
+        assert_eq!(navs.len(), 1);
+        assert_eq!(navs[0].name, "mirror");
+
+        let (analysis, position) = fixture::position(
+            r#"
+//- proc_macros: identity
+#[proc_macros::ident$0ity]
+fn foo() {}
+"#,
+        );
+        let navs = analysis.goto_definition(position).unwrap().expect("no definition found").info;
+        assert_eq!(navs.len(), 1);
+        assert_eq!(navs[0].name, "identity");
+    }
+
     #[test]
     fn goto_def_for_macros_from_other_crates() {
         check(
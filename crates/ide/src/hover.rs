@@ -6,15 +6,15 @@ mod tests;
 use std::iter;
 
 use either::Either;
-use hir::{HasSource, Semantics};
+use hir::{HasSource, InFile, Semantics};
 use ide_db::{
     base_db::FileRange,
     defs::Definition,
     helpers::{pick_best_token, FamousDefs},
-    RootDatabase,
+    RootDatabase, SymbolKind,
 };
 use itertools::Itertools;
-use syntax::{ast, match_ast, AstNode, SyntaxKind::*, SyntaxNode, SyntaxToken, T};
+use syntax::{ast, match_ast, AstNode, NodeOrToken, SyntaxKind::*, SyntaxNode, SyntaxToken, T};
 
 use crate::{
     display::TryToNav,
@@ -48,6 +48,8 @@ pub enum HoverAction {
     Implementation(FilePosition),
     Reference(FilePosition),
     GoToType(Vec<HoverGotoTypeData>),
+    /// The definition of the macro whose expansion the hovered token comes from.
+    GoToMacroDef(NavigationTarget),
 }
 
 impl HoverAction {
@@ -152,7 +154,13 @@ pub(crate) fn hover(
             return res;
         }
     }
-    result.map(|res| RangeInfo::new(original_token.text_range(), res))
+    result.map(|mut res| {
+        if let Some(action) = descended.iter().find_map(|token| goto_macro_def_action(sema, token))
+        {
+            res.actions.push(action);
+        }
+        RangeInfo::new(original_token.text_range(), res)
+    })
 }
 
 pub(crate) fn hover_for_definition(
@@ -308,6 +316,38 @@ fn runnable_action(
     }
 }
 
+fn goto_macro_def_action(
+    sema: &Semantics<RootDatabase>,
+    token: &SyntaxToken,
+) -> Option<HoverAction> {
+    let FileRange { file_id, range } =
+        sema.hir_file_for(&token.parent()?).macro_def_span(sema.db)?;
+    let node = match sema.parse(file_id).syntax().covering_element(range) {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    // Proc macros are defined by the function they are annotated on.
+    let def = node.ancestors().find_map(|it| {
+        match_ast! {
+            match it {
+                ast::Macro(it) => Some(Either::Left(it)),
+                ast::Fn(it) => Some(Either::Right(it)),
+                _ => None,
+            }
+        }
+    })?;
+    let name_owner: &dyn ast::HasName = match &def {
+        Either::Left(it) => it,
+        Either::Right(it) => it,
+    };
+    let nav = NavigationTarget::from_named(
+        sema.db,
+        InFile::new(file_id.into(), name_owner),
+        SymbolKind::Macro,
+    );
+    Some(HoverAction::GoToMacroDef(nav))
+}
+
 fn goto_type_action_for_def(db: &RootDatabase, def: Definition) -> Option<HoverAction> {
     let mut targets: Vec<hir::ModuleDef> = Vec::new();
     let mut push_new_def = |item: hir::ModuleDef| {
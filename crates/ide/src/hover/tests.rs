@@ -1220,6 +1220,40 @@ fn test_hover_trait_show_qualifiers() {
     );
 }
 
+#[test]
+fn test_hover_links_to_macro_definition() {
+    check_actions(
+        r#"
+macro_rules! id { ($($tt:tt)*) => { $($tt)* } }
+struct Foo;
+fn main() { id!(let _ = Fo$0o;); }
+"#,
+        expect![[r#"
+            [
+                Implementation(
+                    FilePosition {
+                        file_id: FileId(
+                            0,
+                        ),
+                        offset: 55,
+                    },
+                ),
+                GoToMacroDef(
+                    NavigationTarget {
+                        file_id: FileId(
+                            0,
+                        ),
+                        full_range: 0..47,
+                        focus_range: 13..15,
+                        name: "id",
+                        kind: Macro,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_hover_extern_crate() {
     check(
@@ -2268,8 +2302,8 @@ fn foo() {
                                     file_id: FileId(
                                         1,
                                     ),
-                                    full_range: 254..436,
-                                    focus_range: 293..299,
+                                    full_range: 255..437,
+                                    focus_range: 294..300,
                                     name: "Future",
                                     kind: Trait,
                                     description: "pub trait Future",
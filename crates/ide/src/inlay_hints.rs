@@ -16,6 +16,7 @@ pub struct InlayHintsConfig {
     pub type_hints: bool,
     pub parameter_hints: bool,
     pub chaining_hints: bool,
+    pub macro_introduced_bindings: bool,
     pub max_length: Option<usize>,
 }
 
@@ -43,6 +44,7 @@ pub struct InlayHint {
 // * types of local variables
 // * names of function arguments
 // * types of chained expressions
+// * types of variables introduced by macro calls, if enabled
 //
 // **Note:** VS Code does not have native support for inlay hints https://github.com/microsoft/vscode/issues/16221[yet] and the hints are implemented using decorations.
 // This approach has limitations, the caret movement and bracket highlighting near the edges of the hint may be weird:
@@ -77,6 +79,9 @@ pub(crate) fn inlay_hints(
                 ast::Expr::MethodCallExpr(it) => {
                     get_param_name_hints(&mut res, &sema, config, ast::Expr::from(it));
                 }
+                ast::Expr::MacroCall(it) => {
+                    get_macro_binding_hints(&mut res, &sema, config, &it);
+                }
                 _ => (),
             }
         } else if let Some(it) = ast::IdentPat::cast(node.clone()) {
@@ -232,6 +237,44 @@ fn get_bind_pat_hints(
     Some(())
 }
 
+fn get_macro_binding_hints(
+    acc: &mut Vec<InlayHint>,
+    sema: &Semantics<RootDatabase>,
+    config: &InlayHintsConfig,
+    macro_call: &ast::MacroCall,
+) -> Option<()> {
+    if !config.macro_introduced_bindings {
+        return None;
+    }
+
+    let expanded = sema.expand(macro_call)?;
+    // only the top-level `let`s of the expansion are visible after the macro call
+    let bindings = expanded
+        .children()
+        .filter_map(ast::LetStmt::cast)
+        .filter_map(|it| it.pat())
+        .flat_map(|pat| pat.syntax().descendants().filter_map(ast::IdentPat::cast).collect_vec())
+        .filter_map(|pat| {
+            let name = pat.name()?;
+            let ty = sema.type_of_pat(&pat.into())?.original;
+            if ty.is_unknown() {
+                return None;
+            }
+            Some(format!("{}: {}", name, ty.display_truncated(sema.db, config.max_length)))
+        })
+        .join(", ");
+    if bindings.is_empty() {
+        return None;
+    }
+
+    acc.push(InlayHint {
+        range: macro_call.syntax().text_range(),
+        kind: InlayKind::TypeHint,
+        label: bindings.into(),
+    });
+    Some(())
+}
+
 fn is_named_constructor(
     sema: &Semantics<RootDatabase>,
     pat: &ast::IdentPat,
@@ -535,6 +578,7 @@ mod tests {
         type_hints: true,
         parameter_hints: true,
         chaining_hints: true,
+        macro_introduced_bindings: false,
         max_length: None,
     };
 
@@ -550,6 +594,7 @@ mod tests {
                 parameter_hints: true,
                 type_hints: false,
                 chaining_hints: false,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             ra_fixture,
@@ -563,6 +608,7 @@ mod tests {
                 parameter_hints: false,
                 type_hints: true,
                 chaining_hints: false,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             ra_fixture,
@@ -576,6 +622,7 @@ mod tests {
                 parameter_hints: false,
                 type_hints: false,
                 chaining_hints: true,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             ra_fixture,
@@ -606,6 +653,7 @@ mod tests {
                 type_hints: false,
                 parameter_hints: false,
                 chaining_hints: false,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             r#"
@@ -1406,6 +1454,7 @@ fn main() {
                 parameter_hints: false,
                 type_hints: false,
                 chaining_hints: true,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             r#"
@@ -1462,6 +1511,7 @@ fn main() {
                 parameter_hints: false,
                 type_hints: false,
                 chaining_hints: true,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             r#"
@@ -1506,6 +1556,7 @@ fn main() {
                 parameter_hints: false,
                 type_hints: false,
                 chaining_hints: true,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             r#"
@@ -1551,6 +1602,7 @@ fn main() {
                 parameter_hints: false,
                 type_hints: false,
                 chaining_hints: true,
+                macro_introduced_bindings: false,
                 max_length: None,
             },
             r#"
@@ -1649,4 +1701,30 @@ fn main() {
             "#]],
         );
     }
+
+    #[test]
+    fn macro_introduced_bindings() {
+        check_with_config(
+            InlayHintsConfig { macro_introduced_bindings: true, ..TEST_CONFIG },
+            r#"
+macro_rules! let_some {
+    ($a:ident, $b:ident) => { let $a = 0u32; let ($b, _) = ("", 1i8); };
+}
+fn main() {
+    let_some!(x, y);
+  //^^^^^^^^^^^^^^^ x: u32, y: &str
+}
+"#,
+        );
+        check_types(
+            r#"
+macro_rules! let_some {
+    ($a:ident) => { let $a = 0u32; };
+}
+fn main() {
+    let_some!(x);
+}
+"#,
+        );
+    }
 }
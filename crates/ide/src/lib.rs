@@ -38,6 +38,7 @@ mod goto_type_definition;
 mod hover;
 mod inlay_hints;
 mod join_lines;
+mod macro_stats;
 mod markdown_remove;
 mod matching_brace;
 mod move_item;
@@ -45,11 +46,13 @@ mod parent_module;
 mod references;
 mod rename;
 mod runnables;
+mod signature_help;
 mod ssr;
 mod static_index;
 mod status;
 mod syntax_highlighting;
 mod syntax_tree;
+mod trace_expansion;
 mod typing;
 mod view_crate_graph;
 mod view_hir;
@@ -81,21 +84,24 @@ pub use crate::{
     hover::{HoverAction, HoverConfig, HoverDocFormat, HoverGotoTypeData, HoverResult},
     inlay_hints::{InlayHint, InlayHintsConfig, InlayKind},
     join_lines::JoinLinesConfig,
+    macro_stats::{MacroInvocationCounts, MacroStats},
     markup::Markup,
     move_item::Direction,
     prime_caches::PrimeCachesProgress,
     references::ReferenceSearchResult,
     rename::RenameError,
     runnables::{Runnable, RunnableKind, TestId},
+    signature_help::SignatureHelp,
     static_index::{StaticIndex, StaticIndexedFile, TokenId, TokenStaticData},
     syntax_highlighting::{
         tags::{Highlight, HlMod, HlMods, HlOperator, HlPunct, HlTag},
         HlRange,
     },
+    trace_expansion::TracedExpansion,
 };
 pub use hir::{Documentation, Semantics};
 pub use ide_assists::{
-    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve,
+    Assist, AssistConfig, AssistId, AssistKind, AssistResolveStrategy, SingleResolve, UseGroup,
 };
 pub use ide_completion::{
     CompletionConfig, CompletionItem, CompletionItemKind, CompletionRelevance, ImportEdit, Snippet,
@@ -235,6 +241,11 @@ impl Analysis {
         self.with_db(|db| status::status(&*db, file_id))
     }
 
+    /// Counts the macro invocations in all crates, expanding all macros in the process.
+    pub fn workspace_macro_statistics(&self) -> Cancellable<MacroStats> {
+        self.with_db(|db| macro_stats::workspace_macro_statistics(db))
+    }
+
     pub fn prime_caches<F>(&self, cb: F) -> Cancellable<()>
     where
         F: Fn(PrimeCachesProgress) + Sync + std::panic::UnwindSafe,
@@ -306,6 +317,16 @@ impl Analysis {
         self.with_db(|db| expand_macro::expand_macro(db, position))
     }
 
+    /// Returns the unformatted text of the expansion of the macro call at `position`.
+    pub fn macro_expansion_text(&self, position: FilePosition) -> Cancellable<Option<String>> {
+        self.with_db(|db| expand_macro::macro_expansion_text(db, position))
+    }
+
+    /// Expands the macro call at `position` again and reports the details of the expansion.
+    pub fn trace_expansion(&self, position: FilePosition) -> Cancellable<Option<TracedExpansion>> {
+        self.with_db(|db| trace_expansion::trace_expansion(db, position))
+    }
+
     /// Returns an edit to remove all newlines in the range, cleaning up minor
     /// stuff like trailing commas.
     pub fn join_lines(&self, config: &JoinLinesConfig, frange: FileRange) -> Cancellable<TextEdit> {
@@ -409,6 +430,14 @@ impl Analysis {
         self.with_db(|db| references::find_all_refs(&Semantics::new(db), position, search_scope))
     }
 
+    /// Finds all invocation sites of the macro at point.
+    pub fn find_all_macro_callers(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<Vec<FileRange>>> {
+        self.with_db(|db| references::find_all_macro_callers(db, position))
+    }
+
     /// Finds all methods and free functions for the file. Does not return tests!
     pub fn find_all_methods(&self, file_id: FileId) -> Cancellable<Vec<FileRange>> {
         self.with_db(|db| fn_references::find_all_methods(db, file_id))
@@ -436,6 +465,11 @@ impl Analysis {
         self.with_db(|db| ide_db::call_info::call_info(db, position))
     }
 
+    /// Computes the signatures of the call or `macro_rules!` invocation at the given position.
+    pub fn signature_help(&self, position: FilePosition) -> Cancellable<Option<SignatureHelp>> {
+        self.with_db(|db| signature_help::signature_help(db, position))
+    }
+
     /// Computes call hierarchy candidates for the given file position.
     pub fn call_hierarchy(
         &self,
@@ -0,0 +1,164 @@
+//! Collects statistics about the macro invocations in all crates of the workspace.
+
+use hir::{db::DefDatabase, AssocItem, Crate, DefWithBody, MacroDef, MacroKind, ModuleDef};
+use ide_db::{
+    base_db::{salsa::debug::DebugQueryTable, CrateId},
+    RootDatabase,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+#[derive(Debug, Default)]
+pub struct MacroStats {
+    /// Macro invocation counts, keyed by the crate the invocations are in.
+    pub invocations: FxHashMap<CrateId, MacroInvocationCounts>,
+    /// Number of invocations whose expansion failed.
+    pub failed_expansions: usize,
+    /// Number of distinct macros that are invoked.
+    pub unique_definitions: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MacroInvocationCounts {
+    /// `macro_rules!` and macros 2.0.
+    pub declarative: usize,
+    /// Built-in and procedural derives.
+    pub derive: usize,
+    /// Built-in and procedural attribute macros.
+    pub attr: usize,
+    /// Built-in and procedural function-like macros, except for eager ones.
+    pub fn_like: usize,
+    /// Built-in macros that expand their arguments first, like `concat!`.
+    pub eager: usize,
+}
+
+impl MacroInvocationCounts {
+    pub fn total(&self) -> usize {
+        self.declarative + self.derive + self.attr + self.fn_like + self.eager
+    }
+}
+
+pub(crate) fn workspace_macro_statistics(db: &RootDatabase) -> MacroStats {
+    let _p = profile::span("workspace_macro_statistics");
+    // Macro calls are only interned once they are expanded, and the ones in bodies are expanded
+    // when the body is lowered.
+    let mut modules: Vec<_> = Crate::all(db).into_iter().map(|it| it.root_module(db)).collect();
+    while let Some(module) = modules.pop() {
+        modules.extend(module.children(db));
+        let mut bodies: Vec<DefWithBody> = Vec::new();
+        for decl in module.declarations(db) {
+            match decl {
+                ModuleDef::Function(it) => bodies.push(it.into()),
+                ModuleDef::Const(it) => bodies.push(it.into()),
+                ModuleDef::Static(it) => bodies.push(it.into()),
+                ModuleDef::Trait(it) => {
+                    bodies.extend(it.items(db).into_iter().filter_map(assoc_item_body))
+                }
+                _ => (),
+            }
+        }
+        bodies.extend(
+            module
+                .impl_defs(db)
+                .into_iter()
+                .flat_map(|it| it.items(db))
+                .filter_map(assoc_item_body),
+        );
+        for body in bodies {
+            db.body(body.into());
+        }
+    }
+
+    let mut res = MacroStats::default();
+    let mut definitions = FxHashSet::default();
+    let mut eager_calls = FxHashSet::default();
+    for entry in hir::db::InternMacroQuery.in_db(db).entries::<Vec<_>>() {
+        let call_id = match entry.value {
+            Some(it) => it,
+            None => continue,
+        };
+        let loc = entry.key;
+        let mac = MacroDef::from(loc.def);
+        // eager macros intern their expanded arguments as a separate call at the same site
+        if mac.is_eager() && !eager_calls.insert(loc.kind.to_node(db)) {
+            continue;
+        }
+        let counts = res.invocations.entry(loc.krate).or_default();
+        match mac.kind() {
+            MacroKind::Declarative => counts.declarative += 1,
+            MacroKind::Derive => counts.derive += 1,
+            MacroKind::Attr => counts.attr += 1,
+            MacroKind::BuiltIn if mac.is_eager() => counts.eager += 1,
+            MacroKind::BuiltIn | MacroKind::ProcMacro => counts.fn_like += 1,
+        }
+        definitions.insert(mac);
+
+        let expansion = hir::db::AstDatabase::macro_expand(db, call_id);
+        if expansion.err.is_some() || expansion.value.is_none() {
+            res.failed_expansions += 1;
+        }
+    }
+    res.unique_definitions = definitions.len();
+    res
+}
+
+fn assoc_item_body(item: AssocItem) -> Option<DefWithBody> {
+    match item {
+        AssocItem::Function(it) => Some(it.into()),
+        AssocItem::Const(it) => Some(it.into()),
+        AssocItem::TypeAlias(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+    use itertools::Itertools;
+
+    use crate::fixture;
+
+    #[test]
+    fn counts_macro_invocations() {
+        let (analysis, _) = fixture::file(
+            r#"
+//- proc_macros: identity
+//- /main.rs crate:main deps:lib
+#[rustc_builtin_macro]
+macro_rules! concat {}
+#[rustc_builtin_macro]
+macro_rules! line {}
+#[rustc_builtin_macro]
+pub macro Clone {}
+
+lib::m!();
+
+#[derive(Clone)]
+struct S;
+
+#[proc_macros::identity]
+fn f() {
+    let _ = concat!("a", "b");
+    let _ = line!();
+    lib::m!(no match);
+}
+//- /lib.rs crate:lib
+#[macro_export]
+macro_rules! m { () => {} }
+m!();
+"#,
+        );
+        let stats = analysis.workspace_macro_statistics().unwrap();
+
+        let actual = stats
+            .invocations
+            .iter()
+            .map(|(krate, counts)| format!("{:?}: {:?}\n", krate, counts))
+            .sorted()
+            .collect::<String>();
+        expect![[r#"
+            CrateId(0): MacroInvocationCounts { declarative: 2, derive: 1, attr: 1, fn_like: 1, eager: 1 }
+            CrateId(1): MacroInvocationCounts { declarative: 1, derive: 0, attr: 0, fn_like: 0, eager: 0 }
+        "#]].assert_eq(&actual);
+        assert_eq!(stats.failed_expansions, 1);
+        assert_eq!(stats.unique_definitions, 5);
+    }
+}
@@ -59,6 +59,17 @@ mod tests {
         do_check("fn main() { |x: i32|$0 x * 2;}", "fn main() { $0|x: i32| x * 2;}");
         do_check("fn main() { $0|x: i32| x * 2;}", "fn main() { |x: i32$0| x * 2;}");
 
+        // nested delimiters
+        do_check("fn main() { f((1, 2)$0) }", "fn main() { f($0(1, 2)) }");
+        do_check("fn main() { f($0(1, 2)) }", "fn main() { f((1, 2)$0) }");
+        do_check("fn main() { [[1, 2], [3]]$0; }", "fn main() { $0[[1, 2], [3]]; }");
+        do_check("fn main() { [[1, 2]$0, [3]]; }", "fn main() { [$0[1, 2], [3]]; }");
+        do_check("fn f() -> Vec<Vec<u8>>$0 {}", "fn f() -> Vec$0<Vec<u8>> {}");
+
+        // unbalanced delimiters
+        do_check("fn main() { f($01, 2; }", "fn main() { f($01, 2; }");
+        do_check("fn main() { 1, 2)$0; }", "fn main() { 1, 2)$0; }");
+
         {
             cov_mark::check!(pipes_not_braces);
             do_check(
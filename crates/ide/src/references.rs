@@ -14,11 +14,12 @@ use std::iter;
 use either::Either;
 use hir::{PathResolution, Semantics};
 use ide_db::{
-    base_db::FileId,
+    base_db::{FileId, FileRange},
     defs::{Definition, NameClass, NameRefClass},
     search::{ReferenceCategory, SearchScope, UsageSearchResult},
     RootDatabase,
 };
+use itertools::Itertools;
 use rustc_hash::FxHashMap;
 use syntax::{
     algo::find_node_at_offset,
@@ -114,6 +115,38 @@ pub(crate) fn find_all_refs(
     )
 }
 
+/// Finds all invocation sites of the macro defined or referenced at the given position.
+pub(crate) fn find_all_macro_callers(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<Vec<FileRange>> {
+    let _p = profile::span("find_all_macro_callers");
+    let sema = Semantics::new(db);
+    let syntax = sema.parse(position.file_id).syntax().clone();
+    let def = find_defs(&sema, &syntax, position.offset)
+        .find(|def| matches!(def, Definition::Macro(_)))?;
+
+    let callers = def
+        .usages(&sema)
+        .all()
+        .into_iter()
+        .flat_map(|(_, refs)| refs)
+        .filter_map(|reference| {
+            let path = reference.name.syntax().ancestors().find_map(ast::Path::cast)?.top_path();
+            let call = path.syntax().parent()?;
+            match_ast! {
+                match call {
+                    ast::MacroCall(it) => Some(sema.original_range(it.syntax())),
+                    ast::Meta(it) => Some(sema.original_range(it.parent_attr()?.syntax())),
+                    _ => None,
+                }
+            }
+        })
+        .unique()
+        .collect();
+    Some(callers)
+}
+
 pub(crate) fn find_defs<'a>(
     sema: &'a Semantics<RootDatabase>,
     syntax: &SyntaxNode,
@@ -275,7 +308,10 @@ fn is_lit_name_ref(name_ref: &ast::NameRef) -> bool {
 #[cfg(test)]
 mod tests {
     use expect_test::{expect, Expect};
-    use ide_db::{base_db::FileId, search::ReferenceCategory};
+    use ide_db::{
+        base_db::{FileId, FileRange},
+        search::ReferenceCategory,
+    };
     use stdx::format_to;
 
     use crate::{fixture, SearchScope};
@@ -853,6 +889,45 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn test_find_all_macro_callers() {
+        let (analysis, pos) = fixture::position(
+            r#"
+//- /lib.rs
+#[macro_use]
+mod macros {
+    macro_rules! m$0 { ($($tt:tt)*) => { $($tt)* } }
+}
+mod foo;
+mod bar;
+
+fn f() {
+    m!();
+}
+//- /foo.rs
+fn f() {
+    m!(m!());
+}
+//- /bar.rs
+struct S;
+m! { impl S {} }
+"#,
+        );
+        let callers = analysis.find_all_macro_callers(pos).unwrap().unwrap();
+
+        let mut actual = String::new();
+        for FileRange { file_id, range } in callers {
+            format_to!(actual, "{:?} {:?}\n", file_id, range);
+        }
+        expect![[r#"
+            FileId(0) 111..115
+            FileId(1) 13..21
+            FileId(1) 16..20
+            FileId(2) 10..26
+        "#]]
+        .assert_eq(&actual);
+    }
+
     #[test]
     fn test_basic_highlight_read_write() {
         check(
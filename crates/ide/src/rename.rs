@@ -1947,4 +1947,84 @@ fn foo() {
 "#,
         )
     }
+
+    #[test]
+    fn test_rename_method_in_nested_macro_args() {
+        check(
+            "bar",
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+macro_rules! n { ($e:expr) => { m!($e) } }
+struct S { field: u32 }
+impl S { fn fo$0o(&self) {} }
+fn main() {
+    let s = S { field: 0 };
+    m!(s.foo());
+    n!(s.foo());
+    m!(m!(S::foo(&s)));
+}
+"#,
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+macro_rules! n { ($e:expr) => { m!($e) } }
+struct S { field: u32 }
+impl S { fn bar(&self) {} }
+fn main() {
+    let s = S { field: 0 };
+    m!(s.bar());
+    n!(s.bar());
+    m!(m!(S::bar(&s)));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_rename_field_shorthand_in_macro_args() {
+        cov_mark::check!(rename_field_shorthand_in_macro);
+        check(
+            "other",
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+struct S { fiel$0d: u32 }
+fn main() {
+    let s = m!(S { field: 0 });
+    let x = m!(s.field);
+    let m!(S { field }) = s;
+}
+"#,
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+struct S { other: u32 }
+fn main() {
+    let s = m!(S { other: 0 });
+    let x = m!(s.other);
+    let m!(S { other: field }) = s;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_rename_local_shorthand_in_macro_args() {
+        check(
+            "value",
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+struct S { field: u32 }
+fn main() {
+    let fie$0ld = 0;
+    let s = m!(S { field });
+}
+"#,
+            r#"
+macro_rules! m { ($($tt:tt)*) => { $($tt)* } }
+struct S { field: u32 }
+fn main() {
+    let value = 0;
+    let s = m!(S { field: value });
+}
+"#,
+        );
+    }
 }
@@ -0,0 +1,179 @@
+use hir::Semantics;
+use ide_db::{
+    base_db::FilePosition,
+    call_info::{call_info, macro_call_info, CallInfo},
+    RootDatabase,
+};
+use syntax::{algo, AstNode, Direction, SyntaxToken};
+
+/// The signatures that apply at a call site. Calls have a single signature, while `macro_rules!`
+/// invocations have one for each rule that accepts the arguments written so far.
+#[derive(Debug)]
+pub struct SignatureHelp {
+    pub signatures: Vec<CallInfo>,
+    pub active_signature: usize,
+}
+
+// Feature: Signature Help
+//
+// Shows the parameters of the function or `macro_rules!` macro being called while typing its
+// arguments, highlighting the current one.
+pub(crate) fn signature_help(db: &RootDatabase, position: FilePosition) -> Option<SignatureHelp> {
+    if let Some(call_info) = call_info(db, position) {
+        return Some(SignatureHelp { signatures: vec![call_info], active_signature: 0 });
+    }
+    let sema = Semantics::new(db);
+    let token = sema
+        .parse(position.file_id)
+        .syntax()
+        .token_at_offset(position.offset)
+        .left_biased()
+        .and_then(|tok| algo::skip_trivia_token(tok, Direction::Prev))?;
+    signature_help_macro(&sema, token)
+}
+
+pub(crate) fn signature_help_macro(
+    sema: &Semantics<RootDatabase>,
+    token: SyntaxToken,
+) -> Option<SignatureHelp> {
+    let signatures = macro_call_info(sema, token)?;
+    let active_signature =
+        signatures.iter().position(|it| it.active_parameter.is_some()).unwrap_or(0);
+    Some(SignatureHelp { signatures, active_signature })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use stdx::format_to;
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let (analysis, position) = fixture::position(ra_fixture);
+        let mut actual = String::new();
+        if let Some(help) = analysis.signature_help(position).unwrap() {
+            for (idx, call_info) in help.signatures.iter().enumerate() {
+                let active = if idx == help.active_signature { "*" } else { " " };
+                let params = call_info
+                    .parameter_labels()
+                    .enumerate()
+                    .map(|(i, param)| {
+                        if Some(i) == call_info.active_parameter {
+                            format!("<{}>", param)
+                        } else {
+                            param.to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format_to!(actual, "{} {}\n  ({})\n", active, call_info.signature, params);
+            }
+        }
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn signature_help_for_calls() {
+        check(
+            r#"
+fn foo(x: u32, y: u32) {}
+fn main() { foo(1, $0) }
+"#,
+            expect![[r#"
+                * fn foo(x: u32, y: u32)
+                  (x: u32, <y: u32>)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_macro_rules() {
+        check(
+            r#"
+macro_rules! add {
+    ($x:expr) => { $x };
+    ($x:expr, $y:expr) => { $x + $y };
+    ($x:expr, $y:expr, $z:expr) => { $x + $y + $z };
+}
+fn main() { add!(1, $0) }
+"#,
+            expect![[r#"
+                * macro_rules! add(arg0: expr, arg1: expr)
+                  (arg0: expr, <arg1: expr>)
+                  macro_rules! add(arg0: expr, arg1: expr, arg2: expr)
+                  (arg0: expr, <arg1: expr>, arg2: expr)
+            "#]],
+        );
+        check(
+            r#"
+macro_rules! add {
+    ($x:expr) => { $x };
+    ($x:expr, $y:expr) => { $x + $y };
+}
+fn main() { add!($0) }
+"#,
+            expect![[r#"
+                * macro_rules! add(arg0: expr)
+                  (<arg0: expr>)
+                  macro_rules! add(arg0: expr, arg1: expr)
+                  (<arg0: expr>, arg1: expr)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_macro_rules_repetitions() {
+        check(
+            r#"
+macro_rules! vec {
+    ($($e:expr),*) => {};
+    ($e:expr; $n:expr) => {};
+}
+fn main() { vec![1, $0] }
+"#,
+            expect![[r#"
+                * macro_rules! vec($($e:expr),*)
+                  (<$($e:expr),*>)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_for_macro_rules_numbers_metavariables() {
+        check(
+            r#"
+macro_rules! log {
+    ($level:expr, $msg:expr) => {};
+    (debug, $msg:expr) => {};
+}
+fn main() { log!(debug, $0) }
+"#,
+            expect![[r#"
+                * macro_rules! log(arg0: expr, arg1: expr)
+                  (arg0: expr, <arg1: expr>)
+                  macro_rules! log(debug, arg0: expr)
+                  (debug, <arg0: expr>)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn signature_help_without_matching_rule_shows_all_rules() {
+        check(
+            r#"
+macro_rules! pair {
+    ($x:expr) => {};
+    ($x:expr, $y:expr) => {};
+}
+fn main() { pair!(1, 2, $0) }
+"#,
+            expect![[r#"
+                * macro_rules! pair(arg0: expr)
+                  (arg0: expr)
+                  macro_rules! pair(arg0: expr, arg1: expr)
+                  (arg0: expr, arg1: expr)
+            "#]],
+        );
+    }
+}
@@ -140,6 +140,7 @@ pub struct HlRange {
 // injected:: Emitted for doc-string injected highlighting like rust source blocks in documentation.
 // intraDocLink:: Emitted for intra doc links in doc-strings.
 // library:: Emitted for items that are defined outside of the current crate.
+// macro:: Emitted for tokens that are mapped into a macro expansion.
 // mutable:: Emitted for mutable locals and statics as well as functions taking `&mut self`.
 // public:: Emitted for items that are from the current crate and are `pub`.
 // reference:: Emitted for locals behind a reference and functions taking `self` by reference.
@@ -290,6 +291,7 @@ fn traverse(
 
         let descend_token = (current_macro_call.is_some() || current_attr_call.is_some())
             && element.kind() != COMMENT;
+        let mut in_macro_expansion = false;
         let element_to_highlight = if descend_token {
             // Inside a macro -- expand it first
             let token = match element.clone().into_token() {
@@ -304,6 +306,8 @@ fn traverse(
                 _ => continue,
             };
             let token = sema.descend_into_macros_single(token);
+            in_macro_expansion =
+                token.parent().map_or(false, |it| sema.hir_file_for(&it).is_macro());
             match token.parent() {
                 Some(parent) => {
                     // We only care Name and Name_ref
@@ -344,6 +348,9 @@ fn traverse(
             if inside_attribute {
                 highlight = highlight | HlMod::Attribute;
             }
+            if in_macro_expansion {
+                highlight = highlight | HlMod::Macro;
+            }
 
             hl.add(HlRange { range, highlight, binding_hash });
         }
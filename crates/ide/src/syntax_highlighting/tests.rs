@@ -4,7 +4,7 @@ use expect_test::{expect_file, ExpectFile};
 use ide_db::SymbolKind;
 use test_utils::{bench, bench_fixture, skip_slow_tests, AssertLinear};
 
-use crate::{fixture, FileRange, HlTag, TextRange};
+use crate::{fixture, FileRange, HlTag, TextRange, TextSize};
 
 #[test]
 fn test_highlighting() {
@@ -396,6 +396,30 @@ struct Foo {
     assert_eq!(&highlights[0].highlight.to_string(), "field.declaration.public");
 }
 
+#[test]
+fn test_macro_modifier() {
+    let (analysis, file_id) = fixture::file(
+        r#"
+//- minicore: derive, clone
+macro_rules! id { ($($tt:tt)*) => { $($tt)* } }
+#[derive(Clone)]
+struct Foo { x: i32 }
+fn main() { let foo = id!(Foo { x: 92 }); }
+"#,
+    );
+    let text = analysis.file_text(file_id).unwrap();
+    let highlights = analysis.highlight(file_id).unwrap();
+    let highlight_of = |needle: &str| {
+        let offset = TextSize::from(text.rfind(needle).unwrap() as u32);
+        highlights.iter().find(|it| it.range.start() == offset).unwrap().highlight.to_string()
+    };
+
+    assert_eq!(highlight_of("x: i32"), "field.declaration");
+    assert_eq!(highlight_of("foo"), "variable.declaration");
+    assert_eq!(highlight_of("x: 92"), "field.macro");
+    assert_eq!(highlight_of("92"), "numeric_literal.macro");
+}
+
 #[test]
 fn test_flattening() {
     check_highlighting(
@@ -0,0 +1,146 @@
+//! Traces a single expansion of the macro call at the cursor, see [`TracedExpansion`].
+
+use std::time::Duration;
+
+use hir::{MacroKind, Semantics};
+use ide_db::{helpers::pick_best_token, RootDatabase};
+use syntax::{ast, AstNode, SyntaxKind};
+
+use crate::FilePosition;
+
+/// The details of a single expansion of a function-like macro call.
+#[derive(Debug)]
+pub struct TracedExpansion {
+    pub name: String,
+    /// What kind of macro is invoked, like `declarative` or `proc-macro`.
+    pub kind: String,
+    /// Debug dump of the token tree the macro is invoked with.
+    pub input: String,
+    /// Debug dump of the token tree the macro expands to.
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub duration: Duration,
+    /// Number of token ids allocated for the input.
+    pub token_ids: u32,
+}
+
+// Feature: Trace Macro Expansion
+//
+// Expands the function-like macro call at the cursor again, bypassing the cache, and shows its
+// input and output token trees together with the time the expansion took. Available through the
+// `rust-analyzer/traceExpansion` request and `rust-analyzer macro-stats --trace-macro`.
+pub(crate) fn trace_expansion(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<TracedExpansion> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(position.file_id);
+
+    let tok = pick_best_token(file.syntax().token_at_offset(position.offset), |kind| match kind {
+        SyntaxKind::IDENT => 1,
+        _ => 0,
+    })?;
+    let mac = tok.ancestors().find_map(ast::MacroCall::cast)?;
+    let name = mac.path()?.segment()?.name_ref()?.to_string();
+    let kind = match sema.resolve_macro_call(&mac) {
+        Some(def) => match def.kind() {
+            MacroKind::Declarative => "declarative",
+            MacroKind::BuiltIn if def.is_eager() => "built-in eager",
+            MacroKind::BuiltIn => "built-in",
+            MacroKind::ProcMacro => "proc-macro",
+            MacroKind::Derive | MacroKind::Attr => return None,
+        },
+        None => return None,
+    };
+
+    let trace = sema.trace_expansion(&mac)?;
+    Some(TracedExpansion {
+        name,
+        kind: kind.to_owned(),
+        input: trace.input.map_or_else(String::new, |it| format!("{:?}", it)),
+        output: trace.output.value.map(|it| format!("{:?}", it)),
+        error: trace.output.err.map(|it| it.to_string()),
+        duration: trace.duration,
+        token_ids: trace.token_ids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::fixture;
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let (analysis, pos) = fixture::position(ra_fixture);
+        let trace = analysis.trace_expansion(pos).unwrap().unwrap();
+        let actual = format!(
+            "{} ({}), {} token ids\ninput:\n{}\noutput:\n{}\nerror: {:?}\n",
+            trace.name,
+            trace.kind,
+            trace.token_ids,
+            trace.input,
+            trace.output.unwrap_or_default(),
+            trace.error,
+        );
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn trace_declarative_macro() {
+        check(
+            r#"
+macro_rules! pair {
+    ($a:expr, $b:expr) => { ($a, $b) };
+}
+fn main() {
+    let p = pa$0ir!(1, x + 2);
+}
+"#,
+            expect![[r#"
+                pair (declarative), 6 token ids
+                input:
+                SUBTREE () 0
+                  LITERAL 1 1
+                  PUNCH   , [alone] 2
+                  IDENT   x 3
+                  PUNCH   + [alone] 4
+                  LITERAL 2 5
+                output:
+                SUBTREE $
+                  SUBTREE () 14
+                    LITERAL 1 21
+                    PUNCH   , [alone] 17
+                    SUBTREE $
+                      IDENT   x 23
+                      PUNCH   + [alone] 24
+                      LITERAL 2 25
+                error: None
+            "#]],
+        );
+    }
+
+    #[test]
+    fn trace_failed_expansion() {
+        check(
+            r#"
+macro_rules! one {
+    (1) => { 1 };
+}
+fn main() {
+    on$0e!(2);
+}
+"#,
+            expect![[r#"
+                one (declarative), 2 token ids
+                input:
+                SUBTREE () 0
+                  LITERAL 2 1
+                output:
+                SUBTREE $
+                  LITERAL 1 6
+                error: Some("unexpected token in input")
+            "#]],
+        );
+    }
+}
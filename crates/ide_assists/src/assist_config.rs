@@ -13,4 +13,16 @@ pub struct AssistConfig {
     pub snippet_cap: Option<SnippetCap>,
     pub allowed: Option<Vec<AssistKind>>,
     pub insert_use: InsertUseConfig,
+    pub use_group_order: [UseGroup; 3],
+}
+
+/// A group of `use` items, in the sense of the `sort_group_use_items` assist.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseGroup {
+    /// `std`, `core` and `alloc`.
+    Std,
+    /// Any crate other than the current one.
+    ExternCrate,
+    /// Paths starting with `crate`, `self` or `super`.
+    ThisCrate,
 }
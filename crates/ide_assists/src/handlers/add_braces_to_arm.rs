@@ -0,0 +1,337 @@
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+    },
+    AstNode, SyntaxKind,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_braces_to_arm
+//
+// Wraps the expression of a match arm in a block.
+//
+// ```
+// fn f(x: Option<u32>) -> u32 {
+//     match x {
+//         Some(it) $0=> it * 2,
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn f(x: Option<u32>) -> u32 {
+//     match x {
+//         Some(it) => { it * 2 },
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn add_braces_to_arm(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let expr = arm.expr()?;
+    if ctx.offset() > expr.syntax().text_range().start() {
+        return None;
+    }
+    if matches!(expr, ast::Expr::BlockExpr(_)) {
+        return None;
+    }
+
+    let target = expr.syntax().text_range();
+    acc.add(
+        AssistId("add_braces_to_arm", AssistKind::RefactorRewrite),
+        "Add braces to match arm",
+        target,
+        |builder| {
+            let replacement = if expr.syntax().text().contains_char('\n') {
+                let indent = IndentLevel::from_node(arm.syntax());
+                format!("{{\n{}{}\n{}}}", indent + 1, expr.indent(IndentLevel(1)), indent)
+            } else {
+                format!("{{ {} }}", expr)
+            };
+            builder.replace(target, replacement);
+        },
+    )
+}
+
+// Assist: remove_braces_from_arm
+//
+// Removes the braces around the only expression of a match arm.
+//
+// ```
+// fn f(x: Option<u32>) -> u32 {
+//     match x {
+//         Some(it) $0=> { it * 2 }
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn f(x: Option<u32>) -> u32 {
+//     match x {
+//         Some(it) => it * 2,
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn remove_braces_from_arm(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let block = match arm.expr()? {
+        ast::Expr::BlockExpr(it) => it,
+        _ => return None,
+    };
+    if ctx.offset() > block.syntax().text_range().start() {
+        return None;
+    }
+    if block.label().is_some()
+        || block.async_token().is_some()
+        || block.unsafe_token().is_some()
+        || block.try_token().is_some()
+        || block.const_token().is_some()
+    {
+        return None;
+    }
+    let stmt_list = block.stmt_list()?;
+    if stmt_list.statements().next().is_some() {
+        cov_mark::hit!(remove_braces_from_arm_with_statements);
+        return None;
+    }
+    let tail_expr = stmt_list.tail_expr()?;
+    // Comments in the block would get lost.
+    let has_comments =
+        stmt_list.syntax().children_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT);
+    if has_comments {
+        return None;
+    }
+
+    let is_last_arm = arm.syntax().next_sibling().is_none();
+    let target = block.syntax().text_range();
+    acc.add(
+        AssistId("remove_braces_from_arm", AssistKind::RefactorRewrite),
+        "Remove braces from match arm",
+        target,
+        |builder| {
+            let mut replacement = tail_expr.dedent(IndentLevel(1)).to_string();
+            // Arms with an expression other than a block need a comma.
+            if arm.comma_token().is_none() && !is_last_arm {
+                replacement.push(',');
+            }
+            builder.replace(target, replacement);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_braces_keeps_comma() {
+        check_assist(
+            add_braces_to_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => zero(),
+        n if n > 1$0 => many(n)
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => zero(),
+        n if n > 1 => { many(n) }
+    }
+}
+"#,
+        );
+        check_assist(
+            add_braces_to_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        $00 => zero(),
+        _ => (),
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => { zero() },
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_braces_to_multiline_arm() {
+        check_assist(
+            add_braces_to_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ =>$0 frobnicate(
+            x,
+        ),
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ => {
+            frobnicate(
+                x,
+            )
+        },
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_braces_not_applicable() {
+        check_assist_not_applicable(
+            add_braces_to_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ =>$0 { x }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            add_braces_to_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ => foo($0x),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_braces() {
+        check_assist(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 $0=> { zero() },
+        _ => { other() }
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => zero(),
+        _ => { other() }
+    }
+}
+"#,
+        );
+        check_assist(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 $0=> {
+            compute(
+                x,
+            )
+        }
+        _ => { other() }
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => compute(
+            x,
+        ),
+        _ => { other() }
+    }
+}
+"#,
+        );
+        check_assist(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => zero(),
+        _ $0=> { other() }
+    }
+}
+"#,
+            r#"
+fn f(x: u32) {
+    match x {
+        0 => zero(),
+        _ => other()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_braces_not_applicable_with_statements() {
+        cov_mark::check!(remove_braces_from_arm_with_statements);
+        check_assist_not_applicable(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ $0=> {
+            log();
+            x
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_braces_not_applicable() {
+        check_assist_not_applicable(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ $0=> unsafe { x },
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            remove_braces_from_arm,
+            r#"
+fn f(x: u32) {
+    match x {
+        _ $0=> {
+            // Keep this.
+            x
+        }
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use std::slice;
+
+use hir::Trait;
+use ide_db::helpers::FamousDefs;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasAttrs, HasGenericParams, HasName},
+    TextSize,
+};
+
+use crate::{
+    handlers::generate_derive::derive_insertion_offset, AssistContext, AssistId, AssistKind,
+    Assists,
+};
+
+// Assist: add_derives_for_all_fields
+//
+// Derives every standard trait that all fields of a struct or enum implement.
+//
+// ```
+// # //- minicore: derive, clone, copy, eq
+// #[derive(Clone, Copy, PartialEq)]
+// struct Meters(u32);
+//
+// struct $0Size {
+//     width: Meters,
+//     height: Meters,
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone, Copy, PartialEq)]
+// struct Meters(u32);
+//
+// #[derive(Clone, Copy, PartialEq)]
+// struct Size {
+//     width: Meters,
+//     height: Meters,
+// }
+// ```
+pub(crate) fn add_derives_for_all_fields(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let adt = ctx.find_node_at_offset::<ast::Adt>()?;
+    let (field_lists, is_enum) = match &adt {
+        ast::Adt::Struct(it) => (it.field_list().into_iter().collect::<Vec<_>>(), false),
+        ast::Adt::Enum(it) => {
+            let variants = it.variant_list()?;
+            if ctx.offset() > variants.syntax().text_range().start() {
+                return None;
+            }
+            (variants.variants().filter_map(|it| it.field_list()).collect(), true)
+        }
+        ast::Adt::Union(_) => return None,
+    };
+    if let Some(field_list) = field_lists.first().filter(|_| !is_enum) {
+        if ctx.offset() > field_list.syntax().text_range().start() {
+            return None;
+        }
+    }
+
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(adt.syntax()).krate());
+    // Each trait comes with the traits that must be derived along with it.
+    let candidates: [(&str, Option<Trait>, &[&str]); 9] = [
+        ("Debug", famous_defs.core_fmt_Debug(), &[]),
+        ("Clone", famous_defs.core_clone_Clone(), &[]),
+        ("Copy", famous_defs.core_marker_Copy(), &["Clone"]),
+        ("PartialEq", famous_defs.core_cmp_PartialEq(), &[]),
+        ("Eq", famous_defs.core_cmp_Eq(), &["PartialEq"]),
+        ("PartialOrd", famous_defs.core_cmp_PartialOrd(), &["PartialEq"]),
+        ("Ord", famous_defs.core_cmp_Ord(), &["Eq", "PartialOrd"]),
+        ("Hash", famous_defs.core_hash_Hash(), &[]),
+        // Deriving `Default` for an enum needs a `#[default]` variant.
+        ("Default", famous_defs.core_default_Default().filter(|_| !is_enum), &[]),
+    ];
+
+    let type_params = adt
+        .generic_param_list()
+        .map(|it| it.type_params().filter_map(|it| it.name()).map(|it| it.to_string()).collect())
+        .unwrap_or_else(Vec::new);
+    let field_types = field_lists
+        .iter()
+        .flat_map(|it| match it {
+            ast::FieldList::RecordFieldList(it) => it.fields().filter_map(|it| it.ty()).collect(),
+            ast::FieldList::TupleFieldList(it) => {
+                it.fields().filter_map(|it| it.ty()).collect::<Vec<_>>()
+            }
+        })
+        // The derived impls are bounded on the type parameters themselves.
+        .filter(|it| !type_params.contains(&it.syntax().text().to_string()))
+        .map(|it| ctx.sema.resolve_type(&it))
+        .collect::<Option<Vec<_>>>()?;
+
+    let derive_attr = adt
+        .attrs()
+        .filter_map(|it| it.as_simple_call())
+        .find(|(name, _)| name == "derive")
+        .map(|(_, tt)| tt);
+    let existing = derive_attr.as_ref().map_or_else(Vec::new, |tt| {
+        let text = tt.syntax().text().to_string();
+        text.trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .filter_map(|it| it.trim().rsplit("::").next().map(ToString::to_string))
+            .filter(|it| !it.is_empty())
+            .collect()
+    });
+
+    let mut derived = existing.clone();
+    let mut added = Vec::new();
+    for (name, trait_, required) in candidates.iter() {
+        let trait_ = match trait_ {
+            Some(it) => *it,
+            None => continue,
+        };
+        if derived.iter().any(|it| it == name)
+            || !required.iter().all(|it| derived.iter().any(|derived| derived == it))
+            || !field_types.iter().all(|ty| {
+                // `PartialEq` and `PartialOrd` take the type compared with.
+                let rhs = match *name {
+                    "PartialEq" | "PartialOrd" => slice::from_ref(ty),
+                    _ => &[],
+                };
+                ty.impls_trait(ctx.db(), trait_, rhs)
+            })
+        {
+            continue;
+        }
+        derived.push(name.to_string());
+        added.push(*name);
+    }
+    if added.is_empty() {
+        return None;
+    }
+    let added = added.join(", ");
+
+    let target = adt.syntax().text_range();
+    acc.add(
+        AssistId("add_derives_for_all_fields", AssistKind::Generate),
+        format!("Derive `{}`", added),
+        target,
+        |builder| match derive_attr {
+            Some(tt) => {
+                let offset = tt.syntax().text_range().end() - TextSize::of(')');
+                if existing.is_empty() {
+                    builder.insert(offset, added);
+                } else {
+                    builder.insert(offset, format!(", {}", added));
+                }
+            }
+            None => {
+                if let Some(offset) = derive_insertion_offset(&adt) {
+                    let indent = IndentLevel::from_node(adt.syntax());
+                    builder.insert(offset, format!("#[derive({})]\n{}", added, indent));
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn derives_intersection_of_field_traits() {
+        check_assist(
+            add_derives_for_all_fields,
+            r#"
+//- minicore: derive, clone, copy, eq, ord, hash, default, fmt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Id(u32);
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Score(u32);
+
+/// A player.
+struct $0Player<T> {
+    id: Id,
+    score: Score,
+    data: T,
+}
+"#,
+            r#"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Id(u32);
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Score(u32);
+
+/// A player.
+#[derive(Debug, Clone, PartialEq)]
+struct Player<T> {
+    id: Id,
+    score: Score,
+    data: T,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extends_existing_derive() {
+        check_assist(
+            add_derives_for_all_fields,
+            r#"
+//- minicore: derive, clone, copy, eq, hash, default
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+struct Id(u32);
+
+mod m {
+    #[derive(core::clone::Clone)]
+    enum Event$0 {
+        Joined(super::Id),
+        Left { id: super::Id },
+        Reset,
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+struct Id(u32);
+
+mod m {
+    #[derive(core::clone::Clone, Copy, PartialEq, Eq)]
+    enum Event {
+        Joined(super::Id),
+        Left { id: super::Id },
+        Reset,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn adds_attribute_in_module() {
+        check_assist(
+            add_derives_for_all_fields,
+            r#"
+//- minicore: derive, clone, eq
+mod m {
+    #[derive(Clone, PartialEq)]
+    pub struct Name;
+
+    pub struct $0Person(Name, Name);
+}
+"#,
+            r#"
+mod m {
+    #[derive(Clone, PartialEq)]
+    pub struct Name;
+
+    #[derive(Clone, PartialEq)]
+    pub struct Person(Name, Name);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_nothing_is_implemented() {
+        check_assist_not_applicable(
+            add_derives_for_all_fields,
+            r#"
+//- minicore: derive, clone, eq
+#[derive(Clone)]
+struct A;
+#[derive(PartialEq)]
+struct B;
+
+struct $0Both(A, B);
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_inside_fields() {
+        check_assist_not_applicable(
+            add_derives_for_all_fields,
+            r#"
+//- minicore: derive, clone
+#[derive(Clone)]
+struct A;
+
+struct Wrapper {
+    a: $0A,
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use syntax::{
+    ast::{self, AstNode, HasLoopBody},
+    SyntaxToken, WalkEvent,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_label_to_loop
+//
+// Labels a loop nested in another loop, and makes its unlabeled `break`s and
+// `continue`s use the label.
+//
+// ```
+// fn main() {
+//     for row in rows() {
+//         $0loop {
+//             if done() {
+//                 break;
+//             }
+//             continue;
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     for row in rows() {
+//         'outer: loop {
+//             if done() {
+//                 break 'outer;
+//             }
+//             continue 'outer;
+//         }
+//     }
+// }
+// ```
+pub(crate) fn add_label_to_loop(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let loop_expr = ctx.find_node_at_offset::<ast::AnyHasLoopBody>()?;
+    let loop_body = loop_expr.loop_body()?;
+    if loop_expr.label().is_some() || ctx.offset() > loop_body.syntax().text_range().start() {
+        return None;
+    }
+    let enclosing_loops = loop_expr
+        .syntax()
+        .ancestors()
+        .skip(1)
+        .take_while(|it| !ast::ClosureExpr::can_cast(it.kind()) && !ast::Item::can_cast(it.kind()))
+        .filter_map(ast::AnyHasLoopBody::cast)
+        .collect::<Vec<_>>();
+    if enclosing_loops.is_empty() {
+        return None;
+    }
+
+    let keywords = unlabeled_jumps(&loop_body);
+    if keywords.is_empty() {
+        cov_mark::hit!(add_label_to_loop_no_jumps);
+        return None;
+    }
+
+    let existing = enclosing_loops
+        .iter()
+        .filter_map(|it| it.label())
+        .chain(loop_body.syntax().descendants().filter_map(ast::Label::cast))
+        .filter_map(|it| it.lifetime())
+        .map(|it| it.to_string())
+        .collect::<Vec<_>>();
+    let label = (0..)
+        .map(|idx| if idx == 0 { "'outer".to_string() } else { format!("'outer{}", idx) })
+        .find(|it| !existing.contains(it))?;
+
+    let target = loop_expr.syntax().text_range();
+    acc.add(
+        AssistId("add_label_to_loop", AssistKind::Generate),
+        format!("Add label `{}` to loop", label),
+        target,
+        |builder| {
+            builder.insert(target.start(), format!("{}: ", label));
+            for keyword in &keywords {
+                builder.insert(keyword.text_range().end(), format!(" {}", label));
+            }
+        },
+    )
+}
+
+/// The `break` and `continue` keywords without a label that jump out of the
+/// loop with the given body, skipping nested loops, closures and items.
+fn unlabeled_jumps(loop_body: &ast::BlockExpr) -> Vec<SyntaxToken> {
+    let mut keywords = Vec::new();
+    let mut preorder = loop_body.syntax().preorder();
+    while let Some(event) = preorder.next() {
+        let node = match event {
+            WalkEvent::Enter(node) => node,
+            WalkEvent::Leave(_) => continue,
+        };
+        if ast::AnyHasLoopBody::can_cast(node.kind())
+            || ast::ClosureExpr::can_cast(node.kind())
+            || ast::Item::can_cast(node.kind())
+        {
+            preorder.skip_subtree();
+            continue;
+        }
+        let keyword = match ast::Expr::cast(node) {
+            Some(ast::Expr::BreakExpr(it)) if it.lifetime().is_none() => it.break_token(),
+            Some(ast::Expr::ContinueExpr(it)) if it.lifetime().is_none() => it.continue_token(),
+            _ => None,
+        };
+        keywords.extend(keyword);
+    }
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn label_while_in_loop() {
+        check_assist(
+            add_label_to_loop,
+            r#"
+fn f() {
+    loop {
+        wh$0ile cond() {
+            if skip() {
+                continue;
+            }
+            for x in xs() {
+                break;
+            }
+            let g = || loop { break; };
+            break;
+        }
+    }
+}
+"#,
+            r#"
+fn f() {
+    loop {
+        'outer: while cond() {
+            if skip() {
+                continue 'outer;
+            }
+            for x in xs() {
+                break;
+            }
+            let g = || loop { break; };
+            break 'outer;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn label_loop_with_break_value() {
+        check_assist(
+            add_label_to_loop,
+            r#"
+fn f() {
+    'outer: for x in xs() {
+        let y = $0loop {
+            break 92;
+        };
+        'inner: loop {
+            break 'inner;
+        }
+    }
+}
+"#,
+            r#"
+fn f() {
+    'outer: for x in xs() {
+        let y = 'outer1: loop {
+            break 'outer1 92;
+        };
+        'inner: loop {
+            break 'inner;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn label_for_in_while() {
+        check_assist(
+            add_label_to_loop,
+            r#"
+fn f() {
+    while running() {
+        $0for x in xs() {
+            if x { continue } else { break }
+        }
+    }
+}
+"#,
+            r#"
+fn f() {
+    while running() {
+        'outer: for x in xs() {
+            if x { continue 'outer } else { break 'outer }
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_enclosing_loop() {
+        check_assist_not_applicable(
+            add_label_to_loop,
+            r#"
+fn f() {
+    let g = || {
+        $0loop {
+            break;
+        }
+    };
+    loop {
+        fn h() {
+            loop { break; }
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_unlabeled_jumps() {
+        cov_mark::check!(add_label_to_loop_no_jumps);
+        check_assist_not_applicable(
+            add_label_to_loop,
+            r#"
+fn f() {
+    'a: loop {
+        $0loop {
+            break 'a;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_loop_body() {
+        check_assist_not_applicable(
+            add_label_to_loop,
+            r#"
+fn f() {
+    loop {
+        loop {
+            $0break;
+        }
+    }
+}
+"#,
+        );
+    }
+}
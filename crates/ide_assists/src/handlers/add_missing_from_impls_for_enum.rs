@@ -0,0 +1,278 @@
+use syntax::ast::{self, AstNode, HasGenericParams, HasName};
+
+use crate::{
+    handlers::generate_from_impl_for_enum::{existing_from_impl, from_impl_code},
+    utils::generate_trait_impl_text,
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: add_missing_from_impls_for_enum
+//
+// Adds From impls for all enum variants with a single field at once.
+//
+// ```
+// enum $0Value { Int(i64), Text(String), Pair(i64, i64) }
+// ```
+// ->
+// ```
+// enum Value { Int(i64), Text(String), Pair(i64, i64) }
+//
+// impl From<i64> for Value {
+//     fn from(v: i64) -> Self {
+//         Self::Int(v)
+//     }
+// }
+//
+// impl From<String> for Value {
+//     fn from(v: String) -> Self {
+//         Self::Text(v)
+//     }
+// }
+// ```
+pub(crate) fn add_missing_from_impls_for_enum(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let enum_ = ctx.find_node_at_offset::<ast::Enum>()?;
+    let variant_list = enum_.variant_list()?;
+    if ctx.offset() > variant_list.syntax().text_range().start() {
+        return None;
+    }
+
+    let candidates = variant_list
+        .variants()
+        .filter_map(|variant| {
+            let (field_type, impl_code) = from_impl_code(&variant)?;
+            // `impl<T> From<T> for E<T>` overlaps with core's `impl<T> From<T> for T`.
+            if is_type_param(&enum_, &field_type) {
+                cov_mark::hit!(add_missing_from_impls_type_param);
+                return None;
+            }
+            let def = ctx.sema.to_def(&variant)?;
+            let wrapped_type = def.fields(ctx.db()).get(0)?.ty(ctx.db());
+            Some((variant, wrapped_type, field_type, impl_code))
+        })
+        .collect::<Vec<_>>();
+    // Two variants wrapping the same type would need conflicting impls.
+    let impls = candidates
+        .iter()
+        .filter(|(_, ty, _, _)| {
+            let unique = candidates.iter().filter(|(_, other, _, _)| other == ty).count() == 1;
+            if !unique {
+                cov_mark::hit!(add_missing_from_impls_ambiguous_type);
+            }
+            unique
+        })
+        .filter(|(variant, _, _, _)| existing_from_impl(&ctx.sema, variant).is_none())
+        .map(|(_, _, field_type, impl_code)| (field_type, impl_code))
+        .collect::<Vec<_>>();
+    if impls.is_empty() {
+        return None;
+    }
+
+    let label = match impls.len() {
+        1 => "Generate 1 missing `From` impl".to_string(),
+        n => format!("Generate {} missing `From` impls", n),
+    };
+    let target = enum_.syntax().text_range();
+    acc.add(
+        AssistId("add_missing_from_impls_for_enum", AssistKind::Generate),
+        label,
+        target,
+        |edit| {
+            let adt = ast::Adt::Enum(enum_.clone());
+            let from_impls = impls
+                .iter()
+                .map(|(field_type, impl_code)| {
+                    let from_trait = format!("From<{}>", field_type.syntax());
+                    generate_trait_impl_text(&adt, &from_trait, impl_code)
+                })
+                .collect::<String>();
+            edit.insert(target.end(), from_impls);
+        },
+    )
+}
+
+fn is_type_param(enum_: &ast::Enum, ty: &ast::Type) -> bool {
+    let path = match ty {
+        ast::Type::PathType(it) => it.path(),
+        _ => None,
+    };
+    let name = match path {
+        Some(path) if path.qualifier().is_none() => path.as_single_name_ref(),
+        _ => None,
+    };
+    let (name, params) = match (name, enum_.generic_param_list()) {
+        (Some(name), Some(params)) => (name, params),
+        _ => return false,
+    };
+    params.type_params().filter_map(|it| it.name()).any(|it| it.text() == name.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_missing_from_impls() {
+        check_assist(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+struct Span;
+
+enum $0Token {
+    Ident(String),
+    Punct { ch: char },
+    Whitespace,
+    Spanned(Span, u32),
+    Number(u64),
+}
+
+impl From<u64> for Token {
+    fn from(v: u64) -> Self {
+        Self::Number(v)
+    }
+}
+"#,
+            r#"
+struct Span;
+
+enum Token {
+    Ident(String),
+    Punct { ch: char },
+    Whitespace,
+    Spanned(Span, u32),
+    Number(u64),
+}
+
+impl From<String> for Token {
+    fn from(v: String) -> Self {
+        Self::Ident(v)
+    }
+}
+
+impl From<char> for Token {
+    fn from(ch: char) -> Self {
+        Self::Punct { ch }
+    }
+}
+
+impl From<u64> for Token {
+    fn from(v: u64) -> Self {
+        Self::Number(v)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_missing_from_impls_generic() {
+        cov_mark::check_count!(add_missing_from_impls_type_param, 2);
+        check_assist_not_applicable(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum Either$0<L, R> { Left(L), Right(R) }
+"#,
+        );
+    }
+
+    #[test]
+    fn add_missing_from_impls_generic_wrapper() {
+        check_assist(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum Value$0<T> { Raw(T), List(Vec<T>), Text(String) }
+struct Vec<T>(T);
+"#,
+            r#"
+enum Value<T> { Raw(T), List(Vec<T>), Text(String) }
+
+impl<T> From<Vec<T>> for Value<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self::List(v)
+    }
+}
+
+impl<T> From<String> for Value<T> {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+struct Vec<T>(T);
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_variants_with_same_type() {
+        cov_mark::check!(add_missing_from_impls_ambiguous_type);
+        check_assist(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum $0Shape { Circle(f32), Square(f32), Named(String) }
+"#,
+            r#"
+enum Shape { Circle(f32), Square(f32), Named(String) }
+
+impl From<String> for Shape {
+    fn from(v: String) -> Self {
+        Self::Named(v)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn label_has_count() {
+        check_assist_by_label(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum $0E { A(u8) }
+"#,
+            r#"
+enum E { A(u8) }
+
+impl From<u8> for E {
+    fn from(v: u8) -> Self {
+        Self::A(v)
+    }
+}
+"#,
+            "Generate 1 missing `From` impl",
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_all_implemented() {
+        check_assist_not_applicable(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum $0E { A(u8), B }
+impl From<u8> for E {
+    fn from(v: u8) -> Self { Self::A(v) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_variant_list() {
+        check_assist_not_applicable(
+            add_missing_from_impls_for_enum,
+            r#"
+//- minicore: from
+enum E { $0A(u8) }
+"#,
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use ide_db::helpers::{
+    insert_use::{insert_use, ImportScope},
+    mod_path_to_ast, FamousDefs,
+};
+use syntax::{
+    ast::{self, AstNode, HasGenericParams, HasName},
+    SyntaxKind, T,
+};
+
+use crate::{
+    assist_context::AssistBuilder, AssistContext, AssistId, AssistKind, Assists, GroupLabel,
+};
+
+// Assist: add_phantom_data
+//
+// Adds a `PhantomData` field for a type parameter that no field of the struct
+// uses.
+//
+// ```
+// # //- minicore: phantom_data
+// struct Id<$0T> {
+//     raw: u32,
+// }
+// ```
+// ->
+// ```
+// use core::marker::PhantomData;
+//
+// struct Id<T> {
+//     raw: u32,
+//     _marker: PhantomData<T>,
+// }
+// ```
+pub(crate) fn add_phantom_data(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let type_param = ctx.find_node_at_offset::<ast::TypeParam>()?;
+    let param_list = type_param.syntax().parent().and_then(ast::GenericParamList::cast)?;
+    let strukt = param_list.syntax().parent().and_then(ast::Struct::cast)?;
+    let param_name = type_param.name()?.to_string();
+
+    let field_list = strukt.field_list();
+    if let Some(field_list) = &field_list {
+        let is_used = field_list
+            .syntax()
+            .descendants()
+            .filter_map(ast::NameRef::cast)
+            .any(|it| it.text() == param_name);
+        if is_used {
+            return None;
+        }
+    }
+
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(strukt.syntax()).krate());
+    let phantom_data = famous_defs.core_marker_PhantomData()?;
+    let module = ctx.sema.scope(strukt.syntax()).module()?;
+    let phantom_data_path = module.find_use_path(ctx.db(), hir::ModuleDef::from(phantom_data))?;
+    // A single segment path means `PhantomData` is already in scope.
+    let import = if phantom_data_path.segments().len() > 1 {
+        let scope = ImportScope::find_insert_use_container_with_macros(strukt.syntax(), &ctx.sema)?;
+        Some((phantom_data_path, scope))
+    } else {
+        None
+    };
+
+    let field_name = match &field_list {
+        Some(ast::FieldList::RecordFieldList(it))
+            if it.fields().filter_map(|it| it.name()).any(|it| it.text() == "_marker") =>
+        {
+            format!("_marker_{}", param_name.to_lowercase())
+        }
+        _ => "_marker".to_string(),
+    };
+
+    let group = GroupLabel(format!("Add `PhantomData` field for `{}`", param_name));
+    let variants = [
+        (format!("PhantomData<{}>", param_name), format!("covariant, owns `{}`", param_name)),
+        (format!("PhantomData<fn({})>", param_name), "contravariant, does not own".to_string()),
+        (
+            format!("PhantomData<*const {}>", param_name),
+            "covariant, not `Send` or `Sync`".to_string(),
+        ),
+    ];
+    let target = type_param.syntax().text_range();
+    for (ty, explanation) in variants.iter() {
+        let ty = ty.clone();
+        let import = import.clone();
+        let strukt = strukt.clone();
+        let field_name = field_name.clone();
+        acc.add_group(
+            &group,
+            AssistId("add_phantom_data", AssistKind::Generate),
+            format!("Add `{}` ({})", ty, explanation),
+            target,
+            move |builder| {
+                add_field(builder, &strukt, &field_name, &ty);
+                if let Some((path, scope)) = import {
+                    let scope = match scope {
+                        ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                        ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                        ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+                    };
+                    insert_use(&scope, mod_path_to_ast(&path), &ctx.config.insert_use);
+                }
+            },
+        );
+    }
+    Some(())
+}
+
+/// Appends a field of type `ty` to the struct, following the layout of the
+/// existing field list.
+fn add_field(builder: &mut AssistBuilder, strukt: &ast::Struct, name: &str, ty: &str) {
+    let (field_list, field, last_field) = match strukt.field_list() {
+        Some(ast::FieldList::RecordFieldList(it)) => {
+            let last = it.fields().last().map(|it| it.syntax().clone());
+            (it.syntax().clone(), format!("{}: {}", name, ty), last)
+        }
+        Some(ast::FieldList::TupleFieldList(it)) => {
+            let last = it.fields().last().map(|it| it.syntax().clone());
+            (it.syntax().clone(), ty.to_string(), last)
+        }
+        None => {
+            // A unit struct becomes a tuple struct; the fields go before any `where` clause.
+            if let Some(param_list) = strukt.generic_param_list() {
+                builder.insert(param_list.syntax().text_range().end(), format!("({})", ty));
+            }
+            return;
+        }
+    };
+
+    let last_field = match last_field {
+        Some(it) => it,
+        None => {
+            let text = match field_list.kind() {
+                SyntaxKind::RECORD_FIELD_LIST => format!("{{ {} }}", field),
+                _ => format!("({})", field),
+            };
+            builder.replace(field_list.text_range(), text);
+            return;
+        }
+    };
+    let separator = match last_field.prev_sibling_or_token() {
+        Some(ws) if ws.kind() == SyntaxKind::WHITESPACE && ws.to_string().contains('\n') => {
+            ws.to_string()
+        }
+        _ => " ".to_string(),
+    };
+    let trailing_comma = last_field
+        .siblings_with_tokens(syntax::Direction::Next)
+        .skip(1)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE)
+        .filter(|it| it.kind() == T![,]);
+    match trailing_comma {
+        Some(comma) => builder.insert(comma.text_range().end(), format!("{}{},", separator, field)),
+        None => builder.insert(last_field.text_range().end(), format!(",{}{}", separator, field)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_covariant_owning_marker() {
+        check_assist_by_label(
+            add_phantom_data,
+            r#"
+//- minicore: phantom_data
+struct Handle<$0T> {
+    id: u32,
+    generation: u32,
+}
+"#,
+            r#"
+use core::marker::PhantomData;
+
+struct Handle<T> {
+    id: u32,
+    generation: u32,
+    _marker: PhantomData<T>,
+}
+"#,
+            "Add `PhantomData<T>` (covariant, owns `T`)",
+        );
+    }
+
+    #[test]
+    fn add_contravariant_marker_in_scope() {
+        check_assist_by_label(
+            add_phantom_data,
+            r#"
+//- minicore: phantom_data
+use core::marker::PhantomData;
+
+struct Callback<$0T>(u32, fn());
+"#,
+            r#"
+use core::marker::PhantomData;
+
+struct Callback<T>(u32, fn(), PhantomData<fn(T)>);
+"#,
+            "Add `PhantomData<fn(T)>` (contravariant, does not own)",
+        );
+    }
+
+    #[test]
+    fn add_raw_pointer_marker_to_unit_struct() {
+        check_assist_by_label(
+            add_phantom_data,
+            r#"
+//- minicore: phantom_data
+struct Token<$0T> where T: Copy;
+"#,
+            r#"
+use core::marker::PhantomData;
+
+struct Token<T>(PhantomData<*const T>) where T: Copy;
+"#,
+            "Add `PhantomData<*const T>` (covariant, not `Send` or `Sync`)",
+        );
+    }
+
+    #[test]
+    fn add_marker_with_trailing_comma_and_taken_name() {
+        check_assist_by_label(
+            add_phantom_data,
+            r#"
+//- minicore: phantom_data
+use core::marker::PhantomData;
+
+struct Pair<A, $0B> { _marker: PhantomData<A>, }
+"#,
+            r#"
+use core::marker::PhantomData;
+
+struct Pair<A, B> { _marker: PhantomData<A>, _marker_b: PhantomData<B>, }
+"#,
+            "Add `PhantomData<B>` (covariant, owns `B`)",
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_used_param() {
+        check_assist_not_applicable(
+            add_phantom_data,
+            r#"
+//- minicore: phantom_data
+struct Wrapper<$0T> {
+    items: Vec<Option<T>>,
+}
+"#,
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use hir::HirDisplay;
 use ide_db::defs::{Definition, NameRefClass};
+use itertools::Itertools;
 use syntax::{ast, AstNode, SyntaxKind, T};
 
 use crate::{
@@ -52,6 +54,7 @@ pub(crate) fn add_turbo_fish(acc: &mut Assists, ctx: &AssistContext) -> Option<(
         cov_mark::hit!(add_turbo_fish_non_generic);
         return None;
     }
+    let type_args = inferred_type_args(ctx, &name_ref, fun, &generics);
 
     if let Some(let_stmt) = ctx.find_node_at_offset::<ast::LetStmt>() {
         if let_stmt.colon_token().is_none() {
@@ -81,13 +84,83 @@ pub(crate) fn add_turbo_fish(acc: &mut Assists, ctx: &AssistContext) -> Option<(
         AssistId("add_turbo_fish", AssistKind::RefactorRewrite),
         "Add `::<>`",
         ident.text_range(),
-        |builder| match ctx.config.snippet_cap {
-            Some(cap) => builder.insert_snippet(cap, ident.text_range().end(), "::<${0:_}>"),
-            None => builder.insert(ident.text_range().end(), "::<_>"),
+        |builder| {
+            let type_args = type_args.as_deref().unwrap_or("_");
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(
+                    cap,
+                    ident.text_range().end(),
+                    format!("::<${{0:{}}}>", type_args),
+                ),
+                None => builder.insert(ident.text_range().end(), format!("::<{}>", type_args)),
+            }
         },
     )
 }
 
+/// Renders the type arguments of the called function as inferred at the call
+/// site, using `_` for the ones that could not be inferred. Returns `None` if
+/// none of them could be inferred.
+fn inferred_type_args(
+    ctx: &AssistContext,
+    name_ref: &ast::NameRef,
+    fun: hir::Function,
+    generics: &[hir::GenericParam],
+) -> Option<String> {
+    let db = ctx.db();
+    let call = name_ref
+        .syntax()
+        .ancestors()
+        .find(|it| matches!(it.kind(), SyntaxKind::CALL_EXPR | SyntaxKind::METHOD_CALL_EXPR))
+        .and_then(ast::Expr::cast)?;
+    let module = ctx.sema.scope(call.syntax()).module()?;
+    let call_ty = ctx.sema.type_of_expr(&call)?.original;
+    let ret_ty = fun.ret_type(db);
+
+    let mut any_inferred = false;
+    let args = generics
+        .iter()
+        .filter_map(|param| match param {
+            hir::GenericParam::TypeParam(it) => Some(it),
+            _ => None,
+        })
+        .map(|param| {
+            find_type_arg(&ret_ty, &call_ty, &param.ty(db))
+                .filter(|ty| !ty.contains_unknown())
+                .and_then(|ty| ty.display_source_code(db, module.into()).ok())
+                .map(|ty| {
+                    any_inferred = true;
+                    ty
+                })
+                .unwrap_or_else(|| "_".to_string())
+        })
+        .join(", ");
+    if any_inferred {
+        Some(args)
+    } else {
+        None
+    }
+}
+
+/// Walks the declared return type alongside the inferred one, looking for the
+/// position of `param`.
+fn find_type_arg(
+    declared: &hir::Type,
+    inferred: &hir::Type,
+    param: &hir::Type,
+) -> Option<hir::Type> {
+    if declared == param {
+        return Some(inferred.clone());
+    }
+    if declared.as_adt().is_none() || declared.as_adt() != inferred.as_adt() {
+        return None;
+    }
+    declared
+        .type_arguments()
+        .zip(inferred.type_arguments())
+        .find_map(|(declared, inferred)| find_type_arg(&declared, &inferred, param))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
@@ -267,7 +340,7 @@ fn main() {
             r#"
 fn make<T>() -> T {}
 fn main() {
-    let x: () = make::<${0:_}>();
+    let x: () = make::<${0:()}>();
 }
 "#,
         );
@@ -292,4 +365,104 @@ fn main() {
             "Add `: _` before assignment operator",
         );
     }
+
+    #[test]
+    fn add_turbo_fish_inferred_from_str_parse() {
+        check_assist(
+            add_turbo_fish,
+            r#"
+//- minicore: result
+trait FromStr: Sized {
+    type Err;
+}
+struct ParseIntError;
+impl FromStr for u32 {
+    type Err = ParseIntError;
+}
+#[lang = "str"]
+impl str {
+    fn parse<F: FromStr>(&self) -> Result<F, F::Err> {}
+}
+fn main() {
+    let x: Result<u32, _> = "42".parse$0();
+}
+"#,
+            r#"
+trait FromStr: Sized {
+    type Err;
+}
+struct ParseIntError;
+impl FromStr for u32 {
+    type Err = ParseIntError;
+}
+#[lang = "str"]
+impl str {
+    fn parse<F: FromStr>(&self) -> Result<F, F::Err> {}
+}
+fn main() {
+    let x: Result<u32, _> = "42".parse::<${0:u32}>();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_turbo_fish_partially_inferred() {
+        check_assist(
+            add_turbo_fish,
+            r#"
+struct Pair<A, B>(A, B);
+fn make<A, B>() -> Pair<B, Vec<A>> {}
+struct Vec<T>(T);
+fn main() {
+    let p: Pair<u8, _> = make$0();
+}
+"#,
+            r#"
+struct Pair<A, B>(A, B);
+fn make<A, B>() -> Pair<B, Vec<A>> {}
+struct Vec<T>(T);
+fn main() {
+    let p: Pair<u8, _> = make::<${0:_, u8}>();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_turbo_fish_inferred_from_argument() {
+        check_assist(
+            add_turbo_fish,
+            r#"
+fn make<T>() -> T {}
+fn consume(x: i32) {}
+fn main() {
+    consume(make$0());
+}
+"#,
+            r#"
+fn make<T>() -> T {}
+fn consume(x: i32) {}
+fn main() {
+    consume(make::<${0:i32}>());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_turbo_fish_not_for_default() {
+        // `Default::default` is generic over `Self` only, which can't be
+        // specified with a turbofish.
+        cov_mark::check!(add_turbo_fish_non_generic);
+        check_assist_not_applicable(
+            add_turbo_fish,
+            r#"
+//- minicore: default
+fn main() {
+    let x: u32 = Default::default$0();
+}
+"#,
+        );
+    }
 }
@@ -0,0 +1,194 @@
+use hir::ModPath;
+use ide_db::helpers::{
+    import_assets::ImportAssets,
+    insert_use::{insert_use, ImportScope},
+    mod_path_to_ast,
+};
+use itertools::Itertools;
+use syntax::{ast, match_ast, AstNode};
+
+use crate::{
+    handlers::auto_import::find_importable_node, AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: auto_import_all_unresolved
+//
+// Imports every unresolved name in the file for which there is exactly one
+// possible import.
+//
+// ```
+// fn main() {
+//     let map = HashMap$0::new();
+//     let set = HashSet::new();
+// }
+// # pub mod std { pub mod collections { pub struct HashMap { } pub struct HashSet { } } }
+// ```
+// ->
+// ```
+// use std::collections::{HashMap, HashSet};
+//
+// fn main() {
+//     let map = HashMap::new();
+//     let set = HashSet::new();
+// }
+// # pub mod std { pub mod collections { pub struct HashMap { } pub struct HashSet { } } }
+// ```
+pub(crate) fn auto_import_all_unresolved(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    // Only offered where a single import would be, to keep the assist list short.
+    let (_, syntax_under_caret) = find_importable_node(ctx)?;
+
+    let source_file = ctx.find_node_at_offset::<ast::SourceFile>()?;
+
+    let mut imports: Vec<(ModPath, ImportScope)> = Vec::new();
+    for node in source_file.syntax().descendants() {
+        let import_assets = match_ast! {
+            match node {
+                ast::Path(it) => ImportAssets::for_exact_path(&it, &ctx.sema),
+                ast::MethodCallExpr(it) => ImportAssets::for_method_call(&it, &ctx.sema),
+                _ => continue,
+            }
+        };
+        let import_assets = match import_assets {
+            Some(it) => it,
+            None => continue,
+        };
+        let proposed_imports = import_assets
+            .search_for_imports(&ctx.sema, ctx.config.insert_use.prefix_kind)
+            .into_iter()
+            .map(|it| it.import_path)
+            .unique()
+            .collect::<Vec<_>>();
+        let import_path = match proposed_imports.as_slice() {
+            [it] => it.clone(),
+            _ => continue,
+        };
+        if imports.iter().any(|(path, _)| *path == import_path) {
+            continue;
+        }
+        let scope = match ImportScope::find_insert_use_container_with_macros(&node, &ctx.sema) {
+            Some(it) => it,
+            None => continue,
+        };
+        imports.push((import_path, scope));
+    }
+    if imports.is_empty() {
+        return None;
+    }
+
+    let label = format!(
+        "Import all unresolved names ({})",
+        imports.iter().map(|(path, _)| format!("`{}`", path)).join(", ")
+    );
+    let range = ctx.sema.original_range(&syntax_under_caret).range;
+    acc.add(AssistId("auto_import_all_unresolved", AssistKind::QuickFix), label, range, |builder| {
+        // Resolve all scopes before the first insertion shifts their ranges.
+        let imports = imports
+            .into_iter()
+            .map(|(path, scope)| {
+                let scope = match scope {
+                    ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                    ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                    ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+                };
+                (path, scope)
+            })
+            .collect::<Vec<_>>();
+        for (path, scope) in imports {
+            insert_use(&scope, mod_path_to_ast(&path), &ctx.config.insert_use);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn imports_all_unambiguous_names() {
+        check_assist(
+            auto_import_all_unresolved,
+            r#"
+mod fmt {
+    pub struct Formatter;
+    pub trait Display {
+        fn fmt(&self);
+    }
+}
+mod io {
+    pub struct Error;
+    impl crate::fmt::Display for Error {
+        fn fmt(&self) {}
+    }
+}
+fn f(e: Error$0, f: Formatter) {
+    e.fmt();
+}
+"#,
+            r#"
+use fmt::{Display, Formatter};
+use io::Error;
+
+mod fmt {
+    pub struct Formatter;
+    pub trait Display {
+        fn fmt(&self);
+    }
+}
+mod io {
+    pub struct Error;
+    impl crate::fmt::Display for Error {
+        fn fmt(&self) {}
+    }
+}
+fn f(e: Error, f: Formatter) {
+    e.fmt();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_ambiguous_names() {
+        check_assist(
+            auto_import_all_unresolved,
+            r#"
+mod a {
+    pub struct Foo;
+    pub struct Bar;
+}
+mod b {
+    pub struct Foo;
+}
+fn f(foo: Foo, bar: Bar$0) {}
+"#,
+            r#"
+use a::Bar;
+
+mod a {
+    pub struct Foo;
+    pub struct Bar;
+}
+mod b {
+    pub struct Foo;
+}
+fn f(foo: Foo, bar: Bar) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_resolved_name() {
+        check_assist_not_applicable(
+            auto_import_all_unresolved,
+            r#"
+mod a {
+    pub struct Foo;
+}
+use a::Foo;
+fn f(foo: Foo$0, bar: Bar) {}
+"#,
+        );
+    }
+}
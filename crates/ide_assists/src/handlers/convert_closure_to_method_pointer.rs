@@ -0,0 +1,267 @@
+use syntax::ast::{self, AstNode, HasArgList, HasName};
+
+use crate::{utils::assoc_fn_qualifier, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_closure_to_method_pointer
+//
+// Replaces a closure that only calls a method on its parameter with a path to
+// that method.
+//
+// ```
+// # //- minicore: fn
+// struct Name(String);
+// impl Name {
+//     fn as_str(&self) -> &str { &self.0 }
+// }
+// fn with_name(f: impl Fn(&Name) -> &str) {}
+// fn main() {
+//     with_name(|name$0| name.as_str());
+// }
+// ```
+// ->
+// ```
+// struct Name(String);
+// impl Name {
+//     fn as_str(&self) -> &str { &self.0 }
+// }
+// fn with_name(f: impl Fn(&Name) -> &str) {}
+// fn main() {
+//     with_name(Name::as_str);
+// }
+// ```
+pub(crate) fn convert_closure_to_method_pointer(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let closure = ctx.find_node_at_offset::<ast::ClosureExpr>()?;
+    let mut params = closure.param_list()?.params();
+    let param = match (params.next()?.pat()?, params.next()) {
+        (ast::Pat::IdentPat(pat), None) if pat.at_token().is_none() => pat,
+        _ => return None,
+    };
+    let param_name = param.name()?;
+
+    let call = match closure.body()? {
+        ast::Expr::MethodCallExpr(call) => call,
+        _ => return None,
+    };
+    let name_ref = call.name_ref()?;
+    if call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+    let receiver = match call.receiver()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+    if receiver.qualifier().is_some() || receiver.segment()?.name_ref()?.text() != param_name.text()
+    {
+        return None;
+    }
+
+    // The method must take the parameter exactly as it is passed to the
+    // closure, without any auto-(de)referencing of the receiver.
+    let db = ctx.db();
+    let receiver_ty = ctx.sema.type_of_expr(&call.receiver()?)?.original;
+    if receiver_ty.is_unknown() {
+        return None;
+    }
+    let callable = ctx.sema.resolve_method_call_as_callable(&call)?;
+    let func = match callable.kind() {
+        hir::CallableKind::Function(func) => func,
+        _ => return None,
+    };
+    if !callable.receiver_type()?.could_unify_with(db, &receiver_ty) {
+        cov_mark::hit!(closure_receiver_adjusted);
+        return None;
+    }
+
+    let module = ctx.sema.scope(closure.syntax()).module()?;
+    let qualifier = assoc_fn_qualifier(db, module, func)?;
+    let generic_args = call.generic_arg_list().map(|it| it.to_string()).unwrap_or_default();
+
+    let target = closure.syntax().text_range();
+    acc.add(
+        AssistId("convert_closure_to_method_pointer", AssistKind::RefactorRewrite),
+        "Replace closure with method",
+        target,
+        |builder| {
+            builder.replace(target, format!("{}::{}{}", qualifier, name_ref, generic_args));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn str_method() {
+        check_assist(
+            convert_closure_to_method_pointer,
+            r#"
+//- minicore: fn
+struct String;
+#[lang = "str"]
+impl str {
+    fn to_uppercase(&self) -> String { String }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn main() {
+    apply("a", |s: &str| $0s.to_uppercase());
+}
+"#,
+            r#"
+struct String;
+#[lang = "str"]
+impl str {
+    fn to_uppercase(&self) -> String { String }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn main() {
+    apply("a", str::to_uppercase);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn char_method() {
+        check_assist(
+            convert_closure_to_method_pointer,
+            r#"
+//- minicore: fn
+#[lang = "char"]
+impl char {
+    fn is_alphabetic(self) -> bool { true }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn main() {
+    apply('a', |c$0| c.is_alphabetic());
+}
+"#,
+            r#"
+#[lang = "char"]
+impl char {
+    fn is_alphabetic(self) -> bool { true }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn main() {
+    apply('a', char::is_alphabetic);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn trait_method() {
+        check_assist(
+            convert_closure_to_method_pointer,
+            r#"
+//- minicore: clone, fn
+struct Foo;
+impl Clone for Foo {
+    fn clone(&self) -> Self { Foo }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn f(x: &Foo) {
+    apply(x, |$0it| it.clone());
+}
+"#,
+            r#"
+struct Foo;
+impl Clone for Foo {
+    fn clone(&self) -> Self { Foo }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn f(x: &Foo) {
+    apply(x, Clone::clone);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn user_defined_method_in_other_module() {
+        check_assist(
+            convert_closure_to_method_pointer,
+            r#"
+//- minicore: fn
+mod m {
+    pub struct Foo;
+    impl Foo {
+        pub fn into_inner(self) -> u32 { 0 }
+    }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn f(x: m::Foo) {
+    apply(x, |foo| foo.into_$0inner());
+}
+"#,
+            r#"
+mod m {
+    pub struct Foo;
+    impl Foo {
+        pub fn into_inner(self) -> u32 { 0 }
+    }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn f(x: m::Foo) {
+    apply(x, m::Foo::into_inner);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_receiver_adjustment() {
+        cov_mark::check!(closure_receiver_adjusted);
+        check_assist_not_applicable(
+            convert_closure_to_method_pointer,
+            r#"
+//- minicore: fn
+struct Foo;
+impl Foo {
+    fn len(&self) -> usize { 0 }
+}
+fn apply<T, U>(x: T, f: impl FnOnce(T) -> U) -> U { f(x) }
+fn f(x: Foo) {
+    apply(x, |foo$0| foo.len());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_extra_arguments() {
+        check_assist_not_applicable(
+            convert_closure_to_method_pointer,
+            r#"
+struct Foo;
+impl Foo {
+    fn add(&self, x: u32) -> u32 { x }
+}
+fn f(foo: &Foo) {
+    let _ = |foo: &Foo$0| foo.add(1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_other_receiver() {
+        check_assist_not_applicable(
+            convert_closure_to_method_pointer,
+            r#"
+struct Foo;
+impl Foo {
+    fn get(&self) -> u32 { 0 }
+}
+fn f(foo: &Foo) {
+    let _ = |_x: &Foo$0| foo.get();
+}
+"#,
+        );
+    }
+}
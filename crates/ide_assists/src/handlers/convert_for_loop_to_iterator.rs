@@ -0,0 +1,449 @@
+use ide_db::helpers::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{self, HasArgList, HasLoopBody, HasName},
+    AstNode, SyntaxKind, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_for_loop_to_iterator
+//
+// Converts a `for` loop which only pushes into a freshly created vector into an iterator chain.
+//
+// ```
+// # //- minicore: iterators, copy
+// fn main() {
+//     let xs = core::iter::repeat(1).take(3);
+//     let mut result = Vec::new();
+//     for$0 x in xs {
+//         if x > 1 {
+//             result.push(x * 2);
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let xs = core::iter::repeat(1).take(3);
+//     let result: Vec<_> = xs.filter(|&x| x > 1).map(|x| x * 2).collect();
+// }
+// ```
+pub(crate) fn convert_for_loop_to_iterator(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let for_loop = ctx.find_node_at_offset::<ast::ForExpr>()?;
+    let iterable = for_loop.iterable()?;
+    let pat = for_loop.pat()?;
+    let body = for_loop.loop_body()?;
+    if body.syntax().text_range().start() < ctx.offset() {
+        cov_mark::hit!(for_loop_to_iterator_not_available_in_body);
+        return None;
+    }
+    if for_loop.label().is_some() {
+        return None;
+    }
+
+    let (cond, push) = match single_expr(&body)? {
+        ast::Expr::IfExpr(if_expr) => {
+            if if_expr.else_branch().is_some() {
+                return None;
+            }
+            let cond = if_expr.condition()?;
+            if cond.pat().is_some() {
+                return None;
+            }
+            match single_expr(&if_expr.then_branch()?)? {
+                ast::Expr::MethodCallExpr(push) => (Some(cond.expr()?), push),
+                _ => return None,
+            }
+        }
+        ast::Expr::MethodCallExpr(push) => (None, push),
+        _ => return None,
+    };
+    let (result_name, pushed) = validate_push(&push)?;
+
+    if body.syntax().descendants().any(|it| {
+        matches!(
+            it.kind(),
+            SyntaxKind::BREAK_EXPR | SyntaxKind::CONTINUE_EXPR | SyntaxKind::RETURN_EXPR
+        )
+    }) {
+        cov_mark::hit!(for_loop_to_iterator_control_flow);
+        return None;
+    }
+
+    let for_stmt = for_loop.syntax().parent().and_then(ast::ExprStmt::cast);
+    let for_node = for_stmt.as_ref().map_or(for_loop.syntax(), AstNode::syntax);
+    let let_stmt = ast::LetStmt::cast(for_node.prev_sibling()?)?;
+    validate_empty_vec_decl(&let_stmt, &result_name)?;
+
+    // `filter` passes its closure a reference to the item, so the pattern has to
+    // dereference it, which only works for `Copy` items and immutable bindings.
+    if cond.is_some() {
+        if pat.syntax().descendants_with_tokens().any(|it| it.kind() == T![mut]) {
+            cov_mark::hit!(for_loop_to_iterator_mut_filter);
+            return None;
+        }
+        if !ctx.sema.type_of_pat(&pat)?.original.is_copy(ctx.db()) {
+            cov_mark::hit!(for_loop_to_iterator_non_copy_filter);
+            return None;
+        }
+    }
+
+    let range = let_stmt.syntax().text_range().cover(for_node.text_range());
+    acc.add(
+        AssistId("convert_for_loop_to_iterator", AssistKind::RefactorRewrite),
+        "Convert for loop to iterator chain",
+        for_loop.syntax().text_range(),
+        |builder| {
+            let mut buf = format!("let {}: Vec<_> = ", result_name);
+            if let ast::Expr::RangeExpr(..) = iterable {
+                format_to!(buf, "({})", iterable);
+            } else if impls_core_iter(&ctx.sema, &iterable) {
+                format_to!(buf, "{}", iterable);
+            } else if let ast::Expr::RefExpr(_) = iterable {
+                format_to!(buf, "({}).into_iter()", iterable);
+            } else {
+                format_to!(buf, "{}.into_iter()", iterable);
+            }
+
+            if let Some(cond) = cond {
+                format_to!(buf, ".filter(|&{}| {})", pat, cond);
+            }
+            if pushed.syntax().to_string() != pat.syntax().to_string() {
+                format_to!(buf, ".map(|{}| {})", pat, pushed);
+            }
+            buf.push_str(".collect();");
+
+            builder.replace(range, buf)
+        },
+    )
+}
+
+/// Returns the only expression of a block, which is either its tail expression or
+/// its single expression statement.
+fn single_expr(block: &ast::BlockExpr) -> Option<ast::Expr> {
+    let stmt_list = block.stmt_list()?;
+    let mut stmts = stmt_list.statements();
+    match (stmts.next(), stmt_list.tail_expr()) {
+        (None, Some(expr)) => Some(expr),
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if stmts.next().is_none() => stmt.expr(),
+        _ => None,
+    }
+}
+
+/// Checks that the call has the form `result.push(expr)`, returning `result` and `expr`.
+fn validate_push(push: &ast::MethodCallExpr) -> Option<(String, ast::Expr)> {
+    if push.name_ref()?.text() != "push" {
+        return None;
+    }
+    let receiver = match push.receiver()? {
+        ast::Expr::PathExpr(it) => it.path()?,
+        _ => return None,
+    };
+    if receiver.qualifier().is_some() {
+        return None;
+    }
+    let result_name = receiver.segment()?.name_ref()?.text().to_string();
+
+    let mut args = push.arg_list()?.args();
+    let pushed = args.next()?;
+    if args.next().is_some() {
+        return None;
+    }
+    Some((result_name, pushed))
+}
+
+/// Checks that the statement is `let mut result = Vec::new();` or `let mut result = vec![];`.
+fn validate_empty_vec_decl(let_stmt: &ast::LetStmt, result_name: &str) -> Option<()> {
+    match let_stmt.pat()? {
+        ast::Pat::IdentPat(pat) if pat.name()?.text() == result_name => (),
+        _ => return None,
+    }
+    let is_empty_vec = match let_stmt.initializer()? {
+        ast::Expr::CallExpr(call) => {
+            call.arg_list()?.args().next().is_none() && call.expr()?.syntax().text() == "Vec::new"
+        }
+        ast::Expr::MacroCall(mac) => {
+            mac.path()?.syntax().text() == "vec"
+                && mac.token_tree()?.syntax().children_with_tokens().count() == 2
+        }
+        _ => false,
+    };
+    if is_empty_vec {
+        Some(())
+    } else {
+        None
+    }
+}
+
+fn impls_core_iter(sema: &hir::Semantics<ide_db::RootDatabase>, iterable: &ast::Expr) -> bool {
+    (|| {
+        let ty = sema.type_of_expr(iterable)?.adjusted();
+        let krate = sema.scope(iterable.syntax()).module()?.krate();
+        let iter_trait = FamousDefs(sema, Some(krate)).core_iter_Iterator()?;
+        Some(ty.impls_trait(sema.db, iter_trait, &[]))
+    })()
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn filter_map() {
+        check_assist(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators, copy
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in core::iter::repeat(1).take(3) {
+        if x > 1 {
+            result.push(x * 2);
+        }
+    }
+}
+"#,
+            r#"
+fn main() {
+    let result: Vec<_> = core::iter::repeat(1).take(3).filter(|&x| x > 1).map(|x| x * 2).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_mut_binding_in_map() {
+        check_assist(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators
+fn main() {
+    let mut result = Vec::new();
+    for$0 mut x in core::iter::repeat(1).take(3) {
+        result.push({ x += 1; x });
+    }
+}
+"#,
+            r#"
+fn main() {
+    let result: Vec<_> = core::iter::repeat(1).take(3).map(|mut x| { x += 1; x }).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_mut_binding_with_filter() {
+        cov_mark::check!(for_loop_to_iterator_mut_filter);
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators, copy
+fn main() {
+    let mut result = Vec::new();
+    for$0 mut x in core::iter::repeat(1).take(3) {
+        if x > 1 {
+            result.push({ x += 1; x });
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_copy_items_with_filter() {
+        cov_mark::check!(for_loop_to_iterator_non_copy_filter);
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators, copy
+struct S;
+impl S {
+    fn is_ok(&self) -> bool { true }
+}
+fn main() {
+    let mut result = Vec::new();
+    for$0 s in core::iter::repeat(S).take(3) {
+        if s.is_ok() {
+            result.push(s);
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn pure_map() {
+        check_assist(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let xs = vec![1, 2, 3];
+    let mut result = vec![];
+    for$0 x in &xs {
+        result.push(x.to_string())
+    }
+}
+"#,
+            r#"
+fn main() {
+    let xs = vec![1, 2, 3];
+    let result: Vec<_> = (&xs).into_iter().map(|x| x.to_string()).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn pure_filter() {
+        check_assist(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators, copy
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in core::iter::repeat(1).take(10) {
+        if x % 2 == 0 {
+            result.push(x);
+        }
+    }
+}
+"#,
+            r#"
+fn main() {
+    let result: Vec<_> = core::iter::repeat(1).take(10).filter(|&x| x % 2 == 0).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_iterator_receiver() {
+        check_assist(
+            convert_for_loop_to_iterator,
+            r#"
+//- minicore: iterators
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in core::iter::repeat(92).take(3) {
+        result.push(x + 1);
+    }
+}
+"#,
+            r#"
+fn main() {
+    let result: Vec<_> = core::iter::repeat(92).take(3).map(|x| x + 1).collect();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_continue() {
+        cov_mark::check!(for_loop_to_iterator_control_flow);
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in 0..10 {
+        if x > 1 {
+            result.push({ if x == 5 { continue; } x });
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_break() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in 0..10 {
+        if x > 5 {
+            break;
+        }
+        result.push(x);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_else() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in 0..10 {
+        if x > 1 {
+            result.push(x);
+        } else {
+            result.push(0);
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_other_statements() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = Vec::new();
+    for$0 x in 0..10 {
+        println!("{}", x);
+        result.push(x);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_empty_vec() {
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = vec![0];
+    for$0 x in 0..10 {
+        result.push(x);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_available_in_body() {
+        cov_mark::check!(for_loop_to_iterator_not_available_in_body);
+        check_assist_not_applicable(
+            convert_for_loop_to_iterator,
+            r#"
+fn main() {
+    let mut result = Vec::new();
+    for x in 0..10 {
+        result.push($0x);
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,703 @@
+use hir::{Access, ModuleDef, PathResolution, Semantics};
+use ide_db::{
+    base_db::FileId,
+    defs::Definition,
+    helpers::mod_path_to_ast,
+    search::{FileReference, UsageSearchResult},
+    RootDatabase,
+};
+use itertools::Itertools;
+use stdx::to_lower_snake_case;
+use syntax::{
+    ast::{self, edit::IndentLevel, edit_in_place::Indent, HasArgList, HasGenericParams, HasName},
+    AstNode,
+    SyntaxKind::WHITESPACE,
+    SyntaxNode, TextRange,
+};
+
+use crate::{
+    utils::{find_impl_block_end, find_struct_impl, generate_impl_text},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_free_function_to_method
+//
+// Moves a free function into an impl of the type of its first parameter,
+// making that parameter the receiver, and updates the calls.
+//
+// ```
+// struct Counter { count: u32 }
+//
+// fn $0bump(counter: &mut Counter, by: u32) {
+//     counter.count += by;
+// }
+//
+// fn main() {
+//     let mut c = Counter { count: 0 };
+//     bump(&mut c, 2);
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn bump(&mut self, by: u32) {
+//         self.count += by;
+//     }
+// }
+//
+// fn main() {
+//     let mut c = Counter { count: 0 };
+//     c.bump(2);
+// }
+// ```
+pub(crate) fn convert_free_function_to_method(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if ctx.offset() > body.syntax().text_range().start() {
+        return None;
+    }
+    let parent = fn_.syntax().parent()?;
+    if !ast::SourceFile::can_cast(parent.kind()) && !ast::ItemList::can_cast(parent.kind()) {
+        return None;
+    }
+    let name = fn_.name()?;
+    let param_list = fn_.param_list()?;
+    let param = param_list.params().next()?;
+    let pat = match param.pat()? {
+        ast::Pat::IdentPat(it) if it.ref_token().is_none() && it.pat().is_none() => it,
+        _ => return None,
+    };
+    let (self_param, adt_path, by_ref) = match param.ty()? {
+        ast::Type::PathType(it) => {
+            let mut_ = if pat.mut_token().is_some() { "mut " } else { "" };
+            (format!("{}self", mut_), it.path()?, false)
+        }
+        ast::Type::RefType(it) => {
+            let path = match it.ty()? {
+                ast::Type::PathType(it) => it.path()?,
+                _ => return None,
+            };
+            let lifetime = it.lifetime().map(|it| format!("{} ", it)).unwrap_or_default();
+            let mut_ = if it.mut_token().is_some() { "mut " } else { "" };
+            (format!("&{}{}self", lifetime, mut_), path, true)
+        }
+        _ => return None,
+    };
+
+    let db = ctx.db();
+    let func = ctx.sema.to_def(&fn_)?;
+    let adt = match ctx.sema.resolve_path(&adt_path)? {
+        PathResolution::Def(ModuleDef::Adt(it)) => it,
+        _ => return None,
+    };
+    // Keep it simple: the type has to be right next to the function.
+    if adt.module(db) != func.module(db) {
+        return None;
+    }
+    let adt_source = ctx.sema.source(adt)?;
+    if adt_source.file_id.original_file(db) != ctx.frange.file_id {
+        return None;
+    }
+    let adt_ast = adt_source.value;
+    let impl_def = find_struct_impl(ctx, &adt_ast, &name.text())?;
+
+    let mut fn_edits = vec![(param.syntax().text_range(), self_param)];
+    let local = ctx.sema.to_def(&pat)?;
+    for (_, refs) in Definition::Local(local).usages(&ctx.sema).all() {
+        for r in refs {
+            let name_ref = r.name.as_name_ref()?;
+            let is_shorthand = name_ref
+                .syntax()
+                .ancestors()
+                .find_map(ast::PathExpr::cast)
+                .and_then(|it| it.syntax().parent())
+                .and_then(ast::RecordExprField::cast)
+                .map_or(false, |it| it.name_ref().is_none());
+            let replacement = match is_shorthand {
+                true => format!("{}: self", name_ref),
+                false => "self".to_string(),
+            };
+            fn_edits.push((r.range, replacement));
+        }
+    }
+
+    let usages = Definition::ModuleDef(ModuleDef::Function(func)).usages(&ctx.sema).all();
+    let call_edits = edits_for_usages(&ctx.sema, usages, fn_.syntax(), |r| {
+        let name_ref = r.name.as_name_ref()?;
+        if name_ref.syntax().ancestors().any(|it| ast::UseTree::can_cast(it.kind())) {
+            return None;
+        }
+        let path_expr = name_ref.syntax().ancestors().find_map(ast::PathExpr::cast)?;
+        let call = path_expr.syntax().parent().and_then(ast::CallExpr::cast);
+        match call {
+            Some(call) => {
+                let mut args = call.arg_list()?.args();
+                let receiver = args.next()?;
+                let receiver = match receiver {
+                    ast::Expr::RefExpr(it) if by_ref => it.expr()?,
+                    it => it,
+                };
+                let receiver = match receiver {
+                    ast::Expr::ArrayExpr(_)
+                    | ast::Expr::AwaitExpr(_)
+                    | ast::Expr::CallExpr(_)
+                    | ast::Expr::FieldExpr(_)
+                    | ast::Expr::IndexExpr(_)
+                    | ast::Expr::Literal(_)
+                    | ast::Expr::MacroCall(_)
+                    | ast::Expr::MethodCallExpr(_)
+                    | ast::Expr::ParenExpr(_)
+                    | ast::Expr::PathExpr(_)
+                    | ast::Expr::TryExpr(_)
+                    | ast::Expr::TupleExpr(_) => receiver.to_string(),
+                    _ => format!("({})", receiver),
+                };
+                let generic_args = path_expr
+                    .path()?
+                    .segment()?
+                    .generic_arg_list()
+                    .map(|it| it.to_string())
+                    .unwrap_or_default();
+                let text = format!("{}.{}{}({})", receiver, name, generic_args, args.join(", "));
+                Some((call.syntax().text_range(), text))
+            }
+            None => {
+                let module = ctx.sema.scope(path_expr.syntax()).module()?;
+                let adt_path = mod_path_to_ast(&module.find_use_path(db, ModuleDef::Adt(adt))?);
+                Some((path_expr.syntax().text_range(), format!("{}::{}", adt_path, name)))
+            }
+        }
+    })?;
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("convert_free_function_to_method", AssistKind::RefactorRewrite),
+        format!("Convert `{}` to a method of `{}`", name, adt_path),
+        target,
+        |builder| {
+            for (file_id, edits) in call_edits {
+                builder.edit_file(file_id);
+                for (range, text) in edits {
+                    builder.replace(range, text);
+                }
+            }
+            builder.edit_file(ctx.frange.file_id);
+            builder.delete(range_with_leading_whitespace(fn_.syntax()));
+
+            let indent = impl_def
+                .as_ref()
+                .map_or(IndentLevel(1), |it| IndentLevel::from_node(it.syntax()) + 1);
+            let method = match rewrite_fn(&fn_, fn_edits, indent) {
+                Some(it) => it,
+                None => return,
+            };
+            let mut buf = format!("{}{}", indent, method);
+            let last_item = impl_def
+                .as_ref()
+                .and_then(|it| it.assoc_item_list())
+                .and_then(|it| it.assoc_items().last());
+            let start_offset = match (last_item, impl_def) {
+                (Some(item), _) => {
+                    buf.insert_str(0, "\n\n");
+                    item.syntax().text_range().end()
+                }
+                (None, impl_def) => {
+                    if impl_def.is_some() {
+                        buf.insert(0, '\n');
+                    }
+                    impl_def
+                        .and_then(|impl_def| find_impl_block_end(impl_def, &mut buf))
+                        .unwrap_or_else(|| {
+                            buf = generate_impl_text(&adt_ast, &buf);
+                            adt_ast.syntax().text_range().end()
+                        })
+                }
+            };
+            builder.insert(start_offset, buf);
+        },
+    )
+}
+
+// Assist: convert_method_to_free_function
+//
+// Moves a method out of its impl into a free function taking the receiver as
+// its first parameter, and updates the calls.
+//
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+//     fn $0bump(&mut self, by: u32) {
+//         self.count += by;
+//     }
+// }
+//
+// fn main() {
+//     let mut c = Counter { count: 0 };
+//     c.bump(2);
+// }
+// ```
+// ->
+// ```
+// struct Counter { count: u32 }
+//
+// impl Counter {
+// }
+//
+// fn bump(counter: &mut Counter, by: u32) {
+//     counter.count += by;
+// }
+//
+// fn main() {
+//     let mut c = Counter { count: 0 };
+//     bump(&mut c, 2);
+// }
+// ```
+pub(crate) fn convert_method_to_free_function(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if ctx.offset() > body.syntax().text_range().start() {
+        return None;
+    }
+    let impl_ = fn_.syntax().ancestors().nth(2).and_then(ast::Impl::cast)?;
+    if impl_.trait_().is_some() || impl_.generic_param_list().is_some() {
+        return None;
+    }
+    let self_ty = match impl_.self_ty()? {
+        ast::Type::PathType(it) => it,
+        _ => return None,
+    };
+    let ty_name = self_ty.path()?.segment()?.name_ref()?;
+    // `Self` is replaced by the type in the body, which doesn't work for
+    // generic types in expressions.
+    if self_ty.path()?.segments().any(|it| it.generic_arg_list().is_some()) {
+        return None;
+    }
+    let name = fn_.name()?;
+    let param_list = fn_.param_list()?;
+    let self_param = param_list.self_param()?;
+    if self_param.colon_token().is_some() {
+        return None;
+    }
+    let param_name = to_lower_snake_case(&ty_name.text());
+    let name_taken = param_list
+        .params()
+        .filter_map(|it| it.pat())
+        .any(|it| it.syntax().text() == param_name.as_str());
+    if name_taken {
+        return None;
+    }
+    let param = match self_param.amp_token() {
+        Some(_) => {
+            let lifetime = self_param.lifetime().map(|it| format!("{} ", it)).unwrap_or_default();
+            let mut_ = if self_param.mut_token().is_some() { "mut " } else { "" };
+            format!("{}: &{}{}{}", param_name, lifetime, mut_, self_ty)
+        }
+        None => {
+            let mut_ = if self_param.mut_token().is_some() { "mut " } else { "" };
+            format!("{}{}: {}", mut_, param_name, self_ty)
+        }
+    };
+
+    let db = ctx.db();
+    let func = ctx.sema.to_def(&fn_)?;
+    let access = func.self_param(db)?.access(db);
+    let module = func.module(db);
+
+    let mut fn_edits = vec![(self_param.syntax().text_range(), param)];
+    let local = ctx.sema.to_def(&self_param)?;
+    for (_, refs) in Definition::Local(local).usages(&ctx.sema).all() {
+        for r in refs {
+            fn_edits.push((r.range, param_name.clone()));
+        }
+    }
+    fn_edits.extend(
+        fn_.syntax()
+            .descendants()
+            .filter_map(ast::NameRef::cast)
+            .filter(|it| it.text() == "Self")
+            .map(|it| (it.syntax().text_range(), self_ty.to_string())),
+    );
+
+    let usages = Definition::ModuleDef(ModuleDef::Function(func)).usages(&ctx.sema).all();
+    let call_edits = edits_for_usages(&ctx.sema, usages, fn_.syntax(), |r| {
+        let name_ref = r.name.as_name_ref()?;
+        let scope_module = ctx.sema.scope(name_ref.syntax()).module()?;
+        let fn_path = match scope_module == module {
+            true => name.to_string(),
+            false => {
+                let module_path =
+                    mod_path_to_ast(&scope_module.find_use_path(db, ModuleDef::Module(module))?);
+                format!("{}::{}", module_path, name)
+            }
+        };
+        match name_ref.syntax().parent().and_then(ast::MethodCallExpr::cast) {
+            Some(call) => {
+                let receiver = call.receiver()?;
+                let receiver_ty = ctx.sema.type_of_expr(&receiver)?.original;
+                let receiver = match access {
+                    Access::Shared if !receiver_ty.is_reference() => format!("&{}", receiver),
+                    Access::Exclusive if !receiver_ty.is_mutable_reference() => {
+                        format!("&mut {}", receiver)
+                    }
+                    _ => receiver.to_string(),
+                };
+                let generic_args =
+                    call.generic_arg_list().map(|it| it.to_string()).unwrap_or_default();
+                let args = std::iter::once(receiver)
+                    .chain(call.arg_list()?.args().map(|it| it.to_string()))
+                    .join(", ");
+                Some((call.syntax().text_range(), format!("{}{}({})", fn_path, generic_args, args)))
+            }
+            None => {
+                let segment = name_ref.syntax().parent().and_then(ast::PathSegment::cast)?;
+                let path = segment.parent_path();
+                let generic_args =
+                    segment.generic_arg_list().map(|it| it.to_string()).unwrap_or_default();
+                Some((path.syntax().text_range(), format!("{}{}", fn_path, generic_args)))
+            }
+        }
+    })?;
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("convert_method_to_free_function", AssistKind::RefactorRewrite),
+        format!("Convert `{}` to a free function", name),
+        target,
+        |builder| {
+            for (file_id, edits) in call_edits {
+                builder.edit_file(file_id);
+                for (range, text) in edits {
+                    builder.replace(range, text);
+                }
+            }
+            builder.edit_file(ctx.frange.file_id);
+            builder.delete(range_with_leading_whitespace(fn_.syntax()));
+
+            let indent = IndentLevel::from_node(impl_.syntax());
+            let free_fn = match rewrite_fn(&fn_, fn_edits, indent) {
+                Some(it) => it,
+                None => return,
+            };
+            builder.insert(impl_.syntax().text_range().end(), format!("\n\n{}{}", indent, free_fn));
+        },
+    )
+}
+
+/// Collects the edits for all usages of the moved function, grouped by file.
+/// Returns `None` if a usage can't be rewritten, or if edits would overlap.
+fn edits_for_usages(
+    sema: &Semantics<RootDatabase>,
+    usages: UsageSearchResult,
+    fn_: &SyntaxNode,
+    mut edit_for: impl FnMut(&FileReference) -> Option<(TextRange, String)>,
+) -> Option<Vec<(FileId, Vec<(TextRange, String)>)>> {
+    let fn_file = sema.original_range(fn_).file_id;
+    let mut res = Vec::new();
+    for (file_id, refs) in usages {
+        let mut edits = Vec::new();
+        for r in &refs {
+            // Recursive calls would have to be rewritten in the moved body.
+            if file_id == fn_file && fn_.text_range().contains_range(r.range) {
+                return None;
+            }
+            edits.push(edit_for(r)?);
+        }
+        edits.sort_by_key(|(range, _)| range.start());
+        if edits.iter().tuple_windows().any(|((a, _), (b, _))| a.end() > b.start()) {
+            return None;
+        }
+        res.push((file_id, edits));
+    }
+    Some(res)
+}
+
+/// Applies the edits to the text of the function, and indents the result.
+fn rewrite_fn(
+    fn_: &ast::Fn,
+    mut edits: Vec<(TextRange, String)>,
+    indent: IndentLevel,
+) -> Option<String> {
+    let start = fn_.syntax().text_range().start();
+    let mut text = fn_.syntax().text().to_string();
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    for (range, replacement) in edits {
+        let range = range - start;
+        text.replace_range(usize::from(range.start())..usize::from(range.end()), &replacement);
+    }
+    let new_fn = ast::SourceFile::parse(&text)
+        .tree()
+        .syntax()
+        .descendants()
+        .find_map(ast::Fn::cast)?
+        .clone_for_update();
+    new_fn.dedent(IndentLevel::from_node(fn_.syntax()));
+    new_fn.indent(indent);
+    Some(new_fn.to_string())
+}
+
+fn range_with_leading_whitespace(node: &SyntaxNode) -> TextRange {
+    let range = node.text_range();
+    match node.prev_sibling_or_token() {
+        Some(it) if it.kind() == WHITESPACE => TextRange::new(it.text_range().start(), range.end()),
+        _ => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn free_function_to_method_by_ref() {
+        check_assist(
+            convert_free_function_to_method,
+            r#"
+struct Point { x: i32, y: i32 }
+
+impl Point {
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+}
+
+/// Manhattan length.
+pub fn $0length(p: &Point, scale: i32) -> i32 {
+    (p.x.abs() + p.y.abs()) * scale
+}
+
+fn main() {
+    let p = Point::origin();
+    let a = length(&p, 2);
+    let b = length(&Point::origin(), 1);
+    let f = length;
+}
+"#,
+            r#"
+struct Point { x: i32, y: i32 }
+
+impl Point {
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+
+    /// Manhattan length.
+    pub fn length(&self, scale: i32) -> i32 {
+        (self.x.abs() + self.y.abs()) * scale
+    }
+}
+
+fn main() {
+    let p = Point::origin();
+    let a = p.length(2);
+    let b = Point::origin().length(1);
+    let f = Point::length;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn free_function_to_method_by_value() {
+        check_assist(
+            convert_free_function_to_method,
+            r#"
+mod money {
+    pub struct Cents(pub u64);
+
+    impl Cents {
+        pub fn zero() -> Cents { Cents(0) }
+    }
+
+    pub fn $0into_dollars(mut cents: Cents) -> f64 {
+        cents.0 /= 100;
+        let w = Wrapper { cents };
+        w.cents.0 as f64
+    }
+
+    struct Wrapper { cents: Cents }
+}
+
+fn main() {
+    let d = money::into_dollars(money::Cents(250));
+    let e = money::into_dollars(*&money::Cents(1));
+}
+"#,
+            r#"
+mod money {
+    pub struct Cents(pub u64);
+
+    impl Cents {
+        pub fn zero() -> Cents { Cents(0) }
+
+        pub fn into_dollars(mut self) -> f64 {
+            self.0 /= 100;
+            let w = Wrapper { cents: self };
+            w.cents.0 as f64
+        }
+    }
+
+    struct Wrapper { cents: Cents }
+}
+
+fn main() {
+    let d = money::Cents(250).into_dollars();
+    let e = (*&money::Cents(1)).into_dollars();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn free_function_not_applicable() {
+        // Recursive
+        check_assist_not_applicable(
+            convert_free_function_to_method,
+            r#"
+struct S;
+fn $0f(s: &S, n: u32) { if n > 0 { f(s, n - 1) } }
+"#,
+        );
+        // Not a local type
+        check_assist_not_applicable(
+            convert_free_function_to_method,
+            r#"
+fn $0f(n: u32) {}
+"#,
+        );
+        // Name already taken
+        check_assist_not_applicable(
+            convert_free_function_to_method,
+            r#"
+struct S;
+impl S { fn f(&self) {} }
+fn $0f(s: &S) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_to_free_function() {
+        check_assist(
+            convert_method_to_free_function,
+            r#"
+struct Stack { items: Vec<u32> }
+
+impl Stack {
+    fn new() -> Self { Stack { items: Vec::new() } }
+
+    /// Pushes twice.
+    pub fn $0push_twice(&mut self, item: u32) -> &mut Self {
+        self.items.push(item);
+        self.items.push(item);
+        self
+    }
+
+    fn fill(&mut self) {
+        self.push_twice(1);
+        Self::push_twice(self, 2);
+    }
+}
+
+fn main() {
+    let mut s = Stack::new();
+    s.push_twice(3);
+    let r = &mut s;
+    r.push_twice(5);
+}
+"#,
+            r#"
+struct Stack { items: Vec<u32> }
+
+impl Stack {
+    fn new() -> Self { Stack { items: Vec::new() } }
+
+    fn fill(&mut self) {
+        push_twice(self, 1);
+        push_twice(self, 2);
+    }
+}
+
+/// Pushes twice.
+pub fn push_twice(stack: &mut Stack, item: u32) -> &mut Stack {
+    stack.items.push(item);
+    stack.items.push(item);
+    stack
+}
+
+fn main() {
+    let mut s = Stack::new();
+    push_twice(&mut s, 3);
+    let r = &mut s;
+    push_twice(r, 5);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_to_free_function_from_other_module() {
+        check_assist(
+            convert_method_to_free_function,
+            r#"
+mod geo {
+    pub struct Meters(pub f64);
+    impl Meters {
+        pub fn $0double(self) -> Meters { Meters(self.0 * 2.0) }
+    }
+}
+
+fn main() {
+    let m = geo::Meters(1.0).double();
+    let f = geo::Meters::double;
+}
+"#,
+            r#"
+mod geo {
+    pub struct Meters(pub f64);
+    impl Meters {
+    }
+
+    pub fn double(meters: Meters) -> Meters { Meters(meters.0 * 2.0) }
+}
+
+fn main() {
+    let m = geo::double(geo::Meters(1.0));
+    let f = geo::double;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_to_free_function_not_applicable() {
+        check_assist_not_applicable(
+            convert_method_to_free_function,
+            r#"
+struct S;
+impl S { fn $0new() -> S { S } }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_method_to_free_function,
+            r#"
+trait T { fn f(&self); }
+struct S;
+impl T for S { fn $0f(&self) {} }
+"#,
+        );
+        check_assist_not_applicable(
+            convert_method_to_free_function,
+            r#"
+struct S<T>(T);
+impl<T> S<T> { fn $0f(&self) {} }
+"#,
+        );
+    }
+}
@@ -0,0 +1,496 @@
+use hir::{Access, AssocItem, PathResolution};
+use itertools::Itertools;
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{utils::assoc_fn_qualifier, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_method_call_to_ufcs
+//
+// Converts a method call into a fully qualified function call.
+//
+// ```
+// struct Foo;
+// impl Foo {
+//     fn add(&self, x: u32) -> u32 { x }
+// }
+// fn main() {
+//     let foo = Foo;
+//     foo.ad$0d(92);
+// }
+// ```
+// ->
+// ```
+// struct Foo;
+// impl Foo {
+//     fn add(&self, x: u32) -> u32 { x }
+// }
+// fn main() {
+//     let foo = Foo;
+//     Foo::add(&foo, 92);
+// }
+// ```
+pub(crate) fn convert_method_call_to_ufcs(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+    let name_ref = call.name_ref()?;
+    if !name_ref.syntax().text_range().contains_range(ctx.frange.range) {
+        return None;
+    }
+    let receiver = call.receiver()?;
+    let arg_list = call.arg_list()?;
+
+    let db = ctx.db();
+    let func = ctx.sema.resolve_method_call(&call)?;
+    let access = func.self_param(db)?.access(db);
+    let module = ctx.sema.scope(call.syntax()).module()?;
+
+    let qualifier = assoc_fn_qualifier(db, module, func)?;
+
+    let receiver_ty = ctx.sema.type_of_expr(&receiver)?.original;
+    let receiver = match access {
+        Access::Shared if !receiver_ty.is_reference() => format!("&{}", receiver),
+        Access::Exclusive if !receiver_ty.is_mutable_reference() => format!("&mut {}", receiver),
+        _ => receiver.to_string(),
+    };
+    let generic_args = call.generic_arg_list().map(|it| it.to_string()).unwrap_or_default();
+    let args =
+        std::iter::once(receiver).chain(arg_list.args().map(|arg| arg.to_string())).join(", ");
+
+    let target = call.syntax().text_range();
+    acc.add(
+        AssistId("convert_method_call_to_ufcs", AssistKind::RefactorRewrite),
+        "Convert to fully qualified call",
+        target,
+        |builder| {
+            builder
+                .replace(target, format!("{}::{}{}({})", qualifier, name_ref, generic_args, args));
+        },
+    )
+}
+
+// Assist: convert_ufcs_to_method_call
+//
+// Converts a fully qualified function call into a method call.
+//
+// ```
+// struct Foo;
+// impl Foo {
+//     fn add(&self, x: u32) -> u32 { x }
+// }
+// fn main() {
+//     let foo = Foo;
+//     Foo::ad$0d(&foo, 92);
+// }
+// ```
+// ->
+// ```
+// struct Foo;
+// impl Foo {
+//     fn add(&self, x: u32) -> u32 { x }
+// }
+// fn main() {
+//     let foo = Foo;
+//     foo.add(92);
+// }
+// ```
+pub(crate) fn convert_ufcs_to_method_call(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let call = ctx.find_node_at_offset::<ast::CallExpr>()?;
+    let path = match call.expr()? {
+        ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+        _ => return None,
+    };
+    if !path.syntax().text_range().contains_range(ctx.frange.range) {
+        return None;
+    }
+    path.qualifier()?;
+    let segment = path.segment()?;
+    let name_ref = segment.name_ref()?;
+
+    let db = ctx.db();
+    let func = match ctx.sema.resolve_path(&path)? {
+        PathResolution::AssocItem(AssocItem::Function(func)) => func,
+        PathResolution::Def(hir::ModuleDef::Function(func)) => func,
+        _ => return None,
+    };
+    let access = func.self_param(db)?.access(db);
+
+    let mut args = call.arg_list()?.args();
+    let first_arg = args.next()?;
+    let receiver = match (&first_arg, access) {
+        (ast::Expr::RefExpr(ref_expr), Access::Shared) if ref_expr.mut_token().is_none() => {
+            ref_expr.expr()?
+        }
+        (ast::Expr::RefExpr(ref_expr), Access::Exclusive) if ref_expr.mut_token().is_some() => {
+            ref_expr.expr()?
+        }
+        _ => first_arg,
+    };
+
+    // Make sure the call resolves back to the same function, so that we don't
+    // produce a method call to a trait that is not in scope.
+    let scope = ctx.sema.scope(call.syntax());
+    let krate = scope.module()?.krate();
+    let traits_in_scope = scope.traits_in_scope();
+    let receiver_ty = ctx.sema.type_of_expr(&receiver)?.original;
+    receiver_ty.iterate_method_candidates(
+        db,
+        krate,
+        &traits_in_scope,
+        Some(&func.name(db)),
+        |_, candidate| (candidate == func).then(|| ()),
+    )?;
+
+    let receiver = if needs_parens_as_receiver(&receiver) {
+        format!("({})", receiver)
+    } else {
+        receiver.to_string()
+    };
+    let generic_args = segment.generic_arg_list().map(|it| it.to_string()).unwrap_or_default();
+    let args = args.map(|arg| arg.to_string()).join(", ");
+
+    let target = call.syntax().text_range();
+    acc.add(
+        AssistId("convert_ufcs_to_method_call", AssistKind::RefactorRewrite),
+        "Convert to method call",
+        target,
+        |builder| {
+            builder.replace(target, format!("{}.{}{}({})", receiver, name_ref, generic_args, args));
+        },
+    )
+}
+
+fn needs_parens_as_receiver(expr: &ast::Expr) -> bool {
+    !matches!(
+        expr,
+        ast::Expr::ArrayExpr(_)
+            | ast::Expr::BlockExpr(_)
+            | ast::Expr::CallExpr(_)
+            | ast::Expr::FieldExpr(_)
+            | ast::Expr::IndexExpr(_)
+            | ast::Expr::Literal(_)
+            | ast::Expr::MacroCall(_)
+            | ast::Expr::MethodCallExpr(_)
+            | ast::Expr::ParenExpr(_)
+            | ast::Expr::PathExpr(_)
+            | ast::Expr::RecordExpr(_)
+            | ast::Expr::TupleExpr(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn method_call_to_ufcs_inherent() {
+        check_assist(
+            convert_method_call_to_ufcs,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self, x: u32) {}
+    fn by_mut(&mut self) {}
+    fn by_value(self, x: u32, y: u32) {}
+}
+fn main() {
+    let mut foo = Foo;
+    foo.by_m$0ut();
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self, x: u32) {}
+    fn by_mut(&mut self) {}
+    fn by_value(self, x: u32, y: u32) {}
+}
+fn main() {
+    let mut foo = Foo;
+    Foo::by_mut(&mut foo);
+}
+"#,
+        );
+        check_assist(
+            convert_method_call_to_ufcs,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_value(self, x: u32, y: u32) {}
+}
+fn main() {
+    Foo.by_val$0ue(1, 2);
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_value(self, x: u32, y: u32) {}
+}
+fn main() {
+    Foo::by_value(Foo, 1, 2);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_call_to_ufcs_keeps_reference_receiver() {
+        check_assist(
+            convert_method_call_to_ufcs,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self) {}
+}
+fn f(foo: &Foo) {
+    foo.by_r$0ef();
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self) {}
+}
+fn f(foo: &Foo) {
+    Foo::by_ref(foo);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_call_to_ufcs_trait() {
+        check_assist(
+            convert_method_call_to_ufcs,
+            r#"
+mod m {
+    pub trait Tr {
+        fn method<T>(&self, t: T);
+    }
+}
+use m::Tr;
+struct Foo;
+impl Tr for Foo {
+    fn method<T>(&self, t: T) {}
+}
+fn main() {
+    Foo.meth$0od::<u8>(0);
+}
+"#,
+            r#"
+mod m {
+    pub trait Tr {
+        fn method<T>(&self, t: T);
+    }
+}
+use m::Tr;
+struct Foo;
+impl Tr for Foo {
+    fn method<T>(&self, t: T) {}
+}
+fn main() {
+    Tr::method::<u8>(&Foo, 0);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_call_to_ufcs_reexport() {
+        check_assist(
+            convert_method_call_to_ufcs,
+            r#"
+mod outer {
+    mod inner {
+        pub struct Foo;
+        impl Foo {
+            pub fn method(&self) {}
+        }
+    }
+    pub use self::inner::Foo;
+}
+fn f(foo: outer::Foo) {
+    foo.meth$0od();
+}
+"#,
+            r#"
+mod outer {
+    mod inner {
+        pub struct Foo;
+        impl Foo {
+            pub fn method(&self) {}
+        }
+    }
+    pub use self::inner::Foo;
+}
+fn f(foo: outer::Foo) {
+    outer::Foo::method(&foo);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn method_call_to_ufcs_not_on_receiver() {
+        check_assist_not_applicable(
+            convert_method_call_to_ufcs,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self) {}
+}
+fn main() {
+    F$0oo.by_ref();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ufcs_to_method_call_inherent() {
+        check_assist(
+            convert_ufcs_to_method_call,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_mut(&mut self, x: u32) {}
+}
+fn main() {
+    let mut foo = Foo;
+    Foo::by_m$0ut(&mut foo, 92);
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_mut(&mut self, x: u32) {}
+}
+fn main() {
+    let mut foo = Foo;
+    foo.by_mut(92);
+}
+"#,
+        );
+        check_assist(
+            convert_ufcs_to_method_call,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self) {}
+}
+fn f(foo: &Foo) {
+    Foo::by_r$0ef(foo);
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_ref(&self) {}
+}
+fn f(foo: &Foo) {
+    foo.by_ref();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ufcs_to_method_call_adds_parens() {
+        check_assist(
+            convert_ufcs_to_method_call,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_value(self) {}
+}
+fn f(foo: &Foo) {
+    Foo::by_val$0ue(*foo);
+}
+"#,
+            r#"
+struct Foo;
+impl Foo {
+    fn by_value(self) {}
+}
+fn f(foo: &Foo) {
+    (*foo).by_value();
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ufcs_to_method_call_trait() {
+        check_assist(
+            convert_ufcs_to_method_call,
+            r#"
+mod m {
+    pub trait Tr {
+        fn method<T>(&self, t: T);
+    }
+}
+use m::Tr;
+struct Foo;
+impl Tr for Foo {
+    fn method<T>(&self, t: T) {}
+}
+fn main() {
+    m::Tr::meth$0od::<u8>(&Foo, 0);
+}
+"#,
+            r#"
+mod m {
+    pub trait Tr {
+        fn method<T>(&self, t: T);
+    }
+}
+use m::Tr;
+struct Foo;
+impl Tr for Foo {
+    fn method<T>(&self, t: T) {}
+}
+fn main() {
+    Foo.method::<u8>(0);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ufcs_to_method_call_trait_not_in_scope() {
+        check_assist_not_applicable(
+            convert_ufcs_to_method_call,
+            r#"
+mod m {
+    pub trait Tr {
+        fn method(&self);
+    }
+}
+struct Foo;
+impl m::Tr for Foo {
+    fn method(&self) {}
+}
+fn main() {
+    m::Tr::meth$0od(&Foo);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ufcs_to_method_call_not_for_associated_fn() {
+        check_assist_not_applicable(
+            convert_ufcs_to_method_call,
+            r#"
+struct Foo;
+impl Foo {
+    fn new() -> Foo { Foo }
+}
+fn main() {
+    Foo::ne$0w();
+}
+"#,
+        );
+    }
+}
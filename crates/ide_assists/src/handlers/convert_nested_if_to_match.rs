@@ -0,0 +1,322 @@
+use std::iter::{self, successors};
+
+use hir::{ModuleDef, PathResolution};
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, BinaryOp, CmpOp,
+    },
+    AstNode, TextRange,
+};
+
+use crate::{utils::unwrap_trivial_block, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_nested_if_to_match
+//
+// Converts an `if`/`else if` chain comparing the same expression against
+// different values into a `match`.
+//
+// ```
+// fn name(n: u32) -> &'static str {
+//     $0if n == 1 {
+//         "one"
+//     } else if n == 2 {
+//         "two"
+//     } else {
+//         "many"
+//     }
+// }
+// ```
+// ->
+// ```
+// fn name(n: u32) -> &'static str {
+//     match n {
+//         1 => "one",
+//         2 => "two",
+//         _ => "many",
+//     }
+// }
+// ```
+pub(crate) fn convert_nested_if_to_match(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let if_expr: ast::IfExpr = ctx.find_node_at_offset()?;
+    let available_range = TextRange::new(
+        if_expr.syntax().text_range().start(),
+        if_expr.then_branch()?.syntax().text_range().start(),
+    );
+    if !available_range.contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+
+    let mut else_block = None;
+    let if_exprs = successors(Some(if_expr.clone()), |expr| match expr.else_branch()? {
+        ast::ElseBranch::IfExpr(expr) => Some(expr),
+        ast::ElseBranch::Block(block) => {
+            else_block = Some(block);
+            None
+        }
+    });
+
+    let mut scrutinee: Option<ast::Expr> = None;
+    let mut arms = Vec::new();
+    for if_expr in if_exprs {
+        let cond = if_expr.condition()?;
+        if cond.pat().is_some() {
+            return None;
+        }
+        let bin_expr = match cond.expr()? {
+            ast::Expr::BinExpr(it) => it,
+            _ => return None,
+        };
+        if bin_expr.op_kind()? != BinaryOp::CmpOp(CmpOp::Eq { negated: false }) {
+            return None;
+        }
+        let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+        match &scrutinee {
+            Some(scrutinee) if scrutinee.syntax().text() != lhs.syntax().text() => return None,
+            Some(_) => {}
+            None => scrutinee = Some(lhs),
+        }
+        arms.push((value_pat(ctx, &rhs)?, if_expr.then_branch()?));
+    }
+    if arms.len() < 2 && else_block.is_none() {
+        return None;
+    }
+    let scrutinee = scrutinee?;
+    // The `if` chain evaluates the scrutinee once per condition, `match` only once.
+    let has_calls = scrutinee.syntax().descendants().any(|it| {
+        ast::CallExpr::can_cast(it.kind())
+            || ast::MethodCallExpr::can_cast(it.kind())
+            || ast::MacroCall::can_cast(it.kind())
+    });
+    if has_calls && arms.len() > 1 {
+        cov_mark::hit!(nested_if_scrutinee_has_calls);
+        return None;
+    }
+    // Comparing a `String` with a string literal needs a `&str` to match on.
+    let matches_on_string = arms.iter().any(|(pat, _)| is_string_pat(pat))
+        && ctx
+            .sema
+            .type_of_expr(&scrutinee)
+            .and_then(|ty| ty.original.as_adt())
+            .map_or(false, |adt| adt.name(ctx.db()).to_string() == "String");
+
+    acc.add(
+        AssistId("convert_nested_if_to_match", AssistKind::RefactorRewrite),
+        "Convert to match",
+        available_range,
+        move |edit| {
+            let arm_body = |body: ast::BlockExpr| {
+                unwrap_trivial_block(body.reset_indent().indent(IndentLevel(1)))
+            };
+            let else_body = else_block.map_or_else(make::expr_empty_block, arm_body);
+            let arms = arms
+                .into_iter()
+                .map(|(pat, body)| make::match_arm(iter::once(pat), None, arm_body(body)))
+                .chain(iter::once(make::match_arm(
+                    iter::once(make::wildcard_pat().into()),
+                    None,
+                    else_body,
+                )));
+            let scrutinee = if matches_on_string {
+                make::expr_method_call(scrutinee, make::name_ref("as_str"), make::arg_list(None))
+            } else {
+                scrutinee
+            };
+            let match_expr = make::expr_match(scrutinee, make::match_arm_list(arms))
+                .indent(IndentLevel::from_node(if_expr.syntax()));
+
+            let has_preceding_if_expr =
+                if_expr.syntax().parent().map_or(false, |it| ast::IfExpr::can_cast(it.kind()));
+            let expr = if has_preceding_if_expr {
+                make::block_expr(None, Some(match_expr)).into()
+            } else {
+                match_expr
+            };
+            edit.replace_ast::<ast::Expr>(if_expr.into(), expr);
+        },
+    )
+}
+
+/// Turns the value an expression is compared against into an equivalent
+/// pattern: literals, and paths to enum variants or constants.
+fn value_pat(ctx: &AssistContext, value: &ast::Expr) -> Option<ast::Pat> {
+    match value {
+        ast::Expr::Literal(_) => Some(make::literal_pat(&value.to_string()).into()),
+        ast::Expr::PrefixExpr(prefix)
+            if prefix.op_kind()? == ast::UnaryOp::Neg
+                && matches!(prefix.expr()?, ast::Expr::Literal(_)) =>
+        {
+            Some(make::literal_pat(&value.to_string()).into())
+        }
+        ast::Expr::PathExpr(path_expr) => {
+            let path = path_expr.path()?;
+            // Any other path would become a binding.
+            match ctx.sema.resolve_path(&path)? {
+                PathResolution::Def(ModuleDef::Variant(_) | ModuleDef::Const(_))
+                | PathResolution::AssocItem(hir::AssocItem::Const(_)) => {}
+                _ => return None,
+            }
+            Some(make::path_pat(path))
+        }
+        _ => None,
+    }
+}
+
+fn is_string_pat(pat: &ast::Pat) -> bool {
+    match pat {
+        ast::Pat::LiteralPat(pat) => {
+            pat.literal().map_or(false, |it| matches!(it.kind(), ast::LiteralKind::String(_)))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn two_branches() {
+        check_assist(
+            convert_nested_if_to_match,
+            r#"
+fn f(c: char) {
+    $0if c == 'a' {
+        foo();
+    } else {
+        bar();
+    }
+}
+"#,
+            r#"
+fn f(c: char) {
+    match c {
+        'a' => {
+            foo();
+        }
+        _ => {
+            bar();
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn three_branches_with_variants() {
+        check_assist(
+            convert_nested_if_to_match,
+            r#"
+#[derive(PartialEq)]
+enum Color { Red, Green, Blue }
+fn f(color: Color) -> u32 {
+    if$0 color == Color::Red {
+        1
+    } else if color == Color::Green {
+        2
+    } else if color == Color::Blue {
+        3
+    } else {
+        0
+    }
+}
+"#,
+            r#"
+#[derive(PartialEq)]
+enum Color { Red, Green, Blue }
+fn f(color: Color) -> u32 {
+    match color {
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Blue => 3,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn negative_integers_without_else() {
+        check_assist(
+            convert_nested_if_to_match,
+            r#"
+fn f(x: i32) {
+    $0if x == -1 { a() } else if x == 1 { b() }
+}
+"#,
+            r#"
+fn f(x: i32) {
+    match x {
+        -1 => a(),
+        1 => b(),
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn string_scrutinee() {
+        check_assist(
+            convert_nested_if_to_match,
+            r#"
+struct String;
+fn f(s: String) -> u32 {
+    $0if s == "a" { 1 } else { 2 }
+}
+"#,
+            r#"
+struct String;
+fn f(s: String) -> u32 {
+    match s.as_str() {
+        "a" => 1,
+        _ => 2,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_different_lhs() {
+        check_assist_not_applicable(
+            convert_nested_if_to_match,
+            r#"
+fn f(x: u32, y: u32) {
+    $0if x == 1 { a() } else if y == 2 { b() } else { c() }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_local_variable() {
+        check_assist_not_applicable(
+            convert_nested_if_to_match,
+            r#"
+fn f(x: u32, y: u32) {
+    $0if x == y { a() } else { b() }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_repeated_calls() {
+        cov_mark::check!(nested_if_scrutinee_has_calls);
+        check_assist_not_applicable(
+            convert_nested_if_to_match,
+            r#"
+fn f() {
+    $0if next() == 1 { a() } else if next() == 2 { b() }
+}
+"#,
+        );
+    }
+}
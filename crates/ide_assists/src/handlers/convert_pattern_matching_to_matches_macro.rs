@@ -0,0 +1,332 @@
+use hir::ScopeDef;
+use itertools::Itertools;
+use syntax::{
+    ast::{self, AstNode},
+    SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_pattern_matching_to_matches_macro
+//
+// Converts a `match` that only produces `true` or `false` into a `matches!` call.
+//
+// ```
+// macro_rules! matches { ($($t:tt)*) => {} }
+// enum Shape { Circle, Square, Line }
+// fn is_round(shape: Shape) -> bool {
+//     $0match shape {
+//         Shape::Circle => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// macro_rules! matches { ($($t:tt)*) => {} }
+// enum Shape { Circle, Square, Line }
+// fn is_round(shape: Shape) -> bool {
+//     matches!(shape, Shape::Circle)
+// }
+// ```
+pub(crate) fn convert_pattern_matching_to_matches_macro(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let match_arm_list = match_expr.match_arm_list()?;
+    if ctx.offset() > match_arm_list.syntax().text_range().start() {
+        return None;
+    }
+    let scrutinee = match_expr.expr()?;
+
+    let mut arms = match_arm_list.arms().collect::<Vec<_>>();
+    let last_arm = arms.pop()?;
+    if !matches!(last_arm.pat()?, ast::Pat::WildcardPat(_)) || last_arm.guard().is_some() {
+        return None;
+    }
+    let fallback = bool_literal(&last_arm.expr()?)?;
+
+    let mut pats = Vec::new();
+    for arm in &arms {
+        if bool_literal(&arm.expr()?)? == fallback {
+            return None;
+        }
+        pats.push(arm.pat()?.to_string());
+    }
+    let guard = match &*arms {
+        [] => return None,
+        [arm] => arm.guard(),
+        // A guard would only apply to the last of the joined patterns.
+        arms if arms.iter().any(|arm| arm.guard().is_some()) => return None,
+        // The alternatives of an or-pattern must bind the same variables.
+        arms if !same_bindings(ctx, arms)? => {
+            cov_mark::hit!(matches_arms_with_different_bindings);
+            return None;
+        }
+        _ => None,
+    };
+
+    if !matches_macro_in_scope(ctx, match_expr.syntax()) {
+        cov_mark::hit!(matches_macro_not_in_scope);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_pattern_matching_to_matches_macro", AssistKind::RefactorRewrite),
+        "Convert to `matches!`",
+        target,
+        |builder| {
+            let guard = guard.map(|it| format!(" {}", it)).unwrap_or_default();
+            let call = format!("matches!({}, {}{})", scrutinee, pats.iter().join(" | "), guard);
+            let replacement = if !fallback {
+                call
+            } else if needs_parens_when_negated(match_expr.syntax()) {
+                format!("(!{})", call)
+            } else {
+                format!("!{}", call)
+            };
+            builder.replace(target, replacement);
+        },
+    )
+}
+
+fn bool_literal(expr: &ast::Expr) -> Option<bool> {
+    let expr = match expr {
+        ast::Expr::BlockExpr(block) => {
+            let stmt_list = block.stmt_list()?;
+            if stmt_list.statements().next().is_some() {
+                return None;
+            }
+            stmt_list.tail_expr()?
+        }
+        expr => expr.clone(),
+    };
+    match expr {
+        ast::Expr::Literal(lit) => match lit.kind() {
+            ast::LiteralKind::Bool(value) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether the patterns of `arms` bind the same variables with the same types.
+fn same_bindings(ctx: &AssistContext, arms: &[ast::MatchArm]) -> Option<bool> {
+    let bindings = |arm: &ast::MatchArm| -> Option<Vec<(Option<hir::Name>, hir::Type)>> {
+        let pat = arm.pat()?;
+        let mut res = pat
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter_map(|it| ctx.sema.to_def(&it))
+            .map(|local| (local.name(ctx.db()), local.ty(ctx.db())))
+            .collect::<Vec<_>>();
+        res.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Some(res)
+    };
+    let (first, rest) = arms.split_first()?;
+    let first = bindings(first)?;
+    for arm in rest {
+        let other = bindings(arm)?;
+        let same = first.len() == other.len()
+            && first.iter().zip(&other).all(|((name, ty), (other_name, other_ty))| {
+                name == other_name && ty.could_unify_with(ctx.db(), other_ty)
+            });
+        if !same {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+fn matches_macro_in_scope(ctx: &AssistContext, node: &SyntaxNode) -> bool {
+    let mut found = false;
+    ctx.sema.scope(node).process_all_names(&mut |name, def| {
+        if matches!(def, ScopeDef::MacroDef(_)) && name.to_string() == "matches" {
+            found = true;
+        }
+    });
+    found
+}
+
+/// Whether `!matches!(..)` in place of `node` would bind less tightly than its parent.
+fn needs_parens_when_negated(node: &SyntaxNode) -> bool {
+    match node.parent() {
+        Some(parent) => {
+            ast::MethodCallExpr::can_cast(parent.kind())
+                || ast::FieldExpr::can_cast(parent.kind())
+                || ast::TryExpr::can_cast(parent.kind())
+                || ast::AwaitExpr::can_cast(parent.kind())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn negated_match() {
+        check_assist(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: Option<u32>) -> bool {
+    match$0 x {
+        Some(0) => false,
+        _ => true,
+    }
+}
+"#,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: Option<u32>) -> bool {
+    !matches!(x, Some(0))
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn multiple_arms_in_if_condition() {
+        check_assist(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(c: char) {
+    if $0match c { 'a' => true, 'b' | 'c' => { true } _ => false } {}
+}
+"#,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(c: char) {
+    if matches!(c, 'a' | 'b' | 'c') {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn guard_in_while_condition() {
+        check_assist(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(mut n: Option<u32>) {
+    while $0match n { Some(x) if x > 0 => true, _ => false } {}
+}
+"#,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(mut n: Option<u32>) {
+    while matches!(n, Some(x) if x > 0) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn negated_method_receiver() {
+        check_assist(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: u32) {
+    $0match x { 0 => false, _ => true }.then(|| ());
+}
+"#,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: u32) {
+    (!matches!(x, 0)).then(|| ());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_non_literal_arms() {
+        check_assist_not_applicable(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: u32, y: bool) -> bool {
+    $0match x { 0 => y, _ => false }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+fn f(x: u32) -> bool {
+    $0match x { 0 => true, 1 => false, _ => false }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_matches_macro() {
+        cov_mark::check!(matches_macro_not_in_scope);
+        check_assist_not_applicable(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+fn f(x: u32) -> bool {
+    $0match x { 0 => true, _ => false }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn joins_arms_with_same_bindings() {
+        check_assist(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum E { A(u32), B(u32), C }
+fn f(e: E) -> bool {
+    $0match e { E::A(x) => true, E::B(x) => true, _ => false }
+}
+"#,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum E { A(u32), B(u32), C }
+fn f(e: E) -> bool {
+    matches!(e, E::A(x) | E::B(x))
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_arms_with_different_bindings() {
+        cov_mark::check_count!(matches_arms_with_different_bindings, 2);
+        check_assist_not_applicable(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum E { A(u32), B(u32), C }
+fn f(e: E) -> bool {
+    $0match e { E::A(x) => true, E::B(y) => true, _ => false }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_pattern_matching_to_matches_macro,
+            r#"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum E { A(u32), B(bool), C }
+fn f(e: E) -> bool {
+    $0match e { E::A(x) => true, E::B(x) => true, _ => false }
+}
+"#,
+        );
+    }
+}
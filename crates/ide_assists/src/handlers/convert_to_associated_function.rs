@@ -0,0 +1,291 @@
+use hir::{Adt, ModuleDef, PathResolution};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{self, AstNode, HasArgList, HasVisibility};
+
+use crate::{
+    utils::{find_impl_block_start, find_struct_impl, generate_impl_text},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_to_associated_function
+//
+// Replaces the construction of a tuple struct with a call to a new `new`
+// associated function.
+//
+// ```
+// struct Point(i32, i32);
+//
+// fn origin() -> Point {
+//     let (x, y) = (0, 0);
+//     Point$0(x, y)
+// }
+// ```
+// ->
+// ```
+// struct Point(i32, i32);
+//
+// impl Point {
+//     fn $0new(x: i32, y: i32) -> Self { Self(x, y) }
+// }
+//
+// fn origin() -> Point {
+//     let (x, y) = (0, 0);
+//     Point::new(x, y)
+// }
+// ```
+pub(crate) fn convert_to_associated_function(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let call = ctx.find_node_at_offset::<ast::CallExpr>()?;
+    let path = match call.expr()? {
+        ast::Expr::PathExpr(it) => it.path()?,
+        _ => return None,
+    };
+    let arg_list = call.arg_list()?;
+    if ctx.offset() > arg_list.syntax().text_range().start() {
+        return None;
+    }
+    let strukt_def = match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(ModuleDef::Adt(Adt::Struct(it))) => it,
+        _ => return None,
+    };
+    let krate = ctx.sema.scope(call.syntax()).krate()?;
+    if strukt_def.module(ctx.db()).krate() != krate {
+        return None;
+    }
+    let source = ctx.sema.source(strukt_def)?;
+    if source.file_id.is_macro() {
+        return None;
+    }
+    let file_id = source.file_id.original_file(ctx.db());
+    let strukt = source.value;
+    let field_list = match strukt.field_list()? {
+        ast::FieldList::TupleFieldList(it) => it,
+        ast::FieldList::RecordFieldList(_) => return None,
+    };
+    let args = arg_list.args().collect::<Vec<_>>();
+    if args.len() != field_list.fields().count() {
+        return None;
+    }
+
+    // Return early if we've found an existing new fn
+    let adt = ast::Adt::Struct(strukt.clone());
+    let impl_def = find_struct_impl(ctx, &adt, "new")?;
+
+    let mut names: Vec<String> = Vec::new();
+    // Arguments that are plain locals name their parameter, the others are
+    // numbered like the fields of a tuple struct converted to a named one.
+    for (idx, arg) in args.iter().enumerate() {
+        let name = match arg {
+            ast::Expr::PathExpr(it) => it
+                .path()
+                .filter(|it| it.qualifier().is_none())
+                .and_then(|it| it.segment()?.name_ref())
+                .map(|it| it.text().to_string()),
+            _ => None,
+        };
+        let name = name.unwrap_or_else(|| format!("field{}", idx + 1));
+        let name = (1..)
+            .map(|n| if n == 1 { name.clone() } else { format!("{}{}", name, n) })
+            .find(|it| !names.contains(it))?;
+        names.push(name);
+    }
+
+    let target = call.syntax().text_range();
+    acc.add(
+        AssistId("convert_to_associated_function", AssistKind::RefactorRewrite),
+        "Convert to `new` associated function",
+        target,
+        |builder| {
+            builder.insert(path.syntax().text_range().end(), "::new");
+            builder.edit_file(file_id);
+
+            let mut buf = String::with_capacity(512);
+            if impl_def.is_some() {
+                buf.push('\n');
+            }
+
+            let vis = strukt.visibility().map_or(String::new(), |v| format!("{} ", v));
+            let params = names
+                .iter()
+                .zip(field_list.fields())
+                .filter_map(|(name, f)| Some(format!("{}: {}", name, f.ty()?.syntax())))
+                .format(", ");
+            let fields = names.iter().format(", ");
+
+            format_to!(buf, "    {}fn new({}) -> Self {{ Self({}) }}", vis, params, fields);
+
+            let start_offset = impl_def
+                .and_then(|impl_def| find_impl_block_start(impl_def, &mut buf))
+                .unwrap_or_else(|| {
+                    buf = generate_impl_text(&adt, &buf);
+                    strukt.syntax().text_range().end()
+                });
+
+            match ctx.config.snippet_cap {
+                None => builder.insert(start_offset, buf),
+                Some(cap) => {
+                    buf = buf.replace("fn new", "fn $0new");
+                    builder.insert_snippet(cap, start_offset, buf);
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_unit_tuple_struct() {
+        check_assist(
+            convert_to_associated_function,
+            r#"
+struct Marker();
+
+fn f() -> Marker {
+    Mark$0er()
+}
+"#,
+            r#"
+struct Marker();
+
+impl Marker {
+    fn $0new() -> Self { Self() }
+}
+
+fn f() -> Marker {
+    Marker::new()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_single_field_into_existing_impl() {
+        check_assist(
+            convert_to_associated_function,
+            r#"
+pub struct Meters(pub f64);
+
+impl Meters {
+    pub fn value(&self) -> f64 { self.0 }
+}
+
+fn f() -> Meters {
+    $0Meters(1.0 + 2.0)
+}
+"#,
+            r#"
+pub struct Meters(pub f64);
+
+impl Meters {
+    pub fn $0new(field1: f64) -> Self { Self(field1) }
+
+    pub fn value(&self) -> f64 { self.0 }
+}
+
+fn f() -> Meters {
+    Meters::new(1.0 + 2.0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_multi_field_generic() {
+        check_assist(
+            convert_to_associated_function,
+            r#"
+struct Pair<T: Clone, U>(T, U, T);
+
+fn f(first: u32, second: &str) {
+    let p = Pa$0ir(first, second, first);
+}
+"#,
+            r#"
+struct Pair<T: Clone, U>(T, U, T);
+
+impl<T: Clone, U> Pair<T, U> {
+    fn $0new(first: T, second: U, first2: T) -> Self { Self(first, second, first2) }
+}
+
+fn f(first: u32, second: &str) {
+    let p = Pair::new(first, second, first);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_struct_in_other_file() {
+        check_assist(
+            convert_to_associated_function,
+            r#"
+//- /main.rs
+mod units;
+
+fn f(raw: u64) -> units::Bytes {
+    units::Bytes$0(raw)
+}
+//- /units.rs
+pub struct Bytes(pub u64);
+"#,
+            r#"
+//- /main.rs
+mod units;
+
+fn f(raw: u64) -> units::Bytes {
+    units::Bytes::new(raw)
+}
+//- /units.rs
+pub struct Bytes(pub u64);
+
+impl Bytes {
+    pub fn $0new(raw: u64) -> Self { Self(raw) }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_existing_new() {
+        check_assist_not_applicable(
+            convert_to_associated_function,
+            r#"
+struct Meters(f64);
+impl Meters {
+    fn new(v: f64) -> Self { Self(v) }
+}
+fn f() -> Meters {
+    Meters$0(1.0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_functions_and_variants() {
+        check_assist_not_applicable(
+            convert_to_associated_function,
+            r#"
+enum E { V(u32) }
+fn g(x: u32) -> E { E::V(x) }
+fn f() {
+    g$0(1);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_to_associated_function,
+            r#"
+enum E { V(u32) }
+fn f() {
+    E::V$0(1);
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use hir::AssocItem;
+use syntax::{
+    ast::{self, AstNode, HasName},
+    SyntaxKind::WHITESPACE,
+    SyntaxNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_to_default_impl_function
+//
+// Removes a trait method implementation that is identical to the default
+// implementation of the trait.
+//
+// ```
+// trait Greet {
+//     fn greet(&self) -> String { String::from("hello") }
+// }
+// struct World;
+// impl Greet for World {
+//     fn $0greet(&self) -> String {
+//         String::from("hello")
+//     }
+// }
+// ```
+// ->
+// ```
+// trait Greet {
+//     fn greet(&self) -> String { String::from("hello") }
+// }
+// struct World;
+// impl Greet for World {
+// }
+// ```
+pub(crate) fn convert_to_default_impl_function(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if ctx.offset() > body.syntax().text_range().start() {
+        return None;
+    }
+    let impl_ = fn_.syntax().ancestors().nth(2).and_then(ast::Impl::cast)?;
+    let db = ctx.db();
+    let impl_def = ctx.sema.to_def(&impl_)?;
+    let trait_ = impl_def.trait_(db)?;
+    // The bodies are compared token by token, so paths in them have to resolve
+    // the same way on both sides.
+    if impl_def.module(db) != trait_.module(db) {
+        cov_mark::hit!(convert_to_default_impl_function_other_module);
+        return None;
+    }
+    let name = fn_.name()?;
+    let default_fn = trait_.items(db).into_iter().find_map(|item| match item {
+        AssocItem::Function(it) if it.name(db).to_string() == name.text() => Some(it),
+        _ => None,
+    })?;
+    if !default_fn.has_body(db) {
+        return None;
+    }
+    let default_body = ctx.sema.source(default_fn)?.value.body()?;
+    if normalized(body.syntax()) != normalized(default_body.syntax()) {
+        cov_mark::hit!(convert_to_default_impl_function_body_differs);
+        return None;
+    }
+
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("convert_to_default_impl_function", AssistKind::RefactorRewrite),
+        format!("Remove `{}`, using the default implementation", name),
+        target,
+        |builder| {
+            let start = match fn_.syntax().prev_sibling_or_token() {
+                Some(it) if it.kind() == WHITESPACE => it.text_range().start(),
+                _ => target.start(),
+            };
+            builder.delete(TextRange::new(start, target.end()))
+        },
+    )
+}
+
+/// The tokens of the node, ignoring whitespace and comments.
+fn normalized(node: &SyntaxNode) -> Vec<String> {
+    node.descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !it.kind().is_trivia())
+        .map(|it| it.text().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn remove_identical_override() {
+        check_assist(
+            convert_to_default_impl_function,
+            r#"
+trait Shape {
+    fn sides(&self) -> u32;
+    fn describe(&self) -> String {
+        // Spell out the number of sides.
+        let sides = self.sides();
+        match sides {
+            3 => "triangle".to_string(),
+            n => format!("{}-gon", n),
+        }
+    }
+}
+struct Square;
+impl Shape for Square {
+    fn sides(&self) -> u32 {
+        4
+    }
+
+    fn describe$0(&self) -> String {
+        let sides = self.sides();
+        match sides { 3 => "triangle".to_string(), n => format!("{}-gon", n), }
+    }
+}
+"#,
+            r#"
+trait Shape {
+    fn sides(&self) -> u32;
+    fn describe(&self) -> String {
+        // Spell out the number of sides.
+        let sides = self.sides();
+        match sides {
+            3 => "triangle".to_string(),
+            n => format!("{}-gon", n),
+        }
+    }
+}
+struct Square;
+impl Shape for Square {
+    fn sides(&self) -> u32 {
+        4
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_different_body() {
+        cov_mark::check!(convert_to_default_impl_function_body_differs);
+        check_assist_not_applicable(
+            convert_to_default_impl_function,
+            r#"
+trait Shape {
+    fn area(&self) -> f64 { let side = 1.0; side * side }
+}
+struct Unit;
+impl Shape for Unit {
+    fn $0area(&self) -> f64 { let side = 2.0; side * side }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_default() {
+        check_assist_not_applicable(
+            convert_to_default_impl_function,
+            r#"
+trait Shape {
+    fn area(&self) -> f64;
+}
+struct Unit;
+impl Shape for Unit {
+    fn $0area(&self) -> f64 { 1.0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_inherent_impl() {
+        check_assist_not_applicable(
+            convert_to_default_impl_function,
+            r#"
+struct Unit;
+impl Unit {
+    fn $0area(&self) -> f64 { 1.0 }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_other_module() {
+        cov_mark::check!(convert_to_default_impl_function_other_module);
+        check_assist_not_applicable(
+            convert_to_default_impl_function,
+            r#"
+mod shapes {
+    pub fn unit() -> f64 { 1.0 }
+    pub trait Shape {
+        fn area(&self) -> f64 { unit() }
+    }
+}
+use shapes::Shape;
+fn unit() -> f64 { 2.0 }
+struct Unit;
+impl Shape for Unit {
+    fn $0area(&self) -> f64 { unit() }
+}
+"#,
+        );
+    }
+}
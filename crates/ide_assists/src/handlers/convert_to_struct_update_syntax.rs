@@ -0,0 +1,205 @@
+use syntax::ast::{self, AstNode, FieldKind};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_to_struct_update_syntax
+//
+// Replaces a struct literal copying every field from another value of the
+// same type with struct update syntax.
+//
+// ```
+// struct Point { x: u32, y: u32 }
+// fn copy(p: Point) -> Point {
+//     $0Point { x: p.x, y: p.y }
+// }
+// ```
+// ->
+// ```
+// struct Point { x: u32, y: u32 }
+// fn copy(p: Point) -> Point {
+//     Point { ..p }
+// }
+// ```
+pub(crate) fn convert_to_struct_update_syntax(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let record_expr = ctx.find_node_at_offset::<ast::RecordExpr>()?;
+    let field_list = record_expr.record_expr_field_list()?;
+    if field_list.dotdot_token().is_some() {
+        return None;
+    }
+
+    let mut source: Option<ast::Expr> = None;
+    let mut field_count = 0;
+    for field in field_list.fields() {
+        let name = field.name_ref()?;
+        let field_expr = match field.expr()? {
+            ast::Expr::FieldExpr(it) => it,
+            _ => return None,
+        };
+        let copied = match field_expr.field_access()? {
+            FieldKind::Name(it) => it.text().to_string(),
+            FieldKind::Index(it) => it.text().to_string(),
+        };
+        if copied != name.text() {
+            return None;
+        }
+        let receiver = field_expr.expr()?;
+        match &source {
+            Some(source) if source.syntax().text() != receiver.syntax().text() => return None,
+            Some(_) => {}
+            None if is_place(&receiver) => source = Some(receiver),
+            None => return None,
+        }
+        field_count += 1;
+    }
+    let source = source?;
+
+    let db = ctx.db();
+    let strukt = match ctx.sema.type_of_expr(&record_expr.clone().into())?.original.as_adt()? {
+        hir::Adt::Struct(it) => it,
+        _ => return None,
+    };
+    if strukt.fields(db).len() != field_count {
+        cov_mark::hit!(struct_update_missing_fields);
+        return None;
+    }
+    let source_ty = ctx.sema.type_of_expr(&source)?.original;
+    let needs_deref = if source_ty.as_adt() == Some(strukt.into()) {
+        false
+    } else if source_ty.remove_ref()?.as_adt() == Some(strukt.into()) {
+        true
+    } else {
+        return None;
+    };
+
+    let target = record_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_to_struct_update_syntax", AssistKind::RefactorRewrite),
+        "Convert to struct update syntax",
+        target,
+        |builder| {
+            let deref = if needs_deref { "*" } else { "" };
+            builder
+                .replace(field_list.syntax().text_range(), format!("{{ ..{}{} }}", deref, source));
+        },
+    )
+}
+
+/// Whether evaluating `expr` once instead of once per field is unobservable.
+fn is_place(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::PathExpr(_) => true,
+        ast::Expr::FieldExpr(it) => it.expr().map_or(false, |it| is_place(&it)),
+        ast::Expr::ParenExpr(it) => it.expr().map_or(false, |it| is_place(&it)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn copies_all_fields() {
+        check_assist(
+            convert_to_struct_update_syntax,
+            r#"
+struct Config { name: &'static str, retries: u32, verbose: bool }
+fn f(state: State) -> Config {
+    Config {
+        name: state.config.name,
+        $0retries: state.config.retries,
+        verbose: state.config.verbose,
+    }
+}
+struct State { config: Config }
+"#,
+            r#"
+struct Config { name: &'static str, retries: u32, verbose: bool }
+fn f(state: State) -> Config {
+    Config { ..state.config }
+}
+struct State { config: Config }
+"#,
+        );
+    }
+
+    #[test]
+    fn dereferences_reference_source() {
+        check_assist(
+            convert_to_struct_update_syntax,
+            r#"
+#[derive(Clone, Copy)]
+struct Pair(u32, u32);
+fn f(other: &Pair) -> Pair {
+    Pair$0 { 1: other.1, 0: other.0 }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+struct Pair(u32, u32);
+fn f(other: &Pair) -> Pair {
+    Pair { ..*other }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_partial_copy() {
+        cov_mark::check!(struct_update_missing_fields);
+        check_assist_not_applicable(
+            convert_to_struct_update_syntax,
+            r#"
+struct Point { x: u32, y: u32, z: u32 }
+fn f(p: Point) -> Point {
+    Point { $0x: p.x, y: p.y }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_different_sources() {
+        check_assist_not_applicable(
+            convert_to_struct_update_syntax,
+            r#"
+struct Point { x: u32, y: u32 }
+fn f(a: Point, b: Point) -> Point {
+    Point { $0x: a.x, y: b.y }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_swapped_fields() {
+        check_assist_not_applicable(
+            convert_to_struct_update_syntax,
+            r#"
+struct Point { x: u32, y: u32 }
+fn f(p: Point) -> Point {
+    Point { $0x: p.y, y: p.x }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_calls() {
+        check_assist_not_applicable(
+            convert_to_struct_update_syntax,
+            r#"
+struct Point { x: u32, y: u32 }
+fn next() -> Point { loop {} }
+fn f() -> Point {
+    Point { $0x: next().x, y: next().y }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use hir::{AsAssocItem, AssocItem, AssocItemContainer, PathResolution};
+use ide_db::helpers::FamousDefs;
+use syntax::{
+    ast::{self, AstNode, HasArgList},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_try_into_to_from
+//
+// Converts a fallible conversion that can't actually fail into the
+// corresponding infallible one.
+//
+// ```
+// # //- minicore: try_from, try
+// use core::convert::TryInto;
+// struct Meters(u32);
+// impl From<u32> for Meters {
+//     fn from(x: u32) -> Meters { Meters(x) }
+// }
+// fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+//     Ok(x.try_in$0to()?)
+// }
+// ```
+// ->
+// ```
+// use core::convert::TryInto;
+// struct Meters(u32);
+// impl From<u32> for Meters {
+//     fn from(x: u32) -> Meters { Meters(x) }
+// }
+// fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+//     Ok(x.into())
+// }
+// ```
+pub(crate) fn convert_try_into_to_from(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let try_expr = ctx.find_node_at_offset::<ast::TryExpr>()?;
+    let question_mark = try_expr.question_mark_token()?;
+    let call = try_expr.expr()?;
+
+    let db = ctx.db();
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(try_expr.syntax()).krate());
+    let (label, source, renames) = match &call {
+        ast::Expr::MethodCallExpr(call) => {
+            let name_ref = call.name_ref()?;
+            let func = ctx.sema.resolve_method_call(call)?;
+            if implemented_trait(db, func)? != famous_defs.core_convert_TryInto()? {
+                return None;
+            }
+            let label = "Convert infallible `TryInto` to `Into`";
+            (label, call.receiver()?, vec![(name_ref.syntax().text_range(), "into")])
+        }
+        ast::Expr::CallExpr(call) => {
+            let path = match call.expr()? {
+                ast::Expr::PathExpr(path_expr) => path_expr.path()?,
+                _ => return None,
+            };
+            let name_ref = path.segment()?.name_ref()?;
+            let func = match ctx.sema.resolve_path(&path)? {
+                PathResolution::AssocItem(AssocItem::Function(func)) => func,
+                PathResolution::Def(hir::ModuleDef::Function(func)) => func,
+                _ => return None,
+            };
+            if implemented_trait(db, func)? != famous_defs.core_convert_TryFrom()? {
+                return None;
+            }
+            let arg = call.arg_list()?.args().next()?;
+            let mut renames: Vec<(TextRange, &str)> =
+                vec![(name_ref.syntax().text_range(), "from")];
+            let trait_name_ref = path
+                .qualifier()
+                .into_iter()
+                .flat_map(|it| it.syntax().descendants().filter_map(ast::NameRef::cast))
+                .filter(|it| it.text() == "TryFrom")
+                .last();
+            if let Some(trait_name_ref) = trait_name_ref {
+                renames.push((trait_name_ref.syntax().text_range(), "From"));
+            }
+            ("Convert infallible `TryFrom` to `From`", arg, renames)
+        }
+        _ => return None,
+    };
+
+    // The blanket `TryFrom` impl, whose `Error` is `Infallible`, applies exactly
+    // when the source type implements `Into` the target type. Any other
+    // `TryFrom` impl would overlap with it.
+    let source_ty = ctx.sema.type_of_expr(&source)?.original;
+    let target_ty = ctx.sema.type_of_expr(&ast::Expr::TryExpr(try_expr.clone()))?.original;
+    if source_ty.is_unknown() || target_ty.is_unknown() {
+        return None;
+    }
+    if !source_ty.impls_trait(db, famous_defs.core_convert_Into()?, &[target_ty]) {
+        cov_mark::hit!(try_conversion_is_fallible);
+        return None;
+    }
+
+    let target = try_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_try_into_to_from", AssistKind::RefactorRewrite),
+        label,
+        target,
+        |builder| {
+            for (range, text) in renames {
+                builder.replace(range, text);
+            }
+            builder.delete(question_mark.text_range());
+        },
+    )
+}
+
+fn implemented_trait(db: &dyn hir::db::HirDatabase, func: hir::Function) -> Option<hir::Trait> {
+    match func.as_assoc_item(db)?.container(db) {
+        AssocItemContainer::Trait(trait_) => Some(trait_),
+        AssocItemContainer::Impl(impl_) => impl_.trait_(db),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn try_into_method() {
+        check_assist(
+            convert_try_into_to_from,
+            r#"
+//- minicore: try_from, try
+use core::convert::TryInto;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    let m: Meters = x.$0try_into()?;
+    Ok(m)
+}
+"#,
+            r#"
+use core::convert::TryInto;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    let m: Meters = x.into();
+    Ok(m)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn try_from_trait_call() {
+        check_assist(
+            convert_try_into_to_from,
+            r#"
+//- minicore: try_from, try
+use core::convert::TryFrom;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    let m: Meters = TryFrom::try_from$0(x)?;
+    Ok(m)
+}
+"#,
+            r#"
+use core::convert::TryFrom;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    let m: Meters = From::from(x);
+    Ok(m)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn try_from_type_call() {
+        check_assist(
+            convert_try_into_to_from,
+            r#"
+//- minicore: try_from, try
+use core::convert::TryFrom;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    Ok(Meters::try_from(x)$0?)
+}
+"#,
+            r#"
+use core::convert::TryFrom;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    Ok(Meters::from(x))
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_fallible_conversion() {
+        cov_mark::check!(try_conversion_is_fallible);
+        check_assist_not_applicable(
+            convert_try_into_to_from,
+            r#"
+//- minicore: try_from, try
+use core::convert::{TryFrom, TryInto};
+struct Even(u32);
+struct Odd;
+impl TryFrom<u32> for Even {
+    type Error = Odd;
+    fn try_from(x: u32) -> Result<Even, Odd> { Err(Odd) }
+}
+fn f(x: u32) -> Result<Even, Odd> {
+    let e: Even = x.try_$0into()?;
+    Ok(e)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_question_mark() {
+        check_assist_not_applicable(
+            convert_try_into_to_from,
+            r#"
+//- minicore: try_from
+use core::convert::TryInto;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) {
+    let m: Result<Meters, _> = x.try_$0into();
+}
+"#,
+        );
+    }
+}
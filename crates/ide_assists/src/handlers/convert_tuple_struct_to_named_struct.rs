@@ -1,5 +1,8 @@
 use either::Either;
-use ide_db::defs::{Definition, NameRefClass};
+use ide_db::{
+    base_db::FileId,
+    defs::{Definition, NameRefClass},
+};
 use syntax::{
     ast::{self, AstNode, HasGenericParams, HasVisibility},
     match_ast, SyntaxNode,
@@ -73,14 +76,14 @@ pub(crate) fn convert_tuple_struct_to_named_struct(
             let names = generate_names(tuple_fields.fields());
             edit_field_references(ctx, edit, tuple_fields.fields(), &names);
             edit_struct_references(ctx, edit, strukt_def, &names);
-            edit_struct_def(ctx, edit, &strukt, tuple_fields, names);
+            edit_struct_def(edit, ctx.frange.file_id, &strukt, tuple_fields, names);
         },
     )
 }
 
-fn edit_struct_def(
-    ctx: &AssistContext,
+pub(crate) fn edit_struct_def(
     edit: &mut AssistBuilder,
+    file_id: FileId,
     strukt: &Either<ast::Struct, ast::Variant>,
     tuple_fields: ast::TupleFieldList,
     names: Vec<ast::Name>,
@@ -92,7 +95,7 @@ fn edit_struct_def(
     let record_fields = ast::make::record_field_list(record_fields);
     let tuple_fields_text_range = tuple_fields.syntax().text_range();
 
-    edit.edit_file(ctx.frange.file_id);
+    edit.edit_file(file_id);
 
     if let Either::Left(strukt) = strukt {
         if let Some(w) = strukt.where_clause() {
@@ -120,7 +123,7 @@ fn edit_struct_def(
     edit.replace(tuple_fields_text_range, record_fields.to_string());
 }
 
-fn edit_struct_references(
+pub(crate) fn edit_struct_references(
     ctx: &AssistContext,
     edit: &mut AssistBuilder,
     strukt: Either<hir::Struct, hir::Variant>,
@@ -201,7 +204,7 @@ fn edit_struct_references(
     }
 }
 
-fn edit_field_references(
+pub(crate) fn edit_field_references(
     ctx: &AssistContext,
     edit: &mut AssistBuilder,
     fields: impl Iterator<Item = ast::TupleField>,
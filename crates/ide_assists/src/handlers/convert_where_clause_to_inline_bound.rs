@@ -0,0 +1,241 @@
+use itertools::Itertools;
+use syntax::{
+    ast::{self, AstNode, HasGenericParams, HasName, HasTypeBounds},
+    SyntaxKind, SyntaxNode, TextRange, T,
+};
+
+use crate::{assist_context::AssistBuilder, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_where_clause_to_inline_bound
+//
+// Moves the `where` clause bounds of a type parameter to the parameter itself.
+// This is the inverse of `move_bounds_to_where_clause`.
+//
+// ```
+// fn print_all<T>(items: &[T]) where T$0: Debug + Clone {}
+// ```
+// ->
+// ```
+// fn print_all<T: Debug + Clone>(items: &[T]) {}
+// ```
+pub(crate) fn convert_where_clause_to_inline_bound(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+) -> Option<()> {
+    let pred = ctx.find_node_at_offset::<ast::WherePred>()?;
+    let where_clause = pred.syntax().parent().and_then(ast::WhereClause::cast)?;
+    let owner = where_clause.syntax().parent().and_then(ast::AnyHasGenericParams::cast)?;
+    let param_name = inlinable_param(&pred)?;
+    let (type_param, name) = owner.generic_param_list()?.type_params().find_map(|it| {
+        let name = it.name()?;
+        (name.text() == param_name).then(|| (it, name))
+    })?;
+
+    let preds = where_clause.predicates().collect::<Vec<_>>();
+    let is_moved = preds
+        .iter()
+        .map(|it| inlinable_param(it).as_deref() == Some(param_name.as_str()))
+        .collect::<Vec<_>>();
+    let bounds = preds
+        .iter()
+        .zip(&is_moved)
+        .filter(|(_, &moved)| moved)
+        .filter_map(|(it, _)| it.type_bound_list())
+        .flat_map(|it| it.bounds())
+        .join(" + ");
+
+    let target = pred.syntax().text_range();
+    acc.add(
+        AssistId("convert_where_clause_to_inline_bound", AssistKind::RefactorRewrite),
+        "Move to inline bound",
+        target,
+        |builder| {
+            match type_param.type_bound_list() {
+                Some(it) => {
+                    builder.insert(it.syntax().text_range().end(), format!(" + {}", bounds))
+                }
+                None => builder.insert(name.syntax().text_range().end(), format!(": {}", bounds)),
+            }
+
+            if is_moved.iter().all(|&it| it) {
+                remove_where_clause(builder, &where_clause);
+                return;
+            }
+            // Moved predicates followed by a kept one are removed along with
+            // their comma; a trailing run of moved predicates is removed along
+            // with the comma before it.
+            let kept_end = is_moved.iter().rposition(|&it| !it).unwrap();
+            for (i, pred) in preds.iter().enumerate().take(kept_end) {
+                if is_moved[i] {
+                    let next_start = preds[i + 1].syntax().text_range().start();
+                    builder.delete(TextRange::new(pred.syntax().text_range().start(), next_start));
+                }
+            }
+            if kept_end + 1 < preds.len() {
+                let last_kept = preds[kept_end].syntax();
+                let last = preds[preds.len() - 1].syntax();
+                let range = match comma_after(last) {
+                    Some(comma) => TextRange::new(
+                        comma_after(last_kept).map_or(last_kept.text_range().end(), |it| it.end()),
+                        comma.end(),
+                    ),
+                    None => TextRange::new(last_kept.text_range().end(), last.text_range().end()),
+                };
+                builder.delete(range);
+            }
+        },
+    )
+}
+
+/// Returns the name of the type parameter constrained by `pred`, if it can be
+/// written as an inline bound.
+fn inlinable_param(pred: &ast::WherePred) -> Option<String> {
+    // `for<'a> T: Fn(&'a u32)` has no inline equivalent.
+    if pred.for_token().is_some() || pred.type_bound_list().is_none() {
+        return None;
+    }
+    let path = match pred.ty()? {
+        ast::Type::PathType(it) => it.path()?,
+        _ => return None,
+    };
+    if path.qualifier().is_some() {
+        return None;
+    }
+    let segment = path.segment()?;
+    if segment.generic_arg_list().is_some() {
+        return None;
+    }
+    Some(segment.name_ref()?.to_string())
+}
+
+fn comma_after(node: &SyntaxNode) -> Option<TextRange> {
+    node.siblings_with_tokens(syntax::Direction::Next)
+        .skip(1)
+        .find(|it| it.kind() != SyntaxKind::WHITESPACE)
+        .filter(|it| it.kind() == T![,])
+        .map(|it| it.text_range())
+}
+
+/// Removes the `where` clause together with the whitespace around it, keeping
+/// a following `{` on the same line.
+fn remove_where_clause(builder: &mut AssistBuilder, where_clause: &ast::WhereClause) {
+    let syntax = where_clause.syntax();
+    let whitespace_before = syntax
+        .prev_sibling_or_token()
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE)
+        .map(|it| it.text_range());
+    let start = whitespace_before.map_or(syntax.text_range().start(), |it| it.start());
+    builder.delete(TextRange::new(start, syntax.text_range().end()));
+
+    if let Some(ws) = syntax
+        .next_sibling_or_token()
+        .filter(|it| it.kind() == SyntaxKind::WHITESPACE && it.to_string().contains('\n'))
+    {
+        let next_is_brace = ws
+            .next_sibling_or_token()
+            .and_then(|it| it.into_node())
+            .and_then(|it| it.first_token())
+            .map_or(false, |it| it.kind() == T!['{']);
+        if next_is_brace && whitespace_before.is_some() {
+            builder.replace(ws.text_range(), " ");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn single_predicate() {
+        check_assist(
+            convert_where_clause_to_inline_bound,
+            r#"fn foo<T>(x: T) where $0T: Clone {}"#,
+            r#"fn foo<T: Clone>(x: T) {}"#,
+        );
+    }
+
+    #[test]
+    fn merges_with_inline_bounds() {
+        check_assist(
+            convert_where_clause_to_inline_bound,
+            r#"
+struct Wrapper<T: Copy, U>
+where
+    T$0: Clone,
+    U: Default,
+    T: Debug + Send,
+{
+    t: T,
+    u: U,
+}
+"#,
+            r#"
+struct Wrapper<T: Copy + Clone + Debug + Send, U>
+where
+    U: Default,
+{
+    t: T,
+    u: U,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn removes_multiline_where_clause() {
+        check_assist(
+            convert_where_clause_to_inline_bound,
+            r#"
+impl<T> Trait for S<T>
+where
+    T: $0Clone,
+{
+}
+"#,
+            r#"
+impl<T: Clone> Trait for S<T> {
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn removes_trailing_predicates() {
+        check_assist(
+            convert_where_clause_to_inline_bound,
+            r#"fn foo<T, U>() where U: Copy, T$0: Clone {}"#,
+            r#"fn foo<T: Clone, U>() where U: Copy {}"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_higher_ranked_predicate() {
+        check_assist_not_applicable(
+            convert_where_clause_to_inline_bound,
+            r#"fn foo<F>(f: F) where for<'a> F$0: Fn(&'a u32) {}"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_complex_type() {
+        check_assist_not_applicable(
+            convert_where_clause_to_inline_bound,
+            r#"fn foo<T: Iterator>(it: T) where T::Item$0: Clone {}"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_outer_parameter() {
+        check_assist_not_applicable(
+            convert_where_clause_to_inline_bound,
+            r#"
+impl<T> S<T> {
+    fn foo(&self) where T$0: Clone {}
+}
+"#,
+        );
+    }
+}
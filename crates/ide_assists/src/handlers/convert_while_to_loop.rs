@@ -6,7 +6,7 @@ use syntax::{
         edit::{AstNodeEdit, IndentLevel},
         make, HasLoopBody,
     },
-    AstNode, T,
+    AstNode, TextRange, T,
 };
 
 use crate::{
@@ -84,6 +84,118 @@ pub(crate) fn convert_while_to_loop(acc: &mut Assists, ctx: &AssistContext) -> O
     )
 }
 
+// Assist: convert_loop_to_while
+//
+// Replace a loop starting with a conditional break with a while.
+//
+// ```
+// fn main() {
+//     $0loop {
+//         if !cond {
+//             break;
+//         }
+//         foo();
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     while cond {
+//         foo();
+//     }
+// }
+// ```
+pub(crate) fn convert_loop_to_while(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let loop_kw = ctx.find_token_syntax_at_offset(T![loop])?;
+    let loop_expr: ast::LoopExpr = loop_kw.parent().and_then(ast::LoopExpr::cast)?;
+    let loop_body = loop_expr.loop_body()?;
+
+    let stmt_list = loop_body.stmt_list()?;
+    let (if_expr, is_only_item) = match loop_body.statements().next() {
+        Some(ast::Stmt::ExprStmt(stmt)) => match stmt.expr()? {
+            ast::Expr::IfExpr(it) => {
+                (it, loop_body.statements().count() == 1 && loop_body.tail_expr().is_none())
+            }
+            _ => return None,
+        },
+        Some(_) => return None,
+        None => match loop_body.tail_expr()? {
+            ast::Expr::IfExpr(it) => (it, true),
+            _ => return None,
+        },
+    };
+    if if_expr.else_branch().is_some() || !is_plain_break(&if_expr.then_branch()?) {
+        return None;
+    }
+    let cond = if_expr.condition()?;
+    // Don't handle if let
+    if cond.pat().is_some() {
+        return None;
+    }
+    let cond_expr = cond.expr()?;
+    if breaks_with_value(&loop_expr) {
+        cov_mark::hit!(convert_loop_to_while_break_with_value);
+        return None;
+    }
+
+    let target = loop_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_loop_to_while", AssistKind::RefactorRewrite),
+        "Convert loop to while",
+        target,
+        |edit| {
+            let cond = invert_boolean_expression(cond_expr);
+            edit.replace(loop_kw.text_range(), format!("while {}", cond.syntax().text()));
+
+            // Remove the conditional break along with the whitespace before it.
+            let break_stmt = match if_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+                Some(stmt) => stmt.syntax().clone(),
+                None => if_expr.syntax().clone(),
+            };
+            let end = match stmt_list.r_curly_token() {
+                Some(r_curly) if is_only_item => r_curly.text_range().start(),
+                _ => break_stmt.text_range().end(),
+            };
+            let start = stmt_list
+                .l_curly_token()
+                .map_or(break_stmt.text_range().start(), |it| it.text_range().end());
+            edit.delete(TextRange::new(start, end));
+        },
+    )
+}
+
+/// Whether the block consists of only an unlabeled `break` without a value.
+pub(crate) fn is_plain_break(block: &ast::BlockExpr) -> bool {
+    let break_expr = match (block.statements().next(), block.tail_expr()) {
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if block.statements().count() == 1 => stmt.expr(),
+        (None, Some(expr)) => Some(expr),
+        _ => None,
+    };
+    match break_expr {
+        Some(ast::Expr::BreakExpr(it)) => it.lifetime().is_none() && it.expr().is_none(),
+        _ => false,
+    }
+}
+
+/// Whether the loop evaluates to a value, which a while loop can't do.
+fn breaks_with_value(loop_expr: &ast::LoopExpr) -> bool {
+    let label = loop_expr.label().and_then(|it| it.lifetime()).map(|it| it.to_string());
+    loop_expr.syntax().descendants().filter_map(ast::BreakExpr::cast).any(|it| {
+        if it.expr().is_none() {
+            return false;
+        }
+        match it.lifetime() {
+            Some(lifetime) => Some(lifetime.to_string()) == label,
+            None => it
+                .syntax()
+                .ancestors()
+                .find_map(ast::LoopExpr::cast)
+                .map_or(false, |it| &it == loop_expr),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -183,6 +295,190 @@ fn main() {
         bar();
     }
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_compound_while_to_loop() {
+        check_assist(
+            convert_while_to_loop,
+            r#"
+fn main() {
+    while$0 i < n && !done {
+        i += 1;
+    }
+}
+"#,
+            r#"
+fn main() {
+    loop {
+        if !(i < n && !done) {
+            break;
+        }
+        i += 1;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_simple() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    loop$0 {
+        if !cond {
+            break;
+        }
+        foo();
+        bar()
+    }
+}
+"#,
+            r#"
+fn main() {
+    while cond {
+        foo();
+        bar()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_negates_condition() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    'outer: $0loop {
+        if finished() { break }
+    }
+}
+"#,
+            r#"
+fn main() {
+    'outer: while !finished() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_compound() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    $0loop {
+        if !(i < n && !done) {
+            break;
+        }
+        if i >= 10 {
+            break;
+        }
+        i += 1;
+    }
+}
+"#,
+            r#"
+fn main() {
+    while i < n && !done {
+        if i >= 10 {
+            break;
+        }
+        i += 1;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_loop_to_while_comparison() {
+        check_assist(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    $0loop {
+        if i >= 10 {
+            break;
+        }
+        i += 1;
+    }
+}
+"#,
+            r#"
+fn main() {
+    while i < 10 {
+        i += 1;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ignore_loop_without_leading_break() {
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    $0loop {
+        foo();
+        if !cond {
+            break;
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    $0loop {
+        if !cond {
+            break;
+        } else {
+            foo();
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    $0loop {
+        if let None = foo() {
+            break;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn ignore_loop_with_break_value() {
+        cov_mark::check!(convert_loop_to_while_break_with_value);
+        check_assist_not_applicable(
+            convert_loop_to_while,
+            r#"
+fn main() {
+    let x = $0loop {
+        if !cond {
+            break;
+        }
+        break 92;
+    };
+}
 "#,
         );
     }
@@ -0,0 +1,305 @@
+use hir::HirDisplay;
+use rustc_hash::FxHashSet;
+use stdx::{format_to, to_upper_snake_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    match_ast, SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_constant
+//
+// Extracts a literal repeated within a module into a `const`, replacing every
+// occurrence.
+//
+// ```
+// fn pages(len: usize) -> usize {
+//     let max_size: usize = $04096;
+//     len / max_size
+// }
+// fn fits(len: usize) -> bool {
+//     len <= 4096
+// }
+// ```
+// ->
+// ```
+// const $0MAX_SIZE: usize = 4096;
+//
+// fn pages(len: usize) -> usize {
+//     let max_size: usize = MAX_SIZE;
+//     len / max_size
+// }
+// fn fits(len: usize) -> bool {
+//     len <= MAX_SIZE
+// }
+// ```
+pub(crate) fn extract_constant(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let literal = ctx.find_node_at_offset::<ast::Literal>()?;
+    match literal.kind() {
+        ast::LiteralKind::String(_)
+        | ast::LiteralKind::IntNumber(_)
+        | ast::LiteralKind::FloatNumber(_)
+        | ast::LiteralKind::Bool(_) => {}
+        _ => return None,
+    }
+    let db = ctx.db();
+    let module = ctx.sema.scope(literal.syntax()).module()?;
+    let ty = ctx.sema.type_of_expr(&literal.clone().into())?.original;
+    if ty.contains_unknown() {
+        return None;
+    }
+    let ty_text = ty.display_source_code(db, module.into()).ok()?;
+
+    let module_body = literal.syntax().ancestors().find(|it| {
+        ast::SourceFile::can_cast(it.kind())
+            || (ast::ItemList::can_cast(it.kind())
+                && it.parent().map_or(false, |it| ast::Module::can_cast(it.kind())))
+    })?;
+    let literal_text = literal.syntax().text().to_string();
+    let occurrences = module_literals(&module_body)
+        .filter(|it| it.syntax().text() == literal_text.as_str())
+        .filter(|it| {
+            ctx.sema.type_of_expr(&it.clone().into()).map_or(false, |it| it.original == ty)
+        })
+        .collect::<Vec<_>>();
+    if occurrences.len() < 2 {
+        cov_mark::hit!(extract_constant_single_occurrence);
+        return None;
+    }
+
+    let name = occurrences.iter().find_map(suggest_name).unwrap_or_else(|| "VALUE".to_string());
+    // The constant must not clash with, or be shadowed by, any name visible at an occurrence.
+    let mut names_in_scope = FxHashSet::default();
+    for occurrence in &occurrences {
+        ctx.sema.scope(occurrence.syntax()).process_all_names(&mut |name, _| {
+            names_in_scope.insert(name.to_string());
+        });
+    }
+    let name = if names_in_scope.contains(&name) {
+        cov_mark::hit!(extract_constant_name_taken);
+        (1..).map(|i| format!("{}_{}", name, i)).find(|it| !names_in_scope.contains(it))?
+    } else {
+        name
+    };
+    let first_item = occurrences[0]
+        .syntax()
+        .ancestors()
+        .find(|it| it.parent().as_ref() == Some(&module_body) && ast::Item::can_cast(it.kind()))?;
+
+    let target = literal.syntax().text_range();
+    acc.add(
+        AssistId("extract_constant", AssistKind::RefactorExtract),
+        format!("Extract `{}` into a constant", literal_text),
+        target,
+        |builder| {
+            for occurrence in &occurrences {
+                builder.replace(occurrence.syntax().text_range(), name.clone());
+            }
+            let indent = IndentLevel::from_node(&first_item);
+            let offset = first_item.text_range().start();
+            let mut buf = String::from("const ");
+            if ctx.config.snippet_cap.is_some() {
+                buf.push_str("$0");
+            }
+            format_to!(buf, "{}: {} = {};\n\n{}", name, ty_text, literal_text, indent);
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, buf),
+                None => builder.insert(offset, buf),
+            }
+        },
+    )
+}
+
+/// Literals in the module, not counting those in nested modules.
+fn module_literals(module_body: &SyntaxNode) -> impl Iterator<Item = ast::Literal> {
+    let mut preorder = module_body.preorder();
+    std::iter::from_fn(move || loop {
+        match preorder.next()? {
+            syntax::WalkEvent::Enter(node) => {
+                if ast::Module::can_cast(node.kind()) {
+                    preorder.skip_subtree();
+                } else if let Some(literal) = ast::Literal::cast(node) {
+                    return Some(literal);
+                }
+            }
+            syntax::WalkEvent::Leave(_) => {}
+        }
+    })
+}
+
+/// Names the constant after what the literal initializes, or after the words
+/// in a string literal.
+fn suggest_name(literal: &ast::Literal) -> Option<String> {
+    let parent = literal.syntax().parent()?;
+    let name = match_ast! {
+        match parent {
+            ast::LetStmt(it) => match it.pat()? {
+                ast::Pat::IdentPat(it) => it.name()?.to_string(),
+                _ => return None,
+            },
+            ast::RecordExprField(it) => it.field_name()?.to_string(),
+            _ => match literal.kind() {
+                ast::LiteralKind::String(it) => {
+                    let value = it.value()?;
+                    let words = value
+                        .split(|c: char| !c.is_ascii_alphanumeric())
+                        .filter(|it| !it.is_empty())
+                        .take(3)
+                        .collect::<Vec<_>>();
+                    words.join("_")
+                }
+                _ => return None,
+            },
+        }
+    };
+    let name = to_upper_snake_case(&name);
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_repeated_integer() {
+        check_assist(
+            extract_constant,
+            r#"
+struct Config { retries: u32 }
+fn default_config() -> Config {
+    Config { retries: 3$0 }
+}
+fn should_retry(attempt: u32) -> bool {
+    attempt < 3
+}
+fn unrelated() -> u64 {
+    3
+}
+"#,
+            r#"
+struct Config { retries: u32 }
+const $0RETRIES: u32 = 3;
+
+fn default_config() -> Config {
+    Config { retries: RETRIES }
+}
+fn should_retry(attempt: u32) -> bool {
+    attempt < RETRIES
+}
+fn unrelated() -> u64 {
+    3
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_repeated_float_in_module() {
+        check_assist(
+            extract_constant,
+            r#"
+mod geometry {
+    pub fn circumference(r: f64) -> f64 {
+        2.0 * 3.14159 * r
+    }
+    pub fn area(r: f64) -> f64 {
+        3.14159$0 * r * r
+    }
+}
+fn outside() -> f64 {
+    3.14159
+}
+"#,
+            r#"
+mod geometry {
+    const $0VALUE: f64 = 3.14159;
+
+    pub fn circumference(r: f64) -> f64 {
+        2.0 * VALUE * r
+    }
+    pub fn area(r: f64) -> f64 {
+        VALUE * r * r
+    }
+}
+fn outside() -> f64 {
+    3.14159
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_repeated_string() {
+        check_assist(
+            extract_constant,
+            r#"
+fn greet() -> &'static str {
+    "hello, world"
+}
+fn check(s: &str) -> bool {
+    s == "hello, world$0"
+}
+"#,
+            r#"
+const $0HELLO_WORLD: &str = "hello, world";
+
+fn greet() -> &'static str {
+    HELLO_WORLD
+}
+fn check(s: &str) -> bool {
+    s == HELLO_WORLD
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn picks_fresh_name() {
+        cov_mark::check!(extract_constant_name_taken);
+        check_assist(
+            extract_constant,
+            r#"
+const LIMIT: u32 = 1;
+fn f() -> u32 {
+    let limit = 8$0;
+    limit
+}
+fn g(LIMIT_1: u32) -> u32 {
+    8
+}
+"#,
+            r#"
+const LIMIT: u32 = 1;
+const $0LIMIT_2: u32 = 8;
+
+fn f() -> u32 {
+    let limit = LIMIT_2;
+    limit
+}
+fn g(LIMIT_1: u32) -> u32 {
+    LIMIT_2
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_single_occurrence() {
+        cov_mark::check!(extract_constant_single_occurrence);
+        check_assist_not_applicable(
+            extract_constant,
+            r#"
+fn f() -> u32 {
+    42$0
+}
+"#,
+        );
+    }
+}
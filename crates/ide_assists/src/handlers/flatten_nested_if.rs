@@ -0,0 +1,388 @@
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        BinaryOp, LogicOp,
+    },
+    AstNode, SyntaxKind, TextRange, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: flatten_nested_if
+//
+// Merges an `if` whose body is only another `if` into a single `if`.
+//
+// ```
+// fn main() {
+//     $0if x > 0 {
+//         if y > 0 {
+//             foo();
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     if x > 0 && y > 0 {
+//         foo();
+//     }
+// }
+// ```
+pub(crate) fn flatten_nested_if(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    let then_branch = if_expr.then_branch()?;
+    let available_range = TextRange::new(
+        if_expr.syntax().text_range().start(),
+        then_branch.syntax().text_range().start(),
+    );
+    if !available_range.contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let outer_cond = plain_condition(&if_expr)?;
+
+    let stmt_list = then_branch.stmt_list()?;
+    let has_comments =
+        stmt_list.syntax().children_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT);
+    if has_comments {
+        return None;
+    }
+    let inner_if = match (stmt_list.statements().next(), stmt_list.tail_expr()) {
+        (None, Some(ast::Expr::IfExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if stmt_list.statements().count() == 1 => {
+            match stmt.expr()? {
+                ast::Expr::IfExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if inner_if.else_branch().is_some() {
+        return None;
+    }
+    let inner_cond = plain_condition(&inner_if)?;
+    let inner_then = inner_if.then_branch()?;
+
+    let target = if_expr.syntax().text_range();
+    acc.add(
+        AssistId("flatten_nested_if", AssistKind::RefactorRewrite),
+        "Merge nested `if`s",
+        target,
+        |builder| {
+            let then_range =
+                TextRange::new(then_branch.syntax().text_range().start(), target.end());
+            let new_cond = format!("{} && {}", operand(&outer_cond), operand(&inner_cond));
+            builder.replace(outer_cond.syntax().text_range(), new_cond);
+            builder.replace(then_range, inner_then.dedent(IndentLevel(1)).to_string());
+        },
+    )
+}
+
+// Assist: split_if_chain
+//
+// Splits an `if` whose condition is joined with `&&` into nested `if`s.
+//
+// ```
+// fn main() {
+//     if x > 0 $0&& y > 0 {
+//         foo();
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     if x > 0 {
+//         if y > 0 {
+//             foo();
+//         }
+//     }
+// }
+// ```
+pub(crate) fn split_if_chain(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let op = ctx.find_token_syntax_at_offset(T![&&])?;
+    let bin_expr = op.parent().and_then(ast::BinExpr::cast)?;
+    let if_expr = bin_expr.syntax().ancestors().find_map(ast::IfExpr::cast)?;
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let cond = plain_condition(&if_expr)?;
+    // The `&&` has to be part of the `&&` chain making up the whole condition.
+    let is_in_chain = bin_expr
+        .syntax()
+        .ancestors()
+        .take_while(|it| it != if_expr.syntax())
+        .filter(|it| it.kind() != SyntaxKind::CONDITION)
+        .all(|it| {
+            ast::BinExpr::cast(it)
+                .and_then(|it| it.op_kind())
+                .map_or(false, |it| it == BinaryOp::LogicOp(LogicOp::And))
+        });
+    if !is_in_chain {
+        return None;
+    }
+    let then_branch = if_expr.then_branch()?;
+
+    let cond_range = cond.syntax().text_range();
+    let op_range = op.text_range() - cond_range.start();
+    let cond_text = cond.syntax().text().to_string();
+    let (lhs, rhs) = (&cond_text[..op_range.start().into()], &cond_text[op_range.end().into()..]);
+
+    let target = if_expr.syntax().text_range();
+    acc.add(
+        AssistId("split_if_chain", AssistKind::RefactorRewrite),
+        "Split into nested `if`s",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(if_expr.syntax());
+            let inner_if = format!("if {} {}", rhs.trim(), then_branch.indent(IndentLevel(1)));
+            let new_then = format!("{{\n{}{}\n{}}}", indent + 1, inner_if, indent);
+            builder.replace(
+                TextRange::new(cond_range.start(), then_branch.syntax().text_range().end()),
+                format!("{} {}", lhs.trim(), new_then),
+            );
+        },
+    )
+}
+
+/// The condition of the `if`, if it is a boolean expression that reads well
+/// in an `&&` chain.
+fn plain_condition(if_expr: &ast::IfExpr) -> Option<ast::Expr> {
+    let cond = if_expr.condition()?;
+    if cond.pat().is_some() {
+        return None;
+    }
+    let expr = cond.expr()?;
+    let is_plain = expr.syntax().descendants().filter_map(ast::Expr::cast).all(|it| match it {
+        ast::Expr::BinExpr(it) => !matches!(it.op_kind(), Some(BinaryOp::Assignment { .. })),
+        ast::Expr::BlockExpr(_)
+        | ast::Expr::MacroCall(_)
+        | ast::Expr::AwaitExpr(_)
+        | ast::Expr::ClosureExpr(_) => false,
+        _ => true,
+    });
+    if !is_plain {
+        cov_mark::hit!(flatten_nested_if_side_effects);
+        return None;
+    }
+    Some(expr)
+}
+
+/// The expression as an operand of `&&`.
+fn operand(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::BinExpr(it) if it.op_kind() == Some(BinaryOp::LogicOp(LogicOp::Or)) => {
+            format!("({})", expr)
+        }
+        ast::Expr::RangeExpr(_) => format!("({})", expr),
+        _ => expr.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn flatten_method_calls() {
+        check_assist(
+            flatten_nested_if,
+            r#"
+fn f(v: &[u32]) {
+    i$0f !v.is_empty() {
+        if v.contains(&1) {
+            println!("found");
+        }
+    }
+}
+"#,
+            r#"
+fn f(v: &[u32]) {
+    if !v.is_empty() && v.contains(&1) {
+        println!("found");
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_compound_conditions() {
+        check_assist(
+            flatten_nested_if,
+            r#"
+fn f(a: bool, b: bool, c: bool) {
+    $0if a || b {
+        if b && c {
+            g();
+        };
+    }
+}
+"#,
+            r#"
+fn f(a: bool, b: bool, c: bool) {
+    if (a || b) && b && c {
+        g();
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable() {
+        check_assist_not_applicable(
+            flatten_nested_if,
+            r#"
+fn f(a: bool, b: bool) {
+    $0if a {
+        if b {
+            g();
+        } else {
+            h();
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            flatten_nested_if,
+            r#"
+fn f(a: bool, b: bool) {
+    $0if a {
+        h();
+        if b {
+            g();
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            flatten_nested_if,
+            r#"
+fn f(a: Option<u32>, b: bool) {
+    $0if let Some(_) = a {
+        if b {
+            g();
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn flatten_not_applicable_with_side_effects() {
+        cov_mark::check!(flatten_nested_if_side_effects);
+        check_assist_not_applicable(
+            flatten_nested_if,
+            r#"
+fn f(a: bool, mut n: u32) {
+    $0if a {
+        if { n += 1; n > 2 } {
+            g();
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_simple_booleans() {
+        check_assist(
+            split_if_chain,
+            r#"
+fn f(a: bool, b: bool) {
+    if a &&$0 b {
+        g();
+    }
+}
+"#,
+            r#"
+fn f(a: bool, b: bool) {
+    if a {
+        if b {
+            g();
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_compound_conditions() {
+        check_assist(
+            split_if_chain,
+            r#"
+fn f(v: &[u32], a: bool) {
+    if !v.is_empty() && (a || v.len() > 2) $0&& v.contains(&1) {
+        g();
+    }
+}
+"#,
+            r#"
+fn f(v: &[u32], a: bool) {
+    if !v.is_empty() && (a || v.len() > 2) {
+        if v.contains(&1) {
+            g();
+        }
+    }
+}
+"#,
+        );
+        check_assist(
+            split_if_chain,
+            r#"
+fn f(a: bool, b: bool, c: bool) {
+    if a $0&& b && c {
+        g();
+    }
+}
+"#,
+            r#"
+fn f(a: bool, b: bool, c: bool) {
+    if a {
+        if b && c {
+            g();
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn split_not_applicable() {
+        check_assist_not_applicable(
+            split_if_chain,
+            r#"
+fn f(a: bool, b: bool, c: bool) {
+    if a || b $0&& c {
+        g();
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            split_if_chain,
+            r#"
+fn f(a: bool, b: bool) {
+    if a $0&& b {
+        g();
+    } else {
+        h();
+    }
+}
+"#,
+        );
+    }
+}
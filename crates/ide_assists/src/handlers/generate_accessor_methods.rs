@@ -0,0 +1,327 @@
+use stdx::{format_to, to_lower_snake_case};
+use syntax::ast::{self, AstNode, HasName, HasVisibility};
+
+use crate::{
+    utils::{find_impl_block_end, find_struct_impl, generate_impl_text, useless_type_special_case},
+    AssistContext, AssistId, AssistKind, Assists, GroupLabel,
+};
+
+// Assist: generate_accessor_methods
+//
+// Generate accessor methods for all private fields of a struct.
+//
+// ```
+// struct $0Person {
+//     name: String,
+//     age: u8,
+// }
+// ```
+// ->
+// ```
+// struct Person {
+//     name: String,
+//     age: u8,
+// }
+//
+// impl Person {
+//     /// Get a reference to the person's name.
+//     fn $0name(&self) -> &str {
+//         self.name.as_str()
+//     }
+//
+//     /// Get a reference to the person's age.
+//     fn age(&self) -> &u8 {
+//         &self.age
+//     }
+// }
+// ```
+pub(crate) fn generate_accessor_methods(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let field_list = match strukt.field_list()? {
+        ast::FieldList::RecordFieldList(it) => it,
+        ast::FieldList::TupleFieldList(_) => return None,
+    };
+    if ctx.offset() > field_list.syntax().text_range().start() {
+        return None;
+    }
+    let fields = field_list
+        .fields()
+        .filter(|it| it.visibility().is_none())
+        .filter_map(|it| Some((to_lower_snake_case(&it.name()?.to_string()), it.ty()?)))
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return None;
+    }
+
+    let adt = ast::Adt::Struct(strukt.clone());
+    // No method is named with the empty string, so this only looks up the impl.
+    let impl_def = find_struct_impl(ctx, &adt, "")?;
+    let strukt_name = to_lower_snake_case(&strukt.name()?.to_string()).replace('_', " ");
+    let vis = strukt.visibility().map_or(String::new(), |v| format!("{} ", v));
+
+    let variants: [(&str, &[Accessor]); 4] = [
+        ("Generate getters", &[Accessor::Getter]),
+        ("Generate `get_` getters", &[Accessor::GetPrefixed]),
+        ("Generate getters and setters", &[Accessor::Getter, Accessor::Setter]),
+        ("Generate `with_` builder methods", &[Accessor::Builder]),
+    ];
+    let target = strukt.syntax().text_range();
+    for (label, accessors) in variants.iter() {
+        let methods = fields
+            .iter()
+            .flat_map(|(name, ty)| accessors.iter().map(move |it| (*it, name, ty)))
+            .filter(|(accessor, name, _)| {
+                let fn_name = accessor.fn_name(name);
+                find_struct_impl(ctx, &adt, &fn_name).is_some()
+            })
+            .map(|(accessor, name, ty)| accessor.method_text(&strukt_name, &vis, name, ty))
+            .collect::<Vec<_>>();
+        if methods.is_empty() {
+            continue;
+        }
+        acc.add_group(
+            &GroupLabel("Generate accessor methods".to_owned()),
+            AssistId("generate_accessor_methods", AssistKind::Generate),
+            *label,
+            target,
+            |builder| {
+                let mut buf = String::with_capacity(512);
+                if impl_def.is_some() {
+                    buf.push('\n');
+                }
+                buf.push_str(&methods.join("\n\n"));
+
+                let start_offset = impl_def
+                    .clone()
+                    .and_then(|impl_def| find_impl_block_end(impl_def, &mut buf))
+                    .unwrap_or_else(|| {
+                        buf = generate_impl_text(&adt, &buf);
+                        strukt.syntax().text_range().end()
+                    });
+
+                match ctx.config.snippet_cap {
+                    Some(cap) => {
+                        builder.insert_snippet(cap, start_offset, buf.replacen("fn ", "fn $0", 1))
+                    }
+                    None => builder.insert(start_offset, buf),
+                }
+            },
+        );
+    }
+    Some(())
+}
+
+#[derive(Clone, Copy)]
+enum Accessor {
+    Getter,
+    GetPrefixed,
+    Setter,
+    Builder,
+}
+
+impl Accessor {
+    fn fn_name(self, field: &str) -> String {
+        match self {
+            Accessor::Getter => field.to_string(),
+            Accessor::GetPrefixed => format!("get_{}", field),
+            Accessor::Setter => format!("set_{}", field),
+            Accessor::Builder => format!("with_{}", field),
+        }
+    }
+
+    fn method_text(self, strukt: &str, vis: &str, field: &str, ty: &ast::Type) -> String {
+        let fn_name = self.fn_name(field);
+        let field_spaced = field.replace('_', " ");
+        let mut buf = String::new();
+        match self {
+            Accessor::Getter | Accessor::GetPrefixed => {
+                let (ty, body) = useless_type_special_case(field, &ty.to_string())
+                    .unwrap_or_else(|| (format!("&{}", ty), format!("&self.{}", field)));
+                format_to!(
+                    buf,
+                    "    /// Get a reference to the {}'s {}.
+    {}fn {}(&self) -> {} {{
+        {}
+    }}",
+                    strukt,
+                    field_spaced,
+                    vis,
+                    fn_name,
+                    ty,
+                    body
+                );
+            }
+            Accessor::Setter => format_to!(
+                buf,
+                "    /// Set the {}'s {}.
+    {}fn {}(&mut self, {}: {}) {{
+        self.{} = {};
+    }}",
+                strukt,
+                field_spaced,
+                vis,
+                fn_name,
+                field,
+                ty,
+                field,
+                field
+            ),
+            Accessor::Builder => format_to!(
+                buf,
+                "    /// Set the {}'s {}, returning the {}.
+    {}fn {}(mut self, {}: {}) -> Self {{
+        self.{} = {};
+        self
+    }}",
+                strukt,
+                field_spaced,
+                strukt,
+                vis,
+                fn_name,
+                field,
+                ty,
+                field,
+                field
+            ),
+        }
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_getters_and_setters() {
+        check_assist_by_label(
+            generate_accessor_methods,
+            r#"
+pub struct Conf$0ig {
+    pub name: String,
+    retries: u32,
+    tags: Vec<String>,
+}
+"#,
+            r#"
+pub struct Config {
+    pub name: String,
+    retries: u32,
+    tags: Vec<String>,
+}
+
+impl Config {
+    /// Get a reference to the config's retries.
+    pub fn $0retries(&self) -> &u32 {
+        &self.retries
+    }
+
+    /// Set the config's retries.
+    pub fn set_retries(&mut self, retries: u32) {
+        self.retries = retries;
+    }
+
+    /// Get a reference to the config's tags.
+    pub fn tags(&self) -> &[String] {
+        self.tags.as_slice()
+    }
+
+    /// Set the config's tags.
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+}
+"#,
+            "Generate getters and setters",
+        );
+    }
+
+    #[test]
+    fn generate_get_prefixed_into_existing_impl() {
+        check_assist_by_label(
+            generate_accessor_methods,
+            r#"
+struct $0Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> {
+    fn get_x(&self) -> &T {
+        &self.x
+    }
+}
+"#,
+            r#"
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> {
+    fn get_x(&self) -> &T {
+        &self.x
+    }
+
+    /// Get a reference to the point's y.
+    fn $0get_y(&self) -> &T {
+        &self.y
+    }
+}
+"#,
+            "Generate `get_` getters",
+        );
+    }
+
+    #[test]
+    fn generate_builder_methods() {
+        check_assist_by_label(
+            generate_accessor_methods,
+            r#"
+struct Request$0Builder {
+    timeout_ms: u64,
+}
+"#,
+            r#"
+struct RequestBuilder {
+    timeout_ms: u64,
+}
+
+impl RequestBuilder {
+    /// Set the request builder's timeout ms, returning the request builder.
+    fn $0with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+}
+"#,
+            "Generate `with_` builder methods",
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_private_fields() {
+        check_assist_not_applicable(
+            generate_accessor_methods,
+            r#"
+struct $0Point {
+    pub x: u32,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_field_list() {
+        check_assist_not_applicable(
+            generate_accessor_methods,
+            r#"
+struct Point {
+    $0x: u32,
+}
+"#,
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use ide_db::{helpers::FamousDefs, RootDatabase};
+use itertools::Itertools;
 use syntax::{
     ast::{self, HasName},
     AstNode, SyntaxNode,
@@ -14,7 +15,8 @@ use crate::{
 
 // Assist: generate_deref
 //
-// Generate `Deref` impl using the given struct field.
+// Generate `Deref` impl using the given struct field. On the name of a
+// single-field struct, also offers `Deref` with `DerefMut`, or `AsRef`.
 //
 // ```
 // struct A;
@@ -38,7 +40,9 @@ use crate::{
 // }
 // ```
 pub(crate) fn generate_deref(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
-    generate_record_deref(acc, ctx).or_else(|| generate_tuple_deref(acc, ctx))
+    generate_record_deref(acc, ctx)
+        .or_else(|| generate_tuple_deref(acc, ctx))
+        .or_else(|| generate_newtype_deref(acc, ctx))
 }
 
 fn generate_record_deref(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
@@ -83,6 +87,76 @@ fn generate_tuple_deref(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
     )
 }
 
+/// Offers `Deref`, `Deref` with `DerefMut`, and `AsRef` on the header of a
+/// single-field struct.
+fn generate_newtype_deref(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let field_list = strukt.field_list()?;
+    if ctx.offset() > field_list.syntax().text_range().start() {
+        return None;
+    }
+    let (field_type, field_name) = match &field_list {
+        ast::FieldList::RecordFieldList(it) => {
+            let field = it.fields().exactly_one().ok()?;
+            (field.ty()?, field.name()?.to_string())
+        }
+        ast::FieldList::TupleFieldList(it) => {
+            (it.fields().exactly_one().ok()?.ty()?, "0".to_string())
+        }
+    };
+
+    let target = strukt.syntax().text_range();
+    if existing_deref_impl(&ctx.sema, &strukt).is_some() {
+        cov_mark::hit!(test_add_newtype_deref_impl_already_exists);
+    } else {
+        acc.add(
+            AssistId("generate_deref", AssistKind::Generate),
+            format!("Generate `Deref` impl using `{}`", field_name),
+            target,
+            |edit| generate_edit(edit, strukt.clone(), field_type.syntax(), &field_name),
+        );
+        acc.add(
+            AssistId("generate_deref", AssistKind::Generate),
+            format!("Generate `Deref` and `DerefMut` impls using `{}`", field_name),
+            target,
+            |edit| {
+                let deref_impl = deref_impl_text(&strukt, field_type.syntax(), &field_name);
+                let impl_code = format!(
+                    r#"    fn deref_mut(&mut self) -> &mut Self::Target {{
+        &mut self.{}
+    }}"#,
+                    field_name
+                );
+                let strukt_adt = ast::Adt::Struct(strukt.clone());
+                let deref_mut_impl =
+                    generate_trait_impl_text(&strukt_adt, "std::ops::DerefMut", &impl_code);
+                edit.insert(target.end(), format!("{}{}", deref_impl, deref_mut_impl));
+            },
+        );
+    }
+
+    if existing_as_ref_impl(&ctx.sema, &strukt, &field_type).is_some() {
+        return None;
+    }
+    acc.add(
+        AssistId("generate_deref", AssistKind::Generate),
+        format!("Generate `AsRef<{}>` impl using `{}`", field_type, field_name),
+        target,
+        |edit| {
+            let impl_code = format!(
+                r#"    fn as_ref(&self) -> &{0} {{
+        &self.{1}
+    }}"#,
+                field_type, field_name
+            );
+            let strukt_adt = ast::Adt::Struct(strukt.clone());
+            let trait_text = format!("AsRef<{}>", field_type);
+            let as_ref_impl = generate_trait_impl_text(&strukt_adt, &trait_text, &impl_code);
+            edit.insert(target.end(), as_ref_impl);
+        },
+    )
+}
+
 fn generate_edit(
     edit: &mut AssistBuilder,
     strukt: ast::Struct,
@@ -90,6 +164,15 @@ fn generate_edit(
     field_name: impl Display,
 ) {
     let start_offset = strukt.syntax().text_range().end();
+    let deref_impl = deref_impl_text(&strukt, field_type_syntax, field_name);
+    edit.insert(start_offset, deref_impl);
+}
+
+fn deref_impl_text(
+    strukt: &ast::Struct,
+    field_type_syntax: &SyntaxNode,
+    field_name: impl Display,
+) -> String {
     let impl_code = format!(
         r#"    type Target = {0};
 
@@ -98,9 +181,8 @@ fn generate_edit(
     }}"#,
         field_type_syntax, field_name
     );
-    let strukt_adt = ast::Adt::Struct(strukt);
-    let deref_impl = generate_trait_impl_text(&strukt_adt, "std::ops::Deref", &impl_code);
-    edit.insert(start_offset, deref_impl);
+    let strukt_adt = ast::Adt::Struct(strukt.clone());
+    generate_trait_impl_text(&strukt_adt, "std::ops::Deref", &impl_code)
 }
 
 fn existing_deref_impl(
@@ -120,9 +202,28 @@ fn existing_deref_impl(
     }
 }
 
+fn existing_as_ref_impl(
+    sema: &'_ hir::Semantics<'_, RootDatabase>,
+    strukt: &ast::Struct,
+    field_type: &ast::Type,
+) -> Option<()> {
+    let strukt = sema.to_def(strukt)?;
+    let krate = strukt.module(sema.db).krate();
+
+    let as_ref_trait = FamousDefs(sema, Some(krate)).core_convert_AsRef()?;
+    let strukt_type = strukt.ty(sema.db);
+    let field_type = sema.resolve_type(field_type)?;
+
+    if strukt_type.impls_trait(sema.db, as_ref_trait, &[field_type]) {
+        Some(())
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
 
     use super::*;
 
@@ -221,4 +322,109 @@ impl core::ops::Deref for B {
 }"#,
         )
     }
+
+    #[test]
+    fn test_generate_newtype_deref() {
+        check_assist_by_label(
+            generate_deref,
+            r#"struct Meters(f64);
+struct $0Distance(Meters);"#,
+            r#"struct Meters(f64);
+struct Distance(Meters);
+
+impl std::ops::Deref for Distance {
+    type Target = Meters;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}"#,
+            "Generate `Deref` impl using `0`",
+        );
+    }
+
+    #[test]
+    fn test_generate_newtype_deref_mut() {
+        check_assist_by_label(
+            generate_deref,
+            r#"
+struct $0Names<T> {
+    inner: Vec<T>,
+}
+struct Vec<T>(T);"#,
+            r#"
+struct Names<T> {
+    inner: Vec<T>,
+}
+
+impl<T> std::ops::Deref for Names<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> std::ops::DerefMut for Names<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+struct Vec<T>(T);"#,
+            "Generate `Deref` and `DerefMut` impls using `inner`",
+        );
+    }
+
+    #[test]
+    fn test_generate_newtype_as_ref() {
+        check_assist_by_label(
+            generate_deref,
+            r#"
+//- minicore: as_ref
+struct $0Name { value: &'static str }"#,
+            r#"
+struct Name { value: &'static str }
+
+impl AsRef<&'static str> for Name {
+    fn as_ref(&self) -> &&'static str {
+        &self.value
+    }
+}"#,
+            "Generate `AsRef<&'static str>` impl using `value`",
+        );
+    }
+
+    #[test]
+    fn test_generate_newtype_deref_not_applicable_if_already_impl() {
+        cov_mark::check!(test_add_newtype_deref_impl_already_exists);
+        check_assist_not_applicable(
+            generate_deref,
+            r#"
+//- minicore: deref, as_ref
+struct A { }
+struct $0B(A);
+
+impl core::ops::Deref for B {
+    type Target = A;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl AsRef<A> for B {
+    fn as_ref(&self) -> &A {
+        &self.0
+    }
+}"#,
+        )
+    }
+
+    #[test]
+    fn test_generate_newtype_deref_not_applicable_to_multiple_fields() {
+        check_assist_not_applicable(
+            generate_deref,
+            r#"
+struct $0Pair(u32, u32);"#,
+        )
+    }
 }
@@ -58,7 +58,7 @@ pub(crate) fn generate_derive(acc: &mut Assists, ctx: &AssistContext) -> Option<
 }
 
 // Insert `derive` after doc comments.
-fn derive_insertion_offset(nominal: &ast::Adt) -> Option<TextSize> {
+pub(crate) fn derive_insertion_offset(nominal: &ast::Adt) -> Option<TextSize> {
     let non_ws_child = nominal
         .syntax()
         .children_with_tokens()
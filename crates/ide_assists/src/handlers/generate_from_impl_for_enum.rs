@@ -23,8 +23,32 @@ use crate::{utils::generate_trait_impl_text, AssistContext, AssistId, AssistKind
 // ```
 pub(crate) fn generate_from_impl_for_enum(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
     let variant = ctx.find_node_at_offset::<ast::Variant>()?;
-    let variant_name = variant.name()?;
     let enum_ = ast::Adt::Enum(variant.parent_enum());
+    let (field_type, impl_code) = from_impl_code(&variant)?;
+
+    if existing_from_impl(&ctx.sema, &variant).is_some() {
+        cov_mark::hit!(test_add_from_impl_already_exists);
+        return None;
+    }
+
+    let target = variant.syntax().text_range();
+    acc.add(
+        AssistId("generate_from_impl_for_enum", AssistKind::Generate),
+        "Generate `From` impl for this enum variant",
+        target,
+        |edit| {
+            let start_offset = variant.parent_enum().syntax().text_range().end();
+            let from_trait = format!("From<{}>", field_type.syntax());
+            let from_impl = generate_trait_impl_text(&enum_, &from_trait, &impl_code);
+            edit.insert(start_offset, from_impl);
+        },
+    )
+}
+
+/// Returns the wrapped type of a variant with a single field, and the body of
+/// the `From` impl constructing the variant from it.
+pub(crate) fn from_impl_code(variant: &ast::Variant) -> Option<(ast::Type, String)> {
+    let variant_name = variant.name()?;
     let (field_name, field_type) = match variant.kind() {
         ast::StructKind::Tuple(field_list) => {
             if field_list.fields().count() != 1 {
@@ -42,44 +66,28 @@ pub(crate) fn generate_from_impl_for_enum(acc: &mut Assists, ctx: &AssistContext
         ast::StructKind::Unit => return None,
     };
 
-    if existing_from_impl(&ctx.sema, &variant).is_some() {
-        cov_mark::hit!(test_add_from_impl_already_exists);
-        return None;
-    }
-
-    let target = variant.syntax().text_range();
-    acc.add(
-        AssistId("generate_from_impl_for_enum", AssistKind::Generate),
-        "Generate `From` impl for this enum variant",
-        target,
-        |edit| {
-            let start_offset = variant.parent_enum().syntax().text_range().end();
-            let from_trait = format!("From<{}>", field_type.syntax());
-            let impl_code = if let Some(name) = field_name {
-                format!(
-                    r#"    fn from({0}: {1}) -> Self {{
+    let impl_code = if let Some(name) = field_name {
+        format!(
+            r#"    fn from({0}: {1}) -> Self {{
         Self::{2} {{ {0} }}
     }}"#,
-                    name.text(),
-                    field_type.syntax(),
-                    variant_name,
-                )
-            } else {
-                format!(
-                    r#"    fn from(v: {}) -> Self {{
+            name.text(),
+            field_type.syntax(),
+            variant_name,
+        )
+    } else {
+        format!(
+            r#"    fn from(v: {}) -> Self {{
         Self::{}(v)
     }}"#,
-                    field_type.syntax(),
-                    variant_name,
-                )
-            };
-            let from_impl = generate_trait_impl_text(&enum_, &from_trait, &impl_code);
-            edit.insert(start_offset, from_impl);
-        },
-    )
+            field_type.syntax(),
+            variant_name,
+        )
+    };
+    Some((field_type, impl_code))
 }
 
-fn existing_from_impl(
+pub(crate) fn existing_from_impl(
     sema: &'_ hir::Semantics<'_, RootDatabase>,
     variant: &ast::Variant,
 ) -> Option<()> {
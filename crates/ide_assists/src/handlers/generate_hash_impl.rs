@@ -0,0 +1,245 @@
+use hir::{AssocItem, HasSource};
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{self, AstNode, BinaryOp, CmpOp, FieldKind, HasName};
+
+use crate::{utils::generate_trait_impl_text, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_hash_impl
+//
+// Generates a `Hash` impl for a struct with a manual `PartialEq` impl, hashing
+// exactly the fields that `eq` compares.
+//
+// ```
+// # //- minicore: eq, hash
+// struct $0User {
+//     id: u32,
+//     last_seen: u64,
+// }
+//
+// impl PartialEq for User {
+//     fn eq(&self, other: &Self) -> bool {
+//         self.id == other.id
+//     }
+// }
+// ```
+// ->
+// ```
+// struct User {
+//     id: u32,
+//     last_seen: u64,
+// }
+//
+// impl core::hash::Hash for User {
+//     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+//         self.id.hash(state);
+//     }
+// }
+//
+// impl PartialEq for User {
+//     fn eq(&self, other: &Self) -> bool {
+//         self.id == other.id
+//     }
+// }
+// ```
+pub(crate) fn generate_hash_impl(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    if let Some(field_list) = strukt.field_list() {
+        if ctx.offset() > field_list.syntax().text_range().start() {
+            return None;
+        }
+    }
+
+    let db = ctx.db();
+    let struct_def = ctx.sema.to_def(&strukt)?;
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(strukt.syntax()).krate());
+    let (partial_eq, hash, hasher) = (
+        famous_defs.core_cmp_PartialEq()?,
+        famous_defs.core_hash_Hash()?,
+        famous_defs.core_hash_Hasher()?,
+    );
+    let ty = struct_def.ty(db);
+    if ty.impls_trait(db, hash, &[]) {
+        cov_mark::hit!(hash_already_implemented);
+        return None;
+    }
+    let eq_impl = hir::Impl::all_for_type(db, ty)
+        .into_iter()
+        .find(|it| it.trait_(db) == Some(partial_eq) && it.is_builtin_derive(db).is_none())?;
+    let eq_fn = eq_impl.items(db).into_iter().find_map(|it| match it {
+        AssocItem::Function(it) if it.name(db).to_string() == "eq" => Some(it),
+        _ => None,
+    })?;
+    let eq_fn = eq_fn.source(db)?.value;
+    let other = match eq_fn.param_list()?.params().next()?.pat()? {
+        ast::Pat::IdentPat(it) => it.name()?.to_string(),
+        _ => return None,
+    };
+    let compared = compared_fields(&eq_fn.body()?, &other)?;
+
+    // Hash fields in declaration order.
+    let fields = struct_def
+        .fields(db)
+        .into_iter()
+        .map(|it| it.name(db).to_string())
+        .filter(|it| compared.contains(it))
+        .collect::<Vec<_>>();
+
+    let module = ctx.sema.scope(strukt.syntax()).module()?;
+    let hash_path = mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Trait(hash))?);
+    let hasher_path = mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Trait(hasher))?);
+
+    let target = strukt.syntax().text_range();
+    acc.add(
+        AssistId("generate_hash_impl", AssistKind::Generate),
+        "Generate `Hash` impl consistent with `PartialEq`",
+        target,
+        |builder| {
+            let mut code = String::new();
+            format_to!(code, "    fn hash<H: {}>(&self, state: &mut H) {{", hasher_path);
+            for field in &fields {
+                format_to!(code, "\n        self.{}.hash(state);", field);
+            }
+            code.push_str("\n    }");
+            let adt = ast::Adt::Struct(strukt.clone());
+            builder.insert(
+                target.end(),
+                generate_trait_impl_text(&adt, &hash_path.to_string(), &code),
+            );
+        },
+    )
+}
+
+/// Collects the fields compared as `self.field == other.field` in the body of
+/// `eq`. Returns `None` if `self` fields are used in any other way, as hashing
+/// them directly might not agree with the custom comparison.
+fn compared_fields(body: &ast::BlockExpr, other: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    for field_expr in body.syntax().descendants().filter_map(ast::FieldExpr::cast) {
+        let field = match field_of(&field_expr, "self") {
+            Some(it) => it,
+            None => continue,
+        };
+        let bin_expr = field_expr.syntax().parent().and_then(ast::BinExpr::cast);
+        let is_plain_comparison = bin_expr.map_or(false, |bin_expr| {
+            let operands = bin_expr.lhs().zip(bin_expr.rhs());
+            bin_expr.op_kind() == Some(BinaryOp::CmpOp(CmpOp::Eq { negated: false }))
+                && operands.into_iter().flat_map(|(lhs, rhs)| [lhs, rhs]).any(|it| match it {
+                    ast::Expr::FieldExpr(it) => field_of(&it, other).as_ref() == Some(&field),
+                    _ => false,
+                })
+        });
+        if !is_plain_comparison {
+            cov_mark::hit!(hash_custom_equality);
+            return None;
+        }
+        fields.push(field);
+    }
+    Some(fields.into_iter().unique().collect())
+}
+
+/// Returns the name of the field accessed by `field_expr` on the variable `receiver`.
+fn field_of(field_expr: &ast::FieldExpr, receiver: &str) -> Option<String> {
+    match field_expr.expr()? {
+        ast::Expr::PathExpr(it) if it.syntax().text() == receiver => {}
+        _ => return None,
+    }
+    match field_expr.field_access()? {
+        FieldKind::Name(it) => Some(it.to_string()),
+        FieldKind::Index(it) => Some(it.text().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hashes_compared_subset_of_fields() {
+        check_assist(
+            generate_hash_impl,
+            r#"
+//- minicore: eq, hash
+struct Point<T>$0(T, T, &'static str);
+
+impl<T: PartialEq> PartialEq for Point<T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        rhs.1 == self.1 && self.0 == rhs.0
+    }
+}
+"#,
+            r#"
+struct Point<T>(T, T, &'static str);
+
+impl<T> core::hash::Hash for Point<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+        self.1.hash(state);
+    }
+}
+
+impl<T: PartialEq> PartialEq for Point<T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        rhs.1 == self.1 && self.0 == rhs.0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_custom_equality() {
+        cov_mark::check!(hash_custom_equality);
+        check_assist_not_applicable(
+            generate_hash_impl,
+            r#"
+//- minicore: eq, hash
+struct Name$0 {
+    text: &'static str,
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.text.eq_ignore_ascii_case(other.text)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_existing_hash() {
+        cov_mark::check!(hash_already_implemented);
+        check_assist_not_applicable(
+            generate_hash_impl,
+            r#"
+//- minicore: eq, hash
+struct Id$0(u32);
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl core::hash::Hash for Id {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_partial_eq() {
+        check_assist_not_applicable(
+            generate_hash_impl,
+            r#"
+//- minicore: eq, hash
+struct Id$0(u32);
+"#,
+        );
+    }
+}
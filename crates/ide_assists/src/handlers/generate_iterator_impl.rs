@@ -0,0 +1,305 @@
+use hir::HirDisplay;
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use stdx::format_to;
+use syntax::ast::{self, AstNode};
+
+use crate::{utils::generate_trait_impl_text, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_iterator_impl
+//
+// Generates an `Iterator` impl for a struct that walks a slice with an index,
+// or that wraps a range.
+//
+// ```
+// # //- minicore: iterator, slice
+// struct $0Scores<'a> {
+//     scores: &'a [u32],
+//     pos: usize,
+// }
+// ```
+// ->
+// ```
+// struct Scores<'a> {
+//     scores: &'a [u32],
+//     pos: usize,
+// }
+//
+// impl<'a> Iterator for Scores<'a> {
+//     type Item = &'a u32;
+//
+//     fn next(&mut self) -> Option<Self::Item> {
+//         let item = self.scores.get(self.pos)?;
+//         self.pos += 1;
+//         Some(item)
+//     }
+// }
+// ```
+pub(crate) fn generate_iterator_impl(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let field_list = strukt.field_list()?;
+    if ctx.offset() > field_list.syntax().text_range().start() {
+        return None;
+    }
+
+    let db = ctx.db();
+    let struct_def = ctx.sema.to_def(&strukt)?;
+    let module = ctx.sema.scope(strukt.syntax()).module()?;
+    let famous_defs = FamousDefs(&ctx.sema, Some(module.krate()));
+    let iterator = famous_defs.core_iter_Iterator()?;
+    if struct_def.ty(db).impls_trait(db, iterator, &[]) {
+        cov_mark::hit!(iterator_already_implemented);
+        return None;
+    }
+
+    let field_tys = match &field_list {
+        ast::FieldList::RecordFieldList(it) => it.fields().map(|it| it.ty()).collect::<Vec<_>>(),
+        ast::FieldList::TupleFieldList(it) => it.fields().map(|it| it.ty()).collect(),
+    };
+    let fields = struct_def
+        .fields(db)
+        .into_iter()
+        .zip(field_tys)
+        .map(|(field, ty)| (field.name(db).to_string(), field.ty(db), ty))
+        .collect::<Vec<_>>();
+
+    let range = famous_defs.core_ops_Range();
+    let range_field = fields.iter().find(|(_, ty, _)| {
+        matches!((ty.as_adt(), range), (Some(hir::Adt::Struct(it)), Some(range)) if it == range)
+    });
+    let (item_ty, next_body) = match range_field {
+        Some((name, ty, _)) => {
+            let item_ty =
+                ty.type_arguments().next()?.display_source_code(db, module.into()).ok()?;
+            (item_ty, format!("self.{}.next()", name))
+        }
+        None => {
+            let (items, items_ty, items_ast_ty, item) =
+                fields.iter().find_map(|(name, ty, ast_ty)| {
+                    let item = ty.autoderef(db).find_map(|it| it.as_slice_item_type())?;
+                    Some((name, ty, ast_ty, item))
+                })?;
+            let (pos, _, _) = fields.iter().find(|(_, ty, _)| ty.is_usize())?;
+            let item_text = item.display_source_code(db, module.into()).ok()?;
+            // Borrowed items can be handed out, owned ones have to be cloned.
+            let (item_ty, get) = if items_ty.is_reference() {
+                let lifetime = match items_ast_ty {
+                    // Items of a `&'a mut [T]` can't be handed out for `'a` from `&mut self`.
+                    Some(ast::Type::RefType(it)) if it.mut_token().is_none() => it.lifetime()?,
+                    _ => {
+                        cov_mark::hit!(iterator_over_mutable_slice);
+                        return None;
+                    }
+                };
+                (format!("&{} {}", lifetime, item_text), "?")
+            } else {
+                if !item.impls_trait(db, famous_defs.core_clone_Clone()?, &[]) {
+                    cov_mark::hit!(iterator_item_not_clone);
+                    return None;
+                }
+                (item_text, "?.clone()")
+            };
+            let mut body = String::new();
+            format_to!(body, "let item = self.{}.get(self.{}){};", items, pos, get);
+            format_to!(body, "\n        self.{} += 1;", pos);
+            body.push_str("\n        Some(item)");
+            (item_ty, body)
+        }
+    };
+
+    let iterator_path =
+        mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Trait(iterator))?);
+    let target = strukt.syntax().text_range();
+    acc.add(
+        AssistId("generate_iterator_impl", AssistKind::Generate),
+        "Generate `Iterator` impl",
+        target,
+        |builder| {
+            let mut code = String::new();
+            format_to!(code, "    type Item = {};\n\n", item_ty);
+            format_to!(
+                code,
+                "    fn next(&mut self) -> Option<Self::Item> {{\n        {}\n    }}",
+                next_body
+            );
+            let adt = ast::Adt::Struct(strukt.clone());
+            builder.insert(
+                target.end(),
+                generate_trait_impl_text(&adt, &iterator_path.to_string(), &code),
+            );
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_for_owned_collection() {
+        check_assist(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, slice, deref, clone
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+
+struct $0TokenIter<T: Clone> {
+    tokens: Vec<T>,
+    cursor: usize,
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+
+struct TokenIter<T: Clone> {
+    tokens: Vec<T>,
+    cursor: usize,
+}
+
+impl<T: Clone> Iterator for TokenIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.tokens.get(self.cursor)?.clone();
+        self.cursor += 1;
+        Some(item)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_for_array() {
+        check_assist(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, slice, clone
+struct Digit(u8);
+impl Clone for Digit {
+    fn clone(&self) -> Self {
+        Digit(self.0)
+    }
+}
+struct $0Digits([Digit; 4], usize);
+"#,
+            r#"
+struct Digit(u8);
+impl Clone for Digit {
+    fn clone(&self) -> Self {
+        Digit(self.0)
+    }
+}
+struct Digits([Digit; 4], usize);
+
+impl Iterator for Digits {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.0.get(self.1)?.clone();
+        self.1 += 1;
+        Some(item)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_for_range() {
+        check_assist(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, range
+use core::ops::Range;
+
+struct $0Countdown {
+    remaining: Range<u32>,
+    label: &'static str,
+}
+"#,
+            r#"
+use core::ops::Range;
+
+struct Countdown {
+    remaining: Range<u32>,
+    label: &'static str,
+}
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining.next()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_index() {
+        check_assist_not_applicable(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, slice
+struct $0Scores<'a> {
+    scores: &'a [u32],
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_clone_items() {
+        cov_mark::check!(iterator_item_not_clone);
+        check_assist_not_applicable(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, slice, clone
+struct Token;
+struct $0Tokens([Token; 4], usize);
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_mutable_slices() {
+        cov_mark::check!(iterator_over_mutable_slice);
+        check_assist_not_applicable(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, slice
+struct $0Scores<'a> {
+    scores: &'a mut [u32],
+    pos: usize,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_implemented() {
+        cov_mark::check!(iterator_already_implemented);
+        check_assist_not_applicable(
+            generate_iterator_impl,
+            r#"
+//- minicore: iterator, range
+struct $0Countdown(core::ops::Range<u32>);
+impl Iterator for Countdown {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { self.0.next() }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use hir::HasSource;
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use syntax::{
+    ast::{
+        self, edit::AstNodeEdit, edit::IndentLevel, edit_in_place::AttrsOwnerEdit, make, AstNode,
+    },
+    ted,
+};
+
+use crate::{
+    utils::{add_trait_assoc_items_to_impl, filter_assoc_items, gen_trait_fn_body, DefaultMethods},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_partial_eq_from_eq
+//
+// Generates the `PartialEq` impl required by a manual `Eq` impl, comparing the
+// type field by field. For a manual `Ord` impl, generates a `PartialOrd` impl
+// delegating to it.
+//
+// ```
+// # //- minicore: eq
+// struct Point {
+//     x: i32,
+//     y: i32,
+// }
+//
+// impl $0Eq for Point {}
+// ```
+// ->
+// ```
+// struct Point {
+//     x: i32,
+//     y: i32,
+// }
+//
+// impl Eq for Point {}
+//
+// impl PartialEq for Point {
+//     fn eq(&self, other: &Self) -> bool {
+//         self.x == other.x && self.y == other.y
+//     }
+// }
+// ```
+pub(crate) fn generate_partial_eq_from_eq(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let impl_ = ctx.find_node_at_offset::<ast::Impl>()?;
+    if let Some(assoc_items) = impl_.assoc_item_list() {
+        if ctx.offset() > assoc_items.syntax().text_range().start() {
+            return None;
+        }
+    }
+
+    let db = ctx.db();
+    let impl_def = ctx.sema.to_def(&impl_)?;
+    let trait_ = impl_def.trait_(db)?;
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(impl_.syntax()).krate());
+    let (super_trait, is_ord) = if trait_ == famous_defs.core_cmp_Eq()? {
+        (famous_defs.core_cmp_PartialEq()?, false)
+    } else if trait_ == famous_defs.core_cmp_Ord()? {
+        (famous_defs.core_cmp_PartialOrd()?, true)
+    } else {
+        return None;
+    };
+
+    let self_ty = impl_def.self_ty(db);
+    if self_ty.impls_trait(db, super_trait, &[self_ty.clone()]) {
+        cov_mark::hit!(partial_trait_already_implemented);
+        return None;
+    }
+    let adt = self_ty.as_adt()?.source(db)?.value;
+
+    let module = ctx.sema.scope(impl_.syntax()).module()?;
+    let super_trait_path =
+        mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Trait(super_trait))?);
+
+    let target = impl_.syntax().text_range();
+    acc.add(
+        AssistId("generate_partial_eq_from_eq", AssistKind::Generate),
+        format!("Generate `{}` impl from `{}`", super_trait.name(db), trait_.name(db)),
+        target,
+        |builder| {
+            // Reuse the header of the existing impl, so that generics and
+            // where clauses carry over.
+            let new_impl = impl_.clone_for_update();
+            new_impl.remove_attrs_and_docs();
+            if let Some(trait_ty) = new_impl.trait_() {
+                let super_trait_ty = make::ty_path(super_trait_path.clone()).clone_for_update();
+                ted::replace(trait_ty.syntax(), super_trait_ty.syntax());
+            }
+            match new_impl.assoc_item_list() {
+                Some(it) => {
+                    ted::replace(it.syntax(), make::assoc_item_list().clone_for_update().syntax())
+                }
+                None => ted::append_child(
+                    new_impl.syntax(),
+                    make::assoc_item_list().clone_for_update().syntax(),
+                ),
+            }
+
+            let new_impl = ast::Impl::cast(new_impl.syntax().clone_subtree()).unwrap();
+
+            let trait_items = filter_assoc_items(db, &super_trait.items(db), DefaultMethods::No);
+            let (new_impl, first_item) = add_trait_assoc_items_to_impl(
+                &ctx.sema,
+                trait_items,
+                super_trait,
+                new_impl,
+                ctx.sema.scope(impl_.syntax()),
+            );
+            if let ast::AssocItem::Fn(func) = first_item {
+                if is_ord {
+                    gen_partial_cmp_from_cmp(&func);
+                } else {
+                    gen_trait_fn_body(&func, &super_trait_path, &adt);
+                }
+            }
+
+            let indent = IndentLevel::from_node(impl_.syntax());
+            builder.insert(
+                impl_.syntax().text_range().end(),
+                format!("\n\n{}{}", indent, new_impl.indent(indent)),
+            );
+        },
+    )
+}
+
+/// Fills in the body of `partial_cmp` with a call to `Ord::cmp`.
+fn gen_partial_cmp_from_cmp(func: &ast::Fn) -> Option<()> {
+    let cmp_call = make::expr_method_call(
+        make::expr_path(make::ext::ident_path("self")),
+        make::name_ref("cmp"),
+        make::arg_list(Some(make::expr_path(make::ext::ident_path("other")))),
+    );
+    let some = make::expr_call(
+        make::expr_path(make::ext::ident_path("Some")),
+        make::arg_list(Some(cmp_call)),
+    );
+    let body = make::block_expr(None, Some(some)).indent(IndentLevel(1));
+    ted::replace(func.body()?.syntax(), body.clone_for_update().syntax());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn partial_eq_for_tuple_struct() {
+        check_assist(
+            generate_partial_eq_from_eq,
+            r#"
+//- minicore: eq
+struct Wrapper<T>(T, u32);
+
+impl<T: Eq> Eq for Wrapper<T>$0 {}
+"#,
+            r#"
+struct Wrapper<T>(T, u32);
+
+impl<T: Eq> Eq for Wrapper<T> {}
+
+impl<T: Eq> PartialEq for Wrapper<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn partial_ord_from_ord() {
+        check_assist(
+            generate_partial_eq_from_eq,
+            r#"
+//- minicore: ord
+struct Version(u32);
+
+impl Ord$0 for Version {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+"#,
+            r#"
+struct Version(u32);
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_partial_eq_exists() {
+        cov_mark::check!(partial_trait_already_implemented);
+        check_assist_not_applicable(
+            generate_partial_eq_from_eq,
+            r#"
+//- minicore: eq
+struct Foo;
+
+impl PartialEq for Foo {
+    fn eq(&self, other: &Self) -> bool { true }
+}
+
+impl $0Eq for Foo {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_impl_body() {
+        check_assist_not_applicable(
+            generate_partial_eq_from_eq,
+            r#"
+//- minicore: ord
+struct Foo;
+
+impl Ord for Foo {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering { $0loop {} }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, HasGenericParams, HasName, HasTypeBounds, HasVisibility},
+    AstNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_proxy_type
+//
+// Generates a struct wrapping a trait object, along with an impl of the trait
+// forwarding every method to the wrapped object.
+//
+// ```
+// trait $0Greet {
+//     fn greet(&self, name: &str) -> String;
+// }
+// ```
+// ->
+// ```
+// trait Greet {
+//     fn greet(&self, name: &str) -> String;
+// }
+//
+// struct GreetProxy {
+//     inner: std::sync::Arc<dyn Greet>,
+// }
+//
+// impl Greet for GreetProxy {
+//     fn greet(&self, name: &str) -> String {
+//         self.inner.greet(name)
+//     }
+// }
+// ```
+pub(crate) fn generate_proxy_type(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let trait_ = ctx.find_node_at_offset::<ast::Trait>()?;
+    let name = trait_.name()?;
+    let assoc_items = trait_.assoc_item_list()?;
+    if ctx.offset() > assoc_items.syntax().text_range().start() {
+        return None;
+    }
+
+    let mut assoc_types = Vec::new();
+    let mut methods = Vec::new();
+    for item in assoc_items.assoc_items() {
+        match item {
+            ast::AssocItem::TypeAlias(alias) => {
+                if alias.generic_param_list().is_some() {
+                    return None;
+                }
+                assoc_types.push(alias.name()?);
+            }
+            ast::AssocItem::Fn(func) => {
+                // Methods that aren't callable on `dyn Trait` make the trait unusable as a
+                // trait object, unless they are opted out with `where Self: Sized`.
+                if !requires_sized_self(&func) && !is_dispatchable(&func) {
+                    return None;
+                }
+                methods.push(func)
+            }
+            // Associated consts make the trait unusable as a trait object.
+            ast::AssocItem::Const(_) => return None,
+            ast::AssocItem::MacroCall(_) => {}
+        }
+    }
+
+    // `&mut self` methods can't be forwarded through a shared pointer.
+    let needs_unique = methods.iter().any(|func| {
+        func.param_list()
+            .and_then(|it| it.self_param())
+            .map_or(false, |it| it.amp_token().is_some() && it.mut_token().is_some())
+    });
+    let pointer = if needs_unique { "Box" } else { "std::sync::Arc" };
+
+    let trait_params = trait_.generic_param_list();
+    let trait_args = trait_params
+        .iter()
+        .flat_map(|it| it.generic_params())
+        .filter_map(|param| match param {
+            ast::GenericParam::LifetimeParam(it) => Some(it.lifetime()?.to_string()),
+            ast::GenericParam::TypeParam(it) => Some(it.name()?.to_string()),
+            ast::GenericParam::ConstParam(it) => Some(it.name()?.to_string()),
+        })
+        .collect::<Vec<_>>();
+    let struct_params = trait_params
+        .iter()
+        .flat_map(|it| it.generic_params())
+        .map(|it| it.to_string())
+        .chain(assoc_types.iter().map(|it| it.to_string()))
+        .collect::<Vec<_>>();
+    let dyn_args = trait_args
+        .iter()
+        .cloned()
+        .chain(assoc_types.iter().map(|it| format!("{0} = {0}", it)))
+        .collect::<Vec<_>>();
+    let struct_args = trait_args
+        .iter()
+        .cloned()
+        .chain(assoc_types.iter().map(|it| it.to_string()))
+        .collect::<Vec<_>>();
+
+    let proxy_name = format!("{}Proxy", name);
+    let vis = trait_.visibility().map(|it| format!("{} ", it)).unwrap_or_default();
+    let indent = IndentLevel::from_node(trait_.syntax());
+
+    let target = name.syntax().text_range();
+    acc.add(
+        AssistId("generate_proxy_type", AssistKind::Generate),
+        format!("Generate `{}` type", proxy_name),
+        target,
+        |builder| {
+            let mut buf = String::new();
+            format_to!(
+                buf,
+                "{vis}struct {proxy}{params} {{\n    {vis}inner: {pointer}<dyn {trait_}{dyn_args}>,\n}}",
+                vis = vis,
+                proxy = proxy_name,
+                params = angled(&struct_params),
+                pointer = pointer,
+                trait_ = name,
+                dyn_args = angled(&dyn_args),
+            );
+            format_to!(
+                buf,
+                "\n\nimpl{params} {trait_}{trait_args} for {proxy}{struct_args} {{",
+                params = angled(&struct_params),
+                trait_ = name,
+                trait_args = angled(&trait_args),
+                proxy = proxy_name,
+                struct_args = angled(&struct_args),
+            );
+            let items = assoc_types
+                .iter()
+                .map(|it| format!("    type {0} = {0};", it))
+                .chain(methods.iter().filter_map(forwarding_method))
+                .join("\n\n");
+            if !items.is_empty() {
+                format_to!(buf, "\n{}", items);
+            }
+            buf.push_str("\n}");
+
+            let buf = buf
+                .lines()
+                .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", indent, line) })
+                .join("\n");
+            builder.insert(trait_.syntax().text_range().end(), format!("\n\n{}", buf));
+        },
+    )
+}
+
+/// Renders a method of the proxy impl, forwarding to the same method of the
+/// wrapped trait object.
+fn forwarding_method(func: &ast::Fn) -> Option<String> {
+    let name = func.name()?;
+    let param_list = func.param_list()?;
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for (idx, param) in param_list.params().enumerate() {
+        let arg = match param.pat() {
+            Some(ast::Pat::IdentPat(pat))
+                if pat.ref_token().is_none() && pat.mut_token().is_none() =>
+            {
+                pat.name()?.to_string()
+            }
+            _ => format!("arg{}", idx),
+        };
+        params.push(format!("{}: {}", arg, param.ty()?));
+        args.push(arg);
+    }
+
+    let self_param = param_list.self_param();
+    let body = match &self_param {
+        Some(self_param)
+            if self_param.amp_token().is_some()
+                && self_param.ty().is_none()
+                && !requires_sized_self(func) =>
+        {
+            format!("self.inner.{}({})", name, args.join(", "))
+        }
+        // Neither by-value receivers, associated functions nor methods
+        // requiring `Self: Sized` can be called on the trait object.
+        _ => "unimplemented!()".to_string(),
+    };
+    let params = self_param.map(|it| it.to_string()).into_iter().chain(params).join(", ");
+
+    let mut buf = String::new();
+    format_to!(buf, "    fn {}", name);
+    if let Some(generics) = func.generic_param_list() {
+        format_to!(buf, "{}", generics);
+    }
+    format_to!(buf, "({})", params);
+    if let Some(ret_type) = func.ret_type() {
+        format_to!(buf, " {}", ret_type);
+    }
+    if let Some(where_clause) = func.where_clause() {
+        format_to!(buf, " {}", where_clause);
+    }
+    format_to!(buf, " {{\n        {}\n    }}", body);
+    Some(buf)
+}
+
+/// Whether `func` has a `where Self: Sized` bound, which excludes it from the
+/// trait object.
+fn requires_sized_self(func: &ast::Fn) -> bool {
+    func.where_clause().map_or(false, |it| {
+        it.predicates().any(|pred| {
+            pred.ty().map_or(false, |ty| ty.syntax().text() == "Self")
+                && pred
+                    .type_bound_list()
+                    .map_or(false, |it| it.bounds().any(|it| it.syntax().text() == "Sized"))
+        })
+    })
+}
+
+/// Whether `func` can be called on a trait object: it needs a receiver, and
+/// can neither be generic over types nor mention `Self` outside the receiver.
+fn is_dispatchable(func: &ast::Fn) -> bool {
+    let param_list = match func.param_list() {
+        Some(it) if it.self_param().is_some() => it,
+        _ => return false,
+    };
+    let has_type_params = func.generic_param_list().map_or(false, |it| {
+        it.generic_params().any(|it| !matches!(it, ast::GenericParam::LifetimeParam(_)))
+    });
+    if has_type_params {
+        return false;
+    }
+    // `Self::Assoc` is fine, only a bare `Self` can't be used by a trait object.
+    let mentions_self = |ty: ast::Type| {
+        ty.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+            path.syntax().text() == "Self"
+                && path.syntax().parent().and_then(ast::Path::cast).is_none()
+        })
+    };
+    let has_impl_trait = |ty: &ast::Type| {
+        ty.syntax().descendants().any(|it| ast::ImplTraitType::can_cast(it.kind()))
+    };
+    let params_ok = param_list.params().all(|param| match param.ty() {
+        Some(ty) => !has_impl_trait(&ty) && !mentions_self(ty),
+        None => true,
+    });
+    params_ok && func.ret_type().and_then(|it| it.ty()).map_or(true, |ty| !mentions_self(ty))
+}
+
+fn angled(args: &[String]) -> String {
+    if args.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", args.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn proxy_for_shared_methods() {
+        check_assist(
+            generate_proxy_type,
+            r#"
+pub trait $0Store {
+    fn get(&self, key: &str) -> Option<u32>;
+    fn len(&self) -> usize;
+}
+"#,
+            r#"
+pub trait Store {
+    fn get(&self, key: &str) -> Option<u32>;
+    fn len(&self) -> usize;
+}
+
+pub struct StoreProxy {
+    pub inner: std::sync::Arc<dyn Store>,
+}
+
+impl Store for StoreProxy {
+    fn get(&self, key: &str) -> Option<u32> {
+        self.inner.get(key)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn proxy_for_mut_and_value_methods() {
+        check_assist(
+            generate_proxy_type,
+            r#"
+trait Counter$0 {
+    fn incr(&mut self, by: u32);
+    fn finish(self) -> u32 where Self: Sized;
+    fn peek(&self, _: bool) -> u32;
+}
+"#,
+            r#"
+trait Counter {
+    fn incr(&mut self, by: u32);
+    fn finish(self) -> u32 where Self: Sized;
+    fn peek(&self, _: bool) -> u32;
+}
+
+struct CounterProxy {
+    inner: Box<dyn Counter>,
+}
+
+impl Counter for CounterProxy {
+    fn incr(&mut self, by: u32) {
+        self.inner.incr(by)
+    }
+
+    fn finish(self) -> u32 where Self: Sized {
+        unimplemented!()
+    }
+
+    fn peek(&self, arg0: bool) -> u32 {
+        self.inner.peek(arg0)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn proxy_with_assoc_types_and_generics() {
+        check_assist(
+            generate_proxy_type,
+            r#"
+trait $0Source<K> {
+    type Item;
+    type Error;
+    fn fetch(&self, key: K) -> Result<Self::Item, Self::Error>;
+}
+"#,
+            r#"
+trait Source<K> {
+    type Item;
+    type Error;
+    fn fetch(&self, key: K) -> Result<Self::Item, Self::Error>;
+}
+
+struct SourceProxy<K, Item, Error> {
+    inner: std::sync::Arc<dyn Source<K, Item = Item, Error = Error>>,
+}
+
+impl<K, Item, Error> Source<K> for SourceProxy<K, Item, Error> {
+    type Item = Item;
+
+    type Error = Error;
+
+    fn fetch(&self, key: K) -> Result<Self::Item, Self::Error> {
+        self.inner.fetch(key)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn proxy_for_nested_trait() {
+        check_assist(
+            generate_proxy_type,
+            r#"
+mod m {
+    trait $0Tr {
+        fn f(&self);
+    }
+}
+"#,
+            r#"
+mod m {
+    trait Tr {
+        fn f(&self);
+    }
+
+    struct TrProxy {
+        inner: std::sync::Arc<dyn Tr>,
+    }
+
+    impl Tr for TrProxy {
+        fn f(&self) {
+            self.inner.f()
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_trait_body() {
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait Tr {
+    fn f(&self$0);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_assoc_consts() {
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait $0Tr {
+    const N: usize;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn proxy_skips_sized_only_methods() {
+        check_assist(
+            generate_proxy_type,
+            r#"
+trait $0Visit {
+    fn visit(&self, node: u32);
+    fn visit_all<I: Iterator<Item = u32>>(&self, nodes: I) where Self: Sized;
+}
+"#,
+            r#"
+trait Visit {
+    fn visit(&self, node: u32);
+    fn visit_all<I: Iterator<Item = u32>>(&self, nodes: I) where Self: Sized;
+}
+
+struct VisitProxy {
+    inner: std::sync::Arc<dyn Visit>,
+}
+
+impl Visit for VisitProxy {
+    fn visit(&self, node: u32) {
+        self.inner.visit(node)
+    }
+
+    fn visit_all<I: Iterator<Item = u32>>(&self, nodes: I) where Self: Sized {
+        unimplemented!()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_non_object_safe_traits() {
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait $0Tr {
+    fn f<T>(&self, x: T);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait $0Tr {
+    fn f(&self, x: impl Into<u32>);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait $0Tr {
+    fn new() -> u32;
+}
+"#,
+        );
+        check_assist_not_applicable(
+            generate_proxy_type,
+            r#"
+trait $0Tr {
+    fn dup(&self) -> Self;
+}
+"#,
+        );
+    }
+}
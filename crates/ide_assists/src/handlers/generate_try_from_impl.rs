@@ -0,0 +1,294 @@
+use hir::{Adt, ModuleDef, ScopeDef, StructKind};
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use stdx::format_to;
+use syntax::ast::{self, AstNode, HasName, HasVisibility};
+
+use crate::{utils::generate_trait_impl_text, AssistContext, AssistId, AssistKind, Assists};
+
+const ERROR_NAME: &str = "ConversionError";
+
+// Assist: generate_try_from_impl
+//
+// Adds a `TryFrom` impl for a struct wrapping a single value, leaving the
+// validation of the value to be filled in.
+//
+// ```
+// # //- minicore: try_from
+// struct $0Even(u32);
+// ```
+// ->
+// ```
+// struct Even(u32);
+//
+// #[derive(Debug)]
+// struct ConversionError(());
+//
+// impl core::convert::TryFrom<u32> for Even {
+//     type Error = ConversionError;
+//
+//     fn try_from(value: u32) -> Result<Self, Self::Error> {
+//         if ${0:false} {
+//             return Err(ConversionError(()));
+//         }
+//         Ok(Self(value))
+//     }
+// }
+// ```
+pub(crate) fn generate_try_from_impl(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let strukt = ctx.find_node_at_offset::<ast::Struct>()?;
+    let field_list = strukt.field_list()?;
+    if ctx.offset() > field_list.syntax().text_range().start() {
+        return None;
+    }
+    let (field_name, field_ty) = match &field_list {
+        ast::FieldList::RecordFieldList(it) if it.fields().count() == 1 => {
+            let field = it.fields().next()?;
+            (Some(field.name()?), field.ty()?)
+        }
+        ast::FieldList::TupleFieldList(it) if it.fields().count() == 1 => {
+            (None, it.fields().next()?.ty()?)
+        }
+        _ => return None,
+    };
+
+    let db = ctx.db();
+    let struct_def = ctx.sema.to_def(&strukt)?;
+    let module = ctx.sema.scope(strukt.syntax()).module()?;
+    let try_from = FamousDefs(&ctx.sema, Some(module.krate())).core_convert_TryFrom()?;
+    let wrapped_ty = struct_def.fields(db).get(0)?.ty(db);
+    // `impl<T> TryFrom<T> for S<T>` overlaps with core's `impl<T, U: Into<T>> TryFrom<U> for T`.
+    if hir::GenericDef::from(struct_def)
+        .type_params(db)
+        .into_iter()
+        .any(|it| it.ty(db) == wrapped_ty)
+    {
+        cov_mark::hit!(try_from_type_param);
+        return None;
+    }
+    if struct_def.ty(db).impls_trait(db, try_from, &[wrapped_ty]) {
+        cov_mark::hit!(try_from_already_implemented);
+        return None;
+    }
+    let try_from_path = mod_path_to_ast(&module.find_use_path(db, ModuleDef::Trait(try_from))?);
+
+    // Reuse the error type generated for another struct of the module, as long as
+    // it can still be built as `ConversionError(())`.
+    let scope = module.scope(db, None);
+    let existing_error = scope.iter().find_map(|(name, def)| match def {
+        ScopeDef::ModuleDef(def) if name.to_string() == ERROR_NAME => Some(def),
+        _ => None,
+    });
+    let (error_name, has_error_type) = match existing_error {
+        None => (ERROR_NAME.to_string(), false),
+        Some(ModuleDef::Adt(Adt::Struct(it))) if is_unit_wrapper(ctx, *it) => {
+            (ERROR_NAME.to_string(), true)
+        }
+        Some(_) => {
+            cov_mark::hit!(try_from_unfit_error_type);
+            let name = format!("{}{}", strukt.name()?, ERROR_NAME);
+            if scope.iter().any(|(it, _)| it.to_string() == name) {
+                return None;
+            }
+            (name, false)
+        }
+    };
+
+    let target = strukt.syntax().text_range();
+    acc.add(
+        AssistId("generate_try_from_impl", AssistKind::Generate),
+        "Generate `TryFrom` impl",
+        target,
+        |builder| {
+            let ok_value = match &field_name {
+                Some(name) => format!("Self {{ {}: value }}", name),
+                None => "Self(value)".to_string(),
+            };
+            let condition = match ctx.config.snippet_cap {
+                Some(_) => "${0:false}",
+                None => "false",
+            };
+            let mut code = String::new();
+            format_to!(code, "    type Error = {};\n\n", error_name);
+            format_to!(
+                code,
+                "    fn try_from(value: {}) -> Result<Self, Self::Error> {{
+        if {} {{
+            return Err({}(()));
+        }}
+        Ok({})
+    }}",
+                field_ty,
+                condition,
+                error_name,
+                ok_value
+            );
+            let adt = ast::Adt::Struct(strukt.clone());
+            let trait_text = format!("{}<{}>", try_from_path, field_ty);
+
+            let mut buf = String::new();
+            if !has_error_type {
+                let vis = strukt.visibility().map_or(String::new(), |v| format!("{} ", v));
+                format_to!(buf, "\n\n#[derive(Debug)]\n{}struct {}(());", vis, error_name);
+            }
+            buf.push_str(&generate_trait_impl_text(&adt, &trait_text, &code));
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, target.end(), buf),
+                None => builder.insert(target.end(), buf),
+            }
+        },
+    )
+}
+
+fn is_unit_wrapper(ctx: &AssistContext, strukt: hir::Struct) -> bool {
+    let db = ctx.db();
+    let fields = strukt.fields(db);
+    strukt.kind(db) == StructKind::Tuple && fields.len() == 1 && fields[0].ty(db).is_unit()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_for_tuple_struct() {
+        check_assist(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from
+use core::convert::TryFrom;
+
+pub struct NonZero$0U32(u32);
+"#,
+            r#"
+use core::convert::TryFrom;
+
+pub struct NonZeroU32(u32);
+
+#[derive(Debug)]
+pub struct ConversionError(());
+
+impl TryFrom<u32> for NonZeroU32 {
+    type Error = ConversionError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if ${0:false} {
+            return Err(ConversionError(()));
+        }
+        Ok(Self(value))
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_for_record_struct_reusing_error() {
+        check_assist(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from, option
+#[derive(Debug)]
+struct ConversionError(());
+
+struct $0Port<T> {
+    number: Option<T>,
+}
+"#,
+            r#"
+#[derive(Debug)]
+struct ConversionError(());
+
+struct Port<T> {
+    number: Option<T>,
+}
+
+impl<T> core::convert::TryFrom<Option<T>> for Port<T> {
+    type Error = ConversionError;
+
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        if ${0:false} {
+            return Err(ConversionError(()));
+        }
+        Ok(Self { number: value })
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_new_error_when_existing_does_not_fit() {
+        cov_mark::check!(try_from_unfit_error_type);
+        check_assist(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from
+enum ConversionError { Overflow }
+
+struct $0Port(u32);
+"#,
+            r#"
+enum ConversionError { Overflow }
+
+struct Port(u32);
+
+#[derive(Debug)]
+struct PortConversionError(());
+
+impl core::convert::TryFrom<u32> for Port {
+    type Error = PortConversionError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if ${0:false} {
+            return Err(PortConversionError(()));
+        }
+        Ok(Self(value))
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_type_param() {
+        cov_mark::check!(try_from_type_param);
+        check_assist_not_applicable(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from
+struct $0Port<T> {
+    number: T,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_several_fields() {
+        check_assist_not_applicable(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from
+struct $0Range(u32, u32);
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_implemented() {
+        cov_mark::check!(try_from_already_implemented);
+        check_assist_not_applicable(
+            generate_try_from_impl,
+            r#"
+//- minicore: try_from
+struct $0Even(u32);
+impl core::convert::TryFrom<u32> for Even {
+    type Error = ();
+    fn try_from(value: u32) -> Result<Self, ()> { Err(()) }
+}
+"#,
+        );
+    }
+}
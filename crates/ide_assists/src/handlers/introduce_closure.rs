@@ -0,0 +1,223 @@
+use hir::HirDisplay;
+use syntax::{
+    ast::{self, AstNode},
+    SyntaxKind::{CLOSURE_EXPR, STMT_LIST},
+    SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: introduce_closure
+//
+// Moves a closure passed as an argument into a named variable.
+//
+// ```
+// fn spawn(f: impl FnOnce()) {}
+// fn main() {
+//     spawn(move |$0| {
+//         println!("working");
+//     });
+// }
+// ```
+// ->
+// ```
+// fn spawn(f: impl FnOnce()) {}
+// fn main() {
+//     let $0spawn_f = move || {
+//         println!("working");
+//     };
+//     spawn(spawn_f);
+// }
+// ```
+pub(crate) fn introduce_closure(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let closure = ctx.find_node_at_offset::<ast::ClosureExpr>()?;
+    if ctx.offset() > closure.body()?.syntax().text_range().start() {
+        return None;
+    }
+    let call = closure.syntax().parent().and_then(ast::ArgList::cast)?.syntax().parent()?;
+    let fn_name = call_name(&call)?;
+
+    // Insert the variable before the statement containing the call, without
+    // leaving the closure or item the call is in.
+    let anchor = call
+        .ancestors()
+        .take_while(|it| it.kind() != CLOSURE_EXPR && !ast::Item::can_cast(it.kind()))
+        .find(|it| it.parent().map_or(false, |parent| parent.kind() == STMT_LIST))?;
+    let indent = anchor.prev_sibling_or_token()?.into_token()?;
+    let indent = format!("\n{}", indent.text().trim_start_matches('\n'));
+
+    // Out of the call, the parameter types can no longer be inferred from the callee.
+    let closure_text = annotate_param_types(ctx, &closure)?;
+
+    let var_name = format!("{}_f", fn_name);
+    let target = closure.syntax().text_range();
+    acc.add(
+        AssistId("introduce_closure", AssistKind::RefactorExtract),
+        "Move closure into a variable",
+        target,
+        |builder| {
+            let insert_offset = anchor.text_range().start();
+            builder.replace(target, var_name.clone());
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(
+                    cap,
+                    insert_offset,
+                    format!("let $0{} = {};{}", var_name, closure_text, indent),
+                ),
+                None => builder.insert(
+                    insert_offset,
+                    format!("let {} = {};{}", var_name, closure_text, indent),
+                ),
+            }
+        },
+    )
+}
+
+/// Renders `closure` with the inferred types added to its untyped parameters.
+fn annotate_param_types(ctx: &AssistContext, closure: &ast::ClosureExpr) -> Option<String> {
+    let module = ctx.sema.scope(closure.syntax()).module()?;
+    let start = closure.syntax().text_range().start();
+    let mut text = closure.syntax().to_string();
+    let params = closure.param_list()?.params().collect::<Vec<_>>();
+    for param in params.iter().rev() {
+        if param.ty().is_some() {
+            continue;
+        }
+        let pat = param.pat()?;
+        let ty = ctx.sema.type_of_pat(&pat)?.original;
+        if ty.contains_unknown() || ty.is_closure() {
+            cov_mark::hit!(introduce_closure_unknown_param_type);
+            return None;
+        }
+        let ty = ty.display_source_code(ctx.db(), module.into()).ok()?;
+        let offset = usize::from(pat.syntax().text_range().end() - start);
+        text.insert_str(offset, &format!(": {}", ty));
+    }
+    Some(text)
+}
+
+fn call_name(call: &SyntaxNode) -> Option<String> {
+    if let Some(call) = ast::CallExpr::cast(call.clone()) {
+        let path = match call.expr()? {
+            ast::Expr::PathExpr(it) => it.path()?,
+            _ => return None,
+        };
+        return Some(path.segment()?.name_ref()?.to_string());
+    }
+    let call = ast::MethodCallExpr::cast(call.clone())?;
+    Some(call.name_ref()?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_closure_to_path_call() {
+        check_assist(
+            introduce_closure,
+            r#"
+fn f() {
+    let handle = std::thread::spawn($0move || {
+        compute();
+    });
+}
+"#,
+            r#"
+fn f() {
+    let $0spawn_f = move || {
+        compute();
+    };
+    let handle = std::thread::spawn(spawn_f);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn closure_to_method_call_in_tail() {
+        check_assist(
+            introduce_closure,
+            r#"
+//- minicore: fn
+struct S;
+impl S {
+    fn map(self, f: impl Fn(u32, &str) -> u32) -> u32 { 0 }
+}
+fn f(s: S) -> u32 {
+    s.map(|x, y|$0 x * 2)
+}
+"#,
+            r#"
+struct S;
+impl S {
+    fn map(self, f: impl Fn(u32, &str) -> u32) -> u32 { 0 }
+}
+fn f(s: S) -> u32 {
+    let $0map_f = |x: u32, y: &str| x * 2;
+    s.map(map_f)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_annotated_param_types() {
+        check_assist(
+            introduce_closure,
+            r#"
+//- minicore: fn
+fn apply(f: impl Fn(u8, u16)) {}
+fn f() {
+    apply(|a: u8, b|$0 {});
+}
+"#,
+            r#"
+fn apply(f: impl Fn(u8, u16)) {}
+fn f() {
+    let $0apply_f = |a: u8, b: u16| {};
+    apply(apply_f);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_unknown_param_types() {
+        cov_mark::check!(introduce_closure_unknown_param_type);
+        check_assist_not_applicable(
+            introduce_closure,
+            r#"
+fn f(v: Vec<u32>) -> Vec<u32> {
+    v.into_iter().map(|x|$0 x * 2).collect()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_closure_body() {
+        check_assist_not_applicable(
+            introduce_closure,
+            r#"
+fn f() {
+    run(|| { $0work(); });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_arguments() {
+        check_assist_not_applicable(
+            introduce_closure,
+            r#"
+fn f() {
+    let g = |$0x: u32| x;
+}
+"#,
+        );
+    }
+}
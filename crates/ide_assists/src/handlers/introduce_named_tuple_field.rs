@@ -0,0 +1,239 @@
+use either::Either;
+use hir::VariantDef;
+use ide_db::defs::Definition;
+use stdx::to_lower_snake_case;
+use syntax::ast::{self, make, AstNode};
+
+use crate::{
+    handlers::convert_tuple_struct_to_named_struct::{
+        edit_field_references, edit_struct_def, edit_struct_references,
+    },
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: introduce_named_tuple_field
+//
+// Converts the tuple struct of the accessed field to a struct with named
+// fields, naming the accessed field after its type.
+//
+// ```
+// struct Inner;
+// struct Wrapper(Inner);
+//
+// impl Wrapper {
+//     fn new(inner: Inner) -> Self {
+//         Wrapper(inner)
+//     }
+//     fn get(&self) -> &Inner {
+//         &self.$00
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Inner;
+// struct Wrapper { inner: Inner }
+//
+// impl Wrapper {
+//     fn new(inner: Inner) -> Self {
+//         Wrapper { inner: inner }
+//     }
+//     fn get(&self) -> &Inner {
+//         &self.$0inner
+//     }
+// }
+// ```
+pub(crate) fn introduce_named_tuple_field(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let field_expr = ctx.find_node_at_offset::<ast::FieldExpr>()?;
+    let name_ref = field_expr.name_ref()?;
+    let idx = name_ref.as_tuple_field()?;
+    if !name_ref.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let db = ctx.db();
+    let field = ctx.sema.resolve_field(&field_expr)?;
+    let strukt_def = match field.parent_def(db) {
+        VariantDef::Struct(it) => it,
+        _ => return None,
+    };
+    let krate = ctx.sema.scope(field_expr.syntax()).krate()?;
+    if strukt_def.module(db).krate() != krate {
+        return None;
+    }
+    let source = ctx.sema.source(strukt_def)?;
+    if source.file_id.is_macro() {
+        return None;
+    }
+    let file_id = source.file_id.original_file(db);
+    let strukt = source.value;
+    let tuple_fields = match strukt.field_list()? {
+        ast::FieldList::TupleFieldList(it) => it,
+        ast::FieldList::RecordFieldList(_) => return None,
+    };
+    let field_ty = tuple_fields.fields().nth(idx)?.ty()?;
+
+    let names = tuple_fields
+        .fields()
+        .enumerate()
+        .map(|(i, _)| match i == idx {
+            true => make::name(&name_for_type(&field_ty)),
+            false => make::name(&format!("field{}", i + 1)),
+        })
+        .collect::<Vec<_>>();
+    let name = names[idx].clone();
+
+    let target = name_ref.syntax().text_range();
+    acc.add(
+        AssistId("introduce_named_tuple_field", AssistKind::RefactorRewrite),
+        format!("Introduce named field `{}`", name),
+        target,
+        |edit| {
+            let other_fields = tuple_fields.fields().enumerate().filter(|(i, _)| *i != idx);
+            let other_names = names
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != idx)
+                .map(|(_, it)| it.clone())
+                .collect::<Vec<_>>();
+            edit_field_references(ctx, edit, other_fields.map(|(_, it)| it), &other_names);
+
+            let usages = Definition::Field(field).usages(&ctx.sema).all();
+            for (file_id, refs) in usages {
+                edit.edit_file(file_id);
+                for r in refs {
+                    let range = match r.name.as_name_ref() {
+                        Some(it) => it.syntax().text_range(),
+                        None => continue,
+                    };
+                    match ctx.config.snippet_cap {
+                        Some(cap) if range == target => {
+                            edit.replace_snippet(cap, range, format!("$0{}", name))
+                        }
+                        _ => edit.replace(range, name.text()),
+                    }
+                }
+            }
+
+            edit_struct_references(ctx, edit, Either::Left(strukt_def), &names);
+            edit_struct_def(edit, file_id, &Either::Left(strukt.clone()), tuple_fields, names);
+        },
+    )
+}
+
+/// Names the field after the last segment of its type, or `value` for types
+/// without a usable name.
+fn name_for_type(ty: &ast::Type) -> String {
+    let segment = match ty {
+        ast::Type::PathType(it) => it.path().and_then(|it| it.segment()),
+        _ => None,
+    };
+    let name = segment.and_then(|it| it.name_ref()).map(|it| it.text().to_string());
+    match name {
+        Some(name) if name.starts_with(char::is_uppercase) => to_lower_snake_case(&name),
+        _ => "value".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn introduce_field_in_newtype() {
+        check_assist(
+            introduce_named_tuple_field,
+            r#"
+struct UserId(u64);
+
+impl UserId {
+    fn new(id: u64) -> Self {
+        UserId(id)
+    }
+    fn get(&self) -> u64 {
+        self.0$0
+    }
+}
+
+fn same(a: &UserId, b: &UserId) -> bool {
+    let UserId(x) = a;
+    *x == b.0
+}
+"#,
+            r#"
+struct UserId { value: u64 }
+
+impl UserId {
+    fn new(id: u64) -> Self {
+        UserId { value: id }
+    }
+    fn get(&self) -> u64 {
+        self.$0value
+    }
+}
+
+fn same(a: &UserId, b: &UserId) -> bool {
+    let UserId { value: x } = a;
+    *x == b.value
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_field_in_other_file() {
+        check_assist(
+            introduce_named_tuple_field,
+            r#"
+//- /main.rs
+mod geo;
+use geo::Span;
+
+fn len(span: Span) -> u32 {
+    span.1$0 - span.0
+}
+//- /geo.rs
+pub struct Pos(pub u32);
+pub struct Span(pub u32, pub Pos);
+"#,
+            r#"
+//- /main.rs
+mod geo;
+use geo::Span;
+
+fn len(span: Span) -> u32 {
+    span.$0pos - span.field1
+}
+//- /geo.rs
+pub struct Pos(pub u32);
+pub struct Span { pub field1: u32, pub pos: Pos }
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_named_field() {
+        check_assist_not_applicable(
+            introduce_named_tuple_field,
+            r#"
+struct S { x: u32 }
+fn f(s: S) -> u32 {
+    s.x$0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_tuples() {
+        check_assist_not_applicable(
+            introduce_named_tuple_field,
+            r#"
+fn f(t: (u32, u32)) -> u32 {
+    t.0$0
+}
+"#,
+        );
+    }
+}
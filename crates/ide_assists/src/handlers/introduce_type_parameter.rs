@@ -0,0 +1,347 @@
+use hir::{AsAssocItem, AssocItemContainer, HirDisplay, ModuleDef, PathResolution};
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use syntax::{
+    ast::{self, edit_in_place::GenericParamsOwnerEdit, make, AstNode, HasGenericParams, HasName},
+    ted,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: introduce_type_parameter
+//
+// Replaces a concrete type used by several parameters of a function with a
+// type parameter, bounded by what the function body needs from the type.
+//
+// ```
+// # //- minicore: slice, deref, as_ref
+// # struct Vec<T>(T);
+// # impl<T> core::ops::Deref for Vec<T> { type Target = [T]; fn deref(&self) -> &[T] { loop {} } }
+// fn total_len(a: &$0Vec<u32>, b: &Vec<u32>) -> usize {
+//     a.len() + b.len()
+// }
+// ```
+// ->
+// ```
+// # struct Vec<T>(T);
+// # impl<T> core::ops::Deref for Vec<T> { type Target = [T]; fn deref(&self) -> &[T] { loop {} } }
+// fn total_len<T: AsRef<[u32]>>(a: &T, b: &T) -> usize {
+//     a.as_ref().len() + b.as_ref().len()
+// }
+// ```
+pub(crate) fn introduce_type_parameter(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let param = ctx.find_node_at_offset::<ast::Param>()?;
+    if !param.ty()?.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let fn_ = param.syntax().parent().and_then(ast::ParamList::cast)?.syntax().parent()?;
+    let fn_ = ast::Fn::cast(fn_)?;
+    let body = fn_.body()?;
+    let concrete_ty = strip_reference(param.ty()?);
+    let ty = ctx.sema.resolve_type(&concrete_ty)?;
+    ty.as_adt()?;
+
+    let mut occurrences = Vec::new();
+    let mut locals = Vec::new();
+    for param in fn_.param_list()?.params() {
+        let param_ty = match param.ty() {
+            Some(it) => strip_reference(it),
+            None => continue,
+        };
+        if ctx.sema.resolve_type(&param_ty).as_ref() != Some(&ty) {
+            continue;
+        }
+        match param.pat()? {
+            ast::Pat::IdentPat(it) => locals.push(ctx.sema.to_def(&it)?),
+            ast::Pat::WildcardPat(_) => (),
+            _ => return None,
+        }
+        occurrences.push(param_ty);
+    }
+    if occurrences.len() < 2 {
+        return None;
+    }
+
+    let db = ctx.db();
+    let module = ctx.sema.scope(fn_.syntax()).module()?;
+    let famous_defs = FamousDefs(&ctx.sema, Some(module.krate()));
+    let trait_path =
+        |trait_| module.find_use_path(db, ModuleDef::Trait(trait_)).map(|it| mod_path_to_ast(&it));
+    let mut bounds = Vec::new();
+    let mut slice_receivers = Vec::new();
+    for usage in body.syntax().descendants().filter_map(ast::PathExpr::cast) {
+        if !is_usage_of(ctx, &usage, &locals) {
+            continue;
+        }
+        let bound = match usage_bound(ctx, &ty, &usage, &locals) {
+            Some(Bound::Trait(it)) => {
+                let has_required_params = ctx
+                    .sema
+                    .source(it)?
+                    .value
+                    .generic_param_list()
+                    .map_or(false, |it| it.type_params().any(|it| it.default_type().is_none()));
+                if has_required_params {
+                    return None;
+                }
+                trait_path(it)?.to_string()
+            }
+            Some(Bound::Slice(item_ty)) => {
+                slice_receivers.push(usage);
+                let as_ref = trait_path(famous_defs.core_convert_AsRef()?)?;
+                format!("{}<[{}]>", as_ref, item_ty.display_source_code(db, module.into()).ok()?)
+            }
+            None => {
+                cov_mark::hit!(introduce_type_parameter_unsupported_usage);
+                return None;
+            }
+        };
+        if !bounds.contains(&bound) {
+            bounds.push(bound);
+        }
+    }
+
+    let existing_names: Vec<String> = fn_
+        .syntax()
+        .ancestors()
+        .filter_map(|it| generic_param_list(&it))
+        .flat_map(|it| it.generic_params())
+        .filter_map(|it| match it {
+            ast::GenericParam::TypeParam(it) => it.name(),
+            ast::GenericParam::ConstParam(it) => it.name(),
+            ast::GenericParam::LifetimeParam(_) => None,
+        })
+        .map(|it| it.to_string())
+        .collect();
+    let name = ["T", "U", "V", "W"].iter().find(|it| !existing_names.iter().any(|n| n == *it))?;
+
+    let target = concrete_ty.syntax().text_range();
+    acc.add(
+        AssistId("introduce_type_parameter", AssistKind::RefactorRewrite),
+        format!("Replace `{}` with a type parameter", concrete_ty),
+        target,
+        |builder| {
+            let occurrences: Vec<_> =
+                occurrences.into_iter().map(|it| builder.make_mut(it)).collect();
+            let slice_receivers: Vec<_> =
+                slice_receivers.into_iter().map(|it| builder.make_mut(it)).collect();
+            let fn_ = builder.make_mut(fn_);
+
+            for ty in occurrences {
+                ted::replace(ty.syntax(), make::ty(name).clone_for_update().syntax());
+            }
+            for receiver in slice_receivers {
+                let as_ref = make::expr_method_call(
+                    ast::Expr::PathExpr(receiver.clone()),
+                    make::name_ref("as_ref"),
+                    make::arg_list(None),
+                )
+                .clone_for_update();
+                ted::replace(receiver.syntax(), as_ref.syntax());
+            }
+            let bounds = make::type_bound_list(bounds.iter().map(|it| make::type_bound(it)));
+            let type_param = make::type_param(make::name(name), bounds).clone_for_update();
+            fn_.get_or_create_generic_param_list().add_generic_param(type_param.into());
+        },
+    )
+}
+
+enum Bound {
+    Trait(hir::Trait),
+    /// The type is used through methods of the slice it dereferences to.
+    Slice(hir::Type),
+}
+
+/// The bound the type needs to support this use of a parameter, if any.
+fn usage_bound(
+    ctx: &AssistContext,
+    ty: &hir::Type,
+    usage: &ast::PathExpr,
+    locals: &[hir::Local],
+) -> Option<Bound> {
+    let db = ctx.db();
+    let parent = usage.syntax().parent()?;
+    if let Some(call) = ast::MethodCallExpr::cast(parent.clone()) {
+        if call.receiver()?.syntax() != usage.syntax() {
+            return None;
+        }
+        let method = ctx.sema.resolve_method_call(&call)?;
+        let impl_ = match method.as_assoc_item(db)?.container(db) {
+            AssocItemContainer::Trait(it) => return Some(Bound::Trait(it)),
+            AssocItemContainer::Impl(it) => it,
+        };
+        if let Some(trait_) = impl_.trait_(db) {
+            return Some(Bound::Trait(trait_));
+        }
+        impl_.self_ty(db).as_slice_item_type()?;
+        return ty.autoderef(db).find_map(|it| it.as_slice_item_type()).map(Bound::Slice);
+    }
+
+    // Comparisons between two of the parameters.
+    let bin_expr = ast::BinExpr::cast(parent)?;
+    let other = if bin_expr.lhs()?.syntax() == usage.syntax() {
+        bin_expr.rhs()?
+    } else {
+        bin_expr.lhs()?
+    };
+    match other {
+        ast::Expr::PathExpr(it) if is_usage_of(ctx, &it, locals) => (),
+        _ => return None,
+    }
+    let krate = ctx.sema.scope(usage.syntax()).module()?.krate();
+    let famous_defs = FamousDefs(&ctx.sema, Some(krate));
+    let trait_ = match bin_expr.op_kind()? {
+        ast::BinaryOp::CmpOp(ast::CmpOp::Eq { .. }) => famous_defs.core_cmp_PartialEq()?,
+        ast::BinaryOp::CmpOp(ast::CmpOp::Ord { .. }) => famous_defs.core_cmp_PartialOrd()?,
+        _ => return None,
+    };
+    Some(Bound::Trait(trait_))
+}
+
+fn is_usage_of(ctx: &AssistContext, expr: &ast::PathExpr, locals: &[hir::Local]) -> bool {
+    match expr.path().and_then(|it| ctx.sema.resolve_path(&it)) {
+        Some(PathResolution::Local(it)) => locals.contains(&it),
+        _ => false,
+    }
+}
+
+fn strip_reference(ty: ast::Type) -> ast::Type {
+    match &ty {
+        ast::Type::RefType(it) => it.ty().unwrap_or(ty),
+        _ => ty,
+    }
+}
+
+fn generic_param_list(node: &syntax::SyntaxNode) -> Option<ast::GenericParamList> {
+    ast::Fn::cast(node.clone())
+        .and_then(|it| it.generic_param_list())
+        .or_else(|| ast::Impl::cast(node.clone()).and_then(|it| it.generic_param_list()))
+        .or_else(|| ast::Trait::cast(node.clone()).and_then(|it| it.generic_param_list()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn introduce_for_vec() {
+        check_assist(
+            introduce_type_parameter,
+            r#"
+//- minicore: slice, deref, as_ref
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+fn longest<'a>(a: &'a Vec<u32>, b: &'a $0Vec<u32>) -> usize {
+    if a.len() > b.len() { a.len() } else { b.len() }
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] { loop {} }
+}
+fn longest<'a, T: AsRef<[u32]>>(a: &'a T, b: &'a T) -> usize {
+    if a.as_ref().len() > b.as_ref().len() { a.as_ref().len() } else { b.as_ref().len() }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_for_hash_map() {
+        check_assist(
+            introduce_type_parameter,
+            r#"
+//- minicore: eq, clone
+struct HashMap<K, V>(K, V);
+impl<K, V> Clone for HashMap<K, V> {
+    fn clone(&self) -> Self { loop {} }
+}
+fn pick(a: HashMap<$0u8, u32>, b: HashMap<u8, u32>) {
+    if a != b {
+        let _ = b.clone();
+    }
+}
+"#,
+            r#"
+struct HashMap<K, V>(K, V);
+impl<K, V> Clone for HashMap<K, V> {
+    fn clone(&self) -> Self { loop {} }
+}
+fn pick<T: PartialEq + Clone>(a: T, b: T) {
+    if a != b {
+        let _ = b.clone();
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn introduce_for_user_type() {
+        check_assist(
+            introduce_type_parameter,
+            r#"
+trait Shape {
+    fn area(&self) -> f64;
+}
+struct Circle;
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 }
+}
+impl Circle {
+    fn larger<T>(&self, a: &Circle, b: &mut $0Circle, _: Circle) -> f64 {
+        a.area().max(b.area())
+    }
+}
+"#,
+            r#"
+trait Shape {
+    fn area(&self) -> f64;
+}
+struct Circle;
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 }
+}
+impl Circle {
+    fn larger<T, U: Shape>(&self, a: &U, b: &mut U, _: U) -> f64 {
+        a.area().max(b.area())
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_inherent_methods() {
+        cov_mark::check!(introduce_type_parameter_unsupported_usage);
+        check_assist_not_applicable(
+            introduce_type_parameter,
+            r#"
+struct Circle;
+impl Circle {
+    fn radius(&self) -> f64 { 1.0 }
+}
+fn sum(a: &$0Circle, b: &Circle) -> f64 {
+    a.radius() + b.radius()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_single_use() {
+        check_assist_not_applicable(
+            introduce_type_parameter,
+            r#"
+struct Circle;
+fn draw(a: &$0Circle, scale: u32) {}
+"#,
+        );
+    }
+}
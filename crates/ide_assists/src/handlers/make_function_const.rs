@@ -0,0 +1,337 @@
+use hir::{HasAttrs, ModuleDef, PathResolution};
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: make_function_const
+//
+// Makes a function `const` if its body looks evaluable at compile time. The
+// check is conservative, but not exhaustive, so the result has to be verified.
+//
+// ```
+// fn $0area(width: u32, height: u32) -> u32 {
+//     width * height
+// }
+// ```
+// ->
+// ```
+// const fn area(width: u32, height: u32) -> u32 {
+//     width * height
+// }
+// ```
+pub(crate) fn make_function_const(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let fn_ = ctx.find_node_at_offset::<ast::Fn>()?;
+    let body = fn_.body()?;
+    if ctx.offset() > body.syntax().text_range().start() {
+        return None;
+    }
+    if fn_.const_token().is_some() || fn_.async_token().is_some() || fn_.abi().is_some() {
+        return None;
+    }
+    // Trait methods can't be `const`.
+    let in_trait = fn_.syntax().ancestors().nth(2).map_or(false, |it| {
+        ast::Trait::can_cast(it.kind())
+            || ast::Impl::cast(it).map_or(false, |it| it.trait_().is_some())
+    });
+    if in_trait {
+        return None;
+    }
+    if !body.syntax().descendants().filter_map(ast::Expr::cast).all(|it| is_const_expr(ctx, &it)) {
+        cov_mark::hit!(make_function_const_non_const_body);
+        return None;
+    }
+    // Running destructors isn't allowed in a `const fn`.
+    let param_tys =
+        fn_.param_list()?.params().map(|it| Some(ctx.sema.type_of_pat(&it.pat()?)?.original));
+    let self_ty = fn_.param_list()?.self_param().and_then(|it| ctx.sema.to_def(&it));
+    let local_tys = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .map(|it| Some(ctx.sema.to_def(&it)?.ty(ctx.db())));
+    let mut tys = param_tys.chain(Some(self_ty.map(|it| it.ty(ctx.db())))).chain(local_tys);
+    if tys.any(|ty| ty.map_or(false, |it| needs_drop(ctx, &it))) {
+        cov_mark::hit!(make_function_const_needs_drop);
+        return None;
+    }
+
+    let insert_before = fn_.unsafe_token().or_else(|| fn_.fn_token())?;
+    let target = fn_.syntax().text_range();
+    acc.add(
+        AssistId("make_function_const", AssistKind::RefactorRewrite),
+        "Make function `const` (manual verification required)",
+        target,
+        |builder| builder.insert(insert_before.text_range().start(), "const "),
+    )
+}
+
+/// Whether the expression, not looking at its subexpressions, can be evaluated
+/// in a `const fn`.
+fn is_const_expr(ctx: &AssistContext, expr: &ast::Expr) -> bool {
+    let db = ctx.db();
+    if let Some(ty) = ctx.sema.type_of_expr(expr).map(|it| it.original) {
+        let ty = ty.remove_ref().unwrap_or(ty);
+        if ty.is_raw_ptr() || ty.as_dyn_trait().is_some() {
+            return false;
+        }
+    }
+    // Operators on other types go through (non-const) trait impls.
+    let is_builtin = |expr: Option<ast::Expr>| {
+        expr.and_then(|it| ctx.sema.type_of_expr(&it))
+            .map_or(false, |it| it.original.as_builtin().is_some())
+    };
+    match expr {
+        ast::Expr::CallExpr(call) => {
+            let path = match call.expr() {
+                Some(ast::Expr::PathExpr(it)) => it.path(),
+                _ => None,
+            };
+            match path.and_then(|it| ctx.sema.resolve_path(&it)) {
+                Some(PathResolution::Def(ModuleDef::Function(it))) => it.is_const(db),
+                Some(PathResolution::Def(ModuleDef::Adt(_)))
+                | Some(PathResolution::Def(ModuleDef::Variant(_))) => true,
+                _ => false,
+            }
+        }
+        ast::Expr::MethodCallExpr(call) => {
+            ctx.sema.resolve_method_call(call).map_or(false, |it| it.is_const(db))
+        }
+        ast::Expr::BinExpr(bin) => match bin.op_kind() {
+            Some(ast::BinaryOp::Assignment { op: None }) => true,
+            _ => is_builtin(bin.lhs()),
+        },
+        ast::Expr::PrefixExpr(prefix) => match prefix.op_kind() {
+            // Dereferencing anything but a reference or a pointer calls `Deref::deref`.
+            Some(ast::UnaryOp::Deref) => prefix
+                .expr()
+                .and_then(|it| ctx.sema.type_of_expr(&it))
+                .map_or(false, |it| it.original.is_reference() || it.original.is_raw_ptr()),
+            _ => is_builtin(prefix.expr()),
+        },
+        ast::Expr::IndexExpr(index) => index
+            .base()
+            .and_then(|it| ctx.sema.type_of_expr(&it))
+            .map(|it| it.original)
+            .map(|it| it.remove_ref().unwrap_or(it))
+            .map_or(false, |it| it.as_slice_item_type().is_some()),
+        ast::Expr::AwaitExpr(_)
+        | ast::Expr::BoxExpr(_)
+        | ast::Expr::ClosureExpr(_)
+        | ast::Expr::ForExpr(_)
+        | ast::Expr::MacroCall(_)
+        | ast::Expr::TryExpr(_) => false,
+        _ => true,
+    }
+}
+
+/// Whether dropping a value of `ty` may run a destructor. Types that can't be
+/// inspected are assumed to need one.
+fn needs_drop(ctx: &AssistContext, ty: &hir::Type) -> bool {
+    let db = ctx.db();
+    if ty.is_copy(db) || ty.is_reference() || ty.is_raw_ptr() || ty.as_builtin().is_some() {
+        return false;
+    }
+    if let Some(item) = ty.as_slice_item_type() {
+        return needs_drop(ctx, &item);
+    }
+    let tuple_fields = ty.tuple_fields(db);
+    if !tuple_fields.is_empty() || ty.is_unit() {
+        return tuple_fields.iter().any(|it| needs_drop(ctx, it));
+    }
+    match ty.as_adt() {
+        Some(hir::Adt::Struct(_)) => {
+            let has_drop_impl = hir::Impl::all_for_type(db, ty.clone()).into_iter().any(|it| {
+                it.trait_(db).map_or(false, |it| {
+                    it.attrs(db).by_key("lang").string_value().map_or(false, |it| it == "drop")
+                })
+            });
+            has_drop_impl || ty.fields(db).iter().any(|(_, ty)| needs_drop(ctx, ty))
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn make_arithmetic_const() {
+        check_assist(
+            make_function_const,
+            r#"
+pub unsafe fn $0clamp_add(a: i32, b: i32, max: i32) -> i32 {
+    let mut sum = a + b;
+    if sum > max {
+        sum = max;
+    }
+    -sum
+}
+"#,
+            r#"
+pub const unsafe fn clamp_add(a: i32, b: i32, max: i32) -> i32 {
+    let mut sum = a + b;
+    if sum > max {
+        sum = max;
+    }
+    -sum
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn make_const_with_const_callees() {
+        check_assist(
+            make_function_const,
+            r#"
+struct Point(u32, u32);
+impl Point {
+    const fn x(&self) -> u32 { self.0 }
+}
+const fn square(x: u32) -> u32 { x * x }
+
+fn $0norm(p: &Point, coords: [u32; 2]) -> Point {
+    Point(square(p.x()), coords[1])
+}
+"#,
+            r#"
+struct Point(u32, u32);
+impl Point {
+    const fn x(&self) -> u32 { self.0 }
+}
+const fn square(x: u32) -> u32 { x * x }
+
+const fn norm(p: &Point, coords: [u32; 2]) -> Point {
+    Point(square(p.x()), coords[1])
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_non_const_calls() {
+        cov_mark::check!(make_function_const_non_const_body);
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+fn now() -> u64 { 0 }
+fn $0elapsed(start: u64) -> u64 {
+    now() - start
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_dyn_or_raw_pointers() {
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+trait Shape { fn area(&self) -> u32; }
+fn $0area(s: &dyn Shape) -> u32 {
+    s.area()
+}
+"#,
+        );
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+fn $0first(p: *const u8) -> u8 {
+    unsafe { *p }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_overloaded_operators() {
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+//- minicore: add
+struct V(u32);
+impl core::ops::Add for V {
+    type Output = V;
+    fn add(self, rhs: V) -> V { V(self.0 + rhs.0) }
+}
+fn $0sum(a: V, b: V) -> V {
+    a + b
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_overloaded_deref() {
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+//- minicore: deref
+struct Wrapper(u32);
+impl core::ops::Deref for Wrapper {
+    type Target = u32;
+    fn deref(&self) -> &u32 { &self.0 }
+}
+fn $0get(w: &Wrapper) -> u32 {
+    **w
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_values_are_dropped() {
+        cov_mark::check!(make_function_const_needs_drop);
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+#[lang = "drop"]
+trait Drop { fn drop(&mut self); }
+struct Buf(*mut u8);
+impl Drop for Buf {
+    fn drop(&mut self) {}
+}
+struct Named { buf: Buf, len: usize }
+fn $0len(name: Named) -> usize {
+    name.len
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_generic_locals() {
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+fn $0second<T>(pair: (T, T)) -> u32 {
+    let (_a, _b) = pair;
+    0
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_const_or_trait_fns() {
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+const fn $0one() -> u32 { 1 }
+"#,
+        );
+        check_assist_not_applicable(
+            make_function_const,
+            r#"
+trait T { fn one() -> u32; }
+struct S;
+impl T for S {
+    fn $0one() -> u32 { 1 }
+}
+"#,
+        );
+    }
+}
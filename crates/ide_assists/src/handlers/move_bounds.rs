@@ -109,4 +109,13 @@ mod tests {
             r#"struct Pair<T>(T, T) where T: u32;"#,
         );
     }
+
+    #[test]
+    fn move_multiple_bounds_to_where_clause() {
+        check_assist(
+            move_bounds_to_where_clause,
+            r#"fn foo<$0T: Clone + Send, U>() {}"#,
+            r#"fn foo<T, U>() where T: Clone + Send {}"#,
+        );
+    }
 }
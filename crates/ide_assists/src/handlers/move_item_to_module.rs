@@ -0,0 +1,339 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::{base_db::AnchoredPathBuf, defs::Definition};
+use itertools::Itertools;
+use stdx::{format_to, to_lower_snake_case};
+use syntax::{
+    ast::{self, AstNode, HasName, HasVisibility},
+    SyntaxKind::{ATTR, COMMENT, SOURCE_FILE, WHITESPACE},
+    SyntaxNode, TextRange, TextSize,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: move_item_to_module
+//
+// Moves a top-level item to a new module file, importing the names it uses
+// from the current module.
+//
+// ```
+// struct Config;
+//
+// pub struct $0Server {
+//     config: Config,
+// }
+// ```
+// ->
+// ```
+// struct Config;
+//
+// pub mod server;
+// pub use server::Server;
+// ```
+pub(crate) fn move_item_to_module(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let item = ctx.find_node_at_offset::<ast::Item>()?;
+    if item.syntax().parent()?.kind() != SOURCE_FILE {
+        return None;
+    }
+    let (name, visibility, def): (_, _, ModuleDef) = match &item {
+        ast::Item::Struct(it) => (it.name()?, it.visibility(), ctx.sema.to_def(it)?.into()),
+        ast::Item::Enum(it) => (it.name()?, it.visibility(), ctx.sema.to_def(it)?.into()),
+        ast::Item::Fn(it) => (it.name()?, it.visibility(), ctx.sema.to_def(it)?.into()),
+        ast::Item::Trait(it) => (it.name()?, it.visibility(), ctx.sema.to_def(it)?.into()),
+        _ => return None,
+    };
+    // Only offered on the item header, up to its name.
+    let header_start = content_start(item.syntax())?;
+    let target = TextRange::new(header_start, name.syntax().text_range().end());
+    if !target.contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+
+    let db = ctx.db();
+    let module = ctx.sema.scope(item.syntax()).module()?;
+    let mod_name = to_lower_snake_case(&name.text());
+    if module.children(db).any(|it| it.name(db).map_or(false, |it| it.to_string() == mod_name)) {
+        cov_mark::hit!(move_item_module_exists);
+        return None;
+    }
+
+    // Names from the current module have to be imported in the new one.
+    let item_name = name.to_string();
+    let module_names =
+        module.scope(db, None).into_iter().map(|(name, _)| name.to_string()).collect::<Vec<_>>();
+    let imports = item
+        .syntax()
+        .descendants()
+        .filter_map(ast::Path::cast)
+        .filter(|path| path.qualifier().is_none())
+        .filter_map(|path| {
+            let name = match path.segment()?.kind()? {
+                ast::PathSegmentKind::Name(it) => it.to_string(),
+                _ => return None,
+            };
+            match ctx.sema.resolve_path(&path)? {
+                PathResolution::Def(_) | PathResolution::Macro(_) => {}
+                _ => return None,
+            }
+            (name != mod_name && name != item_name && module_names.contains(&name)).then(|| name)
+        })
+        .sorted()
+        .dedup()
+        .collect::<Vec<_>>();
+
+    let is_used_elsewhere =
+        Definition::ModuleDef(def).usages(&ctx.sema).all().iter().any(|(&file_id, refs)| {
+            file_id != ctx.frange.file_id
+                || refs.iter().any(|it| !item.syntax().text_range().contains_range(it.range))
+        });
+    let vis = visibility.as_ref().map_or(String::new(), |it| format!("{} ", it));
+
+    // Private fields of a struct have to stay visible to the current module.
+    let private_field_offsets = match &item {
+        ast::Item::Struct(strukt) => match strukt.field_list() {
+            Some(ast::FieldList::RecordFieldList(it)) => it
+                .fields()
+                .filter(|it| it.visibility().is_none())
+                .filter_map(|it| content_start(it.syntax()))
+                .collect(),
+            Some(ast::FieldList::TupleFieldList(it)) => it
+                .fields()
+                .filter(|it| it.visibility().is_none())
+                .filter_map(|it| content_start(it.syntax()))
+                .collect(),
+            None => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+
+    acc.add(
+        AssistId("move_item_to_module", AssistKind::RefactorExtract),
+        format!("Move `{}` to module `{}`", name, mod_name),
+        target,
+        |builder| {
+            let path = {
+                let mut buf = String::from("./");
+                match module.name(db) {
+                    Some(name) if !module.is_mod_rs(db) => format_to!(buf, "{}/", name),
+                    _ => (),
+                }
+                format_to!(buf, "{}.rs", mod_name);
+                buf
+            };
+
+            let mut contents = String::new();
+            for name in &imports {
+                format_to!(contents, "use super::{};\n", name);
+            }
+            if !imports.is_empty() {
+                contents.push('\n');
+            }
+            // The parent module has to be able to see the item and its fields.
+            let mut item_text = item.syntax().to_string();
+            let pub_super_offsets = visibility
+                .is_none()
+                .then(|| header_start)
+                .into_iter()
+                .chain(private_field_offsets.iter().copied());
+            for offset in pub_super_offsets.sorted().rev() {
+                let offset = usize::from(offset - item.syntax().text_range().start());
+                item_text.insert_str(offset, "pub(super) ");
+            }
+            contents.push_str(&item_text);
+            contents.push('\n');
+
+            let mut replacement = format!("{}mod {};", vis, mod_name);
+            if visibility.is_some() || is_used_elsewhere {
+                format_to!(replacement, "\n{}use {}::{};", vis, mod_name, name);
+            }
+            builder.replace(item.syntax().text_range(), replacement);
+
+            let dst = AnchoredPathBuf { anchor: ctx.frange.file_id, path };
+            builder.create_file(dst, contents);
+        },
+    )
+}
+
+/// Returns the start of `node` after its attributes and doc comments.
+fn content_start(node: &SyntaxNode) -> Option<TextSize> {
+    let first =
+        node.children_with_tokens().find(|it| !matches!(it.kind(), ATTR | COMMENT | WHITESPACE))?;
+    Some(first.text_range().start())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_private_fn_with_dependencies() {
+        check_assist(
+            move_item_to_module,
+            r#"
+//- /main.rs crate:main deps:std
+use std::fmt;
+
+const LIMIT: usize = 8;
+struct Token;
+
+/// Parses things.
+fn $0parse_all(input: &str) -> Vec<Token> {
+    let limit = LIMIT;
+    let _: fmt::Result = Ok(());
+    Vec::new()
+}
+
+fn main() {
+    parse_all("");
+}
+//- /std.rs crate:std
+pub mod fmt { pub type Result = (); }
+"#,
+            r#"
+//- /main.rs
+use std::fmt;
+
+const LIMIT: usize = 8;
+struct Token;
+
+mod parse_all;
+use parse_all::parse_all;
+
+fn main() {
+    parse_all("");
+}
+//- /parse_all.rs
+use super::LIMIT;
+use super::Token;
+use super::fmt;
+
+/// Parses things.
+pub(super) fn parse_all(input: &str) -> Vec<Token> {
+    let limit = LIMIT;
+    let _: fmt::Result = Ok(());
+    Vec::new()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_tuple_struct_keeps_fields_visible() {
+        check_assist(
+            move_item_to_module,
+            r#"
+//- /main.rs
+struct $0Meters(
+    /// The value.
+    f64,
+    pub u8,
+);
+fn main() {
+    let m = Meters(1.0, 0);
+    let _ = m.0;
+}
+"#,
+            r#"
+//- /main.rs
+mod meters;
+use meters::Meters;
+fn main() {
+    let m = Meters(1.0, 0);
+    let _ = m.0;
+}
+//- /meters.rs
+pub(super) struct Meters(
+    /// The value.
+    pub(super) f64,
+    pub u8,
+);
+"#,
+        );
+    }
+
+    #[test]
+    fn move_pub_struct_from_submodule() {
+        check_assist(
+            move_item_to_module,
+            r#"
+//- /lib.rs
+pub mod net;
+//- /net.rs
+#[derive(Debug)]
+pub struct $0HttpClient {
+    retries: u32,
+}
+"#,
+            r#"
+//- /net.rs
+pub mod http_client;
+pub use http_client::HttpClient;
+//- /net/http_client.rs
+#[derive(Debug)]
+pub struct HttpClient {
+    pub(super) retries: u32,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_unused_private_trait() {
+        check_assist(
+            move_item_to_module,
+            r#"
+//- /lib.rs
+trait $0Visitor {
+    fn visit(&self);
+}
+"#,
+            r#"
+//- /lib.rs
+mod visitor;
+//- /visitor.rs
+pub(super) trait Visitor {
+    fn visit(&self);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_item_body() {
+        check_assist_not_applicable(
+            move_item_to_module,
+            r#"
+struct Foo {
+    $0field: u32,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_nested_item() {
+        check_assist_not_applicable(
+            move_item_to_module,
+            r#"
+mod m {
+    struct $0Foo;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_module_exists() {
+        cov_mark::check!(move_item_module_exists);
+        check_assist_not_applicable(
+            move_item_to_module,
+            r#"
+//- /lib.rs
+mod foo;
+fn $0foo() {}
+//- /foo.rs
+"#,
+        );
+    }
+}
@@ -1,6 +1,7 @@
+use hir::PathResolution;
 use syntax::{
     ast::{self, make},
-    ted, AstNode,
+    ted, AstNode, SyntaxElement,
 };
 
 use crate::{
@@ -35,6 +36,30 @@ use crate::{
 //     };
 // }
 // ```
+//
+// When the variable is declared right before the `if` or `match` without an initializer,
+// the assignment is merged into the declaration.
+//
+// ```
+// fn main() {
+//     let foo;
+//     if true {
+//         $0foo = 5;
+//     } else {
+//         foo = 4;
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let foo = if true {
+//         5
+//     } else {
+//         4
+//     };
+// }
+// ```
 pub(crate) fn pull_assignment_up(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
     let assign_expr = ctx.find_node_at_offset::<ast::BinExpr>()?;
 
@@ -66,11 +91,14 @@ pub(crate) fn pull_assignment_up(acc: &mut Assists, ctx: &AssistContext) -> Opti
         }
     }
 
+    let uninit_decl = uninitialized_declaration(&ctx.sema, &tgt, &collector);
+
     acc.add(
         AssistId("pull_assignment_up", AssistKind::RefactorExtract),
         "Pull assignment up",
         tgt.syntax().text_range(),
         move |edit| {
+            let uninit_decl = uninit_decl.map(|(let_stmt, pat)| (edit.make_mut(let_stmt), pat));
             let assignments: Vec<_> = collector
                 .assignments
                 .into_iter()
@@ -88,6 +116,26 @@ pub(crate) fn pull_assignment_up(acc: &mut Assists, ctx: &AssistContext) -> Opti
                 }
                 ted::replace(stmt, rhs.syntax());
             }
+
+            if let Some((let_stmt, pat)) = uninit_decl {
+                cov_mark::hit!(test_pull_assignment_up_into_let);
+                let new_let = make::let_stmt(pat, let_stmt.ty(), Some(tgt.clone()));
+                let stmt =
+                    match tgt.syntax().parent().filter(|it| ast::ExprStmt::can_cast(it.kind())) {
+                        Some(stmt) => stmt,
+                        None => tgt.syntax().clone(),
+                    };
+                ted::replace(stmt, new_let.syntax().clone_for_update());
+
+                let let_stmt = let_stmt.syntax().clone();
+                let last: SyntaxElement = match let_stmt.next_sibling_or_token() {
+                    Some(ws) if ws.kind() == syntax::SyntaxKind::WHITESPACE => ws,
+                    _ => let_stmt.clone().into(),
+                };
+                ted::remove_all(let_stmt.into()..=last);
+                return;
+            }
+
             let assign_expr = make::expr_assignment(collector.common_lhs, tgt.clone());
             let assign_stmt = make::expr_stmt(assign_expr);
 
@@ -150,6 +198,54 @@ impl<'a> AssignmentsCollector<'a> {
     }
 }
 
+/// Finds a `let x;` declaration directly preceding `tgt` which can absorb the pulled-up
+/// assignment, that is `x` is only ever assigned to, never read, inside of `tgt`.
+fn uninitialized_declaration(
+    sema: &hir::Semantics<ide_db::RootDatabase>,
+    tgt: &ast::Expr,
+    collector: &AssignmentsCollector,
+) -> Option<(ast::LetStmt, ast::Pat)> {
+    let local = match &collector.common_lhs {
+        ast::Expr::PathExpr(path) => match sema.resolve_path(&path.path()?)? {
+            PathResolution::Local(local) => local,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let stmt = match tgt.syntax().parent().filter(|it| ast::ExprStmt::can_cast(it.kind())) {
+        Some(stmt) => stmt,
+        None => tgt.syntax().clone(),
+    };
+    let let_stmt = ast::LetStmt::cast(stmt.prev_sibling()?)?;
+    if let_stmt.initializer().is_some() || let_stmt.let_else().is_some() {
+        return None;
+    }
+    let pat = match let_stmt.pat()? {
+        ast::Pat::IdentPat(pat) => pat,
+        _ => return None,
+    };
+    if sema.to_def(&pat)? != local {
+        return None;
+    }
+
+    let is_read_in_branches =
+        tgt.syntax().descendants().filter_map(ast::PathExpr::cast).any(|it| {
+            let is_assigned = collector.assignments.iter().any(|(assign, _)| {
+                assign.lhs().map(|lhs| lhs.syntax() == it.syntax()).unwrap_or(false)
+            });
+            !is_assigned
+                && it.path().and_then(|path| sema.resolve_path(&path))
+                    == Some(PathResolution::Local(local))
+        });
+    if is_read_in_branches {
+        cov_mark::hit!(test_pull_assignment_up_into_let_read_in_branch);
+        return None;
+    }
+
+    Some((let_stmt, pat.into()))
+}
+
 fn is_equivalent(
     sema: &hir::Semantics<ide_db::RootDatabase>,
     expr0: &ast::Expr,
@@ -485,6 +581,125 @@ fn foo() {
         )
     }
 
+    #[test]
+    fn test_pull_assignment_up_into_let() {
+        cov_mark::check!(test_pull_assignment_up_into_let);
+        check_assist(
+            pull_assignment_up,
+            r#"
+fn foo() {
+    let a;
+    if true {
+        $0a = 2;
+    } else {
+        a = 3;
+    }
+}"#,
+            r#"
+fn foo() {
+    let a = if true {
+        2
+    } else {
+        3
+    };
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_pull_assignment_up_into_let_chained_if() {
+        check_assist(
+            pull_assignment_up,
+            r#"
+fn foo() {
+    let a: i32;
+    if true {
+        $0a = 2;
+    } else if false {
+        a = 3;
+    } else {
+        a = 4;
+    }
+}"#,
+            r#"
+fn foo() {
+    let a: i32 = if true {
+        2
+    } else if false {
+        3
+    } else {
+        4
+    };
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_pull_assignment_up_into_let_match() {
+        check_assist(
+            pull_assignment_up,
+            r#"
+fn foo() {
+    let a;
+    match 1 {
+        1 => $0a = 2,
+        _ => a = 3,
+    }
+}"#,
+            r#"
+fn foo() {
+    let a = match 1 {
+        1 => 2,
+        _ => 3,
+    };
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_pull_assignment_up_into_let_single_branch_not_applicable() {
+        check_assist_not_applicable(
+            pull_assignment_up,
+            r#"
+fn foo() {
+    let a;
+    if true {
+        $0a = 2;
+    }
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_pull_assignment_up_into_let_read_in_branch() {
+        cov_mark::check!(test_pull_assignment_up_into_let_read_in_branch);
+        check_assist(
+            pull_assignment_up,
+            r#"
+fn foo() {
+    let a;
+    if true {
+        $0a = 2;
+    } else {
+        a = 3;
+        let b = a;
+        a = b;
+    }
+}"#,
+            r#"
+fn foo() {
+    let a;
+    a = if true {
+        2
+    } else {
+        a = 3;
+        let b = a;
+        b
+    };
+}"#,
+        );
+    }
+
     #[test]
     fn test_cant_pull_non_assignments() {
         cov_mark::check!(test_cant_pull_non_assignments);
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use syntax::{
     ast::{self, AstNode, AstToken},
-    match_ast, NodeOrToken, SyntaxElement, TextSize, T,
+    match_ast, NodeOrToken, SyntaxElement, SyntaxNode, TextRange, TextSize, T,
 };
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
@@ -12,13 +12,13 @@ use crate::{AssistContext, AssistId, AssistKind, Assists};
 //
 // ```
 // fn main() {
-//     $0dbg!(92);
+//     let x = $0dbg!(42 * 2);
 // }
 // ```
 // ->
 // ```
 // fn main() {
-//     92;
+//     let x = 42 * 2;
 // }
 // ```
 pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
@@ -41,30 +41,27 @@ pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
         .ok()?;
 
     let parent = macro_call.syntax().parent()?;
+    let is_stmt = match_ast! {
+        match parent {
+            ast::ExprStmt(__) => true,
+            ast::StmtList(__) => macro_call.semicolon_token().is_some(),
+            _ => false,
+        }
+    };
     let (range, text) = match &*input_expressions {
         // dbg!()
-        [] => {
-            match_ast! {
-                match parent {
-                    ast::StmtList(__) => {
-                        let range = macro_call.syntax().text_range();
-                        let range = match whitespace_start(macro_call.syntax().prev_sibling_or_token()) {
-                            Some(start) => range.cover_offset(start),
-                            None => range,
-                        };
-                        (range, String::new())
-                    },
-                    ast::ExprStmt(it) => {
-                        let range = it.syntax().text_range();
-                        let range = match whitespace_start(it.syntax().prev_sibling_or_token()) {
-                            Some(start) => range.cover_offset(start),
-                            None => range,
-                        };
-                        (range, String::new())
-                    },
-                    _ => (macro_call.syntax().text_range(), "()".to_owned())
-                }
+        [] => match_ast! {
+            match parent {
+                ast::StmtList(__) => (stmt_range(macro_call.syntax()), String::new()),
+                ast::ExprStmt(it) => (stmt_range(it.syntax()), String::new()),
+                _ => (macro_call.syntax().text_range(), "()".to_owned())
             }
+        },
+        // dbg!(expr0, ...); where evaluating the arguments has no side effects
+        exprs if is_stmt && exprs.iter().all(is_side_effect_free) => {
+            cov_mark::hit!(remove_dbg_statement);
+            let stmt = ast::ExprStmt::cast(parent.clone()).map(|it| it.syntax().clone());
+            (stmt_range(stmt.as_ref().unwrap_or(macro_call.syntax())), String::new())
         }
         // dbg!(expr0)
         [expr] => {
@@ -114,6 +111,26 @@ pub(crate) fn remove_dbg(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
     })
 }
 
+/// The range of the statement `stmt`, including the whitespace preceding it.
+fn stmt_range(stmt: &SyntaxNode) -> TextRange {
+    let range = stmt.text_range();
+    match whitespace_start(stmt.prev_sibling_or_token()) {
+        Some(start) => range.cover_offset(start),
+        None => range,
+    }
+}
+
+fn is_side_effect_free(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Literal(_) | ast::Expr::PathExpr(_) => true,
+        ast::Expr::FieldExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        ast::Expr::RefExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        ast::Expr::ParenExpr(it) => it.expr().map_or(false, |it| is_side_effect_free(&it)),
+        ast::Expr::TupleExpr(it) => it.fields().all(|it| is_side_effect_free(&it)),
+        _ => false,
+    }
+}
+
 fn whitespace_start(it: Option<SyntaxElement>) -> Option<TextSize> {
     Some(it?.into_token().and_then(ast::Whitespace::cast)?.syntax().text_range().start())
 }
@@ -238,4 +255,42 @@ fn foo() {
         check(r#"$0dbg!(0, 1)"#, r#"(0, 1)"#);
         check(r#"$0dbg!(0, (1, 2))"#, r#"(0, (1, 2))"#);
     }
+
+    #[test]
+    fn test_remove_dbg_statement() {
+        cov_mark::check!(remove_dbg_statement);
+        check_assist(
+            remove_dbg,
+            r#"
+fn foo(x: (u32, u32)) {
+    let y = 92;
+    $0dbg!(x.0, &y);
+    y;
+}
+"#,
+            r#"
+fn foo(x: (u32, u32)) {
+    let y = 92;
+    y;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_remove_dbg_statement_with_side_effects() {
+        check_assist(
+            remove_dbg,
+            r#"
+fn foo() {
+    $0dbg!(bar());
+}
+"#,
+            r#"
+fn foo() {
+    bar();
+}
+"#,
+        );
+    }
 }
@@ -0,0 +1,370 @@
+use ide_db::helpers::FamousDefs;
+use itertools::Itertools;
+use syntax::{
+    algo::least_common_ancestor,
+    ast::{self, AstNode, BinaryOp, CmpOp, LogicOp},
+    NodeOrToken, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: replace_assert_with_assert_eq
+//
+// Replaces `assert!` of an (in)equality with `assert_eq!` or `assert_ne!`,
+// which print both operands when the assertion fails.
+//
+// ```
+// # //- minicore: fmt
+// # #[rustc_builtin_macro]
+// # macro_rules! assert { ($($arg:tt)*) => {} }
+// # impl core::fmt::Debug for i32 {
+// #     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+// # }
+// fn main() {
+//     let x = 92;
+//     $0assert!(x == 92, "wrong answer");
+// }
+// ```
+// ->
+// ```
+// # #[rustc_builtin_macro]
+// # macro_rules! assert { ($($arg:tt)*) => {} }
+// # impl core::fmt::Debug for i32 {
+// #     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+// # }
+// fn main() {
+//     let x = 92;
+//     assert_eq!(x, 92, "wrong answer");
+// }
+// ```
+pub(crate) fn replace_assert_with_assert_eq(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let macro_call = ctx.find_node_at_offset::<ast::MacroCall>()?;
+    let name_ref = macro_call.path()?.segment()?.name_ref()?;
+    let name = name_ref.text();
+    if !matches!(&*name, "assert" | "debug_assert") {
+        return None;
+    }
+    let tt = macro_call.token_tree()?;
+    let args = macro_args(&tt)?;
+    let (cond, message) = args.split_first()?;
+
+    let bin_expr = match ast::Expr::parse(cond).ok()? {
+        ast::Expr::BinExpr(it) => it,
+        _ => return None,
+    };
+    let negated = match bin_expr.op_kind()? {
+        BinaryOp::CmpOp(CmpOp::Eq { negated }) => negated,
+        _ => return None,
+    };
+    let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+    // `assert_eq!` and `assert_ne!` print the operands, so they must implement `Debug`.
+    if !operands_impl_debug(ctx, &tt)? {
+        return None;
+    }
+    let new_name = format!("{}_{}", name, if negated { "ne" } else { "eq" });
+
+    let target = macro_call.syntax().text_range();
+    acc.add(
+        AssistId("replace_assert_with_assert_eq", AssistKind::RefactorRewrite),
+        format!("Replace `{}!` with `{}!`", name, new_name),
+        target,
+        |builder| {
+            let args = [lhs.to_string(), rhs.to_string()].iter().chain(message).join(", ");
+            builder.replace(name_ref.syntax().text_range(), new_name);
+            builder.replace(tt.syntax().text_range(), format!("({})", args));
+        },
+    )
+}
+
+// Assist: replace_assert_eq_with_assert
+//
+// Replaces `assert_eq!` or `assert_ne!` with an `assert!` of the (in)equality.
+//
+// ```
+// fn main() {
+//     let x = 92;
+//     $0assert_ne!(x, 0);
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let x = 92;
+//     assert!(x != 0);
+// }
+// ```
+pub(crate) fn replace_assert_eq_with_assert(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let macro_call = ctx.find_node_at_offset::<ast::MacroCall>()?;
+    let name_ref = macro_call.path()?.segment()?.name_ref()?;
+    let name = name_ref.text();
+    let (new_name, op) = match &*name {
+        "assert_eq" => ("assert", "=="),
+        "assert_ne" => ("assert", "!="),
+        "debug_assert_eq" => ("debug_assert", "=="),
+        "debug_assert_ne" => ("debug_assert", "!="),
+        _ => return None,
+    };
+    let tt = macro_call.token_tree()?;
+    let args = macro_args(&tt)?;
+    let (lhs, rhs, message) = match &*args {
+        [lhs, rhs, message @ ..] => {
+            (ast::Expr::parse(lhs).ok()?, ast::Expr::parse(rhs).ok()?, message)
+        }
+        _ => return None,
+    };
+
+    let target = macro_call.syntax().text_range();
+    acc.add(
+        AssistId("replace_assert_eq_with_assert", AssistKind::RefactorRewrite),
+        format!("Replace `{}!` with `{}!`", name, new_name),
+        target,
+        |builder| {
+            let cond = format!("{} {} {}", operand(&lhs), op, operand(&rhs));
+            let args = std::iter::once(&cond).chain(message).join(", ");
+            builder.replace(name_ref.syntax().text_range(), new_name);
+            builder.replace(tt.syntax().text_range(), format!("({})", args));
+        },
+    )
+}
+
+/// Whether both operands of the (in)equality asserted by the `assert!` with input `tt`
+/// implement `Debug`. The operands are looked up in the expansion of the macro call.
+fn operands_impl_debug(ctx: &AssistContext, tt: &ast::TokenTree) -> Option<bool> {
+    let r_delim = NodeOrToken::Token(tt.right_delimiter_token()?);
+    let mut cond = tt
+        .syntax()
+        .children_with_tokens()
+        .skip(1)
+        .take_while(|it| *it != r_delim && it.kind() != T![,])
+        .filter(|it| !it.kind().is_trivia());
+    let first = cond.next()?;
+    let last = cond.last().unwrap_or_else(|| first.clone());
+    let first = match first {
+        NodeOrToken::Node(it) => it.first_token()?,
+        NodeOrToken::Token(it) => it,
+    };
+    let last = match last {
+        NodeOrToken::Node(it) => it.last_token()?,
+        NodeOrToken::Token(it) => it,
+    };
+
+    let (first_expanded, last_expanded) = (
+        ctx.sema.descend_into_macros_single(first.clone()),
+        ctx.sema.descend_into_macros_single(last),
+    );
+    if first_expanded == first {
+        // the macro couldn't be expanded
+        return None;
+    }
+    let bin_expr = least_common_ancestor(&first_expanded.parent()?, &last_expanded.parent()?)?
+        .ancestors()
+        .find_map(ast::BinExpr::cast)?;
+    let debug_trait =
+        FamousDefs(&ctx.sema, ctx.sema.scope(bin_expr.syntax()).krate()).core_fmt_Debug()?;
+    let impls_debug = |expr: ast::Expr| {
+        ctx.sema
+            .type_of_expr(&expr)
+            .map_or(false, |ty| ty.original.impls_trait(ctx.db(), debug_trait, &[]))
+    };
+    Some(impls_debug(bin_expr.lhs()?) && impls_debug(bin_expr.rhs()?))
+}
+
+/// Splits the input of a macro call at its top-level commas.
+fn macro_args(tt: &ast::TokenTree) -> Option<Vec<String>> {
+    let r_delim = NodeOrToken::Token(tt.right_delimiter_token()?);
+    let input = tt.syntax().children_with_tokens().skip(1).take_while(|it| *it != r_delim);
+    let args = input.group_by(|it| it.kind() == T![,]);
+    let args = args
+        .into_iter()
+        .filter_map(|(is_sep, mut group)| (!is_sep).then(|| group.join("").trim().to_string()))
+        .filter(|it| !it.is_empty())
+        .collect();
+    Some(args)
+}
+
+/// Renders `expr` as an operand of `==` or `!=`, adding parentheses if needed.
+fn operand(expr: &ast::Expr) -> String {
+    let needs_parens = match expr {
+        ast::Expr::BinExpr(it) => matches!(
+            it.op_kind(),
+            Some(
+                BinaryOp::CmpOp(_)
+                    | BinaryOp::LogicOp(LogicOp::And | LogicOp::Or)
+                    | BinaryOp::Assignment { .. }
+            )
+        ),
+        ast::Expr::ClosureExpr(_) | ast::Expr::RangeExpr(_) => true,
+        _ => false,
+    };
+    if needs_parens {
+        format!("({})", expr)
+    } else {
+        expr.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn assert_eq_from_equality() {
+        check_assist(
+            replace_assert_with_assert_eq,
+            r#"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+#[derive(PartialEq)]
+struct P(u32);
+impl core::fmt::Debug for P {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(x: u32, y: u32) -> P { P(x + y) }
+fn main() {
+    assert!$0(f(1, 2) == P(3));
+}
+"#,
+            r#"
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+#[derive(PartialEq)]
+struct P(u32);
+impl core::fmt::Debug for P {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(x: u32, y: u32) -> P { P(x + y) }
+fn main() {
+    assert_eq!(f(1, 2), P(3));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assert_ne_from_inequality_with_message() {
+        check_assist(
+            replace_assert_with_assert_eq,
+            r#"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+macro_rules! debug_assert { ($($arg:tt)*) => { assert!($($arg)*) } }
+impl core::fmt::Debug for u32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(a: u32, b: u32) {
+    debug_$0assert!(a + 1 != b, "{} vs {}", a, b);
+}
+"#,
+            r#"
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+macro_rules! debug_assert { ($($arg:tt)*) => { assert!($($arg)*) } }
+impl core::fmt::Debug for u32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(a: u32, b: u32) {
+    debug_assert_ne!(a + 1, b, "{} vs {}", a, b);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_conditions() {
+        check_assist_not_applicable(
+            replace_assert_with_assert_eq,
+            r#"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+impl core::fmt::Debug for bool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(a: bool, b: bool, c: bool) {
+    assert!$0(a == b && c);
+}
+"#,
+        );
+        check_assist_not_applicable(
+            replace_assert_with_assert_eq,
+            r#"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+impl core::fmt::Debug for bool {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn f(a: bool, b: bool) {
+    assert!$0(a < b);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_debug() {
+        check_assist_not_applicable(
+            replace_assert_with_assert_eq,
+            r#"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+#[derive(PartialEq)]
+struct P(u32);
+fn f(a: P, b: P) {
+    assert!$0(a == b);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assert_from_assert_eq() {
+        check_assist(
+            replace_assert_eq_with_assert,
+            r#"
+fn main() {
+    assert_eq!$0(x, 1 + 1);
+}
+"#,
+            r#"
+fn main() {
+    assert!(x == 1 + 1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn assert_from_assert_ne_keeps_message() {
+        check_assist(
+            replace_assert_eq_with_assert,
+            r#"
+fn main() {
+    assert_ne!$0(a < b, false, "message: {}", m);
+}
+"#,
+            r#"
+fn main() {
+    assert!((a < b) != false, "message: {}", m);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_single_argument() {
+        check_assist_not_applicable(
+            replace_assert_eq_with_assert,
+            r#"
+fn main() {
+    assert_eq!$0(x);
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        HasLoopBody,
+    },
+    AstNode, TextRange, T,
+};
+
+use crate::{
+    handlers::convert_while_to_loop::is_plain_break, AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: replace_if_let_with_while_let
+//
+// Replaces a loop around an `if let` that breaks otherwise with a `while let`.
+//
+// ```
+// fn main() {
+//     loop {
+//         $0if let Some(token) = tokens.next() {
+//             process(token);
+//         } else {
+//             break;
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     while let Some(token) = tokens.next() {
+//         process(token);
+//     }
+// }
+// ```
+pub(crate) fn replace_if_let_with_while_let(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let if_kw = ctx.find_token_syntax_at_offset(T![if])?;
+    let if_expr = if_kw.parent().and_then(ast::IfExpr::cast)?;
+    let cond = if_expr.condition()?;
+    let pat = cond.pat()?;
+    let expr = cond.expr()?;
+    let then_branch = if_expr.then_branch()?;
+    match if_expr.else_branch()? {
+        ast::ElseBranch::Block(it) if is_plain_break(&it) => (),
+        _ => return None,
+    }
+
+    // The `if let` has to be the only thing in the loop.
+    let stmt_list = match if_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(stmt) => stmt.syntax().parent().and_then(ast::StmtList::cast)?,
+        None => if_expr.syntax().parent().and_then(ast::StmtList::cast)?,
+    };
+    if stmt_list.statements().count() + stmt_list.tail_expr().iter().count() != 1 {
+        return None;
+    }
+    let loop_expr = stmt_list.syntax().parent()?.parent().and_then(ast::LoopExpr::cast)?;
+    let loop_kw = loop_expr.loop_token()?;
+
+    let target = loop_expr.syntax().text_range();
+    acc.add(
+        AssistId("replace_if_let_with_while_let", AssistKind::RefactorRewrite),
+        "Replace if let with while let",
+        target,
+        |edit| {
+            let body = then_branch.dedent(IndentLevel(1));
+            let replacement = format!("while let {} = {} {}", pat, expr, body);
+            // Keep the label of the loop, if any.
+            edit.replace(TextRange::new(loop_kw.text_range().start(), target.end()), replacement)
+        },
+    )
+}
+
+// Assist: replace_while_let_with_if_let
+//
+// Replaces a `while let` with a loop around an `if let` that breaks otherwise.
+//
+// ```
+// fn main() {
+//     $0while let Some(token) = tokens.next() {
+//         process(token);
+//     }
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     loop {
+//         if let Some(token) = tokens.next() {
+//             process(token);
+//         } else {
+//             break;
+//         }
+//     }
+// }
+// ```
+pub(crate) fn replace_while_let_with_if_let(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let while_kw = ctx.find_token_syntax_at_offset(T![while])?;
+    let while_expr = while_kw.parent().and_then(ast::WhileExpr::cast)?;
+    let cond = while_expr.condition()?;
+    let pat = cond.pat()?;
+    let expr = cond.expr()?;
+    let while_body = while_expr.loop_body()?;
+
+    let target = while_expr.syntax().text_range();
+    acc.add(
+        AssistId("replace_while_let_with_if_let", AssistKind::RefactorRewrite),
+        "Replace while let with if let",
+        target,
+        |edit| {
+            let indent = IndentLevel::from_node(while_expr.syntax());
+            let body = while_body.indent(IndentLevel(1));
+            let replacement = format!(
+                "loop {{\n{1}if let {3} = {4} {5} else {{\n{2}break;\n{1}}}\n{0}}}",
+                indent,
+                indent + 1,
+                indent + 2,
+                pat,
+                expr,
+                body
+            );
+            // Keep the label of the loop, if any.
+            edit.replace(TextRange::new(while_kw.text_range().start(), target.end()), replacement)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn replace_loop_with_while_let() {
+        check_assist(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    'tokens: loop {
+        i$0f let Some((idx, token)) = iter.next() {
+            if token.is_trivia() {
+                continue 'tokens;
+            }
+            process(idx, token);
+        } else { break }
+    }
+}
+"#,
+            r#"
+fn main() {
+    'tokens: while let Some((idx, token)) = iter.next() {
+        if token.is_trivia() {
+            continue 'tokens;
+        }
+        process(idx, token);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_loop_with_while_let_in_tail() {
+        check_assist(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    let total = {
+        loop {
+            $0if let Ok(n) = read() {
+                sum += n;
+            } else {
+                break;
+            };
+        }
+        sum
+    };
+}
+"#,
+            r#"
+fn main() {
+    let total = {
+        while let Ok(n) = read() {
+            sum += n;
+        }
+        sum
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_other_statements() {
+        check_assist_not_applicable(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    loop {
+        $0if let Some(x) = iter.next() {
+            foo(x);
+        } else {
+            break;
+        }
+        bar();
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_break() {
+        check_assist_not_applicable(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    loop {
+        $0if let Some(x) = iter.next() {
+            foo(x);
+        }
+    }
+}
+"#,
+        );
+        check_assist_not_applicable(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    loop {
+        $0if let Some(x) = iter.next() {
+            foo(x);
+        } else {
+            return;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_loop() {
+        check_assist_not_applicable(
+            replace_if_let_with_while_let,
+            r#"
+fn main() {
+    for _ in 0..3 {
+        $0if let Some(x) = iter.next() {
+            foo(x);
+        } else {
+            break;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_while_let_with_loop() {
+        check_assist(
+            replace_while_let_with_if_let,
+            r#"
+fn main() {
+    'lines: $0while let Some(line) = lines.next() {
+        if line.is_empty() {
+            break 'lines;
+        }
+        print(line);
+    }
+}
+"#,
+            r#"
+fn main() {
+    'lines: loop {
+        if let Some(line) = lines.next() {
+            if line.is_empty() {
+                break 'lines;
+            }
+            print(line);
+        } else {
+            break;
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_plain_while() {
+        check_assist_not_applicable(
+            replace_while_let_with_if_let,
+            r#"
+fn main() {
+    $0while running() {
+        step();
+    }
+}
+"#,
+        );
+    }
+}
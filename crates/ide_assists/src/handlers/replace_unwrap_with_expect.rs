@@ -0,0 +1,180 @@
+use ide_db::helpers::FamousDefs;
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: replace_unwrap_with_expect
+//
+// Replaces `.unwrap()` with `.expect()`, suggesting a message describing what
+// was expected.
+//
+// ```
+// # //- minicore: option
+// fn main() {
+//     let port = Some(8080);
+//     port.unwr$0ap();
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let port = Some(8080);
+//     port.expect("${0:port should be Some}");
+// }
+// ```
+pub(crate) fn replace_unwrap_with_expect(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let call = ctx.find_node_at_offset::<ast::MethodCallExpr>()?;
+    let name_ref = call.name_ref()?;
+    if name_ref.text() != "unwrap" || call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+    if !name_ref.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let receiver = call.receiver()?;
+
+    let ty = ctx.sema.type_of_expr(&receiver)?.adjusted().strip_references().as_adt()?;
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(call.syntax()).krate());
+    let variant = if Some(ty) == famous_defs.core_option_Option().map(hir::Adt::from) {
+        "Some"
+    } else if Some(ty) == famous_defs.core_result_Result().map(hir::Adt::from) {
+        "Ok"
+    } else {
+        return None;
+    };
+    let message = match describe(&receiver) {
+        Some(subject) => format!("{} should be {}", subject, variant),
+        None => format!("value should be {}", variant),
+    };
+
+    let arg_list = call.arg_list()?;
+    let target = name_ref.syntax().text_range().cover(arg_list.syntax().text_range());
+    acc.add(
+        AssistId("replace_unwrap_with_expect", AssistKind::RefactorRewrite),
+        "Replace `unwrap` with `expect`",
+        target,
+        |builder| match ctx.config.snippet_cap {
+            Some(cap) => {
+                builder.replace_snippet(cap, target, format!("expect(\"${{0:{}}}\")", message))
+            }
+            None => builder.replace(target, format!("expect(\"{}\")", message)),
+        },
+    )
+}
+
+/// Names the value being unwrapped, e.g. `config` for `self.config()`.
+fn describe(receiver: &ast::Expr) -> Option<String> {
+    let name = match receiver {
+        ast::Expr::PathExpr(it) => it.path()?.segment()?.name_ref()?,
+        ast::Expr::MethodCallExpr(it) => it.name_ref()?,
+        ast::Expr::FieldExpr(it) => it.name_ref()?,
+        ast::Expr::CallExpr(it) => match it.expr()? {
+            ast::Expr::PathExpr(it) => it.path()?.segment()?.name_ref()?,
+            _ => return None,
+        },
+        ast::Expr::RefExpr(it) => return describe(&it.expr()?),
+        ast::Expr::ParenExpr(it) => return describe(&it.expr()?),
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn unwrap_on_variable() {
+        check_assist(
+            replace_unwrap_with_expect,
+            r#"
+//- minicore: option
+fn f(option: Option<u32>) -> u32 {
+    option.$0unwrap()
+}
+"#,
+            r#"
+fn f(option: Option<u32>) -> u32 {
+    option.expect("${0:option should be Some}")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unwrap_on_method_chain() {
+        check_assist(
+            replace_unwrap_with_expect,
+            r#"
+//- minicore: option
+struct Obj;
+impl Obj {
+    fn field(&self) -> Option<u32> { None }
+}
+fn f(obj: &Obj) -> u32 {
+    obj.field().unwrap$0()
+}
+"#,
+            r#"
+struct Obj;
+impl Obj {
+    fn field(&self) -> Option<u32> { None }
+}
+fn f(obj: &Obj) -> u32 {
+    obj.field().expect("${0:field should be Some}")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn unwrap_on_result() {
+        check_assist(
+            replace_unwrap_with_expect,
+            r#"
+//- minicore: result
+fn parse() -> Result<u32, ()> { Ok(0) }
+fn f() -> u32 {
+    parse().unwrap$0()
+}
+"#,
+            r#"
+fn parse() -> Result<u32, ()> { Ok(0) }
+fn f() -> u32 {
+    parse().expect("${0:parse should be Ok}")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_expect() {
+        check_assist_not_applicable(
+            replace_unwrap_with_expect,
+            r#"
+//- minicore: option
+fn f(option: Option<u32>) -> u32 {
+    option.$0expect("option should be Some")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_other_types() {
+        check_assist_not_applicable(
+            replace_unwrap_with_expect,
+            r#"
+struct Lock;
+impl Lock {
+    fn unwrap(self) -> u32 { 0 }
+}
+fn f(lock: Lock) -> u32 {
+    lock.$0unwrap()
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use syntax::{
+    ast::{self, ArithOp, BinaryOp, HasArgList},
+    AstNode, AstToken,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: replace_with_format_string
+//
+// Replaces a chain of string concatenations with a `format!` call.
+//
+// ```
+// fn main() {
+//     let (name, greeting) = ("world", "Hello");
+//     let s = greeting.to_string() +$0 ", " + name;
+// }
+// ```
+// ->
+// ```
+// fn main() {
+//     let (name, greeting) = ("world", "Hello");
+//     let s = format!("{greeting}, {name}");
+// }
+// ```
+pub(crate) fn replace_with_format_string(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let bin_expr = ctx.find_node_at_offset::<ast::BinExpr>()?;
+    if !is_add(&bin_expr)
+        || !bin_expr.op_token()?.text_range().contains_range(ctx.selection_trimmed())
+    {
+        return None;
+    }
+    let mut concat = bin_expr;
+    while let Some(parent) = concat.syntax().parent().and_then(ast::BinExpr::cast) {
+        if !is_add(&parent) || parent.lhs()?.syntax() != concat.syntax() {
+            break;
+        }
+        concat = parent;
+    }
+
+    let mut operands = Vec::new();
+    collect_operands(&concat, &mut operands)?;
+    if !is_string(ctx, &operands[0]) {
+        return None;
+    }
+
+    let mut template = String::new();
+    let mut args = Vec::new();
+    for operand in &operands {
+        match strip_conversions(operand) {
+            ast::Expr::Literal(lit) => match lit.kind() {
+                ast::LiteralKind::String(s) if !s.is_raw() => {
+                    let text = s.text();
+                    let contents = &text[1..text.len() - 1];
+                    template.push_str(&contents.replace('{', "{{").replace('}', "}}"));
+                }
+                _ => {
+                    template.push_str("{}");
+                    args.push(lit.to_string());
+                }
+            },
+            ast::Expr::PathExpr(path) if is_plain_name(&path) => {
+                template.push_str(&format!("{{{}}}", path));
+            }
+            expr => {
+                template.push_str("{}");
+                args.push(expr.to_string());
+            }
+        }
+    }
+
+    let target = concat.syntax().text_range();
+    acc.add(
+        AssistId("replace_with_format_string", AssistKind::RefactorRewrite),
+        "Replace string concatenation with `format!`",
+        target,
+        |builder| {
+            let args = args.iter().map(|arg| format!(", {}", arg)).collect::<String>();
+            builder.replace(target, format!("format!(\"{}\"{})", template, args));
+        },
+    )
+}
+
+fn is_add(expr: &ast::BinExpr) -> bool {
+    expr.op_kind() == Some(BinaryOp::ArithOp(ArithOp::Add))
+}
+
+/// Flattens `a + b + c`, which parses as `(a + b) + c`, into its operands.
+fn collect_operands(expr: &ast::BinExpr, acc: &mut Vec<ast::Expr>) -> Option<()> {
+    match expr.lhs()? {
+        ast::Expr::BinExpr(lhs) if is_add(&lhs) => collect_operands(&lhs, acc)?,
+        lhs => acc.push(lhs),
+    }
+    acc.push(expr.rhs()?);
+    Some(())
+}
+
+/// Whether the first operand of the concatenation is an owned `String`.
+fn is_string(ctx: &AssistContext, expr: &ast::Expr) -> bool {
+    if let ast::Expr::MethodCallExpr(call) = expr {
+        if call
+            .name_ref()
+            .map_or(false, |it| matches!(it.text().as_str(), "to_string" | "to_owned"))
+        {
+            return true;
+        }
+    }
+    let ty = ctx.sema.type_of_expr(expr).map(|it| it.original);
+    match ty.and_then(|ty| ty.as_adt()) {
+        Some(adt) => adt.name(ctx.db()).to_string() == "String",
+        None => false,
+    }
+}
+
+/// Strips the `&` and `.to_string()` around an operand, which `format!` does not need.
+fn strip_conversions(expr: &ast::Expr) -> ast::Expr {
+    let inner = match expr {
+        ast::Expr::RefExpr(it) if it.mut_token().is_none() => it.expr(),
+        ast::Expr::ParenExpr(it) => it.expr(),
+        ast::Expr::MethodCallExpr(it)
+            if it.arg_list().map_or(false, |args| args.args().next().is_none())
+                && it
+                    .name_ref()
+                    .map_or(false, |it| matches!(it.text().as_str(), "to_string" | "to_owned")) =>
+        {
+            it.receiver()
+        }
+        _ => None,
+    };
+    match inner {
+        Some(inner) => strip_conversions(&inner),
+        None => expr.clone(),
+    }
+}
+
+fn is_plain_name(path: &ast::PathExpr) -> bool {
+    match path.path() {
+        Some(path) => {
+            path.qualifier().is_none()
+                && path
+                    .segment()
+                    .map_or(false, |it| it.generic_arg_list().is_none() && it.name_ref().is_some())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn two_part_concatenation() {
+        check_assist(
+            replace_with_format_string,
+            r#"
+fn f(prefix: &str, suffix: &str) {
+    let s = prefix.to_string() $0+ &suffix;
+}
+"#,
+            r#"
+fn f(prefix: &str, suffix: &str) {
+    let s = format!("{prefix}{suffix}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn three_part_concatenation() {
+        check_assist(
+            replace_with_format_string,
+            r#"
+fn f(prefix: &str, suffix: &str) {
+    let s = prefix.to_string() + " " +$0 &suffix;
+}
+"#,
+            r#"
+fn f(prefix: &str, suffix: &str) {
+    let s = format!("{prefix} {suffix}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn complex_operands_are_positional() {
+        check_assist(
+            replace_with_format_string,
+            r#"
+struct User { name: &'static str }
+fn f(user: User, ids: &[u32]) {
+    let s = user.name.to_string() +$0 ": " + &ids.len().to_string() + " {ids}";
+}
+"#,
+            r#"
+struct User { name: &'static str }
+fn f(user: User, ids: &[u32]) {
+    let s = format!("{}: {} {{ids}}", user.name, ids.len());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn literal_first() {
+        check_assist(
+            replace_with_format_string,
+            r#"
+fn f(name: &str) {
+    let s = "Hello, ".to_owned() + name $0+ "!";
+}
+"#,
+            r#"
+fn f(name: &str) {
+    let s = format!("Hello, {name}!");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_numbers() {
+        check_assist_not_applicable(
+            replace_with_format_string,
+            r#"
+fn f(a: u32, b: u32) -> u32 {
+    a $0+ b
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_operator() {
+        check_assist_not_applicable(
+            replace_with_format_string,
+            r#"
+fn f(prefix: &str, suffix: &str) {
+    let s = pre$0fix.to_string() + &suffix;
+}
+"#,
+        );
+    }
+}
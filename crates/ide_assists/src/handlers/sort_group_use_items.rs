@@ -0,0 +1,229 @@
+use itertools::Itertools;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    Direction, SyntaxKind, TextRange,
+};
+
+use crate::{assist_config::UseGroup, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: sort_group_use_items
+//
+// Sorts a block of consecutive `use` items, grouping imports from the standard
+// library, from other crates and from the current crate.
+//
+// ```
+// use crate::foo::Bar;
+// $0use itertools::Itertools;
+// use std::fmt;
+// use std::collections::HashMap;
+// ```
+// ->
+// ```
+// use std::collections::HashMap;
+// use std::fmt;
+//
+// use itertools::Itertools;
+//
+// use crate::foo::Bar;
+// ```
+pub(crate) fn sort_group_use_items(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let use_item = ctx.find_node_at_offset::<ast::Use>()?;
+    let parent = use_item.syntax().parent()?;
+    if !matches!(parent.kind(), SyntaxKind::SOURCE_FILE | SyntaxKind::ITEM_LIST) {
+        return None;
+    }
+
+    let preceding = adjacent_use_items(&use_item, Direction::Prev).collect::<Vec<_>>();
+    let following = adjacent_use_items(&use_item, Direction::Next);
+    let use_items = preceding.into_iter().rev().chain(following.skip(1)).collect::<Vec<_>>();
+    if use_items.len() < 2 {
+        return None;
+    }
+
+    // Attributes and doc comments are part of the `use` item itself, so they
+    // move along with it.
+    let indent = IndentLevel::from_node(use_item.syntax());
+    let item_sep = format!("\n{}", indent);
+    let group_sep = format!("\n\n{}", indent);
+    let sorted = ctx
+        .config
+        .use_group_order
+        .iter()
+        .map(|&group| {
+            use_items
+                .iter()
+                .filter(|it| use_group(it) == group)
+                .sorted_by_key(|it| it.use_tree().map(|tree| tree.to_string()))
+                .map(|it| it.syntax().to_string())
+                .join(&item_sep)
+        })
+        .filter(|group| !group.is_empty())
+        .join(&group_sep);
+
+    let range = TextRange::new(
+        use_items.first()?.syntax().text_range().start(),
+        use_items.last()?.syntax().text_range().end(),
+    );
+    if parent.text().slice(range - parent.text_range().start()) == sorted.as_str() {
+        return None;
+    }
+
+    acc.add(
+        AssistId("sort_group_use_items", AssistKind::RefactorRewrite),
+        "Sort and group `use` items",
+        range,
+        |builder| builder.replace(range, sorted),
+    )
+}
+
+/// Returns `use_item` followed by the `use` items directly before or after it,
+/// separated only by whitespace.
+fn adjacent_use_items(use_item: &ast::Use, direction: Direction) -> impl Iterator<Item = ast::Use> {
+    use_item
+        .syntax()
+        .siblings_with_tokens(direction)
+        .filter(|it| it.kind() != SyntaxKind::WHITESPACE)
+        .take_while(|it| it.kind() == SyntaxKind::USE)
+        .filter_map(|it| it.into_node().and_then(ast::Use::cast))
+}
+
+fn use_group(use_item: &ast::Use) -> UseGroup {
+    let kind = use_item
+        .use_tree()
+        .and_then(|it| it.path())
+        .and_then(|it| it.first_segment())
+        .and_then(|it| it.kind());
+    match kind {
+        Some(
+            ast::PathSegmentKind::CrateKw
+            | ast::PathSegmentKind::SelfKw
+            | ast::PathSegmentKind::SuperKw,
+        ) => UseGroup::ThisCrate,
+        Some(ast::PathSegmentKind::Name(name))
+            if matches!(name.text().as_str(), "std" | "core" | "alloc") =>
+        {
+            UseGroup::Std
+        }
+        _ => UseGroup::ExternCrate,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{check_assist, check_assist_not_applicable, check_assist_with_config, TEST_CONFIG},
+        AssistConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn sorts_annotated_items() {
+        check_assist(
+            sort_group_use_items,
+            r#"
+use super::baz;
+/// Re-exported for convenience.
+pub use serde::Serialize;
+#[cfg(test)]
+use std::io$0;
+use alloc::vec::Vec;
+
+fn main() {}
+"#,
+            r#"
+use alloc::vec::Vec;
+#[cfg(test)]
+use std::io;
+
+/// Re-exported for convenience.
+pub use serde::Serialize;
+
+use super::baz;
+
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn sorts_items_in_module() {
+        check_assist(
+            sort_group_use_items,
+            r#"
+mod m {
+    use self::a::B;
+
+    $0use core::mem;
+    use anyhow::Result;
+}
+"#,
+            r#"
+mod m {
+    use core::mem;
+
+    use anyhow::Result;
+
+    use self::a::B;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn custom_group_order() {
+        let config = AssistConfig {
+            use_group_order: [UseGroup::ThisCrate, UseGroup::ExternCrate, UseGroup::Std],
+            ..TEST_CONFIG
+        };
+        check_assist_with_config(
+            sort_group_use_items,
+            config,
+            r#"
+use std::fmt;$0
+use log::info;
+use crate::Foo;
+"#,
+            r#"
+use crate::Foo;
+
+use log::info;
+
+use std::fmt;
+"#,
+        );
+    }
+
+    #[test]
+    fn stops_at_other_items() {
+        check_assist(
+            sort_group_use_items,
+            r#"
+use b::B;
+fn f() {}
+use std::fmt;$0
+use a::A;
+"#,
+            r#"
+use b::B;
+fn f() {}
+use std::fmt;
+
+use a::A;
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_sorted() {
+        check_assist_not_applicable(
+            sort_group_use_items,
+            r#"
+use std::fmt;
+
+use a::A;$0
+use b::B;
+"#,
+        );
+    }
+}
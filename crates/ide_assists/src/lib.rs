@@ -75,7 +75,7 @@ use syntax::TextRange;
 
 pub(crate) use crate::assist_context::{AssistContext, Assists};
 
-pub use assist_config::AssistConfig;
+pub use assist_config::{AssistConfig, UseGroup};
 pub use ide_db::assists::{
     Assist, AssistId, AssistKind, AssistResolveStrategy, GroupLabel, SingleResolve,
 };
@@ -104,32 +104,52 @@ mod handlers {
 
     pub(crate) type Handler = fn(&mut Assists, &AssistContext) -> Option<()>;
 
+    mod add_braces_to_arm;
+    mod add_derives_for_all_fields;
     mod add_explicit_type;
+    mod add_label_to_loop;
     mod add_lifetime_to_type;
+    mod add_phantom_data;
+    mod add_missing_from_impls_for_enum;
     mod add_missing_impl_members;
     mod add_turbo_fish;
     mod apply_demorgan;
     mod auto_import;
+    mod auto_import_all_unresolved;
     mod change_visibility;
     mod convert_bool_then;
+    mod convert_closure_to_method_pointer;
     mod convert_comment_block;
+    mod convert_for_loop_to_iterator;
     mod convert_integer_literal;
     mod convert_into_to_from;
+    mod convert_free_function_to_method;
     mod convert_iter_for_each_to_for;
+    mod convert_method_call_to_ufcs;
+    mod convert_nested_if_to_match;
+    mod convert_pattern_matching_to_matches_macro;
+    mod convert_to_struct_update_syntax;
+    mod convert_try_into_to_from;
+    mod convert_to_associated_function;
+    mod convert_to_default_impl_function;
     mod convert_tuple_struct_to_named_struct;
     mod convert_to_guarded_return;
+    mod convert_where_clause_to_inline_bound;
     mod convert_while_to_loop;
     mod destructure_tuple_binding;
     mod expand_glob_import;
+    mod extract_constant;
     mod extract_function;
     mod extract_struct_from_enum_variant;
     mod extract_type_alias;
     mod extract_variable;
     mod add_missing_match_arms;
     mod fix_visibility;
+    mod flatten_nested_if;
     mod flip_binexpr;
     mod flip_comma;
     mod flip_trait_bound;
+    mod generate_accessor_methods;
     mod generate_constant;
     mod generate_default_from_enum_variant;
     mod generate_default_from_new;
@@ -140,19 +160,28 @@ mod handlers {
     mod generate_from_impl_for_enum;
     mod generate_function;
     mod generate_getter;
+    mod generate_hash_impl;
     mod generate_impl;
     mod generate_is_empty_from_len;
+    mod generate_iterator_impl;
     mod generate_new;
+    mod generate_partial_eq_from_eq;
+    mod generate_proxy_type;
     mod generate_setter;
+    mod generate_try_from_impl;
     mod add_return_type;
     mod inline_call;
     mod inline_local_variable;
+    mod introduce_closure;
     mod introduce_named_lifetime;
+    mod introduce_named_tuple_field;
     mod invert_if;
+    mod make_function_const;
     mod merge_imports;
     mod merge_match_arms;
     mod move_bounds;
     mod move_guard;
+    mod move_item_to_module;
     mod move_module_to_file;
     mod move_to_mod_rs;
     mod move_from_mod_rs;
@@ -165,12 +194,18 @@ mod handlers {
     mod reorder_fields;
     mod reorder_impl;
     mod replace_try_expr_with_match;
+    mod replace_assert_with_assert_eq;
     mod replace_derive_with_manual_impl;
     mod replace_if_let_with_match;
+    mod replace_if_let_with_while_let;
     mod introduce_named_generic;
+    mod introduce_type_parameter;
     mod replace_let_with_if_let;
     mod replace_qualified_name_with_use;
     mod replace_string_with_char;
+    mod replace_unwrap_with_expect;
+    mod replace_with_format_string;
+    mod sort_group_use_items;
     mod split_import;
     mod sort_items;
     mod toggle_ignore;
@@ -181,32 +216,57 @@ mod handlers {
     pub(crate) fn all() -> &'static [Handler] {
         &[
             // These are alphabetic for the foolish consistency
+            add_braces_to_arm::add_braces_to_arm,
+            add_braces_to_arm::remove_braces_from_arm,
+            add_derives_for_all_fields::add_derives_for_all_fields,
             add_explicit_type::add_explicit_type,
+            add_label_to_loop::add_label_to_loop,
+            add_missing_from_impls_for_enum::add_missing_from_impls_for_enum,
             add_missing_match_arms::add_missing_match_arms,
             add_lifetime_to_type::add_lifetime_to_type,
+            add_phantom_data::add_phantom_data,
             add_return_type::add_return_type,
             add_turbo_fish::add_turbo_fish,
             apply_demorgan::apply_demorgan,
             auto_import::auto_import,
+            auto_import_all_unresolved::auto_import_all_unresolved,
             change_visibility::change_visibility,
             convert_bool_then::convert_bool_then_to_if,
             convert_bool_then::convert_if_to_bool_then,
+            convert_closure_to_method_pointer::convert_closure_to_method_pointer,
             convert_comment_block::convert_comment_block,
+            convert_for_loop_to_iterator::convert_for_loop_to_iterator,
             convert_integer_literal::convert_integer_literal,
             convert_into_to_from::convert_into_to_from,
+            convert_free_function_to_method::convert_free_function_to_method,
+            convert_free_function_to_method::convert_method_to_free_function,
             convert_iter_for_each_to_for::convert_iter_for_each_to_for,
             convert_iter_for_each_to_for::convert_for_loop_with_for_each,
+            convert_method_call_to_ufcs::convert_method_call_to_ufcs,
+            convert_method_call_to_ufcs::convert_ufcs_to_method_call,
+            convert_nested_if_to_match::convert_nested_if_to_match,
+            convert_pattern_matching_to_matches_macro::convert_pattern_matching_to_matches_macro,
             convert_to_guarded_return::convert_to_guarded_return,
+            convert_to_struct_update_syntax::convert_to_struct_update_syntax,
+            convert_try_into_to_from::convert_try_into_to_from,
+            convert_to_associated_function::convert_to_associated_function,
+            convert_to_default_impl_function::convert_to_default_impl_function,
             convert_tuple_struct_to_named_struct::convert_tuple_struct_to_named_struct,
+            convert_where_clause_to_inline_bound::convert_where_clause_to_inline_bound,
             convert_while_to_loop::convert_while_to_loop,
+            convert_while_to_loop::convert_loop_to_while,
             destructure_tuple_binding::destructure_tuple_binding,
             expand_glob_import::expand_glob_import,
+            extract_constant::extract_constant,
             extract_struct_from_enum_variant::extract_struct_from_enum_variant,
             extract_type_alias::extract_type_alias,
             fix_visibility::fix_visibility,
+            flatten_nested_if::flatten_nested_if,
+            flatten_nested_if::split_if_chain,
             flip_binexpr::flip_binexpr,
             flip_comma::flip_comma,
             flip_trait_bound::flip_trait_bound,
+            generate_accessor_methods::generate_accessor_methods,
             generate_constant::generate_constant,
             generate_default_from_enum_variant::generate_default_from_enum_variant,
             generate_default_from_new::generate_default_from_new,
@@ -217,20 +277,30 @@ mod handlers {
             generate_enum_projection_method::generate_enum_try_into_method,
             generate_from_impl_for_enum::generate_from_impl_for_enum,
             generate_function::generate_function,
+            generate_hash_impl::generate_hash_impl,
             generate_impl::generate_impl,
             generate_is_empty_from_len::generate_is_empty_from_len,
+            generate_iterator_impl::generate_iterator_impl,
             generate_new::generate_new,
+            generate_partial_eq_from_eq::generate_partial_eq_from_eq,
+            generate_proxy_type::generate_proxy_type,
+            generate_try_from_impl::generate_try_from_impl,
             inline_call::inline_call,
             inline_call::inline_into_callers,
             inline_local_variable::inline_local_variable,
+            introduce_closure::introduce_closure,
             introduce_named_generic::introduce_named_generic,
+            introduce_type_parameter::introduce_type_parameter,
             introduce_named_lifetime::introduce_named_lifetime,
+            introduce_named_tuple_field::introduce_named_tuple_field,
             invert_if::invert_if,
+            make_function_const::make_function_const,
             merge_imports::merge_imports,
             merge_match_arms::merge_match_arms,
             move_bounds::move_bounds_to_where_clause,
             move_guard::move_arm_cond_to_match_guard,
             move_guard::move_guard_to_arm_body,
+            move_item_to_module::move_item_to_module,
             move_module_to_file::move_module_to_file,
             move_to_mod_rs::move_to_mod_rs,
             move_from_mod_rs::move_from_mod_rs,
@@ -245,12 +315,17 @@ mod handlers {
             reorder_fields::reorder_fields,
             reorder_impl::reorder_impl,
             replace_try_expr_with_match::replace_try_expr_with_match,
+            replace_assert_with_assert_eq::replace_assert_with_assert_eq,
+            replace_assert_with_assert_eq::replace_assert_eq_with_assert,
             replace_derive_with_manual_impl::replace_derive_with_manual_impl,
             replace_if_let_with_match::replace_if_let_with_match,
+            replace_if_let_with_while_let::replace_if_let_with_while_let,
+            replace_if_let_with_while_let::replace_while_let_with_if_let,
             replace_if_let_with_match::replace_match_with_if_let,
             replace_let_with_if_let::replace_let_with_if_let,
             replace_qualified_name_with_use::replace_qualified_name_with_use,
             sort_items::sort_items,
+            sort_group_use_items::sort_group_use_items,
             split_import::split_import,
             toggle_ignore::toggle_ignore,
             unmerge_use::unmerge_use,
@@ -265,6 +340,8 @@ mod handlers {
             //
             replace_string_with_char::replace_string_with_char,
             replace_string_with_char::replace_char_with_string,
+            replace_unwrap_with_expect::replace_unwrap_with_expect,
+            replace_with_format_string::replace_with_format_string,
             raw_string::make_raw_string,
             //
             extract_variable::extract_variable,
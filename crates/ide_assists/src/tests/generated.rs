@@ -2,6 +2,56 @@
 
 use super::check_doc_test;
 
+#[test]
+fn doctest_add_braces_to_arm() {
+    check_doc_test(
+        "add_braces_to_arm",
+        r#####"
+fn f(x: Option<u32>) -> u32 {
+    match x {
+        Some(it) $0=> it * 2,
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn f(x: Option<u32>) -> u32 {
+    match x {
+        Some(it) => { it * 2 },
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_derives_for_all_fields() {
+    check_doc_test(
+        "add_derives_for_all_fields",
+        r#####"
+//- minicore: derive, clone, copy, eq
+#[derive(Clone, Copy, PartialEq)]
+struct Meters(u32);
+
+struct $0Size {
+    width: Meters,
+    height: Meters,
+}
+"#####,
+        r#####"
+#[derive(Clone, Copy, PartialEq)]
+struct Meters(u32);
+
+#[derive(Clone, Copy, PartialEq)]
+struct Size {
+    width: Meters,
+    height: Meters,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_explicit_type() {
     check_doc_test(
@@ -102,6 +152,37 @@ impl Trait<u32> for () {
     )
 }
 
+#[test]
+fn doctest_add_label_to_loop() {
+    check_doc_test(
+        "add_label_to_loop",
+        r#####"
+fn main() {
+    for row in rows() {
+        $0loop {
+            if done() {
+                break;
+            }
+            continue;
+        }
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    for row in rows() {
+        'outer: loop {
+            if done() {
+                break 'outer;
+            }
+            continue 'outer;
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_lifetime_to_type() {
     check_doc_test(
@@ -121,6 +202,31 @@ struct Point<'a> {
     )
 }
 
+#[test]
+fn doctest_add_missing_from_impls_for_enum() {
+    check_doc_test(
+        "add_missing_from_impls_for_enum",
+        r#####"
+enum $0Value { Int(i64), Text(String), Pair(i64, i64) }
+"#####,
+        r#####"
+enum Value { Int(i64), Text(String), Pair(i64, i64) }
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_missing_match_arms() {
     check_doc_test(
@@ -147,6 +253,27 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_add_phantom_data() {
+    check_doc_test(
+        "add_phantom_data",
+        r#####"
+//- minicore: phantom_data
+struct Id<$0T> {
+    raw: u32,
+}
+"#####,
+        r#####"
+use core::marker::PhantomData;
+
+struct Id<T> {
+    raw: u32,
+    _marker: PhantomData<T>,
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_return_type() {
     check_doc_test(
@@ -217,6 +344,29 @@ pub mod std { pub mod collections { pub struct HashMap { } } }
     )
 }
 
+#[test]
+fn doctest_auto_import_all_unresolved() {
+    check_doc_test(
+        "auto_import_all_unresolved",
+        r#####"
+fn main() {
+    let map = HashMap$0::new();
+    let set = HashSet::new();
+}
+pub mod std { pub mod collections { pub struct HashMap { } pub struct HashSet { } } }
+"#####,
+        r#####"
+use std::collections::{HashMap, HashSet};
+
+fn main() {
+    let map = HashMap::new();
+    let set = HashSet::new();
+}
+pub mod std { pub mod collections { pub struct HashMap { } pub struct HashSet { } } }
+"#####,
+    )
+}
+
 #[test]
 fn doctest_change_visibility() {
     check_doc_test(
@@ -252,6 +402,59 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_closure_to_method_pointer() {
+    check_doc_test(
+        "convert_closure_to_method_pointer",
+        r#####"
+//- minicore: fn
+struct Name(String);
+impl Name {
+    fn as_str(&self) -> &str { &self.0 }
+}
+fn with_name(f: impl Fn(&Name) -> &str) {}
+fn main() {
+    with_name(|name$0| name.as_str());
+}
+"#####,
+        r#####"
+struct Name(String);
+impl Name {
+    fn as_str(&self) -> &str { &self.0 }
+}
+fn with_name(f: impl Fn(&Name) -> &str) {}
+fn main() {
+    with_name(Name::as_str);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_for_loop_to_iterator() {
+    check_doc_test(
+        "convert_for_loop_to_iterator",
+        r#####"
+//- minicore: iterators, copy
+fn main() {
+    let xs = core::iter::repeat(1).take(3);
+    let mut result = Vec::new();
+    for$0 x in xs {
+        if x > 1 {
+            result.push(x * 2);
+        }
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let xs = core::iter::repeat(1).take(3);
+    let result: Vec<_> = xs.filter(|&x| x > 1).map(|x| x * 2).collect();
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_for_loop_with_for_each() {
     check_doc_test(
@@ -275,6 +478,39 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_free_function_to_method() {
+    check_doc_test(
+        "convert_free_function_to_method",
+        r#####"
+struct Counter { count: u32 }
+
+fn $0bump(counter: &mut Counter, by: u32) {
+    counter.count += by;
+}
+
+fn main() {
+    let mut c = Counter { count: 0 };
+    bump(&mut c, 2);
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn bump(&mut self, by: u32) {
+        self.count += by;
+    }
+}
+
+fn main() {
+    let mut c = Counter { count: 0 };
+    c.bump(2);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_if_to_bool_then() {
     check_doc_test(
@@ -364,6 +600,197 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_loop_to_while() {
+    check_doc_test(
+        "convert_loop_to_while",
+        r#####"
+fn main() {
+    $0loop {
+        if !cond {
+            break;
+        }
+        foo();
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    while cond {
+        foo();
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_method_call_to_ufcs() {
+    check_doc_test(
+        "convert_method_call_to_ufcs",
+        r#####"
+struct Foo;
+impl Foo {
+    fn add(&self, x: u32) -> u32 { x }
+}
+fn main() {
+    let foo = Foo;
+    foo.ad$0d(92);
+}
+"#####,
+        r#####"
+struct Foo;
+impl Foo {
+    fn add(&self, x: u32) -> u32 { x }
+}
+fn main() {
+    let foo = Foo;
+    Foo::add(&foo, 92);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_method_to_free_function() {
+    check_doc_test(
+        "convert_method_to_free_function",
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+    fn $0bump(&mut self, by: u32) {
+        self.count += by;
+    }
+}
+
+fn main() {
+    let mut c = Counter { count: 0 };
+    c.bump(2);
+}
+"#####,
+        r#####"
+struct Counter { count: u32 }
+
+impl Counter {
+}
+
+fn bump(counter: &mut Counter, by: u32) {
+    counter.count += by;
+}
+
+fn main() {
+    let mut c = Counter { count: 0 };
+    bump(&mut c, 2);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_nested_if_to_match() {
+    check_doc_test(
+        "convert_nested_if_to_match",
+        r#####"
+fn name(n: u32) -> &'static str {
+    $0if n == 1 {
+        "one"
+    } else if n == 2 {
+        "two"
+    } else {
+        "many"
+    }
+}
+"#####,
+        r#####"
+fn name(n: u32) -> &'static str {
+    match n {
+        1 => "one",
+        2 => "two",
+        _ => "many",
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_pattern_matching_to_matches_macro() {
+    check_doc_test(
+        "convert_pattern_matching_to_matches_macro",
+        r#####"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum Shape { Circle, Square, Line }
+fn is_round(shape: Shape) -> bool {
+    $0match shape {
+        Shape::Circle => true,
+        _ => false,
+    }
+}
+"#####,
+        r#####"
+macro_rules! matches { ($($t:tt)*) => {} }
+enum Shape { Circle, Square, Line }
+fn is_round(shape: Shape) -> bool {
+    matches!(shape, Shape::Circle)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_to_associated_function() {
+    check_doc_test(
+        "convert_to_associated_function",
+        r#####"
+struct Point(i32, i32);
+
+fn origin() -> Point {
+    let (x, y) = (0, 0);
+    Point$0(x, y)
+}
+"#####,
+        r#####"
+struct Point(i32, i32);
+
+impl Point {
+    fn $0new(x: i32, y: i32) -> Self { Self(x, y) }
+}
+
+fn origin() -> Point {
+    let (x, y) = (0, 0);
+    Point::new(x, y)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_to_default_impl_function() {
+    check_doc_test(
+        "convert_to_default_impl_function",
+        r#####"
+trait Greet {
+    fn greet(&self) -> String { String::from("hello") }
+}
+struct World;
+impl Greet for World {
+    fn $0greet(&self) -> String {
+        String::from("hello")
+    }
+}
+"#####,
+        r#####"
+trait Greet {
+    fn greet(&self) -> String { String::from("hello") }
+}
+struct World;
+impl Greet for World {
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_to_guarded_return() {
     check_doc_test(
@@ -388,6 +815,53 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_to_struct_update_syntax() {
+    check_doc_test(
+        "convert_to_struct_update_syntax",
+        r#####"
+struct Point { x: u32, y: u32 }
+fn copy(p: Point) -> Point {
+    $0Point { x: p.x, y: p.y }
+}
+"#####,
+        r#####"
+struct Point { x: u32, y: u32 }
+fn copy(p: Point) -> Point {
+    Point { ..p }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_try_into_to_from() {
+    check_doc_test(
+        "convert_try_into_to_from",
+        r#####"
+//- minicore: try_from, try
+use core::convert::TryInto;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    Ok(x.try_in$0to()?)
+}
+"#####,
+        r#####"
+use core::convert::TryInto;
+struct Meters(u32);
+impl From<u32> for Meters {
+    fn from(x: u32) -> Meters { Meters(x) }
+}
+fn f(x: u32) -> Result<Meters, core::convert::Infallible> {
+    Ok(x.into())
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_tuple_struct_to_named_struct() {
     check_doc_test(
@@ -429,6 +903,46 @@ impl Point {
     )
 }
 
+#[test]
+fn doctest_convert_ufcs_to_method_call() {
+    check_doc_test(
+        "convert_ufcs_to_method_call",
+        r#####"
+struct Foo;
+impl Foo {
+    fn add(&self, x: u32) -> u32 { x }
+}
+fn main() {
+    let foo = Foo;
+    Foo::ad$0d(&foo, 92);
+}
+"#####,
+        r#####"
+struct Foo;
+impl Foo {
+    fn add(&self, x: u32) -> u32 { x }
+}
+fn main() {
+    let foo = Foo;
+    foo.add(92);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_where_clause_to_inline_bound() {
+    check_doc_test(
+        "convert_where_clause_to_inline_bound",
+        r#####"
+fn print_all<T>(items: &[T]) where T$0: Debug + Clone {}
+"#####,
+        r#####"
+fn print_all<T: Debug + Clone>(items: &[T]) {}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_while_to_loop() {
     check_doc_test(
@@ -499,6 +1013,33 @@ fn qux(bar: Bar, baz: Baz) {}
     )
 }
 
+#[test]
+fn doctest_extract_constant() {
+    check_doc_test(
+        "extract_constant",
+        r#####"
+fn pages(len: usize) -> usize {
+    let max_size: usize = $04096;
+    len / max_size
+}
+fn fits(len: usize) -> bool {
+    len <= 4096
+}
+"#####,
+        r#####"
+const $0MAX_SIZE: usize = 4096;
+
+fn pages(len: usize) -> usize {
+    let max_size: usize = MAX_SIZE;
+    len / max_size
+}
+fn fits(len: usize) -> bool {
+    len <= MAX_SIZE
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_function() {
     check_doc_test(
@@ -601,6 +1142,29 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_flatten_nested_if() {
+    check_doc_test(
+        "flatten_nested_if",
+        r#####"
+fn main() {
+    $0if x > 0 {
+        if y > 0 {
+            foo();
+        }
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    if x > 0 && y > 0 {
+        foo();
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_flip_binexpr() {
     check_doc_test(
@@ -648,6 +1212,37 @@ fn foo<T: Copy + Clone>() { }
     )
 }
 
+#[test]
+fn doctest_generate_accessor_methods() {
+    check_doc_test(
+        "generate_accessor_methods",
+        r#####"
+struct $0Person {
+    name: String,
+    age: u8,
+}
+"#####,
+        r#####"
+struct Person {
+    name: String,
+    age: u8,
+}
+
+impl Person {
+    /// Get a reference to the person's name.
+    fn $0name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get a reference to the person's age.
+    fn age(&self) -> &u8 {
+        &self.age
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_constant() {
     check_doc_test(
@@ -957,6 +1552,44 @@ impl Person {
     )
 }
 
+#[test]
+fn doctest_generate_hash_impl() {
+    check_doc_test(
+        "generate_hash_impl",
+        r#####"
+//- minicore: eq, hash
+struct $0User {
+    id: u32,
+    last_seen: u64,
+}
+
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+"#####,
+        r#####"
+struct User {
+    id: u32,
+    last_seen: u64,
+}
+
+impl core::hash::Hash for User {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_impl() {
     check_doc_test(
@@ -999,8 +1632,89 @@ impl MyStruct {
         self.data.len()
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_iterator_impl() {
+    check_doc_test(
+        "generate_iterator_impl",
+        r#####"
+//- minicore: iterator, slice
+struct $0Scores<'a> {
+    scores: &'a [u32],
+    pos: usize,
+}
+"#####,
+        r#####"
+struct Scores<'a> {
+    scores: &'a [u32],
+    pos: usize,
+}
+
+impl<'a> Iterator for Scores<'a> {
+    type Item = &'a u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.scores.get(self.pos)?;
+        self.pos += 1;
+        Some(item)
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_new() {
+    check_doc_test(
+        "generate_new",
+        r#####"
+struct Ctx<T: Clone> {
+     data: T,$0
+}
+"#####,
+        r#####"
+struct Ctx<T: Clone> {
+     data: T,
+}
+
+impl<T: Clone> Ctx<T> {
+    fn $0new(data: T) -> Self { Self { data } }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_generate_partial_eq_from_eq() {
+    check_doc_test(
+        "generate_partial_eq_from_eq",
+        r#####"
+//- minicore: eq
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl $0Eq for Point {}
+"#####,
+        r#####"
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Eq for Point {}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
     }
 }
 "#####,
@@ -1008,21 +1722,27 @@ impl MyStruct {
 }
 
 #[test]
-fn doctest_generate_new() {
+fn doctest_generate_proxy_type() {
     check_doc_test(
-        "generate_new",
+        "generate_proxy_type",
         r#####"
-struct Ctx<T: Clone> {
-     data: T,$0
+trait $0Greet {
+    fn greet(&self, name: &str) -> String;
 }
 "#####,
         r#####"
-struct Ctx<T: Clone> {
-     data: T,
+trait Greet {
+    fn greet(&self, name: &str) -> String;
 }
 
-impl<T: Clone> Ctx<T> {
-    fn $0new(data: T) -> Self { Self { data } }
+struct GreetProxy {
+    inner: std::sync::Arc<dyn Greet>,
+}
+
+impl Greet for GreetProxy {
+    fn greet(&self, name: &str) -> String {
+        self.inner.greet(name)
+    }
 }
 "#####,
     )
@@ -1052,6 +1772,34 @@ impl Person {
     )
 }
 
+#[test]
+fn doctest_generate_try_from_impl() {
+    check_doc_test(
+        "generate_try_from_impl",
+        r#####"
+//- minicore: try_from
+struct $0Even(u32);
+"#####,
+        r#####"
+struct Even(u32);
+
+#[derive(Debug)]
+struct ConversionError(());
+
+impl core::convert::TryFrom<u32> for Even {
+    type Error = ConversionError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if ${0:false} {
+            return Err(ConversionError(()));
+        }
+        Ok(Self(value))
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_call() {
     check_doc_test(
@@ -1128,6 +1876,30 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_introduce_closure() {
+    check_doc_test(
+        "introduce_closure",
+        r#####"
+fn spawn(f: impl FnOnce()) {}
+fn main() {
+    spawn(move |$0| {
+        println!("working");
+    });
+}
+"#####,
+        r#####"
+fn spawn(f: impl FnOnce()) {}
+fn main() {
+    let $0spawn_f = move || {
+        println!("working");
+    };
+    spawn(spawn_f);
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_introduce_named_generic() {
     check_doc_test(
@@ -1166,6 +1938,61 @@ impl<'a> Cursor<'a> {
     )
 }
 
+#[test]
+fn doctest_introduce_named_tuple_field() {
+    check_doc_test(
+        "introduce_named_tuple_field",
+        r#####"
+struct Inner;
+struct Wrapper(Inner);
+
+impl Wrapper {
+    fn new(inner: Inner) -> Self {
+        Wrapper(inner)
+    }
+    fn get(&self) -> &Inner {
+        &self.$00
+    }
+}
+"#####,
+        r#####"
+struct Inner;
+struct Wrapper { inner: Inner }
+
+impl Wrapper {
+    fn new(inner: Inner) -> Self {
+        Wrapper { inner: inner }
+    }
+    fn get(&self) -> &Inner {
+        &self.$0inner
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_introduce_type_parameter() {
+    check_doc_test(
+        "introduce_type_parameter",
+        r#####"
+//- minicore: slice, deref, as_ref
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> { type Target = [T]; fn deref(&self) -> &[T] { loop {} } }
+fn total_len(a: &$0Vec<u32>, b: &Vec<u32>) -> usize {
+    a.len() + b.len()
+}
+"#####,
+        r#####"
+struct Vec<T>(T);
+impl<T> core::ops::Deref for Vec<T> { type Target = [T]; fn deref(&self) -> &[T] { loop {} } }
+fn total_len<T: AsRef<[u32]>>(a: &T, b: &T) -> usize {
+    a.as_ref().len() + b.as_ref().len()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_invert_if() {
     check_doc_test(
@@ -1200,6 +2027,23 @@ fn doctest_line_to_block() {
     )
 }
 
+#[test]
+fn doctest_make_function_const() {
+    check_doc_test(
+        "make_function_const",
+        r#####"
+fn $0area(width: u32, height: u32) -> u32 {
+    width * height
+}
+"#####,
+        r#####"
+const fn area(width: u32, height: u32) -> u32 {
+    width * height
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_make_raw_string() {
     check_doc_test(
@@ -1363,6 +2207,26 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_move_item_to_module() {
+    check_doc_test(
+        "move_item_to_module",
+        r#####"
+struct Config;
+
+pub struct $0Server {
+    config: Config,
+}
+"#####,
+        r#####"
+struct Config;
+
+pub mod server;
+pub use server::Server;
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_module_to_file() {
     check_doc_test(
@@ -1423,6 +2287,32 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_pull_assignment_up_1() {
+    check_doc_test(
+        "pull_assignment_up",
+        r#####"
+fn main() {
+    let foo;
+    if true {
+        $0foo = 5;
+    } else {
+        foo = 4;
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let foo = if true {
+        5
+    } else {
+        4
+    };
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_qualify_path() {
     check_doc_test(
@@ -1442,18 +2332,41 @@ pub mod std { pub mod collections { pub struct HashMap { } } }
     )
 }
 
+#[test]
+fn doctest_remove_braces_from_arm() {
+    check_doc_test(
+        "remove_braces_from_arm",
+        r#####"
+fn f(x: Option<u32>) -> u32 {
+    match x {
+        Some(it) $0=> { it * 2 }
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn f(x: Option<u32>) -> u32 {
+    match x {
+        Some(it) => it * 2,
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_remove_dbg() {
     check_doc_test(
         "remove_dbg",
         r#####"
 fn main() {
-    $0dbg!(92);
+    let x = $0dbg!(42 * 2);
 }
 "#####,
         r#####"
 fn main() {
-    92;
+    let x = 42 * 2;
 }
 "#####,
     )
@@ -1564,6 +2477,55 @@ impl Foo for Bar {
     )
 }
 
+#[test]
+fn doctest_replace_assert_eq_with_assert() {
+    check_doc_test(
+        "replace_assert_eq_with_assert",
+        r#####"
+fn main() {
+    let x = 92;
+    $0assert_ne!(x, 0);
+}
+"#####,
+        r#####"
+fn main() {
+    let x = 92;
+    assert!(x != 0);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_assert_with_assert_eq() {
+    check_doc_test(
+        "replace_assert_with_assert_eq",
+        r#####"
+//- minicore: fmt
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+impl core::fmt::Debug for i32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn main() {
+    let x = 92;
+    $0assert!(x == 92, "wrong answer");
+}
+"#####,
+        r#####"
+#[rustc_builtin_macro]
+macro_rules! assert { ($($arg:tt)*) => {} }
+impl core::fmt::Debug for i32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { loop {} }
+}
+fn main() {
+    let x = 92;
+    assert_eq!(x, 92, "wrong answer");
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_char_with_string() {
     check_doc_test(
@@ -1632,6 +2594,31 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_replace_if_let_with_while_let() {
+    check_doc_test(
+        "replace_if_let_with_while_let",
+        r#####"
+fn main() {
+    loop {
+        $0if let Some(token) = tokens.next() {
+            process(token);
+        } else {
+            break;
+        }
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    while let Some(token) = tokens.next() {
+        process(token);
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_let_with_if_let() {
     check_doc_test(
@@ -1741,6 +2728,91 @@ fn handle() {
     )
 }
 
+#[test]
+fn doctest_replace_unwrap_with_expect() {
+    check_doc_test(
+        "replace_unwrap_with_expect",
+        r#####"
+//- minicore: option
+fn main() {
+    let port = Some(8080);
+    port.unwr$0ap();
+}
+"#####,
+        r#####"
+fn main() {
+    let port = Some(8080);
+    port.expect("${0:port should be Some}");
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_while_let_with_if_let() {
+    check_doc_test(
+        "replace_while_let_with_if_let",
+        r#####"
+fn main() {
+    $0while let Some(token) = tokens.next() {
+        process(token);
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    loop {
+        if let Some(token) = tokens.next() {
+            process(token);
+        } else {
+            break;
+        }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_replace_with_format_string() {
+    check_doc_test(
+        "replace_with_format_string",
+        r#####"
+fn main() {
+    let (name, greeting) = ("world", "Hello");
+    let s = greeting.to_string() +$0 ", " + name;
+}
+"#####,
+        r#####"
+fn main() {
+    let (name, greeting) = ("world", "Hello");
+    let s = format!("{greeting}, {name}");
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_sort_group_use_items() {
+    check_doc_test(
+        "sort_group_use_items",
+        r#####"
+use crate::foo::Bar;
+$0use itertools::Itertools;
+use std::fmt;
+use std::collections::HashMap;
+"#####,
+        r#####"
+use std::collections::HashMap;
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::foo::Bar;
+"#####,
+    )
+}
+
 #[test]
 fn doctest_sort_items() {
     check_doc_test(
@@ -1832,6 +2904,29 @@ enum Animal {
     )
 }
 
+#[test]
+fn doctest_split_if_chain() {
+    check_doc_test(
+        "split_if_chain",
+        r#####"
+fn main() {
+    if x > 0 $0&& y > 0 {
+        foo();
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    if x > 0 {
+        if y > 0 {
+            foo();
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_split_import() {
     check_doc_test(
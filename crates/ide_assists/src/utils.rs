@@ -5,8 +5,12 @@ mod gen_trait_fn_body;
 
 use std::ops;
 
-use hir::HasSource;
-use ide_db::{helpers::SnippetCap, path_transform::PathTransform, RootDatabase};
+use hir::{AsAssocItem, AssocItemContainer, HasSource, HirDisplay};
+use ide_db::{
+    helpers::{mod_path_to_ast, SnippetCap},
+    path_transform::PathTransform,
+    RootDatabase,
+};
 use itertools::Itertools;
 use stdx::format_to;
 use syntax::{
@@ -525,3 +529,29 @@ pub(crate) fn trimmed_text_range(source_file: &SourceFile, initial_range: TextRa
     }
     trimmed_range
 }
+
+/// Renders the path of the trait or type that `func` is an associated function
+/// of, as seen from `module`, e.g. `Foo` for `Foo::new`.
+pub(crate) fn assoc_fn_qualifier(
+    db: &RootDatabase,
+    module: hir::Module,
+    func: hir::Function,
+) -> Option<String> {
+    let qualifier = match func.as_assoc_item(db)?.container(db) {
+        AssocItemContainer::Trait(trait_) => {
+            mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Trait(trait_))?).to_string()
+        }
+        AssocItemContainer::Impl(impl_) => {
+            let self_ty = impl_.self_ty(db);
+            match self_ty.as_adt() {
+                Some(adt) => mod_path_to_ast(&module.find_use_path(db, hir::ModuleDef::Adt(adt))?)
+                    .to_string(),
+                None if self_ty.as_builtin().is_some() => {
+                    self_ty.display_source_code(db, module.into()).ok()?
+                }
+                None => format!("<{}>", self_ty.display_source_code(db, module.into()).ok()?),
+            }
+        }
+    };
+    Some(qualifier)
+}
@@ -1,17 +1,29 @@
 //! This module defines an accumulator for completions which are going to be presented to user.
 
+pub(crate) mod assoc_type_binding;
+pub(crate) mod async_fn_return_type;
 pub(crate) mod attribute;
+pub(crate) mod closure;
+pub(crate) mod const_generic_arg;
+pub(crate) mod doc_link;
 pub(crate) mod dot;
+pub(crate) mod enum_discriminant;
+pub(crate) mod extern_crate;
 pub(crate) mod flyimport;
+pub(crate) mod format_string;
 pub(crate) mod fn_param;
+pub(crate) mod impl_block;
 pub(crate) mod keyword;
 pub(crate) mod lifetime;
+pub(crate) mod macro_rules;
+pub(crate) mod match_arm_guard;
 pub(crate) mod mod_;
 pub(crate) mod pattern;
 pub(crate) mod postfix;
 pub(crate) mod qualified_path;
 pub(crate) mod record;
 pub(crate) mod snippet;
+pub(crate) mod test_name;
 pub(crate) mod trait_impl;
 pub(crate) mod unqualified_path;
 
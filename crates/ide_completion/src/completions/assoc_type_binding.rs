@@ -0,0 +1,157 @@
+//! Completes associated type bindings in the generic argument list of a trait, e.g. `Iterator<Item = u32>`.
+
+use syntax::{ast, AstNode};
+
+use crate::{patterns::ImmediateLocation, CompletionContext, Completions};
+
+pub(crate) fn complete_associated_type_binding(acc: &mut Completions, ctx: &CompletionContext) {
+    let (arg_list, with_eq) = match &ctx.completion_location {
+        // `Trait<$0>`, the binding name is completed together with its `=`
+        Some(ImmediateLocation::GenericArgList(arg_list)) => {
+            if ctx.is_path_disallowed() || !ctx.is_trivial_path() {
+                return;
+            }
+            (arg_list.clone(), true)
+        }
+        // `Trait<$0 = Ty>`
+        Some(ImmediateLocation::AssocTypeArg(arg)) => {
+            match arg.syntax().parent().and_then(ast::GenericArgList::cast) {
+                Some(it) => (it, false),
+                None => return,
+            }
+        }
+        _ => return,
+    };
+
+    let trait_ = match arg_list
+        .syntax()
+        .parent()
+        .and_then(ast::PathSegment::cast)
+        .and_then(|segment| ctx.sema.resolve_path(&segment.parent_path()))
+    {
+        Some(hir::PathResolution::Def(hir::ModuleDef::Trait(trait_))) => trait_,
+        _ => return,
+    };
+
+    let already_bound: Vec<_> = arg_list
+        .generic_args()
+        .filter_map(|arg| match arg {
+            ast::GenericArg::AssocTypeArg(arg) => arg.name_ref(),
+            _ => None,
+        })
+        .filter(|name_ref| !name_ref.syntax().text_range().contains(ctx.position.offset))
+        .map(|name_ref| name_ref.text().to_string())
+        .collect();
+
+    trait_.items(ctx.db).into_iter().for_each(|it| {
+        if let hir::AssocItem::TypeAlias(alias) = it {
+            if already_bound.contains(&alias.name(ctx.db).to_string()) {
+                return;
+            }
+            if with_eq {
+                acc.add_type_alias_with_eq(ctx, alias)
+            } else {
+                acc.add_type_alias(ctx, alias)
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Reference);
+        expect.assert_eq(&actual)
+    }
+
+    #[test]
+    fn completes_binding_name() {
+        check(
+            r#"
+trait Trait {
+    type Foo;
+    type Bar;
+}
+fn f(_: impl Trait<F$0 = ()>) {}
+"#,
+            expect![[r#"
+                ta Foo (as Trait) type Foo;
+                ta Bar (as Trait) type Bar;
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_binding_name_in_dyn_trait() {
+        check_edit(
+            "Bar (as Trait)",
+            r#"
+trait Trait {
+    type Foo;
+    type Bar;
+}
+fn f(_: Box<dyn Trait<B$0 = ()>>) {}
+"#,
+            r#"
+trait Trait {
+    type Foo;
+    type Bar;
+}
+fn f(_: Box<dyn Trait<Bar = ()>>) {}
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_bound_associated_types() {
+        check(
+            r#"
+trait Trait {
+    type Foo;
+    type Bar;
+}
+fn f(_: impl Trait<Foo = (), $0>) {}
+"#,
+            expect![[r#"
+                ta Bar =  (as Trait) type Bar;
+                tt Trait
+            "#]],
+        );
+        check(
+            r#"
+trait Trait {
+    type Foo;
+    type Bar;
+}
+fn f(_: impl Trait<Foo = (), B$0 = ()>) {}
+"#,
+            expect![[r#"
+                ta Bar (as Trait) type Bar;
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_types_in_binding_value() {
+        check(
+            r#"
+trait Trait {
+    type Foo;
+}
+struct S;
+fn f(_: impl Trait<Foo = $0>) {}
+"#,
+            expect![[r#"
+                tt Trait
+                st S
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Completes `Result` return types of `async fn`s, e.g. `async fn foo() -> $0`.
+
+use hir::{Adt, ModuleDef};
+use ide_db::helpers::{mod_path_to_ast, FamousDefs};
+use syntax::T;
+
+use crate::{
+    item::CompletionRelevanceTypeMatch, CompletionContext, CompletionItem, CompletionItemKind,
+    CompletionKind, CompletionRelevance, Completions,
+};
+
+pub(crate) fn complete_async_fn_return_type(acc: &mut Completions, ctx: &CompletionContext) {
+    if !ctx.expects_type() || !ctx.is_trivial_path() || !ctx.previous_token_is(T![->]) {
+        return;
+    }
+    match &ctx.function_def {
+        Some(it) if it.async_token().is_some() => (),
+        _ => return,
+    }
+    let cap = match ctx.config.snippet_cap {
+        Some(it) => it,
+        None => return,
+    };
+    let (module, krate) = match (ctx.scope.module(), ctx.krate) {
+        (Some(module), Some(krate)) => (module, krate),
+        _ => return,
+    };
+    let db = ctx.db;
+    let famous_defs = FamousDefs(&ctx.sema, Some(krate));
+    let path_to =
+        |def: ModuleDef| module.find_use_path(db, def).map(|it| mod_path_to_ast(&it).to_string());
+
+    let mut errors = Vec::new();
+    if krate.dependencies(db).iter().any(|dep| dep.name.to_string() == "anyhow") {
+        errors.push("anyhow::Error".to_owned());
+    }
+    let error_trait = famous_defs.std_error_Error();
+    if let Some(error_trait) = error_trait {
+        if let Some(path) = path_to(error_trait.into()) {
+            errors.push(format!("Box<dyn {} + Send + Sync>", path));
+        }
+    }
+    if let Some(path) = famous_defs.std_io_Error().and_then(|it| path_to(Adt::from(it).into())) {
+        errors.push(path);
+    }
+    if let Some(error_trait) = error_trait {
+        for impl_ in hir::Impl::all_for_trait(db, error_trait) {
+            let adt = match impl_.self_ty(db).as_adt() {
+                Some(it) if it.module(db).krate() == krate => it,
+                _ => continue,
+            };
+            errors.extend(path_to(adt.into()));
+        }
+    }
+
+    for error in errors {
+        let label = format!("Result<…, {}>", error);
+        let mut item = CompletionItem::new(CompletionKind::Snippet, ctx.source_range(), label);
+        item.kind(CompletionItemKind::Snippet)
+            .insert_snippet(cap, format!("Result<$0, {}>", error))
+            .lookup_by("Result")
+            .set_relevance(CompletionRelevance {
+                type_match: Some(CompletionRelevanceTypeMatch::CouldUnify),
+                ..CompletionRelevance::default()
+            });
+        item.add_to(acc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    const STD_FIXTURE: &str = r#"
+//- /std.rs crate:std
+pub mod error {
+    pub trait Error {}
+}
+pub mod io {
+    pub struct Error;
+    impl crate::error::Error for Error {}
+}
+//- /anyhow.rs crate:anyhow
+pub struct Error;
+"#;
+
+    #[test]
+    fn completes_result_types() {
+        check(
+            &format!(
+                "{}{}",
+                STD_FIXTURE,
+                r#"
+//- /main.rs crate:main deps:std,anyhow
+mod errors {
+    pub struct ParseError;
+    impl std::error::Error for ParseError {}
+}
+struct NotAnError;
+async fn foo() -> $0
+"#
+            ),
+            expect![[r#"
+                kw self
+                kw super
+                kw crate
+                st NotAnError
+                md errors
+                md std
+                md anyhow
+                bt u32
+                sn Result<…, anyhow::Error>
+                sn Result<…, Box<dyn std::error::Error + Send + Sync>>
+                sn Result<…, std::io::Error>
+                sn Result<…, errors::ParseError>
+            "#]],
+        );
+    }
+
+    #[test]
+    fn inserts_result_template() {
+        check_edit(
+            "Result",
+            &format!(
+                "{}{}",
+                STD_FIXTURE,
+                r#"
+//- /main.rs crate:main deps:anyhow
+async fn foo() -> $0 {}
+"#
+            ),
+            r#"
+async fn foo() -> Result<$0, anyhow::Error> {}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_completions_in_sync_fn() {
+        check(
+            &format!(
+                "{}{}",
+                STD_FIXTURE,
+                r#"
+//- /main.rs crate:main deps:std
+fn foo() -> $0
+"#
+            ),
+            expect![[r#"
+                kw self
+                kw super
+                kw crate
+                md std
+                bt u32
+            "#]],
+        );
+    }
+}
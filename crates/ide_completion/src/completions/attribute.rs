@@ -38,6 +38,9 @@ pub(crate) fn complete_attribute(acc: &mut Completions, ctx: &CompletionContext)
             "cfg" => {
                 cfg::complete_cfg(acc, ctx);
             }
+            "cfg_attr" if is_in_cfg_attr_predicate(ctx, &token_tree) => {
+                cfg::complete_cfg(acc, ctx);
+            }
             _ => (),
         },
         (None, Some(_)) => (),
@@ -46,6 +49,15 @@ pub(crate) fn complete_attribute(acc: &mut Completions, ctx: &CompletionContext)
     Some(())
 }
 
+/// Checks whether the cursor is placed before the first top-level comma of `cfg_attr(...)`.
+fn is_in_cfg_attr_predicate(ctx: &CompletionContext, token_tree: &ast::TokenTree) -> bool {
+    !token_tree
+        .syntax()
+        .children_with_tokens()
+        .take_while(|it| it.text_range().end() <= ctx.position.offset)
+        .any(|it| it.kind() == T![,])
+}
+
 fn complete_new_attribute(acc: &mut Completions, ctx: &CompletionContext, attribute: &ast::Attr) {
     let is_inner = attribute.kind() == ast::AttrKind::Inner;
     let attribute_annotated_item_kind =
@@ -2,7 +2,8 @@
 
 use std::iter;
 
-use syntax::SyntaxKind;
+use rustc_hash::FxHashSet;
+use syntax::{SyntaxKind, TextRange, TextSize};
 
 use crate::{
     completions::Completions, context::CompletionContext, item::CompletionKind, CompletionItem,
@@ -10,15 +11,35 @@ use crate::{
 };
 
 pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext) {
+    // When completing inside of a string literal, only its contents are replaced.
+    let in_string = ctx.original_token.kind() == SyntaxKind::STRING;
+    let source_range = if in_string {
+        let start = ctx.original_token.text_range().start() + TextSize::of('"');
+        TextRange::new(start, ctx.position.offset.max(start))
+    } else {
+        ctx.source_range()
+    };
+    let quote = |value: &str| {
+        if in_string {
+            value.to_string()
+        } else {
+            format!(r#""{}""#, value)
+        }
+    };
+
     let add_completion = |item: &&str| {
-        let mut completion =
-            CompletionItem::new(CompletionKind::Attribute, ctx.source_range(), *item);
-        completion.insert_text(format!(r#""{}""#, item));
+        let mut completion = CompletionItem::new(CompletionKind::Attribute, source_range, *item);
+        completion.insert_text(quote(item));
         completion.kind(CompletionItemKind::Attribute);
         acc.add(completion.build());
     };
 
-    let previous = iter::successors(ctx.original_token.prev_token(), |t| {
+    // Skip the word that is currently being typed, if any.
+    let start = match ctx.original_token.kind() {
+        SyntaxKind::IDENT | SyntaxKind::STRING => ctx.original_token.prev_token(),
+        _ => Some(ctx.original_token.clone()),
+    };
+    let previous = iter::successors(start, |t| {
         (matches!(t.kind(), SyntaxKind::EQ) || t.kind().is_trivia())
             .then(|| t.prev_token())
             .flatten()
@@ -31,35 +52,53 @@ pub(crate) fn complete_cfg(acc: &mut Completions, ctx: &CompletionContext) {
         Some("target_os") => KNOWN_OS.iter().for_each(add_completion),
         Some("target_vendor") => KNOWN_VENDOR.iter().for_each(add_completion),
         Some("target_endian") => ["little", "big"].iter().for_each(add_completion),
+        Some("target_pointer_width") => ["16", "32", "64"].iter().for_each(add_completion),
         Some(name) => {
             if let Some(krate) = ctx.krate {
-                krate.potential_cfg(ctx.db).get_cfg_values(&name).iter().for_each(|s| {
-                    let mut item = CompletionItem::new(
-                        CompletionKind::Attribute,
-                        ctx.source_range(),
-                        s.as_str(),
-                    );
-                    item.insert_text(format!(r#""{}""#, s));
+                krate.potential_cfg(ctx.db).get_cfg_values(name).iter().for_each(|s| {
+                    let mut item =
+                        CompletionItem::new(CompletionKind::Attribute, source_range, s.as_str());
+                    item.insert_text(quote(s));
+                    item.kind(CompletionItemKind::Attribute);
 
                     acc.add(item.build());
                 })
             };
         }
+        None if in_string => (),
         None => {
-            if let Some(krate) = ctx.krate {
-                krate.potential_cfg(ctx.db).get_cfg_keys().iter().for_each(|s| {
-                    let item = CompletionItem::new(
-                        CompletionKind::Attribute,
-                        ctx.source_range(),
-                        s.as_str(),
-                    );
+            let potential_cfg = ctx.krate.map(|krate| krate.potential_cfg(ctx.db));
+            let crate_keys = potential_cfg.iter().flat_map(|cfg| cfg.get_cfg_keys());
+            let mut seen = FxHashSet::default();
+            KNOWN_KEYS
+                .iter()
+                .copied()
+                .chain(crate_keys.map(|it| it.as_str()))
+                .filter(|key| seen.insert(*key))
+                .for_each(|key| {
+                    let mut item =
+                        CompletionItem::new(CompletionKind::Attribute, source_range, key);
+                    item.kind(CompletionItemKind::Attribute);
                     acc.add(item.build());
-                })
-            }
+                });
         }
     };
 }
 
+const KNOWN_KEYS: [&str; 11] = [
+    "debug_assertions",
+    "feature",
+    "proc_macro",
+    "target_arch",
+    "target_endian",
+    "target_env",
+    "target_os",
+    "target_pointer_width",
+    "target_vendor",
+    "unix",
+    "windows",
+];
+
 const KNOWN_ARCH: [&str; 19] = [
     "aarch64",
     "arm",
@@ -0,0 +1,210 @@
+//! Completes closure templates for arguments of higher-order functions, like `iter.map(|x| $0)`.
+
+use hir::HirDisplay;
+use ide_db::call_info::ActiveParameter;
+use itertools::Itertools;
+use syntax::{ast, AstNode, SyntaxKind};
+
+use crate::{
+    item::CompletionRelevanceTypeMatch, CompletionContext, CompletionItem, CompletionItemKind,
+    CompletionKind, CompletionRelevance, Completions,
+};
+
+pub(crate) fn complete_closure_parameter_type(acc: &mut Completions, ctx: &CompletionContext) {
+    if !ctx.expects_expression() || !ctx.is_trivial_path() {
+        return;
+    }
+    let cap = match ctx.config.snippet_cap {
+        Some(it) => it,
+        None => return,
+    };
+    // only offer the template for a whole argument, not for a part of it
+    let is_argument = ctx
+        .token
+        .ancestors()
+        .find(|it| {
+            !matches!(
+                it.kind(),
+                SyntaxKind::NAME_REF
+                    | SyntaxKind::PATH_SEGMENT
+                    | SyntaxKind::PATH
+                    | SyntaxKind::PATH_EXPR
+            )
+        })
+        .map_or(false, |it| ast::ArgList::can_cast(it.kind()));
+    if !is_argument {
+        return;
+    }
+    let (params, ret) = match ActiveParameter::closure_sig_at_token(&ctx.sema, ctx.token.clone()) {
+        Some(it) => it,
+        None => return,
+    };
+
+    let names: Vec<String> = match params.len() {
+        1 => vec!["x".to_string()],
+        n => ('a'..='z').take(n).map(|it| it.to_string()).collect(),
+    };
+    if names.len() != params.len() {
+        return;
+    }
+    let label = format!(
+        "|{}|",
+        names
+            .iter()
+            .zip(&params)
+            .map(|(name, ty)| format!("{}: {}", name, ty.display(ctx.db)))
+            .format(", ")
+    );
+    let mut tab_stop = 0;
+    let mut next_tab_stop = || {
+        tab_stop += 1;
+        tab_stop
+    };
+    let snippet = format!(
+        "|{}| $0",
+        names
+            .iter()
+            .zip(&params)
+            .map(|(name, ty)| {
+                let name = format!("${{{}:{}}}", next_tab_stop(), name);
+                // leave out types that can't be written in the source, they will be inferred
+                match source_code(ctx, ty) {
+                    Some(ty) => format!("{}: ${{{}:{}}}", name, next_tab_stop(), ty),
+                    None => name,
+                }
+            })
+            .format(", ")
+    );
+    let detail = match ret.as_ref().filter(|it| !it.is_unit()).and_then(|it| source_code(ctx, it)) {
+        Some(ret) => format!("closure -> {}", ret),
+        None => "closure".to_string(),
+    };
+
+    let mut item = CompletionItem::new(CompletionKind::Snippet, ctx.source_range(), label);
+    item.insert_snippet(cap, snippet)
+        .kind(CompletionItemKind::Snippet)
+        .detail(detail)
+        .set_relevance(CompletionRelevance {
+            type_match: Some(CompletionRelevanceTypeMatch::Exact),
+            ..CompletionRelevance::default()
+        });
+    item.add_to(acc);
+}
+
+fn source_code(ctx: &CompletionContext, ty: &hir::Type) -> Option<String> {
+    if ty.contains_unknown() {
+        return None;
+    }
+    ty.display_source_code(ctx.db, ctx.scope.module()?.into()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Snippet);
+        expect.assert_eq(&actual);
+    }
+
+    const ITERATOR: &str = r#"
+trait Iterator {
+    type Item;
+    fn map<B, F>(self, f: F) -> Map<Self, F> where F: FnMut(Self::Item) -> B { loop {} }
+    fn filter<P>(self, predicate: P) -> Filter<Self, P> where P: FnMut(&Self::Item) -> bool { loop {} }
+    fn flat_map<U, F>(self, f: F) -> FlatMap<Self, U, F> where F: FnMut(Self::Item) -> U { loop {} }
+}
+struct Map<I, F>(I, F);
+struct Filter<I, P>(I, P);
+struct FlatMap<I, U, F>(I, U, F);
+struct Iter;
+impl Iterator for Iter {
+    type Item = u32;
+}
+"#;
+
+    #[test]
+    fn closure_for_map() {
+        check_edit(
+            "|x: u32|",
+            &format!("//- minicore: fn{}fn main() {{ Iter.map($0) }}", ITERATOR),
+            &format!("{}fn main() {{ Iter.map(|${{1:x}}: ${{2:u32}}| $0) }}", ITERATOR),
+        );
+    }
+
+    #[test]
+    fn closure_for_filter() {
+        check(
+            &format!("//- minicore: fn{}fn main() {{ Iter.filter($0) }}", ITERATOR),
+            expect![[r#"
+                sn |x: &u32| closure -> bool
+            "#]],
+        );
+    }
+
+    #[test]
+    fn closure_for_flat_map() {
+        check(
+            &format!("//- minicore: fn{}fn main() {{ Iter.flat_map($0) }}", ITERATOR),
+            expect![[r#"
+                sn |x: u32| closure
+            "#]],
+        );
+    }
+
+    #[test]
+    fn closure_for_user_defined_functions() {
+        check(
+            r#"
+//- minicore: fn
+fn apply(f: impl Fn(i32, &str) -> bool) {}
+fn main() { apply($0) }
+"#,
+            expect![[r#"
+                sn |a: i32, b: &str| closure -> bool
+            "#]],
+        );
+        check_edit(
+            "|a: u8, b: bool|",
+            r#"
+fn apply(x: u8, f: fn(u8, bool)) {}
+fn main() { apply(1, $0) }
+"#,
+            r#"
+fn apply(x: u8, f: fn(u8, bool)) {}
+fn main() { apply(1, |${1:a}: ${2:u8}, ${3:b}: ${4:bool}| $0) }
+"#,
+        );
+    }
+
+    #[test]
+    fn closure_for_fn_once_with_output() {
+        check(
+            r#"
+//- minicore: fn
+struct Name;
+fn run<F: FnOnce(u32) -> Name>(f: F) {}
+fn main() { run($0) }
+"#,
+            expect![[r#"
+                sn |x: u32| closure -> Name
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_closure_in_nested_expression() {
+        check(
+            r#"
+fn apply(f: fn(u8)) {}
+fn main() { apply(1 + $0) }
+"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Completes arguments of const generic parameters, e.g. `Foo<$0>` where `Foo` has a `const N: usize` parameter.
+
+use hir::HasSource;
+use syntax::{
+    ast::{self, HasGenericParams, HasName},
+    AstNode,
+};
+
+use crate::{
+    item::{CompletionItem, CompletionItemKind, CompletionKind},
+    patterns::ImmediateLocation,
+    CompletionContext, Completions,
+};
+
+const INTEGER_LITERALS: &[&str] = &["0", "1", "2", "4", "8", "16", "32", "64"];
+
+pub(crate) fn complete_const_generic_arg(acc: &mut Completions, ctx: &CompletionContext) {
+    if ctx.is_path_disallowed() || !ctx.is_trivial_path() {
+        return;
+    }
+    let param = match expected_const_param(ctx) {
+        Some(it) => it,
+        None => return,
+    };
+    let ty = param.ty(ctx.db);
+    let literals: &[&str] = if ty.is_bool() {
+        &["true", "false"]
+    } else if ty.is_integral() {
+        INTEGER_LITERALS
+    } else {
+        return;
+    };
+
+    for &literal in literals {
+        let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), literal);
+        item.kind(CompletionItemKind::Keyword);
+        item.add_to(acc);
+    }
+}
+
+/// If the cursor is in the generic argument list of a path, in the slot of a const generic
+/// parameter, returns that parameter.
+pub(crate) fn expected_const_param(ctx: &CompletionContext) -> Option<hir::ConstParam> {
+    let arg_list = match &ctx.completion_location {
+        Some(ImmediateLocation::GenericArgList(it)) => it,
+        _ => return None,
+    };
+    let segment = arg_list.syntax().parent().and_then(ast::PathSegment::cast)?;
+    let def = match ctx.sema.resolve_path(&segment.parent_path())? {
+        hir::PathResolution::Def(def) => def,
+        _ => return None,
+    };
+    let (def, param_list): (hir::GenericDef, _) = match def {
+        hir::ModuleDef::Adt(hir::Adt::Struct(it)) => {
+            (it.into(), it.source(ctx.db)?.value.generic_param_list())
+        }
+        hir::ModuleDef::Adt(hir::Adt::Enum(it)) => {
+            (it.into(), it.source(ctx.db)?.value.generic_param_list())
+        }
+        hir::ModuleDef::Adt(hir::Adt::Union(it)) => {
+            (it.into(), it.source(ctx.db)?.value.generic_param_list())
+        }
+        hir::ModuleDef::Function(it) => (it.into(), it.source(ctx.db)?.value.generic_param_list()),
+        hir::ModuleDef::TypeAlias(it) => (it.into(), it.source(ctx.db)?.value.generic_param_list()),
+        hir::ModuleDef::Trait(it) => (it.into(), it.source(ctx.db)?.value.generic_param_list()),
+        _ => return None,
+    };
+
+    // lifetimes and associated type bindings don't take up a slot of a type or const parameter
+    let idx = arg_list
+        .generic_args()
+        .filter(|arg| matches!(arg, ast::GenericArg::TypeArg(_) | ast::GenericArg::ConstArg(_)))
+        .take_while(|arg| arg.syntax().text_range().end() < ctx.position.offset)
+        .count();
+    let name = match param_list?
+        .generic_params()
+        .filter(|param| !matches!(param, ast::GenericParam::LifetimeParam(_)))
+        .nth(idx)?
+    {
+        ast::GenericParam::ConstParam(it) => it.name()?,
+        _ => return None,
+    };
+
+    def.params(ctx.db)
+        .into_iter()
+        .filter_map(|param| param.as_const_param())
+        .find(|param| param.name(ctx.db).to_string() == name.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_usize_const_param() {
+        check(
+            r#"
+struct Foo<const N: usize>;
+const SIZE: usize = 92;
+const FLAG: bool = true;
+fn f(_: Foo<$0>) {}
+"#,
+            expect![[r#"
+                kw self
+                kw super
+                kw crate
+                st Foo
+                ct SIZE
+                bt u32
+                kw 0
+                kw 1
+                kw 2
+                kw 4
+                kw 8
+                kw 16
+                kw 32
+                kw 64
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_bool_const_param() {
+        check(
+            r#"
+struct Foo<const B: bool>;
+const SIZE: usize = 92;
+const FLAG: bool = true;
+fn f(_: Foo<$0>) {}
+"#,
+            expect![[r#"
+                kw self
+                kw super
+                kw crate
+                st Foo
+                ct FLAG
+                bt u32
+                kw true
+                kw false
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_const_param_after_type_param() {
+        check_edit(
+            "16",
+            r#"
+struct Foo<'a, T, const N: usize>(&'a T);
+fn f(_: Foo<'_, u8, $0>) {}
+"#,
+            r#"
+struct Foo<'a, T, const N: usize>(&'a T);
+fn f(_: Foo<'_, u8, 16>) {}
+"#,
+        );
+        check(
+            r#"
+struct Foo<T, const N: usize>(T);
+fn f(_: Foo<$0, 1>) {}
+"#,
+            expect![[r#"
+                kw self
+                kw super
+                kw crate
+                st Foo<…>
+                bt u32
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Completes item paths in intra-doc links, like `/// See [`Fo$0`]`.
+
+use hir::{ItemInNs, ModuleDef, ScopeDef};
+use ide_db::{
+    helpers::import_assets::NameToImport,
+    items_locator::{self, AssocItemSearch, DEFAULT_QUERY_SEARCH_LIMIT},
+    SymbolKind,
+};
+use rustc_hash::FxHashSet;
+use syntax::TextRange;
+
+use crate::{
+    context::CompletionContext,
+    item::{CompletionItem, CompletionKind},
+    Completions,
+};
+
+pub(crate) fn complete_doc_link_path(acc: &mut Completions, ctx: &CompletionContext) {
+    let range = match ctx.doc_link_range {
+        Some(it) => it,
+        None => return,
+    };
+    let module = match ctx.scope.module() {
+        Some(it) => it,
+        None => return,
+    };
+
+    let mut seen = FxHashSet::default();
+    ctx.scope.process_all_names(&mut |name, def| {
+        let kind = match def {
+            ScopeDef::ModuleDef(def) => module_def_kind(def),
+            ScopeDef::MacroDef(_) => Some(SymbolKind::Macro),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            let name = name.to_string();
+            if seen.insert(name.clone()) {
+                add_path(acc, range, &name, &name, kind);
+            }
+        }
+    });
+
+    // Items that are not in scope are linked by their full path.
+    let prefix =
+        ctx.original_token.text()[range - ctx.original_token.text_range().start()].to_string();
+    if prefix.is_empty() {
+        return;
+    }
+    let items = items_locator::items_with_name(
+        &ctx.sema,
+        module.krate(),
+        NameToImport::Fuzzy(prefix),
+        AssocItemSearch::Exclude,
+        Some(DEFAULT_QUERY_SEARCH_LIMIT.inner()),
+    );
+    for item in items {
+        let kind = match item {
+            ItemInNs::Types(def) | ItemInNs::Values(def) => module_def_kind(def),
+            ItemInNs::Macros(_) => Some(SymbolKind::Macro),
+        };
+        let (kind, path) = match (kind, module.find_use_path(ctx.db, item)) {
+            (Some(kind), Some(path)) if path.segments().len() > 1 => (kind, path),
+            _ => continue,
+        };
+        let name = match path.segments().last() {
+            Some(it) => it.to_string(),
+            None => continue,
+        };
+        let path = path.to_string();
+        if seen.insert(path.clone()) {
+            add_path(acc, range, &path, &name, kind);
+        }
+    }
+}
+
+fn add_path(acc: &mut Completions, range: TextRange, path: &str, name: &str, kind: SymbolKind) {
+    let mut item = CompletionItem::new(CompletionKind::Reference, range, path);
+    item.kind(kind).lookup_by(name);
+    item.add_to(acc);
+}
+
+fn module_def_kind(def: ModuleDef) -> Option<SymbolKind> {
+    let kind = match def {
+        ModuleDef::Module(_) => SymbolKind::Module,
+        ModuleDef::Function(_) => SymbolKind::Function,
+        ModuleDef::Adt(hir::Adt::Struct(_)) => SymbolKind::Struct,
+        ModuleDef::Adt(hir::Adt::Union(_)) => SymbolKind::Union,
+        ModuleDef::Adt(hir::Adt::Enum(_)) => SymbolKind::Enum,
+        ModuleDef::Variant(_) => SymbolKind::Variant,
+        ModuleDef::Const(_) => SymbolKind::Const,
+        ModuleDef::Static(_) => SymbolKind::Static,
+        ModuleDef::Trait(_) => SymbolKind::Trait,
+        ModuleDef::TypeAlias(_) => SymbolKind::TypeAlias,
+        ModuleDef::BuiltinType(_) => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_items_in_scope() {
+        check(
+            r#"
+struct Config;
+trait Visitor {}
+fn parse() {}
+macro_rules! mac { () => {} }
+
+/// Builds a [`$0`] from the input.
+fn build() {}
+"#,
+            expect![[r#"
+                st Config
+                tt Visitor
+                fn parse
+                fn build
+                ma mac
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_full_path_of_items_out_of_scope() {
+        check(
+            r#"
+//- /lib.rs crate:lib
+//! See [Vis$0] for details.
+mod visit {
+    pub trait Visitor {}
+    pub struct Visit;
+}
+"#,
+            expect![[r#"
+                md visit
+                st visit::Visit
+                tt visit::Visitor
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_function_edit() {
+        check_edit(
+            "parse",
+            r#"
+mod parser {
+    pub fn parse() {}
+}
+/// Calls [`par$0`].
+fn run() {}
+"#,
+            r#"
+mod parser {
+    pub fn parse() {}
+}
+/// Calls [`parser::parse`].
+fn run() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_completions_outside_links() {
+        check(
+            r#"
+struct Config;
+/// Builds a Con$0 from the input.
+fn build() {}
+"#,
+            expect![[""]],
+        );
+        check(
+            r#"
+struct Config;
+// Not a doc comment: [Con$0]
+fn build() {}
+"#,
+            expect![[""]],
+        );
+    }
+}
@@ -75,7 +75,7 @@ fn complete_fields(
     }
 }
 
-fn complete_methods(
+pub(crate) fn complete_methods(
     ctx: &CompletionContext,
     receiver: &hir::Type,
     mut f: impl FnMut(hir::Function),
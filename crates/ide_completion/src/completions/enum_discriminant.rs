@@ -0,0 +1,241 @@
+//! Completes explicit enum discriminants, e.g. `enum Foo { Bar = $0 }`.
+
+use std::convert::TryFrom;
+
+use hir::ScopeDef;
+use syntax::{
+    ast::{self, HasAttrs},
+    AstNode, SyntaxKind,
+};
+
+use crate::{
+    item::{CompletionItem, CompletionItemKind, CompletionKind},
+    patterns::ImmediateLocation,
+    CompletionContext, Completions,
+};
+
+const INTEGER_LITERALS: &[&str] = &["0", "1", "2", "4", "8", "16", "32", "64"];
+
+const INTEGER_TYPES: &[&str] =
+    &["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+
+pub(crate) fn complete_enum_discriminant(acc: &mut Completions, ctx: &CompletionContext) {
+    if ctx.is_path_disallowed() || !ctx.is_trivial_path() {
+        return;
+    }
+    let variant = match &ctx.completion_location {
+        Some(ImmediateLocation::VariantDiscriminant(it)) => it,
+        _ => return,
+    };
+
+    let next = next_discriminant(variant).map(|it| it.to_string());
+    if let Some(next) = &next {
+        cov_mark::hit!(enum_discriminant_next_value);
+        let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), next);
+        item.kind(CompletionItemKind::Keyword).detail("next discriminant");
+        item.add_to(acc);
+    }
+    for &literal in INTEGER_LITERALS {
+        if next.as_deref() == Some(literal) {
+            continue;
+        }
+        let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), literal);
+        item.kind(CompletionItemKind::Keyword);
+        item.add_to(acc);
+    }
+}
+
+/// If the cursor is in the discriminant of an enum variant, returns the integer type the
+/// discriminant has, as given by the `#[repr]` of the enum.
+pub(crate) fn expected_discriminant_ty(ctx: &CompletionContext) -> Option<hir::Type> {
+    let variant = match &ctx.completion_location {
+        Some(ImmediateLocation::VariantDiscriminant(it)) => it,
+        _ => return None,
+    };
+    let enum_ = variant.parent_enum();
+    let repr = enum_
+        .attrs()
+        .filter(|attr| attr.simple_name().as_deref() == Some("repr"))
+        .filter_map(|attr| attr.token_tree())
+        .flat_map(|tt| tt.syntax().children_with_tokens())
+        .filter_map(|it| it.into_token())
+        .filter(|it| it.kind() == SyntaxKind::IDENT)
+        .find(|it| INTEGER_TYPES.contains(&it.text()))
+        .map_or_else(|| "isize".to_owned(), |it| it.text().to_owned());
+
+    let module = ctx.scope.module()?;
+    let mut res = None;
+    ctx.process_all_names(&mut |name, def| match def {
+        ScopeDef::ModuleDef(hir::ModuleDef::BuiltinType(it)) if name.to_string() == repr => {
+            res = Some(it.ty(ctx.db, module))
+        }
+        _ => (),
+    });
+    res
+}
+
+/// The value following the discriminant of the previous variant, if all discriminants up to
+/// here are integer literals or implicit.
+fn next_discriminant(variant: &ast::Variant) -> Option<i128> {
+    let mut next = 0;
+    let prev_variants = variant.syntax().siblings(syntax::Direction::Prev).skip(1);
+    let mut prev_variants: Vec<_> = prev_variants.filter_map(ast::Variant::cast).collect();
+    prev_variants.reverse();
+    for prev in prev_variants {
+        let value = match prev.expr() {
+            Some(expr) => literal_value(&expr)?,
+            None => next,
+        };
+        next = value.checked_add(1)?;
+    }
+    Some(next)
+}
+
+fn literal_value(expr: &ast::Expr) -> Option<i128> {
+    match expr {
+        ast::Expr::Literal(lit) => match lit.kind() {
+            ast::LiteralKind::IntNumber(it) => i128::try_from(it.value()?).ok(),
+            _ => None,
+        },
+        ast::Expr::PrefixExpr(it) if it.op_kind()? == ast::UnaryOp::Neg => {
+            literal_value(&it.expr()?)?.checked_neg()
+        }
+        ast::Expr::ParenExpr(it) => literal_value(&it.expr()?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_next_discriminant() {
+        cov_mark::check!(enum_discriminant_next_value);
+        check(
+            r#"
+enum Foo { A = 1, B, C = $0 }
+"#,
+            expect![[r#"
+                kw unsafe
+                kw match
+                kw while
+                kw while let
+                kw loop
+                kw if
+                kw if let
+                kw for
+                kw true
+                kw false
+                kw self
+                kw super
+                kw crate
+                sp Self
+                en Foo
+                bt u32
+                kw 3         next discriminant
+                kw 0
+                kw 1
+                kw 2
+                kw 4
+                kw 8
+                kw 16
+                kw 32
+                kw 64
+            "#]],
+        );
+        check_edit(
+            "2",
+            r#"
+enum Foo { A = -0x2, B, C, D, E = $0 }
+"#,
+            r#"
+enum Foo { A = -0x2, B, C, D, E = 2 }
+"#,
+        );
+    }
+
+    #[test]
+    fn no_next_discriminant_after_const() {
+        check(
+            r#"
+const BASE: isize = 4;
+enum Foo { A = BASE, B = $0 }
+"#,
+            expect![[r#"
+                kw unsafe
+                kw match
+                kw while
+                kw while let
+                kw loop
+                kw if
+                kw if let
+                kw for
+                kw true
+                kw false
+                kw self
+                kw super
+                kw crate
+                sp Self
+                en Foo
+                ct BASE
+                bt u32
+                kw 0
+                kw 1
+                kw 2
+                kw 4
+                kw 8
+                kw 16
+                kw 32
+                kw 64
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_consts_of_repr_type() {
+        check(
+            r#"
+const SMALL: u8 = 4;
+const LARGE: u32 = 1024;
+const NAME: &str = "";
+#[repr(u8)]
+enum Foo { A = $0 }
+"#,
+            expect![[r#"
+                kw unsafe
+                kw match
+                kw while
+                kw while let
+                kw loop
+                kw if
+                kw if let
+                kw for
+                kw true
+                kw false
+                kw self
+                kw super
+                kw crate
+                sp Self
+                en Foo
+                ct SMALL
+                bt u32
+                kw 0         next discriminant
+                kw 1
+                kw 2
+                kw 4
+                kw 8
+                kw 16
+                kw 32
+                kw 64
+            "#]],
+        );
+    }
+}
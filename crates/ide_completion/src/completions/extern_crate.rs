@@ -0,0 +1,82 @@
+//! Completes crate names in `extern crate` items.
+
+use hir::HasAttrs;
+use ide_db::SymbolKind;
+
+use crate::{
+    context::CompletionContext, item::CompletionKind, patterns::ImmediateLocation, CompletionItem,
+    Completions,
+};
+
+/// Complete dependency names of the current crate, i.e. `extern crate $0;`
+pub(crate) fn complete_extern_crate(acc: &mut Completions, ctx: &CompletionContext) {
+    if ctx.completion_location != Some(ImmediateLocation::ExternCrate) {
+        return;
+    }
+    let krate = match ctx.krate {
+        Some(it) => it,
+        None => return,
+    };
+
+    for dep in krate.dependencies(ctx.db) {
+        let mut item = CompletionItem::new(
+            CompletionKind::Reference,
+            ctx.source_range(),
+            dep.name.to_string(),
+        );
+        item.kind(SymbolKind::Module);
+        if let Some(docs) = dep.krate.root_module(ctx.db).docs(ctx.db) {
+            item.documentation(docs);
+        }
+        item.add_to(acc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_dependencies() {
+        check(
+            r#"
+//- /main.rs crate:main deps:dep,other_dep
+extern crate $0
+//- /dep.rs crate:dep
+pub struct S;
+//- /other_dep.rs crate:other_dep
+"#,
+            expect![[r#"
+                md dep
+                md other_dep
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_dependencies() {
+        check(r#"extern crate f$0;"#, expect![[r#""#]]);
+    }
+
+    #[test]
+    fn inserts_crate_name() {
+        check_edit(
+            "dep",
+            r#"
+//- /main.rs crate:main deps:dep
+extern crate d$0;
+//- /dep.rs crate:dep
+"#,
+            r#"
+extern crate dep;
+"#,
+        );
+    }
+}
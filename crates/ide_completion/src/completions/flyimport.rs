@@ -80,6 +80,9 @@ use super::Completions;
 // NOTE: currently, if an assoc item comes from a trait that's not currently imported, and it also has an unresolved and/or partially-qualified path,
 // no imports will be proposed.
 //
+// When the cursor is placed on an already written name that does not resolve, the imports bringing exactly that
+// name into scope are proposed first, which makes the completion usable as a quick "import this" fix.
+//
 // .Fuzzy search details
 //
 // To avoid an excessive amount of the results returned, completion input is checked for inclusion in the names only
@@ -143,9 +146,12 @@ pub(crate) fn import_on_the_fly(acc: &mut Completions, ctx: &CompletionContext)
                     && !ctx.is_item_hidden(&import.original_item)
             })
             .sorted_by_key(|located_import| {
-                compute_fuzzy_completion_order_key(
-                    &located_import.import_path,
-                    &user_input_lowercased,
+                (
+                    !is_unresolved_name(ctx, &located_import.import_path),
+                    compute_fuzzy_completion_order_key(
+                        &located_import.import_path,
+                        &user_input_lowercased,
+                    ),
                 )
             })
             .filter_map(|import| {
@@ -205,6 +211,14 @@ fn import_assets(ctx: &CompletionContext, fuzzy_name: String) -> Option<ImportAs
     }
 }
 
+/// Checks whether the import provides exactly the name that is written but unresolved under the cursor.
+fn is_unresolved_name(ctx: &CompletionContext, proposed_mod_path: &hir::ModPath) -> bool {
+    match (&ctx.unresolved_name, proposed_mod_path.segments().last()) {
+        (Some(unresolved_name), Some(name)) => name.as_str() == Some(unresolved_name.as_str()),
+        _ => false,
+    }
+}
+
 fn compute_fuzzy_completion_order_key(
     proposed_mod_path: &hir::ModPath,
     user_input_lowercased: &str,
@@ -374,6 +388,28 @@ fn main() {
         );
     }
 
+    #[test]
+    fn unresolved_name_comes_first() {
+        check(
+            r#"
+//- /lib.rs crate:dep
+pub mod iter {
+    pub struct MapIter;
+    pub fn map() {}
+}
+
+//- /main.rs crate:main deps:dep
+fn main() {
+    ma$0p();
+}
+"#,
+            expect![[r#"
+                fn map (use dep::iter::map) fn()
+                st MapIter (use dep::iter::MapIter)
+            "#]],
+        );
+    }
+
     #[test]
     fn trait_function_fuzzy_completion() {
         let fixture = r#"
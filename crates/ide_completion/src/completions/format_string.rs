@@ -0,0 +1,199 @@
+//! Completes format specifiers in format strings, like `println!("{:$0}", x)`.
+
+use syntax::{
+    ast::{self, IsString},
+    AstNode, AstToken, SyntaxKind, T,
+};
+
+use crate::{
+    context::CompletionContext,
+    item::{CompletionItem, CompletionItemKind, CompletionKind},
+    Completions,
+};
+
+const FORMAT_SPECIFIERS: &[(&str, &str)] = &[
+    ("?", "Debug"),
+    ("#?", "pretty-printed Debug"),
+    ("b", "binary"),
+    ("o", "octal"),
+    ("x", "lower-case hexadecimal"),
+    ("X", "upper-case hexadecimal"),
+    ("e", "lower-case exponential"),
+    ("E", "upper-case exponential"),
+    ("p", "pointer"),
+    (">", "right-aligned"),
+    ("<", "left-aligned"),
+    ("^", "centered"),
+    ("+", "always print the sign"),
+];
+
+pub(crate) fn complete_format_specifier(acc: &mut Completions, ctx: &CompletionContext) {
+    let string = match ast::String::cast(ctx.original_token.clone()) {
+        Some(it) => it,
+        None => return,
+    };
+    if !is_format_string(&string) || !is_in_format_spec(&string, ctx) {
+        return;
+    }
+
+    for &(specifier, description) in FORMAT_SPECIFIERS {
+        let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), specifier);
+        item.kind(CompletionItemKind::Keyword).detail(description);
+        item.add_to(acc);
+    }
+
+    let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), "0N");
+    item.kind(CompletionItemKind::Keyword).detail("zero-padded to width N");
+    match ctx.config.snippet_cap {
+        Some(cap) => item.insert_snippet(cap, "0${1:N}"),
+        None => item.insert_text("0"),
+    };
+    item.add_to(acc);
+}
+
+/// Checks whether the string is the format string argument of one of the std formatting macros.
+fn is_format_string(string: &ast::String) -> bool {
+    (|| {
+        let token_tree = string.syntax().parent().and_then(ast::TokenTree::cast)?;
+        let macro_call = token_tree.syntax().parent().and_then(ast::MacroCall::cast)?;
+        let name = macro_call.path()?.segment()?.name_ref()?;
+        let is_write = match name.text().as_str() {
+            "format" | "print" | "println" | "eprint" | "eprintln" => false,
+            "write" | "writeln" => true,
+            _ => return None,
+        };
+
+        // skip the opening delimiter, and the destination of `write!`
+        let mut tokens =
+            token_tree.syntax().children_with_tokens().filter(|it| !it.kind().is_trivia()).skip(1);
+        if is_write {
+            tokens.find(|it| it.kind() == T![,])?;
+        }
+        let format_string = tokens.next()?.into_token()?;
+        Some(format_string.kind() == SyntaxKind::STRING && &format_string == string.syntax())
+    })()
+    .unwrap_or(false)
+}
+
+/// Checks whether the cursor is placed after the `:` of a `{...:` placeholder that isn't closed yet.
+fn is_in_format_spec(string: &ast::String, ctx: &CompletionContext) -> bool {
+    let contents_range = match string.text_range_between_quotes() {
+        Some(it) if it.contains_inclusive(ctx.position.offset) => it,
+        _ => return false,
+    };
+    let text = string.text();
+    let start = contents_range.start() - string.syntax().text_range().start();
+    let end = ctx.position.offset - string.syntax().text_range().start();
+    let before_cursor = &text[usize::from(start)..usize::from(end)];
+
+    let open = match before_cursor.rfind('{') {
+        Some(it) => it,
+        None => return false,
+    };
+    // `{{` is an escaped brace rather than the start of a placeholder
+    let preceding_braces = before_cursor[..open].chars().rev().take_while(|&it| it == '{').count();
+    let placeholder = &before_cursor[open + 1..];
+    preceding_braces % 2 == 0 && placeholder.contains(':') && !placeholder.contains('}')
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::{check_edit, completion_list};
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_format_specifiers() {
+        check(
+            r#"
+fn main() {
+    let x = 92;
+    println!("{:$0}", x);
+}
+"#,
+            expect![[r##"
+                kw ?  Debug
+                kw #? pretty-printed Debug
+                kw b  binary
+                kw o  octal
+                kw x  lower-case hexadecimal
+                kw X  upper-case hexadecimal
+                kw e  lower-case exponential
+                kw E  upper-case exponential
+                kw p  pointer
+                kw >  right-aligned
+                kw <  left-aligned
+                kw ^  centered
+                kw +  always print the sign
+                kw 0N zero-padded to width N
+            "##]],
+        );
+    }
+
+    #[test]
+    fn completes_after_named_argument() {
+        check_edit(
+            "?",
+            r#"
+fn main() {
+    let x = 92;
+    format!("value: {x:$0}");
+}
+"#,
+            r#"
+fn main() {
+    let x = 92;
+    format!("value: {x:?}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_in_write() {
+        check_edit(
+            "#?",
+            r#"
+fn main() {
+    let x = 92;
+    writeln!(f, "{} {:$0}", x, x);
+}
+"#,
+            r#"
+fn main() {
+    let x = 92;
+    writeln!(f, "{} {:#?}", x, x);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn completes_each_specifier() {
+        for specifier in &["?", "#?", "b", "o", "x", "X", "e", "E", "p", ">", "<", "^", "+"] {
+            check_edit(
+                specifier,
+                r#"fn main() { eprintln!("{:$0}", 92); }"#,
+                &format!(r#"fn main() {{ eprintln!("{{:{}}}", 92); }}"#, specifier),
+            );
+        }
+        check_edit(
+            "0N",
+            r#"fn main() { eprintln!("{:$0}", 92); }"#,
+            r#"fn main() { eprintln!("{:0${1:N}}", 92); }"#,
+        );
+    }
+
+    #[test]
+    fn no_completions_outside_of_format_spec() {
+        check(r#"fn main() { println!("{$0}", 92); }"#, expect![[r#""#]]);
+        check(r#"fn main() { println!("{{:$0}}"); }"#, expect![[r#""#]]);
+        check(r#"fn main() { println!("{:?} $0", 92); }"#, expect![[r#""#]]);
+        check(r#"fn main() { foo!("{:$0}", 92); }"#, expect![[r#""#]]);
+    }
+}
@@ -0,0 +1,265 @@
+//! Completes `impl Trait for Type` blocks for traits in scope that the type of an inherent impl
+//! doesn't implement yet.
+//!
+//! ```ignore
+//! struct Foo;
+//! impl Foo {
+//!     Dis$0
+//! }
+//! ```
+//!
+//! adds the following `impl` block after the inherent one:
+//!
+//! ```ignore
+//! impl Display for Foo {
+//!     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+//!         todo!()
+//!     }
+//! }
+//! ```
+
+use hir::{HasSource, ScopeDef};
+use ide_db::SymbolKind;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, HasGenericParams, HasName},
+    display::function_declaration,
+    AstNode, T,
+};
+use text_edit::TextEdit;
+
+use crate::{
+    patterns::ImmediateLocation, CompletionContext, CompletionItem, CompletionKind, Completions,
+};
+
+pub(crate) fn complete_impl_block_for_unimplemented_trait(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+) {
+    if !matches!(ctx.completion_location, Some(ImmediateLocation::Impl)) {
+        return;
+    }
+    // only where a whole item starts, not after attributes or modifiers
+    if !(ctx.previous_token_is(T!['{'])
+        || ctx.previous_token_is(T!['}'])
+        || ctx.previous_token_is(T![;]))
+    {
+        return;
+    }
+    let impl_ = match ctx.token.ancestors().find_map(ast::Impl::cast) {
+        Some(it) if it.trait_().is_none() => it,
+        _ => return,
+    };
+    let self_ty = match (ctx.sema.to_def(&impl_), impl_.self_ty()) {
+        (Some(hir_impl), Some(self_ty)) => (hir_impl.self_ty(ctx.db), self_ty),
+        _ => return,
+    };
+
+    ctx.process_all_names(&mut |name, def| {
+        let trait_ = match def {
+            ScopeDef::ModuleDef(hir::ModuleDef::Trait(it)) => it,
+            _ => return,
+        };
+        if !is_implementable(ctx, trait_, &self_ty.0) {
+            return;
+        }
+
+        let indent = IndentLevel::from_node(impl_.syntax());
+        let mut block = format!("\n\n{}impl", indent);
+        if let Some(generic_params) = impl_.generic_param_list() {
+            format_to!(block, "{}", generic_params);
+        }
+        format_to!(block, " {} for {}", name, self_ty.1);
+        if let Some(where_clause) = impl_.where_clause() {
+            format_to!(block, " {}", where_clause);
+        }
+        block.push_str(" {");
+        let mut is_empty = true;
+        let item_indent = indent + 1;
+        for item in trait_.items(ctx.db) {
+            match item {
+                hir::AssocItem::Function(it) => match it.source(ctx.db) {
+                    Some(it) if it.value.body().is_none() => format_to!(
+                        block,
+                        "\n{}{} {{\n{}todo!()\n{}}}",
+                        item_indent,
+                        function_declaration(&it.value),
+                        item_indent + 1,
+                        item_indent
+                    ),
+                    _ => continue,
+                },
+                hir::AssocItem::TypeAlias(it) => match it.source(ctx.db) {
+                    Some(it) if it.value.ty().is_none() => match it.value.name() {
+                        Some(name) => format_to!(block, "\n{}type {} = ();", item_indent, name),
+                        None => continue,
+                    },
+                    _ => continue,
+                },
+                hir::AssocItem::Const(it) => match it.source(ctx.db) {
+                    Some(it) if it.value.body().is_none() => {
+                        match (it.value.name(), it.value.ty()) {
+                            (Some(name), Some(ty)) => format_to!(
+                                block,
+                                "\n{}const {}: {} = todo!();",
+                                item_indent,
+                                name,
+                                ty
+                            ),
+                            _ => continue,
+                        }
+                    }
+                    _ => continue,
+                },
+            }
+            is_empty = false;
+        }
+        if !is_empty {
+            format_to!(block, "\n{}", indent);
+        }
+        block.push('}');
+
+        let mut edit = TextEdit::builder();
+        edit.delete(ctx.source_range());
+        edit.insert(impl_.syntax().text_range().end(), block);
+
+        let mut item = CompletionItem::new(
+            CompletionKind::Magic,
+            ctx.source_range(),
+            format!("impl {} for {}", name, self_ty.1),
+        );
+        item.kind(SymbolKind::Impl)
+            .lookup_by(name.to_string())
+            .set_documentation(hir::HasAttrs::docs(trait_, ctx.db))
+            .text_edit(edit.finish());
+        item.add_to(acc);
+    });
+}
+
+/// Whether `trait_` can be implemented for `self_ty` by a plain `impl` block that isn't there yet.
+fn is_implementable(ctx: &CompletionContext, trait_: hir::Trait, self_ty: &hir::Type) -> bool {
+    // `Self` is the only generic parameter we can fill in
+    if hir::GenericDef::from(trait_).params(ctx.db).len() != 1 {
+        return false;
+    }
+    // auto traits are implemented automatically, unsafe ones shouldn't be suggested casually
+    if trait_.is_auto(ctx.db) || trait_.is_unsafe(ctx.db) {
+        return false;
+    }
+    // lang traits like `Sized` or `Fn` can't be implemented by hand
+    let lang = hir::HasAttrs::attrs(trait_, ctx.db).by_key("lang").string_value().cloned();
+    if matches!(lang.as_deref(), Some("sized" | "unsize" | "fn" | "fn_mut" | "fn_once")) {
+        return false;
+    }
+    !self_ty.impls_trait(ctx.db, trait_, &[])
+        && trait_.super_traits(ctx.db).into_iter().all(|it| self_ty.impls_trait(ctx.db, it, &[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Magic);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_unimplemented_traits() {
+        check(
+            r#"
+trait Debug {}
+trait Display {}
+trait Into<T> {}
+struct Foo;
+impl Debug for Foo {}
+impl Foo {
+    $0
+}
+"#,
+            expect![[r#"
+                im impl Display for Foo
+            "#]],
+        );
+    }
+
+    #[test]
+    fn adds_impl_block_with_stubs() {
+        check_edit(
+            "Display",
+            r#"
+trait Display {
+    type Output;
+    type Error = ();
+    const LEN: usize;
+    const EMPTY: bool = false;
+    fn fmt(&self) -> String;
+    fn is_empty(&self) -> bool { false }
+}
+struct Foo<T>(T);
+impl<T> Foo<T> { Dis$0 }
+"#,
+            r#"
+trait Display {
+    type Output;
+    type Error = ();
+    const LEN: usize;
+    const EMPTY: bool = false;
+    fn fmt(&self) -> String;
+    fn is_empty(&self) -> bool { false }
+}
+struct Foo<T>(T);
+impl<T> Foo<T> {  }
+
+impl<T> Display for Foo<T> {
+    type Output = ();
+    const LEN: usize = todo!();
+    fn fmt(&self) -> String {
+        todo!()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn skips_inapplicable_traits() {
+        check(
+            r#"
+//- minicore: sized, fn, clone, copy, derive
+trait Marker {}
+unsafe trait Unsafe {}
+trait Ordered: Marker {}
+#[derive(Clone)]
+struct Foo;
+impl Foo {
+    $0
+}
+"#,
+            expect![[r#"
+                im impl Marker for Foo
+                im impl Copy for Foo
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_completions_in_trait_impls() {
+        check(
+            r#"
+trait Display {}
+trait Debug {}
+struct Foo;
+impl Debug for Foo {
+    $0
+}
+"#,
+            expect![[r#""#]],
+        );
+    }
+}
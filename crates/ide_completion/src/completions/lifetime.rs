@@ -8,7 +8,7 @@
 //! show up for normal completions, or they won't show completions other than lifetimes depending
 //! on the fixture input.
 use hir::ScopeDef;
-use syntax::ast;
+use syntax::{ast, AstNode};
 
 use crate::{completions::Completions, context::CompletionContext};
 
@@ -30,7 +30,7 @@ pub(crate) fn complete_lifetime(acc: &mut Completions, ctx: &CompletionContext)
 
     ctx.scope.process_all_names(&mut |name, res| {
         if let ScopeDef::GenericParam(hir::GenericParam::LifetimeParam(_)) = res {
-            if param_lifetime != Some(&*name.to_string()) {
+            if param_lifetime != name.as_str() {
                 acc.add_resolution(ctx, name, &res);
             }
         }
@@ -46,8 +46,17 @@ pub(crate) fn complete_label(acc: &mut Completions, ctx: &CompletionContext) {
         return;
     }
     ctx.scope.process_all_names(&mut |name, res| {
-        if let ScopeDef::Label(_) = res {
-            acc.add_resolution(ctx, name, &res);
+        if let ScopeDef::Label(label) = res {
+            let is_block_label = label
+                .source(ctx.db)
+                .value
+                .syntax()
+                .parent()
+                .map_or(false, |it| ast::BlockExpr::can_cast(it.kind()));
+            // Blocks can be broken out of, but not continued.
+            if !(is_block_label && ctx.is_continue_label_ref) {
+                acc.add_resolution(ctx, name, &res);
+            }
         }
     });
 }
@@ -273,6 +282,60 @@ fn foo() {
         );
     }
 
+    #[test]
+    fn check_block_label_edit() {
+        check_edit(
+            "'foo",
+            r#"
+fn foo() {
+    let x = 'foo: {
+        break '$0 92;
+    };
+}
+"#,
+            r#"
+fn foo() {
+    let x = 'foo: {
+        break 'foo 92;
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn complete_block_and_loop_labels() {
+        check(
+            r#"
+fn foo() {
+    'blk: {
+        'lp: loop {
+            break '$0
+        }
+    }
+}
+"#,
+            expect![[r#"
+                lb 'lp
+                lb 'blk
+            "#]],
+        );
+        check(
+            r#"
+fn foo() {
+    'blk: {
+        'lp: loop {
+            continue '$0
+        }
+    }
+}
+"#,
+            expect![[r#"
+                lb 'lp
+            "#]],
+        );
+    }
+
     #[test]
     fn complete_label_in_loop_with_value() {
         check(
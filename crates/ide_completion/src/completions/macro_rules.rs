@@ -0,0 +1,138 @@
+//! Completes fragment specifiers of metavariables in `macro_rules!` patterns, e.g. `($e:expr) => {}`.
+
+use syntax::{algo::non_trivia_sibling, ast, AstNode, Direction, SyntaxKind, SyntaxToken, T};
+
+use crate::{
+    context::CompletionContext,
+    item::{CompletionItem, CompletionItemKind, CompletionKind},
+    Completions,
+};
+
+const FRAGMENT_SPECIFIERS: &[(&str, &str)] = &[
+    ("block", "a block expression"),
+    ("expr", "an expression"),
+    ("ident", "an identifier or a keyword"),
+    ("item", "an item"),
+    ("lifetime", "a lifetime"),
+    ("literal", "a literal expression"),
+    ("meta", "the contents of an attribute"),
+    ("pat", "a pattern"),
+    ("path", "a type path"),
+    ("stmt", "a statement without the trailing semicolon"),
+    ("tt", "a single token tree"),
+    ("ty", "a type"),
+    ("vis", "a possibly empty visibility qualifier"),
+];
+
+pub(crate) fn complete_macro_rules_fragment_specifier(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let colon = match ctx.token.kind() {
+        T![:] => ctx.token.clone(),
+        SyntaxKind::IDENT => ctx.token.prev_token().filter(|it| it.kind() == T![:])?,
+        _ => return None,
+    };
+    let name = colon.prev_token().filter(|it| it.kind() == SyntaxKind::IDENT)?;
+    name.prev_token().filter(|it| it.kind() == T![$])?;
+    if !is_in_macro_rules_pattern(&colon) {
+        return None;
+    }
+
+    for &(specifier, description) in FRAGMENT_SPECIFIERS {
+        let mut item = CompletionItem::new(CompletionKind::Keyword, ctx.source_range(), specifier);
+        item.kind(CompletionItemKind::Keyword).detail(description);
+        item.add_to(acc);
+    }
+    Some(())
+}
+
+/// Checks whether the token is placed in the matcher of a `macro_rules!` arm, that is in the
+/// token tree that precedes the `=>`.
+fn is_in_macro_rules_pattern(token: &SyntaxToken) -> bool {
+    let arm = token.ancestors().filter_map(ast::TokenTree::cast).find(|tt| {
+        tt.syntax()
+            .parent()
+            .and_then(|it| it.parent())
+            .map_or(false, |it| ast::MacroRules::can_cast(it.kind()))
+    });
+    match arm {
+        // token trees store `=>` as two separate punctuation tokens
+        Some(arm) => non_trivia_sibling(arm.syntax().clone().into(), Direction::Next)
+            .and_then(|it| it.into_token())
+            .filter(|it| it.kind() == T![=])
+            .and_then(|it| it.next_token())
+            .map_or(false, |it| it.kind() == T![>]),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Keyword);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_fragment_specifiers() {
+        check(
+            r#"
+macro_rules! m {
+    ($e:$0) => {};
+}
+"#,
+            expect![[r#"
+                kw block    a block expression
+                kw expr     an expression
+                kw ident    an identifier or a keyword
+                kw item     an item
+                kw lifetime a lifetime
+                kw literal  a literal expression
+                kw meta     the contents of an attribute
+                kw pat      a pattern
+                kw path     a type path
+                kw stmt     a statement without the trailing semicolon
+                kw tt       a single token tree
+                kw ty       a type
+                kw vis      a possibly empty visibility qualifier
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_fragment_specifier_in_repetition() {
+        check_edit(
+            "expr",
+            r#"
+macro_rules! m {
+    ($($e:ex$0),*) => {};
+}
+"#,
+            r#"
+macro_rules! m {
+    ($($e:expr),*) => {};
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_fragment_specifiers_in_macro_body() {
+        check(
+            r#"
+macro_rules! m {
+    ($e:expr) => { let $e:$0 = 92; };
+}
+"#,
+            expect![[r#""#]],
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Completes the variables bound by a match arm and their boolean methods in its guard, like
+//! `Some(it) if it.is_empty$0 =>`.
+
+use hir::ScopeDef;
+
+use crate::{completions::dot::complete_methods, CompletionContext, Completions};
+
+pub(crate) fn complete_match_arm_guard(acc: &mut Completions, ctx: &CompletionContext) {
+    if ctx.match_arm_bindings.is_empty() || !ctx.expects_expression() || !ctx.is_trivial_path() {
+        return;
+    }
+    for local in &ctx.match_arm_bindings {
+        let name = match local.name(ctx.db) {
+            Some(it) => it,
+            None => continue,
+        };
+        // The scope of an empty guard is the one of the match, which doesn't have the bindings.
+        if !ctx.locals.iter().any(|(_, it)| it == local) {
+            cov_mark::hit!(match_arm_guard_adds_bindings);
+            acc.add_resolution(ctx, name.clone(), &ScopeDef::Local(*local));
+        }
+        complete_methods(ctx, &local.ty(ctx.db), |func| {
+            let is_predicate = func.ret_type(ctx.db).is_bool()
+                && func.method_params(ctx.db).map_or(false, |params| params.is_empty());
+            if is_predicate {
+                acc.add_method(ctx, func, Some(name.clone()), None);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Reference);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn completes_bool_methods_of_bindings() {
+        check(
+            r#"
+//- minicore: option
+struct Name;
+impl Name {
+    fn is_empty(&self) -> bool { true }
+    fn starts_with(&self, c: char) -> bool { true }
+    fn len(&self) -> usize { 0 }
+}
+fn main() {
+    match Some(Name) {
+        Some(name) if $0 => (),
+        _ => (),
+    }
+}
+"#,
+            expect![[r#"
+                fn main()          fn()
+                st Name
+                md core
+                ev None            ()
+                ev Some(…)         (T)
+                en Option
+                lc name            Name
+                me name.is_empty() fn(&self) -> bool
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_bindings_in_empty_guard() {
+        cov_mark::check!(match_arm_guard_adds_bindings);
+        check(
+            r#"
+enum Shape { Circle(u32), Rect { w: u32, h: u32 } }
+fn main() {
+    match Shape::Circle(1) {
+        Shape::Rect { w, h: height } if $0 => (),
+        _ => (),
+    }
+}
+"#,
+            expect![[r#"
+                fn main() fn()
+                en Shape
+                lc w      u32
+                lc height u32
+            "#]],
+        );
+    }
+
+    #[test]
+    fn inserts_method_call_on_binding() {
+        check_edit(
+            "is_empty",
+            r#"
+//- minicore: option
+struct Name;
+impl Name {
+    fn is_empty(&self) -> bool { true }
+}
+fn main() {
+    match Some(Name) {
+        Some(name) if $0 => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+struct Name;
+impl Name {
+    fn is_empty(&self) -> bool { true }
+}
+fn main() {
+    match Some(Name) {
+        Some(name) if name.is_empty()$0 => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+}
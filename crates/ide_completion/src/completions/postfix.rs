@@ -2,7 +2,7 @@
 
 mod format_like;
 
-use hir::Documentation;
+use hir::{Documentation, HirDisplay};
 use ide_db::{
     helpers::{insert_use::ImportScope, FamousDefs, SnippetCap},
     ty_filter::TryEnum,
@@ -17,7 +17,7 @@ use text_edit::TextEdit;
 use crate::{
     completions::postfix::format_like::add_format_like_completions,
     context::CompletionContext,
-    item::{Builder, CompletionKind},
+    item::{Builder, CompletionKind, CompletionRelevancePostfixSnippet},
     patterns::ImmediateLocation,
     CompletionItem, CompletionItemKind, CompletionRelevance, Completions, SnippetScope,
 };
@@ -78,6 +78,20 @@ pub(crate) fn complete_postfix(acc: &mut Completions, ctx: &CompletionContext) {
                     &format!("while let Ok($1) = {} {{\n    $0\n}}", receiver_text),
                 )
                 .add_to(acc);
+
+                postfix_snippet(
+                    "map_err",
+                    "expr.map_err(|e| {})",
+                    &format!("{}.map_err(|${{1:e}}| $0)", receiver_text),
+                )
+                .add_to(acc);
+
+                postfix_snippet(
+                    "or_else",
+                    "expr.or_else(|e| {})",
+                    &format!("{}.or_else(|${{1:e}}| $0)", receiver_text),
+                )
+                .add_to(acc);
             }
             TryEnum::Option => {
                 postfix_snippet(
@@ -93,6 +107,16 @@ pub(crate) fn complete_postfix(acc: &mut Completions, ctx: &CompletionContext) {
                     &format!("while let Some($1) = {} {{\n    $0\n}}", receiver_text),
                 )
                 .add_to(acc);
+
+                postfix_snippet("ok_or", "expr.ok_or()", &format!("{}.ok_or($0)", receiver_text))
+                    .add_to(acc);
+
+                postfix_snippet(
+                    "ok_or_else",
+                    "expr.ok_or_else(|| {})",
+                    &format!("{}.ok_or_else(|| $0)", receiver_text),
+                )
+                .add_to(acc);
             }
         }
     } else if receiver_ty.is_bool() || receiver_ty.is_unknown() {
@@ -114,11 +138,48 @@ pub(crate) fn complete_postfix(acc: &mut Completions, ctx: &CompletionContext) {
             )
             .add_to(acc);
         }
+        if let Some(trait_) = FamousDefs(&ctx.sema, ctx.krate).core_iter_Iterator() {
+            if receiver_ty.impls_trait(ctx.db, trait_, &[]) {
+                postfix_snippet(
+                    "while_let_iter",
+                    "while let Some(item) = expr.next() {}",
+                    &format!(
+                        "while let Some(${{1:item}}) = {}.next() {{\n    $0\n}}",
+                        receiver_text
+                    ),
+                )
+                .add_to(acc);
+            }
+        }
     }
 
     postfix_snippet("ref", "&expr", &format!("&{}", receiver_text)).add_to(acc);
     postfix_snippet("refm", "&mut expr", &format!("&mut {}", receiver_text)).add_to(acc);
 
+    if let Some(clone_trait) = FamousDefs(&ctx.sema, ctx.krate).core_clone_Clone() {
+        if receiver_ty.strip_references().impls_trait(ctx.db, clone_trait, &[]) {
+            let mut item =
+                postfix_snippet("clone", "expr.clone()", &format!("{}.clone()", receiver_text));
+            item.set_relevance(CompletionRelevance {
+                postfix_snippet: Some(CompletionRelevancePostfixSnippet::TypeChecked),
+                exact_postfix_snippet_match: ctx.original_token.text() == "clone",
+                ..Default::default()
+            });
+            item.add_to(acc);
+        }
+    }
+
+    if let Some(expected_ty) = ctx.expected_type.as_ref().filter(|ty| **ty != receiver_ty) {
+        let into_trait = FamousDefs(&ctx.sema, ctx.krate).core_convert_Into();
+        if let Some(into_trait) = into_trait {
+            if receiver_ty.impls_trait(ctx.db, into_trait, std::slice::from_ref(expected_ty)) {
+                let label = format!("into() -> {}", expected_ty.display(ctx.db));
+                postfix_snippet(&label, "expr.into()", &format!("{}.into()", receiver_text))
+                    .add_to(acc);
+            }
+        }
+    }
+
     // The rest of the postfix completions create an expression that moves an argument,
     // so it's better to consider references now to avoid breaking the compilation
     let dot_receiver = include_references(dot_receiver);
@@ -213,11 +274,11 @@ fn build_postfix_snippet_builder<'a>(
         let edit = TextEdit::replace(delete_range, snippet.to_string());
         let mut item = CompletionItem::new(CompletionKind::Postfix, ctx.source_range(), label);
         item.detail(detail).kind(CompletionItemKind::Snippet).snippet_edit(cap, edit);
-        if ctx.original_token.text() == label {
-            let relevance =
-                CompletionRelevance { exact_postfix_snippet_match: true, ..Default::default() };
-            item.set_relevance(relevance);
-        }
+        item.set_relevance(CompletionRelevance {
+            postfix_snippet: Some(CompletionRelevancePostfixSnippet::Plain),
+            exact_postfix_snippet_match: ctx.original_token.text() == label,
+            ..Default::default()
+        });
 
         item
     }
@@ -255,7 +316,10 @@ mod tests {
     use expect_test::{expect, Expect};
 
     use crate::{
-        tests::{check_edit, check_edit_with_config, filtered_completion_list, TEST_CONFIG},
+        tests::{
+            check_edit, check_edit_with_config, filtered_completion_list, get_all_items,
+            TEST_CONFIG,
+        },
         CompletionConfig, CompletionKind, Snippet,
     };
 
@@ -401,6 +465,71 @@ fn main() {
         );
     }
 
+    #[test]
+    fn option_while_let() {
+        check_edit(
+            "while",
+            r#"
+//- minicore: option
+fn main() {
+    let bar = Some(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Some(true);
+    while let Some($1) = bar {
+    $0
+}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn result_while_let() {
+        check_edit(
+            "while",
+            r#"
+//- minicore: result
+fn main() {
+    let bar = Ok(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Ok(true);
+    while let Ok($1) = bar {
+    $0
+}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn bool_while() {
+        check_edit(
+            "while",
+            r#"
+fn main() {
+    let bar = true;
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = true;
+    while bar {
+    $0
+}
+}
+"#,
+        );
+    }
+
     #[test]
     fn result_match() {
         check_edit(
@@ -424,6 +553,344 @@ fn main() {
         );
     }
 
+    #[test]
+    fn option_ok_or() {
+        check(
+            r#"
+//- minicore: option
+fn main() {
+    let bar = Some(true);
+    bar.$0
+}
+"#,
+            expect![[r#"
+                sn ifl        if let Some {}
+                sn while      while let Some {}
+                sn ok_or      expr.ok_or()
+                sn ok_or_else expr.ok_or_else(|| {})
+                sn ref        &expr
+                sn refm       &mut expr
+                sn match      match expr {}
+                sn box        Box::new(expr)
+                sn ok         Ok(expr)
+                sn err        Err(expr)
+                sn some       Some(expr)
+                sn dbg        dbg!(expr)
+                sn dbgr       dbg!(&expr)
+                sn call       function(expr)
+                sn let        let
+                sn letm       let mut
+            "#]],
+        );
+        check_edit(
+            "ok_or",
+            r#"
+//- minicore: option
+fn main() {
+    let bar = Some(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Some(true);
+    bar.ok_or($0)
+}
+"#,
+        );
+        check_edit(
+            "ok_or_else",
+            r#"
+//- minicore: option
+fn main() {
+    let bar = Some(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Some(true);
+    bar.ok_or_else(|| $0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn result_map_err() {
+        check(
+            r#"
+//- minicore: result
+fn main() {
+    let bar = Ok(true);
+    bar.$0
+}
+"#,
+            expect![[r#"
+                sn ifl     if let Ok {}
+                sn while   while let Ok {}
+                sn map_err expr.map_err(|e| {})
+                sn or_else expr.or_else(|e| {})
+                sn ref     &expr
+                sn refm    &mut expr
+                sn match   match expr {}
+                sn box     Box::new(expr)
+                sn ok      Ok(expr)
+                sn err     Err(expr)
+                sn some    Some(expr)
+                sn dbg     dbg!(expr)
+                sn dbgr    dbg!(&expr)
+                sn call    function(expr)
+                sn let     let
+                sn letm    let mut
+            "#]],
+        );
+        check_edit(
+            "map_err",
+            r#"
+//- minicore: result
+fn main() {
+    let bar = Ok(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Ok(true);
+    bar.map_err(|${1:e}| $0)
+}
+"#,
+        );
+        check_edit(
+            "or_else",
+            r#"
+//- minicore: result
+fn main() {
+    let bar = Ok(true);
+    bar.$0
+}
+"#,
+            r#"
+fn main() {
+    let bar = Ok(true);
+    bar.or_else(|${1:e}| $0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn clone_for_clone_types() {
+        check(
+            r#"
+//- minicore: clone
+struct S;
+impl Clone for S {
+    fn clone(&self) -> Self { S }
+}
+fn main() {
+    let bar = &S;
+    bar.$0
+}
+"#,
+            expect![[r#"
+                sn ref   &expr
+                sn refm  &mut expr
+                sn clone expr.clone()
+                sn match match expr {}
+                sn box   Box::new(expr)
+                sn ok    Ok(expr)
+                sn err   Err(expr)
+                sn some  Some(expr)
+                sn dbg   dbg!(expr)
+                sn dbgr  dbg!(&expr)
+                sn call  function(expr)
+                sn let   let
+                sn letm  let mut
+            "#]],
+        );
+    }
+
+    #[test]
+    fn clone_ranked_between_semantic_completions_and_snippets() {
+        let items = get_all_items(
+            TEST_CONFIG,
+            r#"
+//- minicore: clone
+struct S;
+impl Clone for S {
+    fn clone(&self) -> Self { S }
+}
+fn main() {
+    let bar = S;
+    let baz: S = bar.$0
+}
+"#,
+        );
+        let score = |kind, label| {
+            items
+                .iter()
+                .find(|it| it.completion_kind == kind && it.label() == label)
+                .map(|it| it.relevance().score())
+                .unwrap()
+        };
+        let method = score(CompletionKind::Reference, "clone() (as Clone)");
+        let clone = score(CompletionKind::Postfix, "clone");
+        let snippet = score(CompletionKind::Postfix, "ref");
+        assert!(method > clone, "{} > {}", method, clone);
+        assert!(clone > snippet, "{} > {}", clone, snippet);
+    }
+
+    #[test]
+    fn no_clone_for_non_clone_types() {
+        check(
+            r#"
+//- minicore: clone
+struct S;
+fn main() {
+    let bar = S;
+    bar.$0
+}
+"#,
+            expect![[r#"
+                sn ref   &expr
+                sn refm  &mut expr
+                sn match match expr {}
+                sn box   Box::new(expr)
+                sn ok    Ok(expr)
+                sn err   Err(expr)
+                sn some  Some(expr)
+                sn dbg   dbg!(expr)
+                sn dbgr  dbg!(&expr)
+                sn call  function(expr)
+                sn let   let
+                sn letm  let mut
+            "#]],
+        );
+    }
+
+    #[test]
+    fn into_with_expected_fn_param_type() {
+        check_edit(
+            "into() -> Bar",
+            r#"
+//- minicore: from
+struct Foo;
+struct Bar;
+impl From<Foo> for Bar {
+    fn from(_: Foo) -> Bar { Bar }
+}
+fn take(_: Bar) {}
+fn main() {
+    let foo = Foo;
+    take(foo.$0)
+}
+"#,
+            r#"
+struct Foo;
+struct Bar;
+impl From<Foo> for Bar {
+    fn from(_: Foo) -> Bar { Bar }
+}
+fn take(_: Bar) {}
+fn main() {
+    let foo = Foo;
+    take(foo.into())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn into_with_expected_let_type() {
+        check(
+            r#"
+//- minicore: from
+struct Foo;
+struct Bar;
+impl From<Foo> for Bar {
+    fn from(_: Foo) -> Bar { Bar }
+}
+fn main() {
+    let foo = Foo;
+    let bar: Bar = foo.$0
+}
+"#,
+            expect![[r#"
+                sn ref           &expr
+                sn refm          &mut expr
+                sn into() -> Bar expr.into()
+                sn match         match expr {}
+                sn box           Box::new(expr)
+                sn ok            Ok(expr)
+                sn err           Err(expr)
+                sn some          Some(expr)
+                sn dbg           dbg!(expr)
+                sn dbgr          dbg!(&expr)
+                sn call          function(expr)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn no_into_without_into_impl() {
+        check(
+            r#"
+//- minicore: from
+struct Foo;
+struct Bar;
+fn main() {
+    let foo = Foo;
+    let bar: Bar = foo.$0
+}
+"#,
+            expect![[r#"
+                sn ref   &expr
+                sn refm  &mut expr
+                sn match match expr {}
+                sn box   Box::new(expr)
+                sn ok    Ok(expr)
+                sn err   Err(expr)
+                sn some  Some(expr)
+                sn dbg   dbg!(expr)
+                sn dbgr  dbg!(&expr)
+                sn call  function(expr)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn postfix_completion_for_iterators() {
+        check_edit(
+            "while_let_iter",
+            r#"
+//- minicore: iterator
+struct Counter;
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+fn main() {
+    let mut counter = Counter;
+    counter.$0
+}
+"#,
+            r#"
+struct Counter;
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+fn main() {
+    let mut counter = Counter;
+    while let Some(${1:item}) = counter.next() {
+    $0
+}
+}
+"#,
+        );
+    }
+
     #[test]
     fn postfix_completion_works_for_ambiguous_float_literal() {
         check_edit("refm", r#"fn main() { 42.$0 }"#, r#"fn main() { &mut 42 }"#)
@@ -3,7 +3,7 @@
 use std::iter;
 
 use rustc_hash::FxHashSet;
-use syntax::{ast, AstNode};
+use syntax::ast;
 
 use crate::{
     context::PathCompletionContext, patterns::ImmediateLocation, CompletionContext, Completions,
@@ -93,7 +93,7 @@ pub(crate) fn complete_qualified_path(acc: &mut Completions, ctx: &CompletionCon
                 if ctx.in_use_tree() {
                     if let hir::ScopeDef::Unknown = def {
                         if let Some(ast::NameLike::NameRef(name_ref)) = ctx.name_syntax.as_ref() {
-                            if name_ref.syntax().text() == name.to_string().as_str() {
+                            if name.as_str() == Some(name_ref.text().as_str()) {
                                 // for `use self::foo$0`, don't suggest `foo` as a completion
                                 cov_mark::hit!(dont_complete_current_use);
                                 continue;
@@ -130,9 +130,7 @@ pub(crate) fn complete_qualified_path(acc: &mut Completions, ctx: &CompletionCon
             }
         }
         hir::PathResolution::Def(
-            def
-            @
-            (hir::ModuleDef::Adt(_)
+            def @ (hir::ModuleDef::Adt(_)
             | hir::ModuleDef::TypeAlias(_)
             | hir::ModuleDef::BuiltinType(_)),
         ) => {
@@ -286,6 +284,42 @@ fn foo() { let _ = lib::S::$0 }
         );
     }
 
+    #[test]
+    fn completes_inherent_associated_types() {
+        check(
+            r#"
+#![feature(inherent_associated_types)]
+struct Foo;
+impl Foo {
+    type Output = u32;
+    const LEN: usize = 2;
+    fn new() -> Foo { Foo }
+}
+fn foo(_: Foo::$0) {}
+"#,
+            expect![[r#"
+                ta Output type Output = u32;
+            "#]],
+        );
+        check(
+            r#"
+#![feature(inherent_associated_types)]
+struct Foo;
+impl Foo {
+    type Output = u32;
+    const LEN: usize = 2;
+    fn new() -> Foo { Foo }
+}
+fn foo() { Foo::$0 }
+"#,
+            expect![[r#"
+                ct LEN    const LEN: usize = 2;
+                fn new()  fn() -> Foo
+                ta Output type Output = u32;
+            "#]],
+        );
+    }
+
     #[test]
     fn completes_union_associated_method() {
         check(
@@ -652,6 +686,68 @@ impl u8 {
         );
     }
 
+    #[test]
+    fn completes_primitive_methods() {
+        check(
+            r#"
+//- /lib.rs crate:lib deps:core
+fn f() {
+    str::$0
+}
+
+//- /core.rs crate:core
+#[lang = "str"]
+impl str {
+    pub const fn len(&self) -> usize { 0 }
+}
+#[lang = "char"]
+impl char {
+    pub fn is_alphabetic(self) -> bool { false }
+}
+"#,
+            expect![[r#"
+                me len(…) fn(&self) -> usize
+            "#]],
+        );
+        check(
+            r#"
+//- /lib.rs crate:lib deps:core
+fn f() {
+    char::$0
+}
+
+//- /core.rs crate:core
+#[lang = "str"]
+impl str {
+    pub const fn len(&self) -> usize { 0 }
+}
+#[lang = "char"]
+impl char {
+    pub fn is_alphabetic(self) -> bool { false }
+}
+"#,
+            expect![[r#"
+                me is_alphabetic(…) fn(self) -> bool
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_trait_methods_on_primitives() {
+        check(
+            r#"
+//- minicore: from
+fn f() {
+    u32::$0
+}
+"#,
+            expect![[r#"
+                fn from(…) (as From) fn(T) -> Self
+                me into(…) (as Into) fn(self) -> T
+            "#]],
+        );
+    }
+
     #[test]
     fn completes_variant_through_alias() {
         cov_mark::check!(completes_variant_through_alias);
@@ -1,10 +1,12 @@
 //! Complete fields in record literals and patterns.
+use hir::{HasAttrs, HirDisplay};
 use ide_db::{helpers::FamousDefs, SymbolKind};
 use syntax::{ast::Expr, T};
 
 use crate::{
-    item::CompletionKind, patterns::ImmediateLocation, CompletionContext, CompletionItem,
-    CompletionItemKind, Completions,
+    item::{CompletionKind, CompletionRelevanceTypeMatch},
+    patterns::ImmediateLocation,
+    CompletionContext, CompletionItem, CompletionItemKind, CompletionRelevance, Completions,
 };
 
 pub(crate) fn complete_record(acc: &mut Completions, ctx: &CompletionContext) -> Option<()> {
@@ -47,8 +49,112 @@ pub(crate) fn complete_record(acc: &mut Completions, ctx: &CompletionContext) ->
         _ => return None,
     };
 
+    let is_record_literal =
+        matches!(ctx.completion_location, Some(ImmediateLocation::RecordExpr(_)));
     for (field, ty) in missing_fields {
-        acc.add_field(ctx, None, field, &ty);
+        if is_record_literal && has_local_for_field(ctx, field, &ty) {
+            add_field_from_local(acc, ctx, field, &ty);
+        } else {
+            acc.add_field(ctx, None, field, &ty);
+        }
+    }
+
+    Some(())
+}
+
+/// Whether a local with the name of the field is in scope and can initialize it.
+fn has_local_for_field(ctx: &CompletionContext, field: hir::Field, ty: &hir::Type) -> bool {
+    let name = field.name(ctx.db);
+    let mut found = false;
+    ctx.scope.process_all_names(&mut |it, def| {
+        if let hir::ScopeDef::Local(local) = def {
+            found |= it == name && local.ty(ctx.db).could_unify_with(ctx.db, ty);
+        }
+    });
+    found
+}
+
+/// Completes the field in its shorthand form, ranked above everything else, along
+/// with the explicit `field: local` form.
+fn add_field_from_local(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+    field: hir::Field,
+    ty: &hir::Type,
+) {
+    cov_mark::hit!(record_field_from_local);
+    let name = field.name(ctx.db).to_string();
+    let detail = ty.display(ctx.db).to_string();
+
+    let mut item = CompletionItem::new(CompletionKind::Reference, ctx.source_range(), &name);
+    item.kind(SymbolKind::Field)
+        .detail(&detail)
+        .set_documentation(field.docs(ctx.db))
+        .set_relevance(CompletionRelevance {
+            exact_name_match: true,
+            type_match: Some(CompletionRelevanceTypeMatch::Exact),
+            is_local: true,
+            ..CompletionRelevance::default()
+        });
+    item.add_to(acc);
+
+    let label = format!("{}: {}", name, name);
+    let mut item = CompletionItem::new(CompletionKind::Reference, ctx.source_range(), &label);
+    item.kind(SymbolKind::Field).detail(detail);
+    match ctx.config.snippet_cap {
+        Some(cap) => item.insert_snippet(cap, format!("{}: ${{1:{}}}", name, name)),
+        None => item.insert_text(label),
+    };
+    item.add_to(acc);
+}
+
+/// Completes the constructors of the struct, like `Foo { ..Foo::new() }`, after the `..` of a
+/// struct update. Locals and free functions of the struct's type are ranked by the expected type.
+pub(crate) fn complete_record_struct_update(
+    acc: &mut Completions,
+    ctx: &CompletionContext,
+) -> Option<()> {
+    let record_expr = match &ctx.completion_location {
+        Some(ImmediateLocation::RecordExprUpdate(record_expr)) => record_expr,
+        _ => return None,
+    };
+    if !ctx.previous_token_is(T![..]) {
+        return None;
+    }
+    let path = record_expr.path()?;
+    let ty = ctx.sema.type_of_expr(&Expr::RecordExpr(record_expr.clone()))?.original;
+    ty.as_adt()?;
+
+    for impl_ in hir::Impl::all_for_type(ctx.db, ty.clone()) {
+        if impl_.trait_(ctx.db).is_some() {
+            continue;
+        }
+        for item in impl_.items(ctx.db) {
+            let func = match item {
+                hir::AssocItem::Function(it) => it,
+                _ => continue,
+            };
+            if func.self_param(ctx.db).is_some()
+                || !func.assoc_fn_params(ctx.db).is_empty()
+                || func.ret_type(ctx.db) != ty
+                || !ctx.is_visible(&func)
+            {
+                continue;
+            }
+            let label = format!("{}::{}()", path, func.name(ctx.db));
+            let mut item =
+                CompletionItem::new(CompletionKind::Reference, ctx.source_range(), label);
+            item.kind(SymbolKind::Function)
+                .detail(format!("fn() -> {}", ty.display(ctx.db)))
+                .set_relevance(CompletionRelevance {
+                    type_match: Some(CompletionRelevanceTypeMatch::Exact),
+                    ..CompletionRelevance::default()
+                });
+            if let Some(docs) = func.docs(ctx.db) {
+                item.documentation(docs);
+            }
+            item.add_to(acc);
+        }
     }
 
     Some(())
@@ -71,7 +177,19 @@ pub(crate) fn complete_record_literal(
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::check_edit;
+    use std::cmp::Reverse;
+
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, filtered_completion_list, get_all_items, TEST_CONFIG},
+        CompletionKind,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = filtered_completion_list(ra_fixture, CompletionKind::Reference);
+        expect.assert_eq(&actual);
+    }
 
     #[test]
     fn literal_struct_completion_edit() {
@@ -129,6 +247,101 @@ fn baz() {
         );
     }
 
+    #[test]
+    fn field_from_local_ranked_first() {
+        cov_mark::check!(record_field_from_local);
+        let mut items = get_all_items(
+            TEST_CONFIG,
+            r#"
+struct Foo { count: u32, name: u32 }
+fn main() {
+    let aaa = 0u32;
+    let count = 1u32;
+    let name = "other";
+    let foo = Foo { $0 };
+}
+"#,
+        );
+        items.sort_by_key(|it| Reverse(it.relevance().score()));
+        let (first, rest) = items.split_first().unwrap();
+        assert_eq!(first.label(), "count");
+        assert!(rest.iter().all(|it| it.relevance().score() < first.relevance().score()));
+        assert!(rest.iter().any(|it| it.label() == "count: count"));
+    }
+
+    #[test]
+    fn field_from_local_edit() {
+        check_edit(
+            "count: count",
+            r#"
+struct Foo { count: u32 }
+fn main() {
+    let count = 1u32;
+    let foo = Foo { $0 };
+}
+"#,
+            r#"
+struct Foo { count: u32 }
+fn main() {
+    let count = 1u32;
+    let foo = Foo { count: ${1:count} };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn struct_update_constructors() {
+        check(
+            r#"
+struct Foo { a: u32, b: u32 }
+impl Foo {
+    fn new() -> Foo { Foo { a: 0, b: 0 } }
+    fn with_a(a: u32) -> Foo { Foo { a, b: 0 } }
+    fn get(&self) -> Foo { Foo { a: 0, b: 0 } }
+    fn zero() -> u32 { 0 }
+}
+fn main() {
+    let base = Foo::new();
+    let other = 92;
+    let foo = Foo { a: 1, ..$0 };
+}
+"#,
+            expect![[r#"
+                lc base       Foo
+                st Foo
+                fn main()     fn()
+                fd b          u32
+                fn Foo::new() fn() -> Foo
+            "#]],
+        );
+    }
+
+    #[test]
+    fn struct_update_constructor_edit() {
+        check_edit(
+            "Foo::new()",
+            r#"
+struct Foo { a: u32, b: u32 }
+impl Foo {
+    fn new() -> Self { Foo { a: 0, b: 0 } }
+}
+fn main() {
+    let foo = Foo { a: 1, ..$0 };
+}
+"#,
+            r#"
+struct Foo { a: u32, b: u32 }
+impl Foo {
+    fn new() -> Self { Foo { a: 0, b: 0 } }
+}
+fn main() {
+    let foo = Foo { a: 1, ..Foo::new() };
+}
+"#,
+        );
+    }
+
     #[test]
     fn default_completion_edit() {
         check_edit(
@@ -0,0 +1,151 @@
+//! Completes names of new `#[test]` functions, like `#[test] fn $0` => `test_parse`,
+//! after the functions of the module under test.
+
+use hir::{HasAttrs, ModuleDef};
+use rustc_hash::FxHashSet;
+
+use crate::{CompletionContext, CompletionItem, CompletionItemKind, CompletionKind, Completions};
+
+pub(crate) fn complete_test_name(acc: &mut Completions, ctx: &CompletionContext) -> Option<()> {
+    if !ctx.is_test_fn_name || !ctx.config.enable_test_name_suggestions {
+        return None;
+    }
+    let tests_module = ctx.scope.module()?;
+    let module = tests_module.parent(ctx.db)?;
+
+    let existing = tests_module
+        .declarations(ctx.db)
+        .into_iter()
+        .filter_map(|it| it.name(ctx.db))
+        .map(|it| it.to_string())
+        .collect::<FxHashSet<_>>();
+    let module_name = module.name(ctx.db).map(|it| it.to_string());
+
+    for def in module.declarations(ctx.db) {
+        let func = match def {
+            ModuleDef::Function(it) => it,
+            _ => continue,
+        };
+        if func.attrs(ctx.db).by_key("test").exists() {
+            continue;
+        }
+        let fn_name = func.name(ctx.db).to_string();
+
+        let mut names = vec![format!("test_{}", fn_name)];
+        if let Some(module_name) = &module_name {
+            names.push(format!("test_{}_{}", module_name, fn_name));
+        }
+        let returns_result = func
+            .ret_type(ctx.db)
+            .as_adt()
+            .map_or(false, |it| it.name(ctx.db).to_string() == "Result");
+        if returns_result {
+            names.push(format!("test_{}_returns_error_on_invalid_input", fn_name));
+        }
+
+        for name in names.into_iter().filter(|it| !existing.contains(it)) {
+            let mut item = CompletionItem::new(CompletionKind::Snippet, ctx.source_range(), name);
+            item.kind(CompletionItemKind::Snippet).detail(format!("fn {}", fn_name));
+            item.add_to(acc);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        tests::{check_edit, completion_list, completion_list_with_config, TEST_CONFIG},
+        CompletionConfig,
+    };
+
+    fn check(ra_fixture: &str, expect: Expect) {
+        let actual = completion_list(ra_fixture);
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn suggests_names_for_module_functions() {
+        check(
+            r#"
+//- minicore: result
+mod parser {
+    pub fn parse(input: &str) -> Result<u32, ()> { Ok(0) }
+    fn skip_whitespace() {}
+
+    mod tests {
+        #[test]
+        fn test_skip_whitespace() {}
+
+        #[test]
+        fn $0
+    }
+}
+"#,
+            expect![[r#"
+                sn test_parse             fn parse
+                sn test_parser_parse      fn parse
+                sn test_parse_returns_error_on_invalid_input fn parse
+                sn test_parser_skip_whitespace fn skip_whitespace
+            "#]],
+        );
+    }
+
+    #[test]
+    fn completes_partial_name() {
+        check_edit(
+            "test_frobnicate",
+            r#"
+//- /lib.rs cfg:test
+fn frobnicate() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_fr$0() {}
+}
+"#,
+            r#"
+fn frobnicate() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_frobnicate() {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn no_suggestions_outside_tests() {
+        check(
+            r#"
+fn frobnicate() {}
+
+mod tests {
+    fn $0
+}
+"#,
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn no_suggestions_when_disabled() {
+        let actual = completion_list_with_config(
+            CompletionConfig { enable_test_name_suggestions: false, ..TEST_CONFIG },
+            r#"
+fn frobnicate() {}
+
+mod tests {
+    #[test]
+    fn $0
+}
+"#,
+        );
+        expect![[""]].assert_eq(&actual);
+    }
+}
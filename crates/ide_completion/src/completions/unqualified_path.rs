@@ -3,7 +3,11 @@
 use hir::ScopeDef;
 use syntax::{ast, AstNode};
 
-use crate::{patterns::ImmediateLocation, CompletionContext, Completions};
+use crate::{
+    completions::{const_generic_arg, enum_discriminant},
+    patterns::ImmediateLocation,
+    CompletionContext, Completions,
+};
 
 pub(crate) fn complete_unqualified_path(acc: &mut Completions, ctx: &CompletionContext) {
     if ctx.is_path_disallowed() || !ctx.is_trivial_path() || ctx.has_impl_or_trait_prev_sibling() {
@@ -41,7 +45,31 @@ pub(crate) fn complete_unqualified_path(acc: &mut Completions, ctx: &CompletionC
             });
             return;
         }
-        Some(ImmediateLocation::TypeBound) => {
+        Some(ImmediateLocation::TypeBound(bound_list)) => {
+            let is_lifetime_pred = bound_list
+                .syntax()
+                .parent()
+                .and_then(ast::WherePred::cast)
+                .map_or(false, |it| it.lifetime().is_some());
+            if is_lifetime_pred {
+                // Lifetimes can only be bounded by lifetimes.
+                cov_mark::hit!(only_lifetimes_bound_lifetimes);
+                ctx.process_all_names(&mut |name, res| {
+                    if let ScopeDef::GenericParam(hir::GenericParam::LifetimeParam(_)) = res {
+                        acc.add_resolution(ctx, name, &res);
+                    }
+                });
+                acc.add_static_lifetime(ctx);
+                return;
+            }
+            // `?Sized` relaxes the implicit bound of type parameters only.
+            let bounds_type_param = bound_list.syntax().parent().map_or(false, |it| {
+                ast::WherePred::can_cast(it.kind()) || ast::TypeParam::can_cast(it.kind())
+            });
+            if bounds_type_param {
+                acc.add_keyword(ctx, "?Sized");
+            }
+            acc.add_static_lifetime(ctx);
             ctx.process_all_names(&mut |name, res| {
                 let add_resolution = match res {
                     ScopeDef::MacroDef(mac) => mac.is_fn_like(),
@@ -69,19 +97,17 @@ pub(crate) fn complete_unqualified_path(acc: &mut Completions, ctx: &CompletionC
         }
     }
 
-    if let Some(ImmediateLocation::GenericArgList(arg_list)) = &ctx.completion_location {
-        if let Some(path_seg) = arg_list.syntax().parent().and_then(ast::PathSegment::cast) {
-            if let Some(hir::PathResolution::Def(hir::ModuleDef::Trait(trait_))) =
-                ctx.sema.resolve_path(&path_seg.parent_path())
-            {
-                trait_.items(ctx.sema.db).into_iter().for_each(|it| {
-                    if let hir::AssocItem::TypeAlias(alias) = it {
-                        acc.add_type_alias_with_eq(ctx, alias)
-                    }
-                });
-            }
-        }
-    }
+    let const_param_ty = const_generic_arg::expected_const_param(ctx).map(|it| it.ty(ctx.db));
+    let matches_const_param = |ty: &hir::Type| match &const_param_ty {
+        Some(param_ty) => param_ty.could_unify_with(ctx.db, ty),
+        None => true,
+    };
+    let discriminant_ty = enum_discriminant::expected_discriminant_ty(ctx);
+    // only locals of the struct's type can be the base of a struct update
+    let struct_update_ty = match &ctx.completion_location {
+        Some(ImmediateLocation::RecordExprUpdate(_)) => ctx.expected_type.as_ref(),
+        _ => None,
+    };
 
     ctx.process_all_names(&mut |name, res| {
         let add_resolution = match res {
@@ -99,12 +125,23 @@ pub(crate) fn complete_unqualified_path(acc: &mut Completions, ctx: &CompletionC
                 hir::ModuleDef::Function(_)
                 | hir::ModuleDef::Variant(_)
                 | hir::ModuleDef::Static(_),
-            )
-            | ScopeDef::Local(_) => !ctx.expects_type(),
-            // unless its a constant in a generic arg list position
-            ScopeDef::ModuleDef(hir::ModuleDef::Const(_))
-            | ScopeDef::GenericParam(hir::GenericParam::ConstParam(_)) => {
-                !ctx.expects_type() || ctx.expects_generic_arg()
+            ) => !ctx.expects_type(),
+            ScopeDef::Local(it) => match struct_update_ty {
+                Some(ty) => ty.could_unify_with(ctx.db, &it.ty(ctx.db)),
+                None => !ctx.expects_type(),
+            },
+            // unless its a constant of the right type in a generic arg list position
+            ScopeDef::ModuleDef(hir::ModuleDef::Const(it)) => match &discriminant_ty {
+                // only constants of the enum's repr type in a discriminant
+                Some(ty) => ty.could_unify_with(ctx.db, &it.ty(ctx.db)),
+                None => {
+                    !ctx.expects_type()
+                        || ctx.expects_generic_arg() && matches_const_param(&it.ty(ctx.db))
+                }
+            },
+            ScopeDef::GenericParam(hir::GenericParam::ConstParam(it)) => {
+                !ctx.expects_type()
+                    || ctx.expects_generic_arg() && matches_const_param(&it.ty(ctx.db))
             }
             _ => true,
         };
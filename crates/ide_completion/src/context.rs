@@ -10,7 +10,7 @@ use ide_db::{
 use syntax::{
     algo::find_node_at_offset,
     ast::{self, HasName, NameOrNameRef},
-    match_ast, AstNode, NodeOrToken,
+    match_ast, AstNode, AstToken, NodeOrToken, SmolStr,
     SyntaxKind::{self, *},
     SyntaxNode, SyntaxToken, TextRange, TextSize, T,
 };
@@ -19,7 +19,7 @@ use text_edit::Indel;
 use crate::{
     patterns::{
         determine_location, determine_prev_sibling, for_is_prev2, inside_impl_trait_block,
-        is_in_loop_body, previous_token, ImmediateLocation, ImmediatePrevSibling,
+        is_in_loop_body, is_test_fn_name, previous_token, ImmediateLocation, ImmediatePrevSibling,
     },
     CompletionConfig,
 };
@@ -95,11 +95,16 @@ pub(crate) struct CompletionContext<'a> {
     /// The parent impl of the cursor position if it exists.
     pub(super) impl_def: Option<ast::Impl>,
     pub(super) name_syntax: Option<ast::NameLike>,
+    /// The name under the cursor, if it belongs to an existing path that resolves to nothing.
+    pub(super) unresolved_name: Option<SmolStr>,
 
     // potentially set if we are completing a lifetime
     pub(super) lifetime_param_syntax: Option<ast::LifetimeParam>,
     pub(super) lifetime_allowed: bool,
     pub(super) is_label_ref: bool,
+    /// Whether the label being completed is the target of a `continue`, which can't jump to
+    /// labeled blocks.
+    pub(super) is_continue_label_ref: bool,
 
     pub(super) completion_location: Option<ImmediateLocation>,
     pub(super) prev_sibling: Option<ImmediatePrevSibling>,
@@ -109,8 +114,21 @@ pub(crate) struct CompletionContext<'a> {
     pub(super) pattern_ctx: Option<PatternContext>,
     pub(super) path_context: Option<PathCompletionContext>,
     pub(super) locals: Vec<(String, Local)>,
+    /// Types that are likely to be used as generic arguments when completing in a turbofish, like
+    /// `"92".parse::<$0>()`: the types making up the inferred type of the call, and the types of
+    /// locals in scope.
+    pub(super) turbofish_types: Vec<Type>,
+    /// The traits already used as bounds by the item a bound is being completed in.
+    pub(super) item_bound_traits: Vec<hir::Trait>,
+    /// The variables bound by the pattern of the match arm whose guard is being completed.
+    pub(super) match_arm_bindings: Vec<Local>,
 
     pub(super) incomplete_let: bool,
+    /// Whether the name of a `#[test]` function in an inline module is being typed.
+    pub(super) is_test_fn_name: bool,
+
+    /// The range of the item name being typed in an intra-doc link, like `/// [`Fo$0`]`.
+    pub(super) doc_link_range: Option<TextRange>,
 
     no_completion_required: bool,
 }
@@ -139,7 +157,21 @@ impl<'a> CompletionContext<'a> {
         let original_token =
             original_file.syntax().token_at_offset(position.offset).left_biased()?;
         let token = sema.descend_into_macros_single(original_token.clone());
-        let scope = sema.scope_at_offset(&token, position.offset);
+        // A lone `'` starts an unterminated char literal, which swallows the rest of the item
+        // a lifetime is being typed in, so look up the scope from the token before it.
+        let is_lone_quote = original_token.kind() == CHAR
+            && original_token.text_range().start() + TextSize::of('\'') == position.offset;
+        let scope_token = if is_lone_quote {
+            let start = original_token.text_range().start();
+            original_file
+                .syntax()
+                .token_at_offset(start)
+                .left_biased()
+                .unwrap_or_else(|| token.clone())
+        } else {
+            token.clone()
+        };
+        let scope = sema.scope_at_offset(&scope_token, position.offset);
         let krate = scope.krate();
         let mut locals = vec![];
         scope.process_all_names(&mut |name, scope| {
@@ -161,9 +193,11 @@ impl<'a> CompletionContext<'a> {
             function_def: None,
             impl_def: None,
             name_syntax: None,
+            unresolved_name: None,
             lifetime_param_syntax: None,
             lifetime_allowed: false,
             is_label_ref: false,
+            is_continue_label_ref: false,
             pattern_ctx: None,
             completion_location: None,
             prev_sibling: None,
@@ -171,9 +205,15 @@ impl<'a> CompletionContext<'a> {
             previous_token: None,
             path_context: None,
             locals,
+            turbofish_types: Vec::new(),
+            item_bound_traits: Vec::new(),
+            match_arm_bindings: Vec::new(),
             incomplete_let: false,
+            is_test_fn_name: false,
+            doc_link_range: None,
             no_completion_required: false,
         };
+        ctx.doc_link_range = doc_link_range(&ctx.original_token, position.offset);
         ctx.expand_and_fill(
             original_file.syntax().clone(),
             file_with_fake_ident.syntax().clone(),
@@ -570,6 +610,7 @@ impl<'a> CompletionContext<'a> {
                             it.field_name().map(NameOrNameRef::NameRef),
                         )
                     },
+                    ast::MatchGuard(_it) => (None, None),
                     ast::MatchExpr(it) => {
                         cov_mark::hit!(expected_type_match_arm_without_leading_char);
                         let ty = it.expr().and_then(|e| self.sema.type_of_expr(&e)).map(TypeInfo::original);
@@ -617,6 +658,18 @@ impl<'a> CompletionContext<'a> {
         }
     }
 
+    fn unresolved_name(&self) -> Option<SmolStr> {
+        let name_ref = match self.name_syntax.as_ref()? {
+            ast::NameLike::NameRef(it) => it,
+            _ => return None,
+        };
+        let path = name_ref.syntax().ancestors().find_map(ast::Path::cast)?;
+        if self.sema.resolve_path(&path).is_some() {
+            return None;
+        }
+        Some(SmolStr::new(name_ref.text().as_str()))
+    }
+
     fn fill(
         &mut self,
         original_file: &SyntaxNode,
@@ -627,11 +680,14 @@ impl<'a> CompletionContext<'a> {
         let syntax_element = NodeOrToken::Token(fake_ident_token);
         self.previous_token = previous_token(syntax_element.clone());
         self.attribute_under_caret = syntax_element.ancestors().find_map(ast::Attr::cast);
+        self.is_test_fn_name = is_test_fn_name(syntax_element.clone());
         self.no_completion_required = {
             let inside_impl_trait_block = inside_impl_trait_block(syntax_element.clone());
             let fn_is_prev = self.previous_token_is(T![fn]);
             let for_is_prev2 = for_is_prev2(syntax_element.clone());
-            (fn_is_prev && !inside_impl_trait_block) || for_is_prev2
+            let suggests_test_name =
+                self.is_test_fn_name && self.config.enable_test_name_suggestions;
+            (fn_is_prev && !inside_impl_trait_block && !suggests_test_name) || for_is_prev2
         };
 
         self.incomplete_let =
@@ -650,8 +706,12 @@ impl<'a> CompletionContext<'a> {
         self.completion_location =
             determine_location(&self.sema, original_file, offset, &name_like);
         self.prev_sibling = determine_prev_sibling(&name_like);
+        self.turbofish_types = self.turbofish_types();
+        self.item_bound_traits = self.item_bound_traits();
+        self.match_arm_bindings = self.match_arm_bindings(original_file, &name_like);
         self.name_syntax =
             find_node_at_offset(original_file, name_like.syntax().text_range().start());
+        self.unresolved_name = self.unresolved_name();
         match name_like {
             ast::NameLike::Lifetime(lifetime) => {
                 self.classify_lifetime(original_file, lifetime, offset);
@@ -665,6 +725,90 @@ impl<'a> CompletionContext<'a> {
         }
     }
 
+    fn turbofish_types(&self) -> Vec<Type> {
+        let arg_list = match &self.completion_location {
+            Some(ImmediateLocation::GenericArgList(it)) if it.coloncolon_token().is_some() => it,
+            _ => return Vec::new(),
+        };
+        let call = arg_list.syntax().ancestors().find_map(|node| {
+            match_ast! {
+                match node {
+                    ast::MethodCallExpr(it) => Some(ast::Expr::from(it)),
+                    ast::CallExpr(it) => Some(ast::Expr::from(it)),
+                    _ => None,
+                }
+            }
+        });
+
+        let mut types = Vec::new();
+        if let Some(call_ty) = call.and_then(|it| self.sema.type_of_expr(&it)) {
+            let mut stack = vec![call_ty.original];
+            while let Some(ty) = stack.pop() {
+                stack.extend(ty.type_arguments());
+                types.push(ty);
+            }
+        }
+        types.extend(self.locals.iter().map(|(_, local)| local.ty(self.db)));
+        types.retain(|ty| !ty.is_unknown() && !ty.is_unit());
+        types
+    }
+
+    fn item_bound_traits(&self) -> Vec<hir::Trait> {
+        if !matches!(self.completion_location, Some(ImmediateLocation::TypeBound(_))) {
+            return Vec::new();
+        }
+        let item = match self.token.ancestors().find_map(ast::AnyHasGenericParams::cast) {
+            Some(it) => it,
+            None => return Vec::new(),
+        };
+        let mut traits = Vec::new();
+        for bound in item.syntax().descendants().filter_map(ast::TypeBound::cast) {
+            // Skip the bounds of items nested in a function body.
+            let mut ancestors = bound.syntax().ancestors().take_while(|it| it != item.syntax());
+            if ancestors.any(|it| ast::BlockExpr::can_cast(it.kind())) {
+                continue;
+            }
+            let path = match bound.ty() {
+                Some(ast::Type::PathType(it)) => it.path(),
+                _ => None,
+            };
+            if let Some(hir::PathResolution::Def(hir::ModuleDef::Trait(it))) =
+                path.and_then(|it| self.sema.resolve_path(&it))
+            {
+                traits.push(it);
+            }
+        }
+        traits
+    }
+
+    fn match_arm_bindings(
+        &self,
+        original_file: &SyntaxNode,
+        name_like: &ast::NameLike,
+    ) -> Vec<Local> {
+        let guard = match name_like.syntax().ancestors().find_map(ast::MatchGuard::cast) {
+            Some(it) => it,
+            None => return Vec::new(),
+        };
+        let pat =
+            match guard.syntax().parent().and_then(ast::MatchArm::cast).and_then(|it| it.pat()) {
+                Some(it) => it,
+                None => return Vec::new(),
+            };
+        // The pattern comes before the cursor, so its offsets are the same in the original file.
+        pat.syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter_map(|it| {
+                find_node_at_offset::<ast::IdentPat>(
+                    original_file,
+                    it.syntax().text_range().start(),
+                )
+            })
+            .filter_map(|it| self.sema.to_def(&it))
+            .collect()
+    }
+
     fn classify_lifetime(
         &mut self,
         original_file: &SyntaxNode,
@@ -684,7 +828,10 @@ impl<'a> CompletionContext<'a> {
                             self.sema.find_node_at_offset_with_macros(original_file, offset);
                     },
                     ast::BreakExpr(_it) => self.is_label_ref = true,
-                    ast::ContinueExpr(_it) => self.is_label_ref = true,
+                    ast::ContinueExpr(_it) => {
+                        self.is_label_ref = true;
+                        self.is_continue_label_ref = true;
+                    },
                     ast::Label(_it) => (),
                     _ => self.lifetime_allowed = true,
                 }
@@ -861,6 +1008,30 @@ fn has_ref(token: &SyntaxToken) -> bool {
     token.kind() == T![&]
 }
 
+/// Returns the range of the (possibly empty) item name before the cursor if
+/// it is inside the brackets of a Markdown link in a doc comment.
+fn doc_link_range(token: &SyntaxToken, offset: TextSize) -> Option<TextRange> {
+    let comment = ast::Comment::cast(token.clone())?;
+    comment.kind().doc?;
+    let comment_start = comment.syntax().text_range().start();
+    let text = comment.text();
+    let (before, after) = text.split_at(usize::from(offset - comment_start));
+    let link_start = before.rfind('[')?;
+    let link = before[link_start + 1..].trim_start_matches('`');
+    if !link.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    // The link has to be closed on the same line.
+    let rest = after.split('\n').next().unwrap_or_default();
+    if !rest
+        .trim_start_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '`')
+        .starts_with(']')
+    {
+        return None;
+    }
+    Some(TextRange::new(offset - TextSize::of(link), offset))
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::{expect, Expect};
@@ -148,6 +148,16 @@ pub struct CompletionRelevance {
     /// This is set in cases like these:
     ///
     /// ```
+    /// match opt {
+    ///     Some(it) if $0 => {} // `it` is bound by the pattern of the arm
+    /// }
+    /// ```
+    pub is_match_arm_binding: bool,
+    /// This is set for postfix snippets, see [`CompletionRelevancePostfixSnippet`].
+    pub postfix_snippet: Option<CompletionRelevancePostfixSnippet>,
+    /// This is set in cases like these:
+    ///
+    /// ```
     /// (a > b).not$0
     /// ```
     ///
@@ -180,7 +190,21 @@ pub enum CompletionRelevanceTypeMatch {
     Exact,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CompletionRelevancePostfixSnippet {
+    /// A snippet that is offered for any receiver, like `expr.ref`. These rank
+    /// below everything else.
+    Plain,
+    /// A snippet that is only offered after checking the type of the receiver,
+    /// like `expr.clone` for `Clone` types. These rank above plain snippets, but
+    /// below other completions.
+    TypeChecked,
+}
+
 impl CompletionRelevance {
+    /// Score of an item without any relevance factors, postfix snippets rank below it.
+    const BASE_SCORE: u32 = 2;
+
     /// Provides a relevance score. Higher values are more relevant.
     ///
     /// The absolute value of the relevance score is not meaningful, for
@@ -191,8 +215,13 @@ impl CompletionRelevance {
     /// See is_relevant if you need to make some judgement about score
     /// in an absolute sense.
     pub fn score(&self) -> u32 {
-        let mut score = 0;
+        let mut score = Self::BASE_SCORE;
 
+        score -= match self.postfix_snippet {
+            Some(CompletionRelevancePostfixSnippet::Plain) => 2,
+            Some(CompletionRelevancePostfixSnippet::TypeChecked) => 1,
+            None => 0,
+        };
         if self.exact_name_match {
             score += 1;
         }
@@ -204,6 +233,9 @@ impl CompletionRelevance {
         if self.is_local {
             score += 1;
         }
+        if self.is_match_arm_binding {
+            score += 10;
+        }
         if self.exact_postfix_snippet_match {
             score += 100;
         }
@@ -214,7 +246,7 @@ impl CompletionRelevance {
     /// some threshold such that we think it is especially likely
     /// to be relevant.
     pub fn is_relevant(&self) -> bool {
-        self.score() > 0
+        self.score() > Self::BASE_SCORE
     }
 }
 
@@ -546,7 +578,9 @@ mod tests {
     use itertools::Itertools;
     use test_utils::assert_eq_text;
 
-    use super::{CompletionRelevance, CompletionRelevanceTypeMatch};
+    use super::{
+        CompletionRelevance, CompletionRelevancePostfixSnippet, CompletionRelevanceTypeMatch,
+    };
 
     /// Check that these are CompletionRelevance are sorted in ascending order
     /// by their relevance score.
@@ -589,6 +623,14 @@ mod tests {
         // This test asserts that the relevance score for these items is ascending, and
         // that any items in the same vec have the same score.
         let expected_relevance_order = vec![
+            vec![CompletionRelevance {
+                postfix_snippet: Some(CompletionRelevancePostfixSnippet::Plain),
+                ..CompletionRelevance::default()
+            }],
+            vec![CompletionRelevance {
+                postfix_snippet: Some(CompletionRelevancePostfixSnippet::TypeChecked),
+                ..CompletionRelevance::default()
+            }],
             vec![CompletionRelevance::default()],
             vec![
                 CompletionRelevance { exact_name_match: true, ..CompletionRelevance::default() },
@@ -618,10 +660,17 @@ mod tests {
                 is_local: true,
                 ..CompletionRelevance::default()
             }],
+            vec![CompletionRelevance {
+                is_local: true,
+                is_match_arm_binding: true,
+                ..CompletionRelevance::default()
+            }],
             vec![CompletionRelevance {
                 exact_name_match: false,
                 type_match: None,
                 is_local: false,
+                is_match_arm_binding: false,
+                postfix_snippet: None,
                 exact_postfix_snippet_match: true,
             }],
         ];
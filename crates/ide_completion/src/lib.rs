@@ -157,17 +157,30 @@ pub fn completions(
     completions::snippet::complete_expr_snippet(&mut acc, &ctx);
     completions::snippet::complete_item_snippet(&mut acc, &ctx);
     completions::qualified_path::complete_qualified_path(&mut acc, &ctx);
+    completions::assoc_type_binding::complete_associated_type_binding(&mut acc, &ctx);
     completions::unqualified_path::complete_unqualified_path(&mut acc, &ctx);
+    completions::closure::complete_closure_parameter_type(&mut acc, &ctx);
+    completions::const_generic_arg::complete_const_generic_arg(&mut acc, &ctx);
+    completions::enum_discriminant::complete_enum_discriminant(&mut acc, &ctx);
+    completions::async_fn_return_type::complete_async_fn_return_type(&mut acc, &ctx);
+    completions::match_arm_guard::complete_match_arm_guard(&mut acc, &ctx);
     completions::dot::complete_dot(&mut acc, &ctx);
     completions::record::complete_record(&mut acc, &ctx);
     completions::record::complete_record_literal(&mut acc, &ctx);
+    completions::record::complete_record_struct_update(&mut acc, &ctx);
     completions::pattern::complete_pattern(&mut acc, &ctx);
     completions::postfix::complete_postfix(&mut acc, &ctx);
     completions::trait_impl::complete_trait_impl(&mut acc, &ctx);
+    completions::impl_block::complete_impl_block_for_unimplemented_trait(&mut acc, &ctx);
     completions::mod_::complete_mod(&mut acc, &ctx);
+    completions::extern_crate::complete_extern_crate(&mut acc, &ctx);
+    completions::macro_rules::complete_macro_rules_fragment_specifier(&mut acc, &ctx);
+    completions::format_string::complete_format_specifier(&mut acc, &ctx);
     completions::flyimport::import_on_the_fly(&mut acc, &ctx);
     completions::lifetime::complete_lifetime(&mut acc, &ctx);
     completions::lifetime::complete_label(&mut acc, &ctx);
+    completions::doc_link::complete_doc_link_path(&mut acc, &ctx);
+    completions::test_name::complete_test_name(&mut acc, &ctx);
 
     Some(acc)
 }
@@ -8,7 +8,7 @@ use hir::Semantics;
 use ide_db::RootDatabase;
 use syntax::{
     algo::non_trivia_sibling,
-    ast::{self, HasArgList, HasLoopBody},
+    ast::{self, HasArgList, HasAttrs, HasLoopBody},
     match_ast, AstNode, Direction, SyntaxElement,
     SyntaxKind::*,
     SyntaxNode, SyntaxToken, TextRange, TextSize, T,
@@ -36,6 +36,7 @@ pub(crate) enum ImmediateLocation {
     Use,
     UseTree,
     Rename,
+    ExternCrate,
     Impl,
     Trait,
     RecordField,
@@ -44,11 +45,16 @@ pub(crate) enum ImmediateLocation {
     IdentPat,
     StmtList,
     ItemList,
-    TypeBound,
+    /// Fake file ast node
+    TypeBound(ast::TypeBoundList),
     /// Fake file ast node
     Attribute(ast::Attr),
     /// Fake file ast node
     ModDeclaration(ast::Module),
+    /// The variant whose discriminant we are completing
+    ///
+    /// Fake file ast node
+    VariantDiscriminant(ast::Variant),
     Visibility(ast::Visibility),
     /// Original file ast node
     MethodCall {
@@ -63,6 +69,10 @@ pub(crate) enum ImmediateLocation {
     // Only set from a type arg
     /// Original file ast node
     GenericArgList(ast::GenericArgList),
+    /// The associated type binding whose name we are completing
+    ///
+    /// Original file ast node
+    AssocTypeArg(ast::AssocTypeArg),
     /// The record expr of the field name we are completing
     ///
     /// Original file ast node
@@ -172,7 +182,7 @@ pub(crate) fn determine_location(
 
     match_ast! {
         match node {
-            ast::TypeBoundList(_it) => return Some(ImmediateLocation::TypeBound),
+            ast::TypeBoundList(it) => return Some(ImmediateLocation::TypeBound(it)),
             _ => (),
         }
     };
@@ -209,6 +219,7 @@ pub(crate) fn determine_location(
             ast::UseTree(_it) => ImmediateLocation::UseTree,
             ast::UseTreeList(_it) => ImmediateLocation::UseTree,
             ast::Rename(_it) => ImmediateLocation::Rename,
+            ast::ExternCrate(_it) => ImmediateLocation::ExternCrate,
             ast::StmtList(_it) => ImmediateLocation::StmtList,
             ast::SourceFile(_it) => ImmediateLocation::ItemList,
             ast::ItemList(_it) => ImmediateLocation::ItemList,
@@ -223,8 +234,10 @@ pub(crate) fn determine_location(
                 .map(ImmediateLocation::RecordExprUpdate)?,
             ast::TupleField(_it) => ImmediateLocation::TupleField,
             ast::TupleFieldList(_it) => ImmediateLocation::TupleField,
-            ast::TypeBound(_it) => ImmediateLocation::TypeBound,
-            ast::TypeBoundList(_it) => ImmediateLocation::TypeBound,
+            ast::TypeBound(it) => {
+                ImmediateLocation::TypeBound(ast::TypeBoundList::cast(it.syntax().parent()?)?)
+            },
+            ast::TypeBoundList(it) => ImmediateLocation::TypeBound(it),
             ast::AssocItemList(it) => match it.syntax().parent().map(|it| it.kind()) {
                 Some(IMPL) => ImmediateLocation::Impl,
                 Some(TRAIT) => ImmediateLocation::Trait,
@@ -233,6 +246,12 @@ pub(crate) fn determine_location(
             ast::GenericArgList(_it) => sema
                 .find_node_at_offset_with_macros(original_file, offset)
                 .map(ImmediateLocation::GenericArgList)?,
+            ast::AssocTypeArg(it) => if it.name_ref().map_or(false, |it| it.syntax() == &node) {
+                sema.find_node_at_offset_with_macros(original_file, offset)
+                    .map(ImmediateLocation::AssocTypeArg)?
+            } else {
+                return None;
+            },
             ast::Module(it) => {
                 if it.item_list().is_none() {
                     ImmediateLocation::ModDeclaration(it)
@@ -241,6 +260,11 @@ pub(crate) fn determine_location(
                 }
             },
             ast::Attr(it) => ImmediateLocation::Attribute(it),
+            ast::Variant(it) => if it.expr().map_or(false, |it| it.syntax() == &node) {
+                ImmediateLocation::VariantDiscriminant(it)
+            } else {
+                return None;
+            },
             ast::FieldExpr(it) => {
                 let receiver = it
                     .expr()
@@ -337,6 +361,27 @@ fn test_for_is_prev2() {
     check_pattern_is_applicable(r"for i i$0", for_is_prev2);
 }
 
+/// Check if the element is the name of a `#[test]` function in an inline module.
+/// For example, `mod tests { #[test] fn t$0 }` => true.
+pub(crate) fn is_test_fn_name(element: SyntaxElement) -> bool {
+    element
+        .ancestors()
+        .find_map(ast::Name::cast)
+        .and_then(|it| it.syntax().parent())
+        .and_then(ast::Fn::cast)
+        .filter(|it| it.attrs().any(|attr| attr.simple_name().as_deref() == Some("test")))
+        .and_then(|it| it.syntax().parent())
+        .and_then(|it| it.parent())
+        .map_or(false, |it| it.kind() == MODULE)
+}
+#[test]
+fn test_is_test_fn_name() {
+    check_pattern_is_applicable(r"mod tests { #[test] fn t$0 }", is_test_fn_name);
+    check_pattern_is_applicable(r"mod tests { #[test] fn t$0() {} }", is_test_fn_name);
+    check_pattern_is_not_applicable(r"mod tests { fn t$0 }", is_test_fn_name);
+    check_pattern_is_not_applicable(r"#[test] fn t$0", is_test_fn_name);
+}
+
 pub(crate) fn is_in_loop_body(node: &SyntaxNode) -> bool {
     node.ancestors()
         .take_while(|it| it.kind() != FN && it.kind() != CLOSURE_EXPR)
@@ -423,6 +468,13 @@ mod tests {
         check_location(r"use {f$0}", ImmediateLocation::UseTree);
     }
 
+    #[test]
+    fn test_extern_crate_loc() {
+        check_location(r"extern crate f$0", ImmediateLocation::ExternCrate);
+        check_location(r"extern crate f$0;", ImmediateLocation::ExternCrate);
+        check_location(r"extern crate foo as b$0;", ImmediateLocation::Rename);
+    }
+
     #[test]
     fn test_record_field_loc() {
         check_location(r"struct Foo { f$0 }", ImmediateLocation::RecordField);
@@ -83,7 +83,10 @@ pub(crate) fn render_field(
     ty: &hir::Type,
 ) -> CompletionItem {
     let is_deprecated = ctx.is_deprecated(field);
-    let name = field.name(ctx.db()).to_string();
+    let field_name = field.name(ctx.db());
+    // `to_string` keeps the `r#` prefix of raw fields, `to_smol_str` is what the user types.
+    let name = field_name.to_string();
+    let text = field_name.to_smol_str();
     let mut item = CompletionItem::new(
         CompletionKind::Reference,
         ctx.source_range(),
@@ -91,16 +94,19 @@ pub(crate) fn render_field(
     );
     item.set_relevance(CompletionRelevance {
         type_match: compute_type_match(ctx.completion, ty),
-        exact_name_match: compute_exact_name_match(ctx.completion, name.as_str()),
+        exact_name_match: compute_exact_name_match(ctx.completion, text.as_str()),
         ..CompletionRelevance::default()
     });
     item.kind(SymbolKind::Field)
         .detail(ty.display(ctx.db()).to_string())
         .set_documentation(field.docs(ctx.db()))
         .set_deprecated(is_deprecated)
-        .lookup_by(name.as_str());
-    let is_keyword = SyntaxKind::from_keyword(name.as_str()).is_some();
-    if is_keyword && !matches!(name.as_str(), "self" | "crate" | "super" | "Self") {
+        .lookup_by(text.as_str());
+    let is_keyword = SyntaxKind::from_keyword(text.as_str()).is_some();
+    if is_keyword
+        && !field_name.is_raw()
+        && !matches!(text.as_str(), "self" | "crate" | "super" | "Self")
+    {
         item.insert_text(format!("r#{}", name));
     }
     if let Some(_ref_match) = compute_ref_match(ctx.completion, ty) {
@@ -232,6 +238,7 @@ fn render_resolution_(
             type_match: compute_type_match(ctx.completion, &ty),
             exact_name_match: compute_exact_name_match(ctx.completion, &local_name),
             is_local: true,
+            is_match_arm_binding: ctx.completion.match_arm_bindings.contains(local),
             ..CompletionRelevance::default()
         });
 
@@ -240,6 +247,36 @@ fn render_resolution_(
         }
     };
 
+    if !ctx.completion.turbofish_types.is_empty() {
+        let ty = match resolution {
+            hir::ScopeDef::ModuleDef(Adt(it)) => Some(it.ty(ctx.db())),
+            hir::ScopeDef::ModuleDef(TypeAlias(it)) => Some(it.ty(ctx.db())),
+            hir::ScopeDef::ModuleDef(BuiltinType(it)) => {
+                ctx.completion.scope.module().map(|module| it.ty(ctx.db(), module))
+            }
+            _ => None,
+        };
+        let is_turbofish_type = ty.map_or(false, |ty| {
+            ctx.completion.turbofish_types.iter().any(|it| it.could_unify_with(ctx.db(), &ty))
+        });
+        if is_turbofish_type {
+            item.set_relevance(CompletionRelevance {
+                type_match: Some(CompletionRelevanceTypeMatch::CouldUnify),
+                ..CompletionRelevance::default()
+            });
+        }
+    }
+
+    if let hir::ScopeDef::ModuleDef(Trait(it)) = resolution {
+        if ctx.completion.item_bound_traits.contains(it) {
+            cov_mark::hit!(ranks_item_bound_traits);
+            item.set_relevance(CompletionRelevance {
+                type_match: Some(CompletionRelevanceTypeMatch::CouldUnify),
+                ..CompletionRelevance::default()
+            });
+        }
+    }
+
     // Add `<>` for generic types
     if matches!(
         ctx.completion.path_context,
@@ -400,6 +437,7 @@ mod tests {
                 ),
                 (relevance.exact_name_match, "name"),
                 (relevance.is_local, "local"),
+                (relevance.is_match_arm_binding, "arm_binding"),
                 (relevance.exact_postfix_snippet_match, "snippet"),
             ]
             .into_iter()
@@ -552,6 +590,8 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            is_match_arm_binding: false,
+                            postfix_snippet: None,
                             exact_postfix_snippet_match: false,
                         },
                         trigger_call_info: true,
@@ -581,6 +621,8 @@ fn main() { let _: m::Spam = S$0 }
                                 Exact,
                             ),
                             is_local: false,
+                            is_match_arm_binding: false,
+                            postfix_snippet: None,
                             exact_postfix_snippet_match: false,
                         },
                     },
@@ -675,6 +717,8 @@ fn foo() { A { the$0 } }
                                 CouldUnify,
                             ),
                             is_local: false,
+                            is_match_arm_binding: false,
+                            postfix_snippet: None,
                             exact_postfix_snippet_match: false,
                         },
                     },
@@ -1423,4 +1467,149 @@ fn main() {
             "#]],
         );
     }
+
+    #[test]
+    fn item_bound_trait_relevance() {
+        cov_mark::check!(ranks_item_bound_traits);
+        check_relevance(
+            r#"
+trait Clone {}
+trait Debug {}
+trait Other {}
+fn foo<T: Clone, U>(t: T, u: impl Debug) where U: $0 {
+    trait Inner {}
+    fn inner<V: Inner>() {}
+}
+"#,
+            expect![[r#"
+                tt Debug [type_could_unify]
+                tt Clone [type_could_unify]
+                lt 'static []
+                tt Inner []
+                tt Other []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn closure_template_relevance() {
+        check_relevance_for_kinds(
+            &[CompletionKind::Reference, CompletionKind::Snippet],
+            r#"
+//- minicore: fn
+fn apply(f: impl Fn(i32)) {}
+fn main() {
+    let g = |_: i32| ();
+    apply($0)
+}
+"#,
+            expect![[r#"
+                lc g [type_could_unify+local]
+                sn |x: i32| [type]
+                fn main() [type_could_unify]
+                fn apply(…) [type_could_unify]
+                md core []
+                tt FnMut []
+                tt FnOnce []
+                tt Fn []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn match_arm_binding_relevance() {
+        check_relevance(
+            r#"
+enum Shape { Circle(u32), Square(u32) }
+fn main() {
+    let limit = 92;
+    match Shape::Circle(1) {
+        Shape::Circle(radius) if $0 => (),
+        _ => (),
+    }
+}
+"#,
+            expect![[r#"
+                lc radius [local+arm_binding]
+                lc limit [local]
+                fn main() []
+                en Shape []
+            "#]],
+        );
+    }
+
+    #[test]
+    fn turbofish_type_relevance() {
+        check_relevance_for_kinds(
+            &[CompletionKind::Reference, CompletionKind::BuiltinType],
+            r#"
+struct Str;
+struct Error;
+impl Str {
+    fn parse<F>(&self) -> Result<F, Error> { loop {} }
+}
+enum Result<T, E> { Ok(T), Err(E) }
+fn main() {
+    let s = Str;
+    let x: Result<u32, Error> = s.parse::<$0>();
+}
+"#,
+            expect![[r#"
+                st Str [type_could_unify]
+                en Result<…> [type_could_unify]
+                st Error [type_could_unify]
+                bt u32 [type_could_unify]
+                bt bool []
+                bt u8 []
+                bt isize []
+                bt u16 []
+                bt u64 []
+                bt u128 []
+                bt f32 []
+                bt i128 []
+                bt i16 []
+                bt str []
+                bt i64 []
+                bt char []
+                bt f64 []
+                bt i32 []
+                bt i8 []
+                bt usize []
+            "#]],
+        );
+        check_relevance_for_kinds(
+            &[CompletionKind::Reference, CompletionKind::BuiltinType],
+            r#"
+struct String;
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+}
+fn main() {
+    let v: Vec<String> = Vec::<$0>::new();
+}
+"#,
+            expect![[r#"
+                st String [type_could_unify]
+                st Vec<…> [type_could_unify]
+                bt u32 []
+                bt bool []
+                bt u8 []
+                bt isize []
+                bt u16 []
+                bt u64 []
+                bt u128 []
+                bt f32 []
+                bt i128 []
+                bt i16 []
+                bt str []
+                bt i64 []
+                bt char []
+                bt f64 []
+                bt i32 []
+                bt i8 []
+                bt usize []
+            "#]],
+        );
+    }
 }
@@ -47,7 +47,22 @@ use self as this;
 
 #[test]
 fn inside_nested_attr() {
-    check(r#"#[cfg($0)]"#, expect![[]])
+    check(
+        r#"#[cfg($0)]"#,
+        expect![[r#"
+            at debug_assertions
+            at feature
+            at proc_macro
+            at target_arch
+            at target_endian
+            at target_env
+            at target_os
+            at target_pointer_width
+            at target_vendor
+            at unix
+            at windows
+        "#]],
+    )
 }
 
 #[test]
@@ -555,6 +570,87 @@ fn attr_in_source_file_end() {
 mod cfg {
     use super::*;
 
+    #[test]
+    fn cfg_keys() {
+        check(
+            r#"
+//- /main.rs cfg:feature=serde,my_flag
+#[cfg($0)]
+"#,
+            expect![[r#"
+                at debug_assertions
+                at feature
+                at proc_macro
+                at target_arch
+                at target_endian
+                at target_env
+                at target_os
+                at target_pointer_width
+                at target_vendor
+                at unix
+                at windows
+                at my_flag
+            "#]],
+        );
+    }
+
+    #[test]
+    fn cfg_nested_keys() {
+        check(
+            r#"#[cfg(all(unix, t$0))]"#,
+            expect![[r#"
+                at debug_assertions
+                at feature
+                at proc_macro
+                at target_arch
+                at target_endian
+                at target_env
+                at target_os
+                at target_pointer_width
+                at target_vendor
+                at unix
+                at windows
+            "#]],
+        );
+    }
+
+    #[test]
+    fn cfg_feature_value_in_string() {
+        check(
+            r#"
+//- /main.rs cfg:feature=serde,feature=std
+#[cfg(feature = "$0")]
+"#,
+            expect![[r#"
+                at serde
+                at std
+            "#]],
+        );
+        check_edit(
+            "serde",
+            r#"
+//- /main.rs cfg:feature=serde,feature=std
+#[cfg(feature = "se$0")]
+"#,
+            r#"
+#[cfg(feature = "serde")]
+"#,
+        );
+    }
+
+    #[test]
+    fn cfg_attr_predicate() {
+        check(
+            r#"#[cfg_attr(target_pointer_width = $0, derive(Debug))]"#,
+            expect![[r#"
+                at 16
+                at 32
+                at 64
+            "#]],
+        );
+        check(r#"#[cfg_attr(unix, $0)]"#, expect![[]]);
+    }
+
     #[test]
     fn cfg_target_endian() {
         check(
@@ -335,3 +335,40 @@ fn func() {
         "#]],
     );
 }
+
+#[test]
+fn completes_locals_in_format_string_placeholder() {
+    check_empty(
+        r#"
+#[rustc_builtin_macro]
+macro_rules! format_args {
+    ($fmt:expr) => ({ /* compiler built-in */ });
+    ($fmt:expr, $($args:tt)*) => ({ /* compiler built-in */ })
+}
+fn main() {
+    let name = 92;
+    format_args!("{} {na$0}", 1);
+}
+"#,
+        expect![[r#"
+            kw unsafe
+            kw match
+            kw while
+            kw while let
+            kw loop
+            kw if
+            kw if let
+            kw for
+            kw true
+            kw false
+            kw return
+            kw self
+            kw super
+            kw crate
+            lc name            i32
+            bt u32
+            fn main()          fn()
+            ma format_args!(…) macro_rules! format_args
+        "#]],
+    );
+}
@@ -174,7 +174,8 @@ fn in_impl_assoc_item_list() {
             kw super
             kw crate
             md module
-            ma makro!(…)  #[macro_export] macro_rules! makro
+            ma makro!(…)             #[macro_export] macro_rules! makro
+            im impl Trait for Struct
         "##]],
     )
 }
@@ -43,6 +43,8 @@ struct Foo<'lt, T, const C: usize> where T: $0 {}
             kw self
             kw super
             kw crate
+            kw ?Sized
+            lt 'static
             tt Trait
             md module
             ma makro!(…) #[macro_export] macro_rules! makro
@@ -52,20 +54,18 @@ struct Foo<'lt, T, const C: usize> where T: $0 {}
 
 #[test]
 fn bound_for_lifetime_pred() {
-    // FIXME: should only show lifetimes here, that is we shouldn't get any completions here when not typing
-    // a `'`
+    cov_mark::check!(only_lifetimes_bound_lifetimes);
     check(
         r#"
 struct Foo<'lt, T, const C: usize> where 'lt: $0 {}
 "#,
-        expect![[r##"
+        expect![[r#"
             kw self
             kw super
             kw crate
-            tt Trait
-            md module
-            ma makro!(…) #[macro_export] macro_rules! makro
-        "##]],
+            lt 'lt
+            lt 'static
+        "#]],
     );
 }
 
@@ -79,6 +79,8 @@ struct Foo<'lt, T, const C: usize> where for<'a> T: $0 {}
             kw self
             kw super
             kw crate
+            kw ?Sized
+            lt 'static
             tt Trait
             md module
             ma makro!(…) #[macro_export] macro_rules! makro
@@ -135,3 +137,48 @@ impl Record {
         "##]],
     );
 }
+
+#[test]
+fn lifetime_bound_for_lifetime_pred() {
+    check(
+        r#"
+struct Foo<'lt, 'lt2, T> where 'lt: '$0 {}
+"#,
+        expect![[r#"
+            lt 'lt
+            lt 'lt2
+            lt 'static
+        "#]],
+    );
+}
+
+#[test]
+fn lifetime_bound_for_type_pred() {
+    check(
+        r#"
+fn foo<'lt, T>(t: &'lt T) where T: '$0 {}
+"#,
+        expect![[r#"
+            lt 'lt
+            lt 'static
+        "#]],
+    );
+}
+
+#[test]
+fn bound_in_impl_trait() {
+    check(
+        r#"
+fn foo(_: impl $0) {}
+"#,
+        expect![[r##"
+            kw self
+            kw super
+            kw crate
+            lt 'static
+            tt Trait
+            md module
+            ma makro!(…) #[macro_export] macro_rules! makro
+        "##]],
+    );
+}
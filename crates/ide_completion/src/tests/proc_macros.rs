@@ -1,7 +1,14 @@
 //! Completion tests for expressions.
+use std::sync::Arc;
+
+use base_db::{
+    fixture::ChangeFixture, Env, FilePosition, ProcMacro, ProcMacroExpander,
+    ProcMacroExpansionError, ProcMacroKind,
+};
 use expect_test::{expect, Expect};
+use ide_db::RootDatabase;
 
-use crate::tests::completion_list;
+use crate::tests::{completion_list, TEST_CONFIG};
 
 fn check(ra_fixture: &str, expect: Expect) {
     let actual = completion_list(ra_fixture);
@@ -143,3 +150,137 @@ fn main() {}
         "#]],
     )
 }
+
+#[test]
+fn complete_path_in_attr() {
+    check(
+        r#"
+//- proc_macros: identity
+pub struct Foo;
+
+#[proc_macros::identity]
+fn main() {
+    let local = Foo;
+    lo$0
+}
+"#,
+        expect![[r#"
+            kw unsafe
+            kw fn
+            kw const
+            kw type
+            kw impl
+            kw extern
+            kw use
+            kw trait
+            kw static
+            kw mod
+            kw match
+            kw while
+            kw while let
+            kw loop
+            kw if
+            kw if let
+            kw for
+            kw true
+            kw false
+            kw let
+            kw return
+            sn pd
+            sn ppd
+            kw self
+            kw super
+            kw crate
+            lc local       Foo
+            st Foo
+            fn main()      fn()
+            md proc_macros
+            bt u32
+        "#]],
+    )
+}
+
+/// Derives `impl Name { pub fn builder() -> Self }` for a struct `Name`.
+#[derive(Debug)]
+struct BuilderDeriveExpander;
+impl ProcMacroExpander for BuilderDeriveExpander {
+    fn expand(
+        &self,
+        subtree: &tt::Subtree,
+        _: Option<&tt::Subtree>,
+        _: &Env,
+    ) -> Result<tt::Subtree, ProcMacroExpansionError> {
+        fn ident(text: &str) -> tt::TokenTree {
+            tt::Leaf::from(tt::Ident { text: text.into(), id: tt::TokenId::unspecified() }).into()
+        }
+        fn punct(char: char, spacing: tt::Spacing) -> tt::TokenTree {
+            tt::Leaf::from(tt::Punct { char, spacing, id: tt::TokenId::unspecified() }).into()
+        }
+        fn group(kind: tt::DelimiterKind, token_trees: Vec<tt::TokenTree>) -> tt::TokenTree {
+            let delimiter = Some(tt::Delimiter { id: tt::TokenId::unspecified(), kind });
+            tt::Subtree { delimiter, token_trees }.into()
+        }
+
+        let name = subtree
+            .token_trees
+            .iter()
+            .skip_while(
+                |tt| !matches!(tt, tt::TokenTree::Leaf(tt::Leaf::Ident(it)) if it.text == "struct"),
+            )
+            .nth(1)
+            .cloned()
+            .ok_or_else(|| ProcMacroExpansionError::Panic("expected a struct".into()))?;
+        let builder = vec![
+            ident("pub"),
+            ident("fn"),
+            ident("builder"),
+            group(tt::DelimiterKind::Parenthesis, Vec::new()),
+            punct('-', tt::Spacing::Joint),
+            punct('>', tt::Spacing::Alone),
+            ident("Self"),
+            group(
+                tt::DelimiterKind::Brace,
+                vec![ident("loop"), group(tt::DelimiterKind::Brace, Vec::new())],
+            ),
+        ];
+        let token_trees = vec![ident("impl"), name, group(tt::DelimiterKind::Brace, builder)];
+        Ok(tt::Subtree { delimiter: None, token_trees })
+    }
+}
+
+#[test]
+fn complete_items_from_custom_derive() {
+    let change_fixture = ChangeFixture::parse_with_proc_macros(
+        r#"
+#[derive(proc_macros::Builder)]
+struct Foo;
+
+fn main() {
+    Foo::$0
+}
+"#,
+        vec![(
+            "#[proc_macro_derive(Builder)]\npub fn builder(item: TokenStream) -> TokenStream { item }"
+                .to_string(),
+            ProcMacro {
+                name: "Builder".into(),
+                kind: ProcMacroKind::CustomDerive,
+                expander: Arc::new(BuilderDeriveExpander),
+            },
+        )],
+    );
+    let mut db = RootDatabase::default();
+    db.apply_change(change_fixture.change);
+    let (file_id, range_or_offset) = change_fixture.file_position.unwrap();
+    let position = FilePosition { file_id, offset: range_or_offset.expect_offset() };
+
+    let items =
+        crate::completions(&db, &TEST_CONFIG, position).map_or_else(Vec::default, Vec::from);
+    let labels: Vec<_> = items.iter().map(|it| it.label().to_string()).collect();
+    expect![[r#"
+        [
+            "builder()",
+        ]
+    "#]]
+    .assert_debug_eq(&labels);
+}
@@ -169,7 +169,6 @@ fn main() {
             kw super
             kw crate
             lc foo                  Foo
-            lc thing                i32
             st Foo
             fn main()               fn()
             md core
@@ -145,10 +145,10 @@ trait Trait2 {
 fn foo<'lt, T: Trait2<$0>, const CONST_PARAM: usize>(_: T) {}
 "#,
         expect![[r##"
+            ta Foo =  (as Trait2) type Foo;
             kw self
             kw super
             kw crate
-            ta Foo =  (as Trait2) type Foo;
             tp T
             cp CONST_PARAM
             tt Trait
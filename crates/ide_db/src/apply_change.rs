@@ -78,6 +78,7 @@ impl RootDatabase {
             hir::db::ParseMacroExpansionQuery
             hir::db::MacroExpandQuery
             hir::db::HygieneFrameQuery
+            hir::db::FormatArgsPiecesQuery
             hir::db::InternMacroQuery
 
             // DefDatabase
@@ -1,12 +1,12 @@
 //! This crate provides primitives for tracking the information about a call site.
 use base_db::FilePosition;
 use either::Either;
-use hir::{HasAttrs, HirDisplay, Semantics, Type};
+use hir::{HasAttrs, HasSource, HirDisplay, Semantics, Type};
 use stdx::format_to;
 use syntax::{
     algo,
     ast::{self, HasArgList, HasName},
-    AstNode, Direction, SyntaxToken, TextRange, TextSize,
+    AstNode, Direction, SyntaxKind, SyntaxToken, TextRange, TextSize, T,
 };
 
 use crate::RootDatabase;
@@ -112,6 +112,114 @@ pub fn call_info(db: &RootDatabase, position: FilePosition) -> Option<CallInfo>
     Some(res)
 }
 
+/// Computes parameter information for a `macro_rules!` invocation, with one `CallInfo` for each
+/// rule that accepts the arguments written so far. If no rule does, all of them are returned.
+pub fn macro_call_info(
+    sema: &Semantics<RootDatabase>,
+    token: SyntaxToken,
+) -> Option<Vec<CallInfo>> {
+    let macro_call = token.ancestors().filter_map(ast::MacroCall::cast).find(|it| {
+        it.token_tree()
+            .map_or(false, |tt| tt.syntax().text_range().contains(token.text_range().start()))
+    })?;
+    let mac = sema.resolve_macro_call(&macro_call)?;
+    let rules = match mac.source(sema.db)?.value {
+        Either::Left(ast::Macro::MacroRules(it)) => it,
+        _ => return None,
+    };
+    let doc: Option<String> = mac.docs(sema.db).map(|it| it.into());
+    let name = mac.name(sema.db)?;
+
+    let active_parameter = macro_call
+        .token_tree()?
+        .syntax()
+        .children_with_tokens()
+        .filter(|it| it.kind() == T![,] && it.text_range().end() <= token.text_range().end())
+        .count();
+    let matchers: Vec<Vec<String>> = rules
+        .token_tree()?
+        .syntax()
+        .children()
+        .filter_map(ast::TokenTree::cast)
+        .filter(|tt| {
+            algo::non_trivia_sibling(tt.syntax().clone().into(), Direction::Next)
+                .map_or(false, |it| it.kind() == T![=])
+        })
+        .map(|matcher| matcher_params(&matcher))
+        .collect();
+
+    // any further arguments are matched by a trailing repetition like `$($e:expr),*`
+    let active_parameter_of = |params: &[String]| match params.last() {
+        _ if active_parameter < params.len() => Some(active_parameter),
+        Some(last) if last.starts_with("$(") => Some(params.len() - 1),
+        _ => None,
+    };
+    let any_accepts = matchers.iter().any(|params| active_parameter_of(params).is_some());
+    let res = matchers
+        .iter()
+        .filter(|params| !any_accepts || active_parameter_of(params).is_some())
+        .map(|params| {
+            let mut res = CallInfo {
+                doc: doc.clone(),
+                signature: format!("macro_rules! {}(", name),
+                parameters: vec![],
+                active_parameter: active_parameter_of(params),
+            };
+            params.iter().for_each(|param| res.push_param(param));
+            res.signature.push(')');
+            res
+        })
+        .collect();
+    Some(res)
+}
+
+/// Renders the comma separated parts of a `macro_rules!` matcher, e.g. `($x:expr, $y:ty)`
+/// becomes `["arg0: expr", "arg1: ty"]`. Only metavariables are numbered, so `(foo, $x:expr)`
+/// becomes `["foo", "arg0: expr"]`.
+fn matcher_params(matcher: &ast::TokenTree) -> Vec<String> {
+    let r_delim = matcher.right_delimiter_token();
+    let tokens: Vec<_> = matcher
+        .syntax()
+        .children_with_tokens()
+        .skip(1)
+        .filter(|it| !it.kind().is_trivia() && it.as_token() != r_delim.as_ref())
+        .collect();
+    let mut parts = vec![Vec::new()];
+    for (idx, token) in tokens.iter().enumerate() {
+        // the `,` in `$($e:expr),*` is the separator of a repetition rather than of the matcher
+        let is_separator = token.kind() == T![,]
+            && !(idx >= 2
+                && tokens[idx - 1].kind() == SyntaxKind::TOKEN_TREE
+                && tokens[idx - 2].kind() == T![$]);
+        match parts.last_mut() {
+            Some(part) if !is_separator => part.push(token.clone()),
+            _ => parts.push(Vec::new()),
+        }
+    }
+    let mut metavar_idx = 0;
+    parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.as_slice() {
+            [dollar, _name, colon, fragment] if dollar.kind() == T![$] && colon.kind() == T![:] => {
+                let param = format!("arg{}: {}", metavar_idx, fragment);
+                metavar_idx += 1;
+                param
+            }
+            [first, .., last] => {
+                let range = TextRange::new(first.text_range().start(), last.text_range().end());
+                matcher
+                    .syntax()
+                    .text()
+                    .slice(range - matcher.syntax().text_range().start())
+                    .to_string()
+            }
+            [single] => single.to_string(),
+            [] => String::new(),
+        })
+        .collect()
+}
+
 fn call_info_impl(
     sema: &Semantics<RootDatabase>,
     token: SyntaxToken,
@@ -162,6 +270,18 @@ impl ActiveParameter {
         pat.map(|pat| ActiveParameter { ty, pat })
     }
 
+    /// Returns the parameter types and, if known, the return type of a closure that can be
+    /// passed as the argument at `token`.
+    pub fn closure_sig_at_token(
+        sema: &Semantics<RootDatabase>,
+        token: SyntaxToken,
+    ) -> Option<(Vec<Type>, Option<Type>)> {
+        let (signature, active_parameter) = call_info_impl(sema, token)?;
+        let active_parameter = active_parameter?;
+        let params = signature.closure_params(sema.db, active_parameter)?;
+        Some((params, signature.closure_ret(sema.db, active_parameter)))
+    }
+
     pub fn ident(&self) -> Option<ast::Name> {
         self.pat.as_ref().right().and_then(|param| match param {
             ast::Pat::IdentPat(ident) => ident.name(),
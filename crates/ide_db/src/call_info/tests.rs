@@ -562,3 +562,18 @@ fn main() {
         "#]],
     )
 }
+
+#[test]
+fn call_info_prefers_calls_inside_macros() {
+    check(
+        r#"
+macro_rules! id { ($($tt:tt)*) => { $($tt)* } }
+fn foo(x: u32) {}
+fn main() { id!(foo($0)) }
+"#,
+        expect![[r#"
+            fn foo(x: u32)
+            (<x: u32>)
+        "#]],
+    );
+}
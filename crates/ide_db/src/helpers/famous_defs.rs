@@ -1,5 +1,5 @@
 //! See [`FamousDefs`].
-use hir::{Crate, Enum, Module, ScopeDef, Semantics, Trait};
+use hir::{Crate, Enum, Module, ScopeDef, Semantics, Struct, Trait};
 
 use crate::RootDatabase;
 
@@ -28,10 +28,26 @@ impl FamousDefs<'_, '_> {
         self.find_crate("core")
     }
 
+    pub fn core_clone_Clone(&self) -> Option<Trait> {
+        self.find_trait("core:clone:Clone")
+    }
+
+    pub fn core_cmp_Eq(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:Eq")
+    }
+
     pub fn core_cmp_Ord(&self) -> Option<Trait> {
         self.find_trait("core:cmp:Ord")
     }
 
+    pub fn core_cmp_PartialEq(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:PartialEq")
+    }
+
+    pub fn core_cmp_PartialOrd(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:PartialOrd")
+    }
+
     pub fn core_convert_From(&self) -> Option<Trait> {
         self.find_trait("core:convert:From")
     }
@@ -40,6 +56,30 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:convert:Into")
     }
 
+    pub fn core_convert_AsRef(&self) -> Option<Trait> {
+        self.find_trait("core:convert:AsRef")
+    }
+
+    pub fn core_convert_TryFrom(&self) -> Option<Trait> {
+        self.find_trait("core:convert:TryFrom")
+    }
+
+    pub fn core_convert_TryInto(&self) -> Option<Trait> {
+        self.find_trait("core:convert:TryInto")
+    }
+
+    pub fn core_fmt_Debug(&self) -> Option<Trait> {
+        self.find_trait("core:fmt:Debug")
+    }
+
+    pub fn core_marker_Copy(&self) -> Option<Trait> {
+        self.find_trait("core:marker:Copy")
+    }
+
+    pub fn core_marker_PhantomData(&self) -> Option<Struct> {
+        self.find_struct("core:marker:PhantomData")
+    }
+
     pub fn core_option_Option(&self) -> Option<Enum> {
         self.find_enum("core:option:Option")
     }
@@ -52,6 +92,14 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:default:Default")
     }
 
+    pub fn core_hash_Hash(&self) -> Option<Trait> {
+        self.find_trait("core:hash:Hash")
+    }
+
+    pub fn core_hash_Hasher(&self) -> Option<Trait> {
+        self.find_trait("core:hash:Hasher")
+    }
+
     pub fn core_iter_Iterator(&self) -> Option<Trait> {
         self.find_trait("core:iter:traits:iterator:Iterator")
     }
@@ -68,6 +116,18 @@ impl FamousDefs<'_, '_> {
         self.find_trait("core:ops:Deref")
     }
 
+    pub fn core_ops_Range(&self) -> Option<Struct> {
+        self.find_struct("core:ops:Range")
+    }
+
+    pub fn std_error_Error(&self) -> Option<Trait> {
+        self.find_trait("std:error:Error")
+    }
+
+    pub fn std_io_Error(&self) -> Option<Struct> {
+        self.find_struct("std:io:Error")
+    }
+
     pub fn alloc(&self) -> Option<Crate> {
         self.find_crate("alloc")
     }
@@ -98,6 +158,13 @@ impl FamousDefs<'_, '_> {
         }
     }
 
+    fn find_struct(&self, path: &str) -> Option<Struct> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Struct(it))) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_enum(&self, path: &str) -> Option<Enum> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Enum(it))) => Some(it),
@@ -304,7 +304,15 @@ pub fn source_edit_from_references(
             ast::NameLike::Name(name) if name.syntax().text_range() == range => {
                 source_edit_from_name(&mut edit, name, new_name)
             }
-            _ => false,
+            _ if edited_ranges.contains(&range.start()) => true,
+            _ => {
+                let has_emitted_edit =
+                    source_edit_from_shorthand_in_macro(&mut edit, name, range, new_name, def);
+                if has_emitted_edit {
+                    edited_ranges.push(range.start());
+                }
+                has_emitted_edit
+            }
         };
         if !has_emitted_edit {
             if !edited_ranges.contains(&range.start()) {
@@ -317,6 +325,45 @@ pub fn source_edit_from_references(
     edit.finish()
 }
 
+/// Splits a field shorthand that comes from a macro call. Its single token in the macro input
+/// stands for both the field and the local, so it can't be renamed as a whole.
+fn source_edit_from_shorthand_in_macro(
+    edit: &mut TextEditBuilder,
+    name: &ast::NameLike,
+    range: TextRange,
+    new_name: &str,
+    def: Definition,
+) -> bool {
+    let is_shorthand = match name {
+        ast::NameLike::NameRef(name_ref) => {
+            ast::RecordExprField::for_name_ref(name_ref).map_or(false, |it| it.name_ref().is_none())
+        }
+        ast::NameLike::Name(name) => {
+            ast::RecordPatField::for_field_name(name).is_some()
+                && name.syntax().parent().and_then(ast::IdentPat::cast).is_some()
+        }
+        ast::NameLike::Lifetime(_) => false,
+    };
+    if !is_shorthand {
+        return false;
+    }
+    match def {
+        Definition::Field(_) => {
+            cov_mark::hit!(rename_field_shorthand_in_macro);
+            // m!(Foo { field }) -> m!(Foo { new_name: field })
+            //          ^ insert `new_name: `
+            edit.insert(range.start(), format!("{}: ", new_name));
+        }
+        Definition::Local(_) => {
+            // m!(Foo { field }) -> m!(Foo { field: new_name })
+            //               ^ insert `: new_name`
+            edit.insert(range.end(), format!(": {}", new_name));
+        }
+        _ => return false,
+    }
+    true
+}
+
 fn source_edit_from_name(edit: &mut TextEditBuilder, name: &ast::Name, new_name: &str) -> bool {
     if ast::RecordPatField::for_field_name(name).is_some() {
         if let Some(ident_pat) = name.syntax().parent().and_then(ast::IdentPat::cast) {
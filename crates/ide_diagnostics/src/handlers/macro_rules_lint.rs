@@ -0,0 +1,48 @@
+use crate::{Diagnostic, DiagnosticsContext, Severity};
+
+// Diagnostic: macro-rules-lint
+//
+// This diagnostic is shown for `macro_rules!` definitions that bind a metavariable they never use
+// or give a metavariable a fragment specifier that does not exist.
+pub(crate) fn macro_rules_lint(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MacroRulesLint,
+) -> Diagnostic {
+    // Use more accurate position if available.
+    let display_range = d
+        .precise_location
+        .unwrap_or_else(|| ctx.sema.diagnostics_display_range(d.node.clone()).range);
+    Diagnostic::new("macro-rules-lint", d.message.clone(), display_range)
+        .severity(Severity::WeakWarning)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::check_diagnostics;
+
+    #[test]
+    fn unused_metavariable() {
+        check_diagnostics(
+            r#"
+macro_rules! m {
+    ($a:expr, $b:expr) => { $a };
+             //^ weak: unused metavariable `$b`
+    ($($x:ident),*) => { $(let $x = 1;)* };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn invalid_fragment_specifier() {
+        check_diagnostics(
+            r#"
+macro_rules! m {
+    ($e:expression) => { $e };
+    //^ weak: invalid fragment specifier `expression` for `$e`
+    ($p:pat_param) => { $p };
+}
+"#,
+        );
+    }
+}
@@ -1,15 +1,20 @@
 //! This module add real world mbe example for benchmark tests
 
+use parser::TokenSource;
 use rustc_hash::FxHashMap;
 use syntax::{
     ast::{self, HasName},
-    AstNode, SmolStr,
+    AstNode, SmolStr, SyntaxKind, T,
 };
 use test_utils::{bench, bench_fixture, skip_slow_tests};
 
 use crate::{
+    parse_to_token_tree,
     parser::{Op, RepeatKind, Separator},
-    syntax_node_to_token_tree, DeclarativeMacro,
+    subtree_source::SubtreeTokenSource,
+    syntax_node_to_token_tree, token_tree_to_syntax_node_streaming,
+    tt_iter::{FlatTtIter, TtIter},
+    DeclarativeMacro, ParserEntryPoint,
 };
 
 #[test]
@@ -47,6 +52,61 @@ fn benchmark_expand_macro_rules() {
     assert_eq!(hash, 69413);
 }
 
+#[test]
+fn benchmark_streaming_large_expansion() {
+    if skip_slow_tests() {
+        return;
+    }
+    let mut text = String::new();
+    let mut n_items = 0;
+    while text.len() < 50 * 1024 {
+        text.push_str(&format!(
+            "fn f{0}(x: u32) -> u32 {{ let y = x * {0}; if y > 10 {{ y - 1 }} else {{ y + 1 }} }}\n",
+            n_items
+        ));
+        n_items += 1;
+    }
+    let (tt, _) = parse_to_token_tree(&text).unwrap();
+
+    let parsed_items = {
+        let _pt = bench("mbe streaming parse of a 50KB expansion");
+        let (parse, _) =
+            token_tree_to_syntax_node_streaming(TtIter::new(&tt), ParserEntryPoint::Items).unwrap();
+        parse.syntax_node().children().filter_map(ast::Fn::cast).count()
+    };
+    assert_eq!(parsed_items, n_items);
+
+    // What converting the input upfront used to cost, against the most the streaming token
+    // source holds on to while the parser walks the same input.
+    let buffered = {
+        let before = profile::memory_usage().allocated;
+        let buffer = tt::buffer::TokenBuffer::from_tokens(&tt.token_trees);
+        let after = profile::memory_usage().allocated;
+        drop(buffer);
+        after - before
+    };
+    let streamed = {
+        let before = profile::memory_usage().allocated;
+        let mut source = SubtreeTokenSource::new(FlatTtIter::new(TtIter::new(&tt)));
+        let mut peak = profile::memory_usage().allocated - before;
+        let mut n_fns = 0;
+        while source.current().kind != SyntaxKind::EOF {
+            if source.current().kind == T![fn] {
+                n_fns += 1;
+                source.lookahead_nth(4);
+                let used = profile::memory_usage().allocated - before;
+                if used > peak {
+                    peak = used;
+                }
+            }
+            source.bump();
+        }
+        assert_eq!(n_fns, n_items);
+        peak
+    };
+    eprintln!("token buffer: {}, streaming token source: {}", buffered, streamed);
+}
+
 fn macro_rules_fixtures() -> FxHashMap<String, DeclarativeMacro> {
     macro_rules_fixtures_tt()
         .into_iter()
@@ -16,13 +16,16 @@ mod subtree_source;
 mod benchmark;
 mod token_map;
 
-use std::fmt;
-
-use crate::{
-    parser::{MetaTemplate, Op},
-    tt_iter::TtIter,
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
 };
 
+use rustc_hash::{FxHashSet, FxHasher};
+use syntax::SmolStr;
+
+use crate::parser::{MetaTemplate, Op};
+
 // FIXME: we probably should re-think  `token_tree_to_syntax_node` interfaces
 pub use ::parser::ParserEntryPoint;
 pub use tt::{Delimiter, DelimiterKind, Punct};
@@ -46,6 +49,38 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// A suspicious, but not invalid, part of a macro definition.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MacroRulesLint {
+    /// A metavariable bound in a pattern is never used in the rule's transcriber.
+    UnusedMetaVar { name: SmolStr, id: tt::TokenId },
+    /// A metavariable has a fragment specifier that does not exist, like `$x:expression`.
+    InvalidFragmentSpecifier { name: SmolStr, kind: SmolStr, id: tt::TokenId },
+}
+
+impl MacroRulesLint {
+    /// The id of the metavariable's name in the definition's token tree.
+    pub fn token_id(&self) -> tt::TokenId {
+        match self {
+            MacroRulesLint::UnusedMetaVar { id, .. }
+            | MacroRulesLint::InvalidFragmentSpecifier { id, .. } => *id,
+        }
+    }
+}
+
+impl fmt::Display for MacroRulesLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MacroRulesLint::UnusedMetaVar { name, .. } => {
+                write!(f, "unused metavariable `${}`", name)
+            }
+            MacroRulesLint::InvalidFragmentSpecifier { name, kind, .. } => {
+                write!(f, "invalid fragment specifier `{}` for `${}`", kind, name)
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ExpandError {
     NoMatchingRule,
@@ -74,8 +109,10 @@ pub use crate::{
     syntax_bridge::{
         parse_exprs_with_sep, parse_to_token_tree, syntax_node_to_token_tree,
         syntax_node_to_token_tree_censored, token_tree_to_syntax_node,
+        token_tree_to_syntax_node_streaming,
     },
     token_map::TokenMap,
+    tt_iter::TtIter,
 };
 
 /// This struct contains AST for a single `macro_rules` definition. What might
@@ -235,6 +272,34 @@ impl DeclarativeMacro {
         expander::expand_rules(&self.rules, &tt)
     }
 
+    /// Checks the rules for metavariables that are never used and for unknown fragment
+    /// specifiers. Neither prevents the macro from being parsed, but rustc rejects the latter
+    /// when the rule is matched.
+    pub fn validate(&self) -> Vec<MacroRulesLint> {
+        let mut res = Vec::new();
+        for rule in &self.rules {
+            let mut used = FxHashSet::default();
+            collect_vars(&rule.rhs, &mut |name, _, _| {
+                used.insert(name.clone());
+            });
+            collect_vars(&rule.lhs, &mut |name, kind, id| {
+                if let Some(kind) = kind {
+                    if !FRAGMENT_SPECIFIERS.contains(&kind.as_str()) {
+                        res.push(MacroRulesLint::InvalidFragmentSpecifier {
+                            name: name.clone(),
+                            kind: kind.clone(),
+                            id,
+                        });
+                    }
+                }
+                if !used.contains(name) {
+                    res.push(MacroRulesLint::UnusedMetaVar { name: name.clone(), id });
+                }
+            });
+        }
+        res
+    }
+
     pub fn map_id_down(&self, id: tt::TokenId) -> tt::TokenId {
         self.shift.shift(id)
     }
@@ -306,6 +371,70 @@ fn validate(pattern: &MetaTemplate) -> Result<(), ParseError> {
     Ok(())
 }
 
+const FRAGMENT_SPECIFIERS: &[&str] = &[
+    "block",
+    "expr",
+    "ident",
+    "item",
+    "lifetime",
+    "literal",
+    "meta",
+    "pat",
+    "pat_param",
+    "path",
+    "stmt",
+    "tt",
+    "ty",
+    "vis",
+];
+
+fn collect_vars(
+    template: &MetaTemplate,
+    cb: &mut dyn FnMut(&SmolStr, Option<&SmolStr>, tt::TokenId),
+) {
+    for op in template.iter() {
+        match op {
+            Op::Var { name, kind, id } => cb(name, kind.as_ref(), *id),
+            Op::Repeat { tokens, .. } | Op::Subtree { tokens, .. } => collect_vars(tokens, cb),
+            Op::Leaf(_) => (),
+        }
+    }
+}
+
+/// Hashes the token tree by value, ignoring `TokenId`s. Two trees hash the same if they consist
+/// of the same tokens with the same delimiters, regardless of where they came from.
+pub fn structural_hash(subtree: &tt::Subtree) -> u64 {
+    let mut hasher = FxHasher::default();
+    hash_subtree(subtree, &mut hasher);
+    return hasher.finish();
+
+    fn hash_subtree(subtree: &tt::Subtree, hasher: &mut FxHasher) {
+        subtree.delimiter_kind().hash(hasher);
+        subtree.token_trees.len().hash(hasher);
+        for tt in &subtree.token_trees {
+            match tt {
+                tt::TokenTree::Leaf(tt::Leaf::Literal(lit)) => {
+                    0u8.hash(hasher);
+                    lit.text.hash(hasher);
+                }
+                tt::TokenTree::Leaf(tt::Leaf::Punct(punct)) => {
+                    1u8.hash(hasher);
+                    punct.char.hash(hasher);
+                    punct.spacing.hash(hasher);
+                }
+                tt::TokenTree::Leaf(tt::Leaf::Ident(ident)) => {
+                    2u8.hash(hasher);
+                    ident.text.hash(hasher);
+                }
+                tt::TokenTree::Subtree(subtree) => {
+                    3u8.hash(hasher);
+                    hash_subtree(subtree, hasher);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ExpandResult<T> {
     pub value: T,
@@ -345,3 +474,20 @@ impl<T: Default> From<Result<T, ExpandError>> for ExpandResult<T> {
         result.map_or_else(Self::only_err, Self::ok)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structural_hash_ignores_token_ids() {
+        let (tt, _) = parse_to_token_tree("foo!(a, [b + 1], { c })").unwrap();
+        let mut shifted = tt.clone();
+        Shift::new(&tt).shift_all(&mut shifted);
+        assert_ne!(tt, shifted);
+        assert_eq!(structural_hash(&tt), structural_hash(&shifted));
+
+        let (other, _) = parse_to_token_tree("foo!(a, (b + 1), { c })").unwrap();
+        assert_ne!(structural_hash(&tt), structural_hash(&other));
+    }
+}
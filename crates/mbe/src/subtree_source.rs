@@ -2,9 +2,12 @@
 //!
 //! This module defines tokens sourced from declarative macros.
 
+use std::{cell::RefCell, collections::VecDeque};
+
 use parser::{Token, TokenSource};
 use syntax::{lex_single_syntax_kind, SmolStr, SyntaxKind, SyntaxKind::*, T};
-use tt::buffer::TokenBuffer;
+
+use crate::tt_iter::{FlatTt, FlatTtIter};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct TtToken {
@@ -12,84 +15,61 @@ struct TtToken {
     text: SmolStr,
 }
 
-pub(crate) struct SubtreeTokenSource {
-    cached: Vec<TtToken>,
-    curr: (Token, usize),
+/// Converts token trees to parser tokens as the parser asks for them. Only the tokens the parser
+/// has looked ahead at are kept, so the input is never flattened into a buffer of its own, which
+/// matters for large macro expansions.
+pub(crate) struct SubtreeTokenSource<'a> {
+    tts: RefCell<FlatTtIter<'a>>,
+    /// The current token, followed by the tokens after it that the parser has looked at.
+    lookahead: RefCell<VecDeque<TtToken>>,
 }
 
-impl<'a> SubtreeTokenSource {
-    pub(crate) fn new(buffer: &TokenBuffer) -> SubtreeTokenSource {
-        let mut current = buffer.begin();
-        let mut cached = Vec::with_capacity(100);
-
-        while !current.eof() {
-            let cursor = current;
-            let tt = cursor.token_tree();
-
-            // Check if it is lifetime
-            if let Some(tt::buffer::TokenTreeRef::Leaf(tt::Leaf::Punct(punct), _)) = tt {
-                if punct.char == '\'' {
-                    let next = cursor.bump();
-                    if let Some(tt::buffer::TokenTreeRef::Leaf(tt::Leaf::Ident(ident), _)) =
-                        next.token_tree()
-                    {
-                        let text = SmolStr::new("'".to_string() + &ident.text);
-                        cached.push(TtToken {
-                            tt: Token { kind: LIFETIME_IDENT, is_jointed_to_next: false },
-                            text,
-                        });
-                        current = next.bump();
-                        continue;
-                    } else {
-                        panic!("Next token must be ident : {:#?}", next.token_tree());
-                    }
-                }
-            }
-
-            current = match tt {
-                Some(tt::buffer::TokenTreeRef::Leaf(leaf, _)) => {
-                    cached.push(convert_leaf(leaf));
-                    cursor.bump()
-                }
-                Some(tt::buffer::TokenTreeRef::Subtree(subtree, _)) => {
-                    cached.push(convert_delim(subtree.delimiter_kind(), false));
-                    cursor.subtree().unwrap()
-                }
-                None => {
-                    if let Some(subtree) = cursor.end() {
-                        cached.push(convert_delim(subtree.delimiter_kind(), true));
-                        cursor.bump()
-                    } else {
-                        continue;
-                    }
-                }
-            };
-        }
-
-        let mut res = SubtreeTokenSource {
-            curr: (Token { kind: EOF, is_jointed_to_next: false }, 0),
-            cached,
-        };
-        res.curr = (res.token(0), 0);
-        res
+impl<'a> SubtreeTokenSource<'a> {
+    pub(crate) fn new(tts: FlatTtIter<'a>) -> SubtreeTokenSource<'a> {
+        SubtreeTokenSource { tts: RefCell::new(tts), lookahead: RefCell::new(VecDeque::new()) }
     }
 
-    fn token(&self, pos: usize) -> Token {
-        match self.cached.get(pos) {
+    fn token(&self, n: usize) -> Token {
+        let mut lookahead = self.lookahead.borrow_mut();
+        let mut tts = self.tts.borrow_mut();
+        while lookahead.len() <= n {
+            match next_token(&mut tts) {
+                Some(token) => lookahead.push_back(token),
+                None => break,
+            }
+        }
+        match lookahead.get(n) {
             Some(it) => it.tt,
             None => Token { kind: EOF, is_jointed_to_next: false },
         }
     }
 }
 
-impl<'a> TokenSource for SubtreeTokenSource {
+fn next_token(tts: &mut FlatTtIter<'_>) -> Option<TtToken> {
+    let token = match tts.next()? {
+        // Check if it is lifetime
+        FlatTt::Leaf(tt::Leaf::Punct(punct)) if punct.char == '\'' => match tts.next() {
+            Some(FlatTt::Leaf(tt::Leaf::Ident(ident))) => TtToken {
+                tt: Token { kind: LIFETIME_IDENT, is_jointed_to_next: false },
+                text: SmolStr::new("'".to_string() + &ident.text),
+            },
+            next => panic!("Next token must be ident : {:#?}", next),
+        },
+        FlatTt::Leaf(leaf) => convert_leaf(leaf),
+        FlatTt::Open(subtree) => convert_delim(subtree.delimiter_kind(), false),
+        FlatTt::Close(subtree) => convert_delim(subtree.delimiter_kind(), true),
+    };
+    Some(token)
+}
+
+impl<'a> TokenSource for SubtreeTokenSource<'a> {
     fn current(&self) -> Token {
-        self.curr.0
+        self.token(0)
     }
 
     /// Lookahead n token
     fn lookahead_nth(&self, n: usize) -> Token {
-        self.token(self.curr.1 + n)
+        self.token(n)
     }
 
     /// bump cursor to next token
@@ -97,12 +77,13 @@ impl<'a> TokenSource for SubtreeTokenSource {
         if self.current().kind == EOF {
             return;
         }
-        self.curr = (self.token(self.curr.1 + 1), self.curr.1 + 1);
+        self.lookahead.get_mut().pop_front();
     }
 
     /// Is the current token a specified keyword?
     fn is_keyword(&self, kw: &str) -> bool {
-        match self.cached.get(self.curr.1) {
+        self.token(0);
+        match self.lookahead.borrow().front() {
             Some(t) => t.text == *kw,
             None => false,
         }
@@ -1,5 +1,7 @@
 //! Conversions between [`SyntaxNode`] and [`tt::TokenTree`].
 
+use std::iter::Peekable;
+
 use parser::{ParseError, TreeSink};
 use rustc_hash::{FxHashMap, FxHashSet};
 use syntax::{
@@ -9,10 +11,11 @@ use syntax::{
     SyntaxNode, SyntaxToken, SyntaxTreeBuilder, TextRange, TextSize, Token as RawToken, WalkEvent,
     T,
 };
-use tt::buffer::{Cursor, TokenBuffer};
 
 use crate::{
-    subtree_source::SubtreeTokenSource, tt_iter::TtIter, ExpandError, ParserEntryPoint, TokenMap,
+    subtree_source::SubtreeTokenSource,
+    tt_iter::{FlatTt, FlatTtIter, TtIter},
+    ExpandError, ParserEntryPoint, TokenMap,
 };
 
 /// Convert the syntax node to a `TokenTree` (what macro
@@ -50,14 +53,28 @@ pub fn token_tree_to_syntax_node(
     tt: &tt::Subtree,
     entry_point: ParserEntryPoint,
 ) -> Result<(Parse<SyntaxNode>, TokenMap), ExpandError> {
-    let buffer = match tt {
-        tt::Subtree { delimiter: None, token_trees } => {
-            TokenBuffer::from_tokens(token_trees.as_slice())
-        }
-        _ => TokenBuffer::from_subtree(tt),
+    let tts = match tt {
+        tt::Subtree { delimiter: None, .. } => FlatTtIter::new(TtIter::new(tt)),
+        _ => FlatTtIter::with_delimiters(tt),
     };
-    let mut token_source = SubtreeTokenSource::new(&buffer);
-    let mut tree_sink = TtTreeSink::new(buffer.begin());
+    tts_to_syntax_node(tts, entry_point)
+}
+
+/// Like [`token_tree_to_syntax_node`], but parses the remaining token trees of `iter`. They are
+/// converted to parser tokens only as the parser gets to them.
+pub fn token_tree_to_syntax_node_streaming(
+    iter: TtIter<'_>,
+    entry_point: ParserEntryPoint,
+) -> Result<(Parse<SyntaxNode>, TokenMap), ExpandError> {
+    tts_to_syntax_node(FlatTtIter::new(iter), entry_point)
+}
+
+fn tts_to_syntax_node(
+    tts: FlatTtIter<'_>,
+    entry_point: ParserEntryPoint,
+) -> Result<(Parse<SyntaxNode>, TokenMap), ExpandError> {
+    let mut token_source = SubtreeTokenSource::new(tts.clone());
+    let mut tree_sink = TtTreeSink::new(tts);
     parser::parse(&mut token_source, &mut tree_sink, entry_point);
     if tree_sink.roots.len() != 1 {
         return Err(ExpandError::ConversionError);
@@ -602,7 +619,7 @@ impl TokenConvertor for Convertor<'_> {
 
 struct TtTreeSink<'a> {
     buf: String,
-    cursor: Cursor<'a>,
+    tts: Peekable<FlatTtIter<'a>>,
     open_delims: FxHashMap<tt::TokenId, TextSize>,
     text_pos: TextSize,
     inner: SyntaxTreeBuilder,
@@ -614,10 +631,10 @@ struct TtTreeSink<'a> {
 }
 
 impl<'a> TtTreeSink<'a> {
-    fn new(cursor: Cursor<'a>) -> Self {
+    fn new(tts: FlatTtIter<'a>) -> Self {
         TtTreeSink {
             buf: String::new(),
-            cursor,
+            tts: tts.peekable(),
             open_delims: FxHashMap::default(),
             text_pos: 0.into(),
             inner: SyntaxTreeBuilder::default(),
@@ -647,22 +664,23 @@ fn delim_to_str(d: Option<tt::DelimiterKind>, closing: bool) -> &'static str {
 impl<'a> TreeSink for TtTreeSink<'a> {
     fn token(&mut self, kind: SyntaxKind, mut n_tokens: u8) {
         if kind == L_DOLLAR || kind == R_DOLLAR {
-            self.cursor = self.cursor.bump_subtree();
+            self.tts.next();
             return;
         }
         if kind == LIFETIME_IDENT {
             n_tokens = 2;
         }
 
-        let mut last = self.cursor;
+        let mut last = None;
         for _ in 0..n_tokens {
             let tmp_str: SmolStr;
-            if self.cursor.eof() {
-                break;
-            }
-            last = self.cursor;
-            let text: &str = match self.cursor.token_tree() {
-                Some(tt::buffer::TokenTreeRef::Leaf(leaf, _)) => {
+            let tt = match self.tts.next() {
+                Some(it) => it,
+                None => break,
+            };
+            last = Some(tt);
+            let text: &str = match tt {
+                FlatTt::Leaf(leaf) => {
                     // Mark the range if needed
                     let (text, id) = match leaf {
                         tt::Leaf::Ident(ident) => (&ident.text, ident.id),
@@ -678,30 +696,23 @@ impl<'a> TreeSink for TtTreeSink<'a> {
                     };
                     let range = TextRange::at(self.text_pos, TextSize::of(text.as_str()));
                     self.token_map.insert(id, range);
-                    self.cursor = self.cursor.bump();
                     text
                 }
-                Some(tt::buffer::TokenTreeRef::Subtree(subtree, _)) => {
-                    self.cursor = self.cursor.subtree().unwrap();
+                FlatTt::Open(subtree) => {
                     if let Some(id) = subtree.delimiter.map(|it| it.id) {
                         self.open_delims.insert(id, self.text_pos);
                     }
                     delim_to_str(subtree.delimiter_kind(), false)
                 }
-                None => {
-                    if let Some(parent) = self.cursor.end() {
-                        self.cursor = self.cursor.bump();
-                        if let Some(id) = parent.delimiter.map(|it| it.id) {
-                            if let Some(open_delim) = self.open_delims.get(&id) {
-                                let open_range = TextRange::at(*open_delim, TextSize::of('('));
-                                let close_range = TextRange::at(self.text_pos, TextSize::of('('));
-                                self.token_map.insert_delim(id, open_range, close_range);
-                            }
+                FlatTt::Close(subtree) => {
+                    if let Some(id) = subtree.delimiter.map(|it| it.id) {
+                        if let Some(open_delim) = self.open_delims.get(&id) {
+                            let open_range = TextRange::at(*open_delim, TextSize::of('('));
+                            let close_range = TextRange::at(self.text_pos, TextSize::of('('));
+                            self.token_map.insert_delim(id, open_range, close_range);
                         }
-                        delim_to_str(parent.delimiter_kind(), true)
-                    } else {
-                        continue;
                     }
+                    delim_to_str(subtree.delimiter_kind(), true)
                 }
             };
             self.buf += text;
@@ -711,11 +722,8 @@ impl<'a> TreeSink for TtTreeSink<'a> {
         self.inner.token(kind, self.buf.as_str());
         self.buf.clear();
         // Add whitespace between adjoint puncts
-        let next = last.bump();
-        if let (
-            Some(tt::buffer::TokenTreeRef::Leaf(tt::Leaf::Punct(curr), _)),
-            Some(tt::buffer::TokenTreeRef::Leaf(tt::Leaf::Punct(_), _)),
-        ) = (last.token_tree(), next.token_tree())
+        if let (Some(FlatTt::Leaf(tt::Leaf::Punct(curr))), Some(FlatTt::Leaf(tt::Leaf::Punct(_)))) =
+            (last, self.tts.peek())
         {
             // Note: We always assume the semi-colon would be the last token in
             // other parts of RA such that we don't add whitespace here.
@@ -5,7 +5,6 @@ use crate::{subtree_source::SubtreeTokenSource, ExpandError, ExpandResult, Parse
 
 use parser::TreeSink;
 use syntax::SyntaxKind;
-use tt::buffer::{Cursor, TokenBuffer};
 
 macro_rules! err {
     () => {
@@ -16,13 +15,14 @@ macro_rules! err {
     };
 }
 
+/// An iterator over the token trees of a subtree.
 #[derive(Debug, Clone)]
-pub(crate) struct TtIter<'a> {
+pub struct TtIter<'a> {
     pub(crate) inner: std::slice::Iter<'a, tt::TokenTree>,
 }
 
 impl<'a> TtIter<'a> {
-    pub(crate) fn new(subtree: &'a tt::Subtree) -> TtIter<'a> {
+    pub fn new(subtree: &'a tt::Subtree) -> TtIter<'a> {
         TtIter { inner: subtree.token_trees.iter() }
     }
 
@@ -95,7 +95,7 @@ impl<'a> TtIter<'a> {
         entry_point: ParserEntryPoint,
     ) -> ExpandResult<Option<tt::TokenTree>> {
         struct OffsetTokenSink<'a> {
-            cursor: Cursor<'a>,
+            tts: FlatTtIter<'a>,
             error: bool,
         }
 
@@ -105,7 +105,7 @@ impl<'a> TtIter<'a> {
                     n_tokens = 2;
                 }
                 for _ in 0..n_tokens {
-                    self.cursor = self.cursor.bump_subtree();
+                    self.tts.next();
                 }
             }
             fn start_node(&mut self, _kind: SyntaxKind) {}
@@ -115,39 +115,30 @@ impl<'a> TtIter<'a> {
             }
         }
 
-        let buffer = TokenBuffer::from_tokens(self.inner.as_slice());
-        let mut src = SubtreeTokenSource::new(&buffer);
-        let mut sink = OffsetTokenSink { cursor: buffer.begin(), error: false };
+        let tts = FlatTtIter::new(self.clone());
+        let mut src = SubtreeTokenSource::new(tts.clone());
+        let mut sink = OffsetTokenSink { tts, error: false };
 
         parser::parse(&mut src, &mut sink, entry_point);
 
-        let mut err = if !sink.cursor.is_root() || sink.error {
+        let mut err = if !sink.tts.is_root() || sink.error {
             Some(err!("expected {:?}", entry_point))
         } else {
             None
         };
 
-        let mut curr = buffer.begin();
-        let mut res = vec![];
-
-        if sink.cursor.is_root() {
-            while curr != sink.cursor {
-                if let Some(token) = curr.token_tree() {
-                    res.push(token);
-                }
-                curr = curr.bump();
-            }
-        }
-        self.inner = self.inner.as_slice()[res.len()..].iter();
+        let consumed = if sink.tts.is_root() { self.len() - sink.tts.remaining() } else { 0 };
+        let res = &self.inner.as_slice()[..consumed];
+        self.inner = self.inner.as_slice()[consumed..].iter();
         if res.is_empty() && err.is_none() {
             err = Some(err!("no tokens consumed"));
         }
-        let res = match res.len() {
-            1 => Some(res[0].cloned()),
-            0 => None,
+        let res = match res {
+            [single] => Some(single.clone()),
+            [] => None,
             _ => Some(tt::TokenTree::Subtree(tt::Subtree {
                 delimiter: None,
-                token_trees: res.into_iter().map(|it| it.cloned()).collect(),
+                token_trees: res.to_vec(),
             })),
         };
         ExpandResult { value: res, err }
@@ -170,3 +161,64 @@ impl<'a> Iterator for TtIter<'a> {
 }
 
 impl<'a> std::iter::ExactSizeIterator for TtIter<'a> {}
+
+/// A token tree seen by [`FlatTtIter`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FlatTt<'a> {
+    Leaf(&'a tt::Leaf),
+    Open(&'a tt::Subtree),
+    Close(&'a tt::Subtree),
+}
+
+/// Walks token trees depth first, yielding the opening and closing delimiters of subtrees around
+/// their contents. This is the order in which the parser consumes them.
+#[derive(Debug, Clone)]
+pub(crate) struct FlatTtIter<'a> {
+    /// The subtrees being walked, along with their remaining token trees. The outermost level
+    /// has no subtree unless its delimiters are walked too.
+    stack: Vec<(Option<&'a tt::Subtree>, TtIter<'a>)>,
+    /// Set until the opening delimiter of the outermost subtree has been yielded.
+    open: Option<&'a tt::Subtree>,
+}
+
+impl<'a> FlatTtIter<'a> {
+    pub(crate) fn new(iter: TtIter<'a>) -> FlatTtIter<'a> {
+        FlatTtIter { stack: vec![(None, iter)], open: None }
+    }
+
+    /// Walks `subtree` including its own delimiters.
+    pub(crate) fn with_delimiters(subtree: &'a tt::Subtree) -> FlatTtIter<'a> {
+        FlatTtIter { stack: vec![(Some(subtree), TtIter::new(subtree))], open: Some(subtree) }
+    }
+
+    /// Whether the walk is between two outermost token trees rather than inside of one.
+    pub(crate) fn is_root(&self) -> bool {
+        self.stack.len() <= 1
+    }
+
+    /// The number of outermost token trees that haven't been entered yet.
+    pub(crate) fn remaining(&self) -> usize {
+        self.stack.first().map_or(0, |(_, iter)| iter.len())
+    }
+}
+
+impl<'a> Iterator for FlatTtIter<'a> {
+    type Item = FlatTt<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(subtree) = self.open.take() {
+            return Some(FlatTt::Open(subtree));
+        }
+        let (_, iter) = self.stack.last_mut()?;
+        match iter.next() {
+            Some(tt::TokenTree::Leaf(leaf)) => Some(FlatTt::Leaf(leaf)),
+            Some(tt::TokenTree::Subtree(subtree)) => {
+                self.stack.push((Some(subtree), TtIter::new(subtree)));
+                Some(FlatTt::Open(subtree))
+            }
+            None => match self.stack.pop()? {
+                (Some(subtree), _) => Some(FlatTt::Close(subtree)),
+                (None, _) => None,
+            },
+        }
+    }
+}
@@ -7,6 +7,7 @@ mod symbols;
 mod highlight;
 mod analysis_stats;
 mod diagnostics;
+mod macro_stats;
 mod ssr;
 mod lsif;
 
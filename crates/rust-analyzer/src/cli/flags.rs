@@ -88,6 +88,20 @@ xflags::xflags! {
             optional --disable-proc-macros
         }
 
+        /// Expand all macros of the project and print statistics about their invocations.
+        cmd macro-stats
+            /// Directory with Cargo.toml.
+            required path: PathBuf
+        {
+            /// Don't run build scripts or load `OUT_DIR` values by running `cargo check` before analysis.
+            optional --disable-build-scripts
+            /// Don't use expand proc macros.
+            optional --disable-proc-macros
+            /// Instead of the statistics, print a trace of expanding the macro call at
+            /// `file:line:col`, with the file relative to `path`.
+            optional --trace-macro location: String
+        }
+
         cmd ssr
             /// A structured search replace rule (`$a.foo($b) ==> bar($a, $b)`)
             repeated rule: SsrRule
@@ -130,6 +144,7 @@ pub enum RustAnalyzerCmd {
     Highlight(Highlight),
     AnalysisStats(AnalysisStats),
     Diagnostics(Diagnostics),
+    MacroStats(MacroStats),
     Ssr(Ssr),
     Search(Search),
     ProcMacro(ProcMacro),
@@ -180,6 +195,15 @@ pub struct Diagnostics {
     pub disable_proc_macros: bool,
 }
 
+#[derive(Debug)]
+pub struct MacroStats {
+    pub path: PathBuf,
+
+    pub disable_build_scripts: bool,
+    pub disable_proc_macros: bool,
+    pub trace_macro: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct Ssr {
     pub rule: Vec<SsrRule>,
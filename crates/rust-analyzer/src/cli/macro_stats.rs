@@ -0,0 +1,107 @@
+//! Expands all macros of a project and prints how often each kind of macro is invoked.
+
+use anyhow::format_err;
+use ide::{Analysis, FilePosition, LineCol};
+use ide_db::base_db::SourceDatabase;
+use itertools::Itertools;
+use vfs::{AbsPathBuf, Vfs, VfsPath};
+
+use crate::cli::{
+    flags,
+    load_cargo::{load_workspace_at, LoadCargoConfig},
+};
+
+impl flags::MacroStats {
+    pub fn run(self) -> anyhow::Result<()> {
+        let cargo_config = Default::default();
+        let load_cargo_config = LoadCargoConfig {
+            load_out_dirs_from_check: !self.disable_build_scripts,
+            with_proc_macro: !self.disable_proc_macros,
+            prefill_caches: false,
+        };
+        let (host, vfs, _proc_macro) =
+            load_workspace_at(&self.path, &cargo_config, &load_cargo_config, &|_| {})?;
+        if let Some(location) = &self.trace_macro {
+            return self.trace_macro(&host.analysis(), &vfs, location);
+        }
+        let db = host.raw_database();
+        let stats = host.analysis().workspace_macro_statistics()?;
+
+        let crate_graph = db.crate_graph();
+        let crate_name = |krate| {
+            crate_graph[krate]
+                .display_name
+                .as_ref()
+                .map_or_else(|| format!("{:?}", krate), |it| it.to_string())
+        };
+        println!(
+            "{:<30} {:>11} {:>8} {:>8} {:>8} {:>8}",
+            "crate", "declarative", "derive", "attr", "fn-like", "eager"
+        );
+        for (krate, counts) in stats
+            .invocations
+            .iter()
+            .map(|(&krate, counts)| (crate_name(krate), counts))
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        {
+            println!(
+                "{:<30} {:>11} {:>8} {:>8} {:>8} {:>8}",
+                krate, counts.declarative, counts.derive, counts.attr, counts.fn_like, counts.eager
+            );
+        }
+        println!();
+        println!("invocations: {}", stats.invocations.values().map(|it| it.total()).sum::<usize>());
+        println!("failed expansions: {}", stats.failed_expansions);
+        println!("unique definitions: {}", stats.unique_definitions);
+        Ok(())
+    }
+
+    fn trace_macro(&self, analysis: &Analysis, vfs: &Vfs, location: &str) -> anyhow::Result<()> {
+        let position = self.parse_location(analysis, vfs, location)?;
+        let trace = analysis
+            .trace_expansion(position)?
+            .ok_or_else(|| format_err!("no function-like macro call at {}", location))?;
+        println!("macro: {} ({})", trace.name, trace.kind);
+        println!("expansion time: {:?}", trace.duration);
+        println!("token ids: {}", trace.token_ids);
+        println!("input:\n{}", trace.input);
+        if let Some(output) = &trace.output {
+            println!("output:\n{}", output);
+        }
+        if let Some(error) = &trace.error {
+            println!("error: {}", error);
+        }
+        Ok(())
+    }
+
+    /// Parses a `file:line:col` location, with the file relative to the project directory and
+    /// one-based line and column.
+    fn parse_location(
+        &self,
+        analysis: &Analysis,
+        vfs: &Vfs,
+        location: &str,
+    ) -> anyhow::Result<FilePosition> {
+        let mut parts = location.rsplitn(3, ':');
+        let (col, line, file) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(col), Some(line), Some(file)) => (col, line, file),
+            _ => return Err(format_err!("expected `file:line:col`, got `{}`", location)),
+        };
+        let line: u32 = line.parse()?;
+        let col: u32 = col.parse()?;
+        if line == 0 || col == 0 {
+            return Err(format_err!("lines and columns start at 1"));
+        }
+
+        let path = AbsPathBuf::assert(std::env::current_dir()?.join(&self.path).join(file));
+        let file_id = vfs
+            .file_id(&VfsPath::from(path.normalize()))
+            .ok_or_else(|| format_err!("{} is not part of the project", file))?;
+        if line as usize > analysis.file_text(file_id)?.lines().count() {
+            return Err(format_err!("{} has less than {} lines", file, line));
+        }
+        let line_index = analysis.file_line_index(file_id)?;
+        let offset = line_index.offset(LineCol { line: line - 1, col: col - 1 });
+        Ok(FilePosition { file_id, offset })
+    }
+}
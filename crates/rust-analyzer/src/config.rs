@@ -12,7 +12,7 @@ use std::{ffi::OsString, iter, path::PathBuf};
 use flycheck::FlycheckConfig;
 use ide::{
     AssistConfig, CompletionConfig, DiagnosticsConfig, HighlightRelatedConfig, HoverConfig,
-    HoverDocFormat, InlayHintsConfig, JoinLinesConfig, Snippet, SnippetScope,
+    HoverDocFormat, InlayHintsConfig, JoinLinesConfig, Snippet, SnippetScope, UseGroup,
 };
 use ide_db::helpers::{
     insert_use::{ImportGranularity, InsertUseConfig, PrefixKind},
@@ -122,6 +122,9 @@ config_data! {
         /// Toggles the additional completions that automatically show method calls and field accesses
         /// with `self` prefixed to them when inside a method.
         completion_autoself_enable: bool       = "true",
+        /// Whether to suggest names for new `#[test]` functions based on the
+        /// functions of the module under test.
+        completion_tests_nameSuggestions: bool = "true",
 
         /// Whether to show native rust-analyzer diagnostics.
         diagnostics_enable: bool                = "true",
@@ -184,6 +187,9 @@ config_data! {
         /// Whether to show `Go to Type Definition` action. Only applies when
         /// `#rust-analyzer.hoverActions.enable#` is set.
         hoverActions_gotoTypeDef: bool     = "true",
+        /// Whether to show `Defined at` action for tokens produced by a macro. Only applies when
+        /// `#rust-analyzer.hoverActions.enable#` is set.
+        hoverActions_gotoMacroDef: bool    = "true",
         /// Whether to show `Implementations` action. Only applies when
         /// `#rust-analyzer.hoverActions.enable#` is set.
         hoverActions_implementations: bool = "true",
@@ -196,6 +202,9 @@ config_data! {
 
         /// Whether to show inlay type hints for method chains.
         inlayHints_chainingHints: bool      = "true",
+        /// Whether to show inlay type hints for variables introduced by
+        /// macro calls.
+        inlayHints_macroIntroducedBindings: bool = "false",
         /// Maximum length for inlay hints. Set to null to have an unlimited length.
         inlayHints_maxLength: Option<usize> = "25",
         /// Whether to show function parameter name inlay hints at the call
@@ -357,6 +366,7 @@ pub struct HoverActionsConfig {
     pub run: bool,
     pub debug: bool,
     pub goto_type_def: bool,
+    pub goto_macro_def: bool,
 }
 
 impl HoverActionsConfig {
@@ -366,10 +376,15 @@ impl HoverActionsConfig {
         run: false,
         debug: false,
         goto_type_def: false,
+        goto_macro_def: false,
     };
 
     pub fn any(&self) -> bool {
-        self.implementations || self.references || self.runnable() || self.goto_type_def
+        self.implementations
+            || self.references
+            || self.runnable()
+            || self.goto_type_def
+            || self.goto_macro_def
     }
 
     pub fn none(&self) -> bool {
@@ -767,6 +782,7 @@ impl Config {
             type_hints: self.data.inlayHints_typeHints,
             parameter_hints: self.data.inlayHints_parameterHints,
             chaining_hints: self.data.inlayHints_chainingHints,
+            macro_introduced_bindings: self.data.inlayHints_macroIntroducedBindings,
             max_length: self.data.inlayHints_maxLength,
         }
     }
@@ -794,6 +810,7 @@ impl Config {
             enable_imports_on_the_fly: self.data.completion_autoimport_enable
                 && completion_item_edit_resolve(&self.caps),
             enable_self_on_the_fly: self.data.completion_autoself_enable,
+            enable_test_name_suggestions: self.data.completion_tests_nameSuggestions,
             add_call_parenthesis: self.data.completion_addCallParenthesis,
             add_call_argument_snippets: self.data.completion_addCallArgumentSnippets,
             insert_use: self.insert_use_config(),
@@ -816,6 +833,7 @@ impl Config {
             snippet_cap: SnippetCap::new(self.experimental("snippetTextEdit")),
             allowed: None,
             insert_use: self.insert_use_config(),
+            use_group_order: [UseGroup::Std, UseGroup::ExternCrate, UseGroup::ThisCrate],
         }
     }
     pub fn join_lines(&self) -> JoinLinesConfig {
@@ -847,6 +865,7 @@ impl Config {
             run: enable && self.data.hoverActions_run,
             debug: enable && self.data.hoverActions_debug,
             goto_type_def: enable && self.data.hoverActions_gotoTypeDef,
+            goto_macro_def: enable && self.data.hoverActions_gotoMacroDef,
         }
     }
     pub fn highlighting_strings(&self) -> bool {
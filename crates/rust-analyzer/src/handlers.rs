@@ -10,8 +10,8 @@ use std::{
 use anyhow::Context;
 use ide::{
     AnnotationConfig, AssistKind, AssistResolveStrategy, FileId, FilePosition, FileRange,
-    HoverAction, HoverGotoTypeData, Query, RangeInfo, Runnable, RunnableKind, SingleResolve,
-    SourceChange, TextEdit,
+    HoverAction, HoverGotoTypeData, NavigationTarget, Query, RangeInfo, Runnable, RunnableKind,
+    SingleResolve, SourceChange, TextEdit,
 };
 use ide_db::SymbolKind;
 use itertools::Itertools;
@@ -145,8 +145,35 @@ pub(crate) fn handle_expand_macro(
     let line_index = snap.file_line_index(file_id)?;
     let offset = from_proto::offset(&line_index, params.position);
 
-    let res = snap.analysis.expand_macro(FilePosition { file_id, offset })?;
-    Ok(res.map(|it| lsp_ext::ExpandedMacro { name: it.name, expansion: it.expansion }))
+    let position = FilePosition { file_id, offset };
+    let res = snap.analysis.expand_macro(position)?;
+    let expansion_text = snap.analysis.macro_expansion_text(position)?;
+    Ok(res.map(|it| lsp_ext::ExpandedMacro {
+        name: it.name,
+        expansion: it.expansion,
+        expansion_text,
+    }))
+}
+
+pub(crate) fn handle_trace_expansion(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::TraceExpansionParams,
+) -> Result<Option<lsp_ext::TracedExpansion>> {
+    let _p = profile::span("handle_trace_expansion");
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let offset = from_proto::offset(&line_index, params.position);
+
+    let res = snap.analysis.trace_expansion(FilePosition { file_id, offset })?;
+    Ok(res.map(|it| lsp_ext::TracedExpansion {
+        name: it.name,
+        kind: it.kind,
+        input: it.input,
+        output: it.output,
+        error: it.error,
+        duration_micros: it.duration.as_micros() as u64,
+        token_ids: it.token_ids,
+    }))
 }
 
 pub(crate) fn handle_selection_range(
@@ -835,13 +862,16 @@ pub(crate) fn handle_signature_help(
 ) -> Result<Option<lsp_types::SignatureHelp>> {
     let _p = profile::span("handle_signature_help");
     let position = from_proto::file_position(&snap, params.text_document_position_params)?;
-    let call_info = match snap.analysis.call_info(position)? {
+    let signature_help = match snap.analysis.signature_help(position)? {
         Some(it) => it,
         None => return Ok(None),
     };
     let concise = !snap.config.call_info_full();
-    let res =
-        to_proto::signature_help(call_info, concise, snap.config.signature_help_label_offsets());
+    let res = to_proto::signature_help(
+        signature_help,
+        concise,
+        snap.config.signature_help_label_offsets(),
+    );
     Ok(Some(res))
 }
 
@@ -1591,6 +1621,21 @@ fn goto_type_action_links(
     })
 }
 
+fn goto_macro_def_action_link(
+    snap: &GlobalStateSnapshot,
+    nav: &NavigationTarget,
+) -> Option<lsp_ext::CommandLinkGroup> {
+    if !snap.config.hover_actions().goto_macro_def || !snap.config.client_commands().goto_location {
+        return None;
+    }
+
+    let command = to_proto::command::goto_location(snap, nav)?;
+    Some(lsp_ext::CommandLinkGroup {
+        title: Some("Defined at ".into()),
+        commands: vec![to_command_link(command, "Go to macro definition".into())],
+    })
+}
+
 fn prepare_hover_actions(
     snap: &GlobalStateSnapshot,
     actions: &[HoverAction],
@@ -1602,6 +1647,7 @@ fn prepare_hover_actions(
             HoverAction::Reference(position) => show_ref_command_link(snap, position),
             HoverAction::Runnable(r) => runnable_action_links(snap, r.clone()),
             HoverAction::GoToType(targets) => goto_type_action_links(snap, targets),
+            HoverAction::GoToMacroDef(nav) => goto_macro_def_action_link(snap, nav),
         })
         .collect()
 }
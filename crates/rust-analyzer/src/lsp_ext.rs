@@ -111,6 +111,34 @@ pub struct ExpandMacroParams {
 pub struct ExpandedMacro {
     pub name: String,
     pub expansion: String,
+    pub expansion_text: Option<String>,
+}
+
+pub enum TraceExpansion {}
+
+impl Request for TraceExpansion {
+    type Params = TraceExpansionParams;
+    type Result = Option<TracedExpansion>;
+    const METHOD: &'static str = "rust-analyzer/traceExpansion";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceExpansionParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedExpansion {
+    pub name: String,
+    pub kind: String,
+    pub input: String,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub duration_micros: u64,
+    pub token_ids: u32,
 }
 
 pub enum MatchingBrace {}
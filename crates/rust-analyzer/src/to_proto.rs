@@ -10,7 +10,7 @@ use ide::{
     CompletionItemKind, CompletionRelevance, Documentation, FileId, FileRange, FileSystemEdit,
     Fold, FoldKind, Highlight, HlMod, HlOperator, HlPunct, HlRange, HlTag, Indel, InlayHint,
     InlayKind, Markup, NavigationTarget, ReferenceCategory, RenameError, Runnable, Severity,
-    SourceChange, StructureNodeKind, SymbolKind, TextEdit, TextRange, TextSize,
+    SignatureHelp, SourceChange, StructureNodeKind, SymbolKind, TextEdit, TextRange, TextSize,
 };
 use itertools::Itertools;
 use serde_json::to_value;
@@ -327,10 +327,30 @@ fn completion_item(
 }
 
 pub(crate) fn signature_help(
-    call_info: CallInfo,
+    signature_help: SignatureHelp,
     concise: bool,
     label_offsets: bool,
 ) -> lsp_types::SignatureHelp {
+    let active_signature = signature_help.active_signature;
+    let signatures: Vec<_> = signature_help
+        .signatures
+        .into_iter()
+        .map(|call_info| signature_information(call_info, concise, label_offsets))
+        .collect();
+    let active_parameter =
+        signatures.get(active_signature).and_then(|signature| signature.active_parameter);
+    lsp_types::SignatureHelp {
+        signatures,
+        active_signature: Some(active_signature as u32),
+        active_parameter,
+    }
+}
+
+fn signature_information(
+    call_info: CallInfo,
+    concise: bool,
+    label_offsets: bool,
+) -> lsp_types::SignatureInformation {
     let (label, parameters) = match (concise, label_offsets) {
         (_, false) => {
             let params = call_info
@@ -391,16 +411,11 @@ pub(crate) fn signature_help(
 
     let active_parameter = call_info.active_parameter.map(|it| it as u32);
 
-    let signature = lsp_types::SignatureInformation {
+    lsp_types::SignatureInformation {
         label,
         documentation,
         parameters: Some(parameters),
         active_parameter,
-    };
-    lsp_types::SignatureHelp {
-        signatures: vec![signature],
-        active_signature: Some(0),
-        active_parameter,
     }
 }
 
@@ -548,6 +563,7 @@ fn semantic_token_type_and_modifiers(
             HlMod::Injected => semantic_tokens::INJECTED,
             HlMod::IntraDocLink => semantic_tokens::INTRA_DOC_LINK,
             HlMod::Library => semantic_tokens::LIBRARY,
+            HlMod::Macro => semantic_tokens::MACRO_MODIFIER,
             HlMod::Mutable => semantic_tokens::MUTABLE,
             HlMod::Public => semantic_tokens::PUBLIC,
             HlMod::Reference => semantic_tokens::REFERENCE,
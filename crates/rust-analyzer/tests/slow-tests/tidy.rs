@@ -275,6 +275,8 @@ fn check_todo(path: &Path, text: &str) {
         "handlers/generate_function.rs",
         "handlers/add_missing_match_arms.rs",
         "handlers/replace_derive_with_manual_impl.rs",
+        // Impl block completions stub out missing methods with `todo!()`.
+        "completions/impl_block.rs",
         // To support generating `todo!()` in assists, we have `expr_todo()` in
         // `ast::make`.
         "ast/make.rs",
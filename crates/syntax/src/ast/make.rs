@@ -513,6 +513,20 @@ pub fn match_arm_list(arms: impl IntoIterator<Item = ast::MatchArm>) -> ast::Mat
     }
 }
 
+pub fn type_bound(bound: &str) -> ast::TypeBound {
+    ast_from_text(&format!("fn f() where (): {} {{ }}", bound))
+}
+
+pub fn type_bound_list(
+    bounds: impl IntoIterator<Item = ast::TypeBound>,
+) -> Option<ast::TypeBoundList> {
+    let bounds = bounds.into_iter().join(" + ");
+    if bounds.is_empty() {
+        return None;
+    }
+    Some(ast_from_text(&format!("fn f() where (): {} {{ }}", bounds)))
+}
+
 pub fn where_pred(
     path: ast::Path,
     bounds: impl IntoIterator<Item = ast::TypeBound>,
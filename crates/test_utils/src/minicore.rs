@@ -9,6 +9,7 @@
 //!
 //! Available flags:
 //!     sized:
+//!     phantom_data:
 //!     unsize: sized
 //!     coerce_unsized: unsize
 //!     slice:
@@ -35,6 +36,8 @@
 //!     fmt: result
 //!     bool_impl: option, fn
 //!     add:
+//!     try_from: from, result
+//!     as_ref: from
 
 pub mod marker {
     // region:sized
@@ -49,6 +52,11 @@ pub mod marker {
     pub trait Unsize<T: ?Sized> {}
     // endregion:unsize
 
+    // region:phantom_data
+    #[lang = "phantom_data"]
+    pub struct PhantomData<T: ?Sized>;
+    // endregion:phantom_data
+
     // region:copy
     #[lang = "copy"]
     pub trait Copy: Clone {}
@@ -87,6 +95,10 @@ pub mod default {
     pub trait Default: Sized {
         fn default() -> Self;
     }
+    // region:derive
+    #[rustc_builtin_macro]
+    pub macro Default($item:item) {}
+    // endregion:derive
 }
 // endregion:default
 
@@ -136,6 +148,45 @@ pub mod convert {
             t
         }
     }
+
+    pub enum Infallible {}
+
+    // region:as_ref
+    pub trait AsRef<T: ?Sized> {
+        fn as_ref(&self) -> &T;
+    }
+    // endregion:as_ref
+
+    // region:try_from
+    pub trait TryFrom<T>: Sized {
+        type Error;
+        fn try_from(value: T) -> crate::result::Result<Self, Self::Error>;
+    }
+    pub trait TryInto<T>: Sized {
+        type Error;
+        fn try_into(self) -> crate::result::Result<T, Self::Error>;
+    }
+
+    impl<T, U> TryInto<U> for T
+    where
+        U: TryFrom<T>,
+    {
+        type Error = U::Error;
+        fn try_into(self) -> crate::result::Result<U, U::Error> {
+            U::try_from(self)
+        }
+    }
+
+    impl<T, U> TryFrom<U> for T
+    where
+        U: Into<T>,
+    {
+        type Error = Infallible;
+        fn try_from(value: U) -> crate::result::Result<Self, Self::Error> {
+            crate::result::Result::Ok(U::into(value))
+        }
+    }
+    // endregion:try_from
 }
 // endregion:from
 
@@ -417,6 +468,19 @@ pub mod result {
         #[lang = "Err"]
         Err(E),
     }
+
+    // region:try
+    impl<T, E> crate::ops::Try for Result<T, E> {
+        type Output = T;
+        type Residual = Result<crate::convert::Infallible, E>;
+        fn from_output(output: Self::Output) -> Self {}
+        fn branch(self) -> crate::ops::ControlFlow<Self::Residual, Self::Output> {}
+    }
+
+    impl<T, E> crate::ops::FromResidual for Result<T, E> {
+        fn from_residual(residual: Result<crate::convert::Infallible, E>) -> Self {}
+    }
+    // endregion:try
 }
 // endregion:result
 
@@ -585,6 +649,11 @@ mod macros {
         pub macro derive($item:item) {
             /* compiler built-in */
         }
+
+        // region:fmt
+        #[rustc_builtin_macro]
+        pub macro Debug($item:item) {}
+        // endregion:fmt
     }
 }
 // endregion:derive
@@ -608,10 +677,14 @@ pub mod prelude {
             clone::Clone,                       // :clone
             cmp::{Eq, PartialEq},               // :eq
             cmp::{Ord, PartialOrd},             // :ord
+            convert::AsRef,                     // :as_ref
             convert::{From, Into},              // :from
             default::Default,                   // :default
             iter::{IntoIterator, Iterator},     // :iterator
             macros::builtin::derive,            // :derive
+            // region:fmt
+            macros::builtin::Debug,             // :derive
+            // endregion:fmt
             marker::Copy,                       // :copy
             marker::Sized,                      // :sized
             ops::{Fn, FnMut, FnOnce},           // :fn
@@ -553,6 +553,20 @@ export function expandMacro(ctx: Ctx): Cmd {
     const tdcp = new class implements vscode.TextDocumentContentProvider {
         uri = vscode.Uri.parse('rust-analyzer://expandMacro/[EXPANSION].rs');
         eventEmitter = new vscode.EventEmitter<vscode.Uri>();
+        constructor() {
+            vscode.workspace.onDidChangeTextDocument(this.onDidChangeTextDocument, this, ctx.subscriptions);
+        }
+
+        private onDidChangeTextDocument(event: vscode.TextDocumentChangeEvent) {
+            const expansionOpen = vscode.workspace.textDocuments.some(
+                (doc) => doc.uri.toString() === this.uri.toString(),
+            );
+            if (expansionOpen && isRustDocument(event.document)) {
+                // Same as for the syntax tree, give the server a moment to see the edit.
+                void sleep(10).then(() => this.eventEmitter.fire(this.uri));
+            }
+        }
+
         async provideTextDocumentContent(_uri: vscode.Uri): Promise<string> {
             const editor = vscode.window.activeTextEditor;
             const client = ctx.client;
@@ -593,6 +607,89 @@ export function expandMacro(ctx: Ctx): Cmd {
     };
 }
 
+// Opens a virtual file that shows the expansion of the macro call at the cursor, and keeps it up to
+// date while the call's arguments are edited.
+//
+// Each preview is a `vfs://macro-expansion/<n>.rs` document tracking a position in the source
+// document. The position is moved along with the edits before it, and the expansion is requested
+// at that position.
+export function previewMacroExpansion(ctx: Ctx): Cmd {
+    const previews = new Map<string, { document: vscode.Uri; offset: number }>();
+    let nextPreview = 0;
+
+    const tdcp = new class implements vscode.TextDocumentContentProvider {
+        eventEmitter = new vscode.EventEmitter<vscode.Uri>();
+        constructor() {
+            vscode.workspace.onDidChangeTextDocument(this.onDidChangeTextDocument, this, ctx.subscriptions);
+            vscode.workspace.onDidCloseTextDocument(this.onDidCloseTextDocument, this, ctx.subscriptions);
+        }
+
+        private onDidChangeTextDocument(event: vscode.TextDocumentChangeEvent) {
+            if (!isRustDocument(event.document)) return;
+            for (const [uri, position] of previews) {
+                if (position.document.toString() !== event.document.uri.toString()) continue;
+                for (const change of event.contentChanges) {
+                    position.offset = shiftOffset(position.offset, change);
+                }
+                void sleep(10).then(() => this.eventEmitter.fire(vscode.Uri.parse(uri)));
+            }
+        }
+
+        private onDidCloseTextDocument(doc: vscode.TextDocument) {
+            previews.delete(doc.uri.toString());
+        }
+
+        async provideTextDocumentContent(uri: vscode.Uri): Promise<string> {
+            const position = previews.get(uri.toString());
+            if (!position) return 'Not available';
+
+            const document = await vscode.workspace.openTextDocument(position.document);
+            const expanded = await ctx.client.sendRequest(ra.expandMacro, {
+                textDocument: ctx.client.code2ProtocolConverter.asTextDocumentIdentifier(document),
+                position: document.positionAt(position.offset),
+            });
+
+            return expanded?.expansionText ?? 'Not available';
+        }
+
+        get onDidChange(): vscode.Event<vscode.Uri> {
+            return this.eventEmitter.event;
+        }
+    }();
+
+    ctx.pushCleanup(
+        vscode.workspace.registerTextDocumentContentProvider(
+            'vfs',
+            tdcp,
+        ),
+    );
+
+    return async () => {
+        const editor = vscode.window.activeTextEditor;
+        if (!editor || !isRustEditor(editor)) return;
+
+        const uri = vscode.Uri.parse(`vfs://macro-expansion/${nextPreview++}.rs`);
+        previews.set(uri.toString(), {
+            document: editor.document.uri,
+            offset: editor.document.offsetAt(editor.selection.active),
+        });
+        const document = await vscode.workspace.openTextDocument(uri);
+        return vscode.window.showTextDocument(
+            document,
+            vscode.ViewColumn.Two,
+            true,
+        );
+    };
+}
+
+// Moves an offset in a document along with an edit of that document. An offset inside the replaced
+// range ends up at its start.
+function shiftOffset(offset: number, change: vscode.TextDocumentContentChangeEvent): number {
+    if (offset < change.rangeOffset) return offset;
+    if (offset < change.rangeOffset + change.rangeLength) return change.rangeOffset;
+    return offset + change.text.length - change.rangeLength;
+}
+
 export function reloadWorkspace(ctx: Ctx): Cmd {
     return async () => ctx.client.sendRequest(ra.reloadWorkspace);
 }
@@ -152,6 +152,7 @@ export class Config {
             run: this.get<boolean>("hoverActions.run"),
             debug: this.get<boolean>("hoverActions.debug"),
             gotoTypeDef: this.get<boolean>("hoverActions.gotoTypeDef"),
+            gotoMacroDef: this.get<boolean>("hoverActions.gotoMacroDef"),
         };
     }
 
@@ -53,6 +53,7 @@ export interface ExpandMacroParams {
 export interface ExpandedMacro {
     name: string;
     expansion: string;
+    expansionText: string | null;
 }
 export const expandMacro = new lc.RequestType<ExpandMacroParams, ExpandedMacro | null, void>("rust-analyzer/expandMacro");
 